@@ -72,11 +72,21 @@ mod tests {
     #[allow(clippy::vec_init_then_push)]
     mod individual_args {
         use super::*;
+        use sic_core::FrameRange;
         use sic_image_engine::engine::EnvItem;
+        use sic_image_engine::wrapper::diff::DiffInputs;
         use sic_image_engine::wrapper::filter_type::FilterTypeWrap;
+        use sic_image_engine::wrapper::frame_delay::FrameDelay;
+        use sic_image_engine::wrapper::gravity::Gravity;
+        use sic_image_engine::wrapper::icc_profile::IccProfileTarget;
         use sic_image_engine::wrapper::image_path::ImageFromPath;
+        use sic_image_engine::wrapper::levels::LevelsInputs;
+        use sic_image_engine::wrapper::pad::{Margins, PadInputs, PadSize};
+        use sic_image_engine::wrapper::resize_mode::{ResizeMode, ScaleFactor};
+        use sic_image_engine::wrapper::trim::TrimInputs;
         use sic_image_engine::ImgOp;
         use sic_testing::setup_test_image;
+        use std::time::Duration;
 
         macro_rules! op {
             ($expr:expr) => {
@@ -112,19 +122,35 @@ mod tests {
 
         #[parameterized(
             ops = {
+                vec!["--auto-orient"],
                 vec!["--blur", "1.0"],
                 vec!["--brighten", "-1"],
                 vec!["--contrast", "1.0"],
+                vec!["--convert-profile", "srgb"],
+                vec!["--convert-profile", "display_p3.icc"],
                 vec!["--crop", "0", "1", "2", "3"],
+                vec!["--curves", "points((0, 0), (128, 150), (255, 255))"],
                 vec!["--diff", "▲"],
+                vec!["--drop-frames", "::2"],
+                vec!["--drop-frames", "5..20"],
+                vec!["--extend", "1", "2", "3", "4"],
                 vec!["--filter3x3", "1.0", "1.0", "1.0", "-1.0", "-1.0", "-1.0", "0.0", "0.0", "0.0"],
                 vec!["--flip-horizontal"],
                 vec!["--flip-vertical"],
+                vec!["--gamma", "2.2"],
                 vec!["--grayscale"],
                 vec!["--hue-rotate", "-1"],
                 vec!["--invert"],
+                vec!["--levels", "10", "240"],
+                vec!["--pad", "1", "2"],
                 vec!["--resize", "1", "1"],
+                vec!["--resize-exact", "1", "2"],
+                vec!["--resize-fit", "1", "2"],
+                vec!["--resize-cover", "1", "2"],
+                vec!["--resize-scale", "50%"],
+                vec!["--resize-max-edge", "1"],
                 vec!["--preserve-aspect-ratio", "true"],
+                vec!["--shrink-only", "true"],
                 vec!["--sampling-filter", "catmullrom"],
                 vec!["--sampling-filter", "gaussian"],
                 vec!["--sampling-filter", "lanczos3"],
@@ -132,23 +158,44 @@ mod tests {
                 vec!["--sampling-filter", "triangle"],
                 vec!["--rotate90"],
                 vec!["--rotate180"],
+                vec!["--reverse-frames"],
                 vec!["--rotate270"],
+                vec!["--set-frame-delay", "50ms"],
+                vec!["--set-frame-delay", "1.5s"],
+                vec!["--speed", "2.0"],
+                vec!["--trim"],
                 vec!["--unsharpen", "-1.0", "-1"],
             },
             expected = {
+                op![ImgOp::AutoOrient],
                 op![ImgOp::Blur(1.0)],
                 op![ImgOp::Brighten(-1)],
                 op![ImgOp::Contrast(1.0)],
+                op![ImgOp::ConvertProfile(IccProfileTarget::Srgb)],
+                op![ImgOp::ConvertProfile(IccProfileTarget::Path("display_p3.icc".into()))],
                 op![ImgOp::Crop((0, 1, 2, 3))],
-                op![ImgOp::Diff(ImageFromPath::new(setup_test_image("aaa.png")))],
+                op![ImgOp::Curves(vec![(0, 0), (128, 150), (255, 255)])],
+                op![ImgOp::Diff(DiffInputs::new(ImageFromPath::new(setup_test_image("aaa.png"))))],
+                op![ImgOp::DropFrames(FrameRange::new(0, None, 2).unwrap())],
+                op![ImgOp::DropFrames(FrameRange::new(5, Some(20), 1).unwrap())],
+                op![ImgOp::Pad(PadInputs::new(PadSize::Margins(Margins { top: 1, right: 2, bottom: 3, left: 4 })))],
                 op![ImgOp::Filter3x3([1.0, 1.0, 1.0, -1.0, -1.0, -1.0, 0.0, 0.0, 0.0])],
                 op![ImgOp::FlipHorizontal],
                 op![ImgOp::FlipVertical],
+                op![ImgOp::Gamma(2.2)],
                 op![ImgOp::Grayscale],
                 op![ImgOp::HueRotate(-1)],
                 op![ImgOp::Invert],
+                op![ImgOp::Levels(LevelsInputs::new(10, 240))],
+                op![ImgOp::Pad(PadInputs::new(PadSize::Canvas((1, 2), Gravity::Center)))],
                 op![ImgOp::Resize((1, 1))],
+                op![ImgOp::ResizeWithMode(ResizeMode::Exact((1, 2)))],
+                op![ImgOp::ResizeWithMode(ResizeMode::Fit((1, 2)))],
+                op![ImgOp::ResizeWithMode(ResizeMode::Cover((1, 2), Gravity::Center))],
+                op![ImgOp::ResizeWithMode(ResizeMode::Scale(ScaleFactor::try_from_str("50%").unwrap()))],
+                op![ImgOp::ResizeWithMode(ResizeMode::MaxEdge(1))],
                 modifier![EnvItem::PreserveAspectRatio(true)],
+                modifier![EnvItem::ShrinkOnly(true)],
                 modifier![EnvItem::CustomSamplingFilter(FilterTypeWrap::try_from_str("catmullrom").unwrap())],
                 modifier![EnvItem::CustomSamplingFilter(FilterTypeWrap::try_from_str("gaussian").unwrap())],
                 modifier![EnvItem::CustomSamplingFilter(FilterTypeWrap::try_from_str("lanczos3").unwrap())],
@@ -156,7 +203,12 @@ mod tests {
                 modifier![EnvItem::CustomSamplingFilter(FilterTypeWrap::try_from_str("triangle").unwrap())],
                 op![ImgOp::Rotate90],
                 op![ImgOp::Rotate180],
+                op![ImgOp::ReverseFrames],
                 op![ImgOp::Rotate270],
+                op![ImgOp::SetFrameDelay(FrameDelay::new(Duration::from_millis(50)))],
+                op![ImgOp::SetFrameDelay(FrameDelay::new(Duration::from_millis(1500)))],
+                op![ImgOp::Speed(2.0)],
+                op![ImgOp::Trim(TrimInputs::default())],
                 op![ImgOp::Unsharpen((-1.0, -1))],
             },
         )]
@@ -171,16 +223,21 @@ mod tests {
             use sic_core::image::Rgba;
             use sic_image_engine::wrapper::draw_text_inner::DrawTextInner;
             use sic_image_engine::wrapper::font_options::{FontOptions, FontScale};
+            use sic_image_engine::wrapper::rotate::RotateInputs;
             use std::path::PathBuf;
 
             ide!();
 
             #[parameterized(
                 ops = {
+                    vec!["--rotate", "45"],
+                    vec!["--rotate", "-30.5"],
                     vec!["--draw-text", "my text", "coord(0, 1)", "rgba(10, 10, 255, 255)", "size(16.0)", r#"font("resources/font/Lato-Regular.ttf")"#],
                     vec!["--draw-text", "my text", "coord(0, 1)", "rgba(10, 10, 255, 255)", "size(16.0)", r#"font("resources/font/Lato-Regular()".ttf")"#],
                 },
                 expected = {
+                    op![ImgOp::Rotate(RotateInputs::new(45.0))],
+                    op![ImgOp::Rotate(RotateInputs::new(-30.5))],
                     op![ImgOp::DrawText(DrawTextInner::new("my text".to_string(),
                         (0, 1),
                         FontOptions::new(
@@ -239,7 +296,9 @@ mod tests {
                 ImgOp::Brighten(-1),
                 ImgOp::Contrast(1.0),
                 ImgOp::Crop((0, 1, 2, 3)),
-                ImgOp::Diff(ImageFromPath::new(setup_test_image("aaa.png"))),
+                ImgOp::Diff(DiffInputs::new(ImageFromPath::new(setup_test_image(
+                    "aaa.png"
+                )))),
                 ImgOp::Filter3x3([1.0, 1.0, 1.0, -1.0, -1.0, -1.0, 0.0, 0.0, 0.0]),
                 ImgOp::FlipHorizontal
             ];
@@ -252,14 +311,29 @@ mod tests {
                 vec!["--blur", "A"],
                 vec!["--brighten", "-1.0"],
                 vec!["--contrast", ""],
+                vec!["--convert-profile"],
                 vec!["--crop", "--crop", "0", "1", "2", "3"],
+                vec!["--curves", "(0, 0), (255, 255)"],
+                vec!["--curves", "points((0, 0), (256, 255))"],
                 vec!["--diff"],
+                vec!["--drop-frames", "1..2..3"],
+                vec!["--drop-frames", "::0"],
                 vec!["--filter3x3", "[", "1.0", "1.0", "1.0", "-1.0", "-1.0", "-1.0", "0.0", "0.0", "0.0", "]"],
+                vec!["--extend", "1"],
+                vec!["--gamma", "fast"],
                 vec!["--hue-rotate", "-100.8"],
+                vec!["--levels", "10", "256"],
+                vec!["--pad", "1", "-2"],
                 vec!["--resize", "1", "1", "--crop"],
+                vec!["--resize-scale", "50"],
+                vec!["--rotate", "45deg"],
+                vec!["--resize-max-edge", "-1"],
                 vec!["--preserve-aspect-ratio", "yes"],
                 vec!["--sampling-filter", "tri"],
                 vec!["--sampling-filter", ""],
+                vec!["--set-frame-delay", "50"],
+                vec!["--set-frame-delay", "fast"],
+                vec!["--speed", "x2"],
                 vec!["--unsharpen", "-1.0", "-1.0"],
             }
         )]
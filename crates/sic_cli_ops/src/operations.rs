@@ -1,9 +1,15 @@
 use crate::errors::{InternalErrorSource, SicCliOpsError};
 use crate::TResult;
+use sic_core::FrameRange;
 use sic_image_engine::engine::{EnvItem, Instr};
+use sic_image_engine::wrapper::diff::DiffInputs;
 use sic_image_engine::wrapper::filter_type::FilterTypeWrap;
-use sic_image_engine::wrapper::image_path::ImageFromPath;
+use sic_image_engine::wrapper::frame_delay::FrameDelay;
+use sic_image_engine::wrapper::gravity::Gravity;
+use sic_image_engine::wrapper::icc_profile::IccProfileTarget;
 use sic_image_engine::wrapper::overlay::OverlayInputs;
+use sic_image_engine::wrapper::pad::{Margins, PadInputs, PadSize};
+use sic_image_engine::wrapper::resize_mode::{ResizeMode, ScaleFactor};
 use sic_image_engine::ImgOp;
 use sic_parser::errors::SicParserError;
 use sic_parser::value_parser::{Describable, ParseInputsFromIter};
@@ -17,33 +23,54 @@ use std::str::FromStr;
 #[strum(serialize_all = "kebab_case")]
 pub enum OperationId {
     // image operations
+    AutoOrient,
     Blur,
     Brighten,
     Contrast,
+    ConvertProfile,
     Crop,
+    Curves,
     Diff,
     #[cfg(feature = "imageproc-ops")]
     DrawText,
+    DropFrames,
+    Extend,
     Filter3x3,
     FlipHorizontal,
     FlipVertical,
+    Gamma,
     Grayscale,
     HueRotate,
     HorizontalGradient,
     Invert,
+    Levels,
     Overlay,
+    Pad,
     Resize,
+    ResizeExact,
+    ResizeFit,
+    ResizeCover,
+    ResizeScale,
+    ResizeMaxEdge,
+    ReverseFrames,
+    #[cfg(feature = "imageproc-ops")]
+    Rotate,
     Rotate90,
     Rotate180,
     Rotate270,
+    SetFrameDelay,
+    SmartCrop,
+    Speed,
     #[cfg(feature = "imageproc-ops")]
     Threshold,
+    Trim,
     Unsharpen,
     VerticalGradient,
 
     // modifiers
     PreserveAspectRatio,
     SamplingFilter,
+    ShrinkOnly,
 }
 
 impl OperationId {
@@ -72,33 +99,54 @@ impl OperationId {
     pub fn takes_number_of_arguments(self) -> usize {
         match self {
             // image operations
+            OperationId::AutoOrient => 0,
             OperationId::Blur => 1,
             OperationId::Brighten => 1,
             OperationId::Contrast => 1,
+            OperationId::ConvertProfile => 1,
             OperationId::Crop => 4,
+            OperationId::Curves => 1,
             OperationId::Diff => 1,
             #[cfg(feature = "imageproc-ops")]
             OperationId::DrawText => 5,
+            OperationId::DropFrames => 1,
+            OperationId::Extend => 4,
             OperationId::Filter3x3 => 9,
             OperationId::FlipHorizontal => 0,
             OperationId::FlipVertical => 0,
+            OperationId::Gamma => 1,
             OperationId::Grayscale => 0,
             OperationId::HueRotate => 1,
             OperationId::HorizontalGradient => 2,
             OperationId::Invert => 0,
+            OperationId::Levels => 2,
             OperationId::Overlay => 3,
+            OperationId::Pad => 2,
             OperationId::Resize => 2,
+            OperationId::ResizeExact => 2,
+            OperationId::ResizeFit => 2,
+            OperationId::ResizeCover => 2,
+            OperationId::ResizeScale => 1,
+            OperationId::ResizeMaxEdge => 1,
+            OperationId::ReverseFrames => 0,
+            #[cfg(feature = "imageproc-ops")]
+            OperationId::Rotate => 1,
             OperationId::Rotate90 => 0,
             OperationId::Rotate180 => 0,
             OperationId::Rotate270 => 0,
+            OperationId::SetFrameDelay => 1,
+            OperationId::SmartCrop => 2,
+            OperationId::Speed => 1,
             #[cfg(feature = "imageproc-ops")]
             OperationId::Threshold => 0,
+            OperationId::Trim => 0,
             OperationId::Unsharpen => 2,
             OperationId::VerticalGradient => 2,
 
             // image operation modifiers
             OperationId::PreserveAspectRatio => 1,
             OperationId::SamplingFilter => 1,
+            OperationId::ShrinkOnly => 1,
         }
     }
 }
@@ -125,6 +173,7 @@ impl OperationId {
     {
         let stmt = match self {
             // image operations
+            OperationId::AutoOrient => Instr::Operation(ImgOp::AutoOrient),
             OperationId::Blur => Instr::Operation(ImgOp::Blur(parse_inputs_by_type!(inputs, f32)?)),
             OperationId::Brighten => {
                 Instr::Operation(ImgOp::Brighten(parse_inputs_by_type!(inputs, i32)?))
@@ -132,12 +181,18 @@ impl OperationId {
             OperationId::Contrast => {
                 Instr::Operation(ImgOp::Contrast(parse_inputs_by_type!(inputs, f32)?))
             }
+            OperationId::ConvertProfile => Instr::Operation(ImgOp::ConvertProfile(
+                parse_inputs_by_type!(inputs, IccProfileTarget)?,
+            )),
             OperationId::Crop => Instr::Operation(ImgOp::Crop(parse_inputs_by_type!(
                 inputs,
                 (u32, u32, u32, u32)
             )?)),
+            OperationId::Curves => {
+                Instr::Operation(ImgOp::Curves(parse_inputs_by_type!(inputs, Vec<(u8, u8)>)?))
+            }
             OperationId::Diff => {
-                Instr::Operation(ImgOp::Diff(parse_inputs_by_type!(inputs, ImageFromPath)?))
+                Instr::Operation(ImgOp::Diff(parse_inputs_by_type!(inputs, DiffInputs)?))
             }
             #[cfg(feature = "imageproc-ops")]
             OperationId::DrawText => {
@@ -147,11 +202,29 @@ impl OperationId {
                     DrawTextInner
                 )?))
             }
+            OperationId::DropFrames => Instr::Operation(ImgOp::DropFrames(parse_inputs_by_type!(
+                inputs, FrameRange
+            )?)),
+            // the fill color of extend and pad can only be chosen in image script
+            OperationId::Extend => {
+                let (top, right, bottom, left) =
+                    parse_inputs_by_type!(inputs, (u32, u32, u32, u32))?;
+
+                Instr::Operation(ImgOp::Pad(PadInputs::new(PadSize::Margins(Margins {
+                    top,
+                    right,
+                    bottom,
+                    left,
+                }))))
+            }
             OperationId::Filter3x3 => {
                 Instr::Operation(ImgOp::Filter3x3(parse_inputs_by_type!(inputs, [f32; 9])?))
             }
             OperationId::FlipHorizontal => Instr::Operation(ImgOp::FlipHorizontal),
             OperationId::FlipVertical => Instr::Operation(ImgOp::FlipVertical),
+            OperationId::Gamma => {
+                Instr::Operation(ImgOp::Gamma(parse_inputs_by_type!(inputs, f32)?))
+            }
             OperationId::Grayscale => Instr::Operation(ImgOp::Grayscale),
             OperationId::HueRotate => {
                 Instr::Operation(ImgOp::HueRotate(parse_inputs_by_type!(inputs, i32)?))
@@ -164,18 +237,68 @@ impl OperationId {
                 )?))
             }
             OperationId::Invert => Instr::Operation(ImgOp::Invert),
+            // the gamma and color channel of levels can only be chosen in image script
+            OperationId::Levels => {
+                use sic_image_engine::wrapper::levels::LevelsInputs;
+                let (black, white) = parse_inputs_by_type!(inputs, (u8, u8))?;
+                Instr::Operation(ImgOp::Levels(LevelsInputs::new(black, white)))
+            }
             OperationId::Overlay => Instr::Operation(ImgOp::Overlay(parse_inputs_by_type!(
                 inputs,
                 OverlayInputs
             )?)),
+            OperationId::Pad => Instr::Operation(ImgOp::Pad(PadInputs::new(PadSize::Canvas(
+                parse_inputs_by_type!(inputs, (u32, u32))?,
+                Gravity::default(),
+            )))),
             OperationId::Resize => {
                 Instr::Operation(ImgOp::Resize(parse_inputs_by_type!(inputs, (u32, u32))?))
             }
+            OperationId::ResizeExact => Instr::Operation(ImgOp::ResizeWithMode(ResizeMode::Exact(
+                parse_inputs_by_type!(inputs, (u32, u32))?,
+            ))),
+            OperationId::ResizeFit => Instr::Operation(ImgOp::ResizeWithMode(ResizeMode::Fit(
+                parse_inputs_by_type!(inputs, (u32, u32))?,
+            ))),
+            // the gravity of the cover mode can only be chosen in image script
+            OperationId::ResizeCover => Instr::Operation(ImgOp::ResizeWithMode(ResizeMode::Cover(
+                parse_inputs_by_type!(inputs, (u32, u32))?,
+                Gravity::default(),
+            ))),
+            OperationId::ResizeScale => Instr::Operation(ImgOp::ResizeWithMode(ResizeMode::Scale(
+                parse_inputs_by_type!(inputs, ScaleFactor)?,
+            ))),
+            OperationId::ResizeMaxEdge => Instr::Operation(ImgOp::ResizeWithMode(
+                ResizeMode::MaxEdge(parse_inputs_by_type!(inputs, u32)?),
+            )),
+            OperationId::ReverseFrames => Instr::Operation(ImgOp::ReverseFrames),
+            // the canvas, interpolation and fill color of rotate can only be chosen in image script
+            #[cfg(feature = "imageproc-ops")]
+            OperationId::Rotate => {
+                use sic_image_engine::wrapper::rotate::RotateInputs;
+                Instr::Operation(ImgOp::Rotate(RotateInputs::new(parse_inputs_by_type!(
+                    inputs, f32
+                )?)))
+            }
             OperationId::Rotate90 => Instr::Operation(ImgOp::Rotate90),
             OperationId::Rotate180 => Instr::Operation(ImgOp::Rotate180),
             OperationId::Rotate270 => Instr::Operation(ImgOp::Rotate270),
+            OperationId::SetFrameDelay => Instr::Operation(ImgOp::SetFrameDelay(
+                parse_inputs_by_type!(inputs, FrameDelay)?,
+            )),
+            OperationId::SmartCrop => {
+                Instr::Operation(ImgOp::SmartCrop(parse_inputs_by_type!(inputs, (u32, u32))?))
+            }
+            OperationId::Speed => {
+                Instr::Operation(ImgOp::Speed(parse_inputs_by_type!(inputs, f32)?))
+            }
             #[cfg(feature = "imageproc-ops")]
             OperationId::Threshold => Instr::Operation(ImgOp::Threshold),
+            // the color, tolerance and margin of trim can only be chosen in image script
+            OperationId::Trim => {
+                use sic_image_engine::wrapper::trim::TrimInputs;
+                Instr::Operation(ImgOp::Trim(TrimInputs::default()))
+            }
             OperationId::Unsharpen => {
                 Instr::Operation(ImgOp::Unsharpen(parse_inputs_by_type!(inputs, (f32, i32))?))
             }
@@ -196,6 +319,9 @@ impl OperationId {
                     .map_err(SicParserError::FilterTypeError)?;
                 Instr::EnvAdd(EnvItem::CustomSamplingFilter(filter))
             }
+            OperationId::ShrinkOnly => {
+                Instr::EnvAdd(EnvItem::ShrinkOnly(parse_inputs_by_type!(inputs, bool)?))
+            }
         };
 
         Ok(stmt)
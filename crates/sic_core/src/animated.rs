@@ -1,6 +1,7 @@
 //! The primary definition of an animated image, for the `sic` project.
 
 use crate::errors::SicCoreError;
+use crate::frame_range::FrameRange;
 use image::{DynamicImage, Frames};
 use std::fmt::{Debug, Formatter};
 
@@ -38,6 +39,54 @@ impl AnimatedImage {
         &mut self.frames
     }
 
+    /// Sets the delay of each frame to the delay returned by `f`, which receives the current delay
+    /// of the frame
+    pub fn map_delays(&mut self, f: impl Fn(image::Delay) -> image::Delay) {
+        for frame in &mut self.frames {
+            let delay = f(frame.delay());
+            let (left, top) = (frame.left(), frame.top());
+            let buffer = std::mem::take(frame.buffer_mut());
+
+            *frame = image::Frame::from_parts(buffer, left, top, delay);
+        }
+    }
+
+    /// Reverses the order of the frames; each frame keeps its own delay
+    pub fn reverse_frames(&mut self) {
+        self.frames.reverse();
+    }
+
+    /// Keeps the frames within the given range, and removes all other frames
+    pub fn retain_frames(&mut self, range: &FrameRange) -> Result<(), SicCoreError> {
+        self.retain_frames_by_index(|index| range.contains(index))
+    }
+
+    /// Removes the frames within the given range
+    pub fn remove_frames(&mut self, range: &FrameRange) -> Result<(), SicCoreError> {
+        self.retain_frames_by_index(|index| !range.contains(index))
+    }
+
+    fn retain_frames_by_index(&mut self, keep: impl Fn(usize) -> bool) -> Result<(), SicCoreError> {
+        if !(0..self.frames.len()).any(&keep) {
+            return Err(SicCoreError::NoFramesSelected);
+        }
+
+        let frames = std::mem::take(&mut self.frames)
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| keep(*index))
+            .map(|(_, frame)| frame)
+            .collect();
+
+        self.frames = frames;
+        Ok(())
+    }
+
+    /// Consumes the animated image and returns its frames
+    pub fn into_frames(self) -> Vec<image::Frame> {
+        self.frames
+    }
+
     /// Collects and returns an owned collection of image frames
     pub fn collect_frames(&self) -> Vec<image::Frame> {
         self.frames.clone()
@@ -8,6 +8,15 @@ pub enum SicCoreError {
     #[error("Invalid frame index: index (is {index}) should be < len (is {len}) ")]
     InvalidFrameIndex { index: usize, len: usize },
 
+    #[error("Invalid frame range '{0}': expected a frame index (e.g. '3'), a range (e.g. '0..10' or '0..=9') or a slice (e.g. '::2' or '1:10:3')")]
+    InvalidFrameRange(String),
+
+    #[error("Invalid frame range: the step of a frame range should be at least 1")]
+    InvalidFrameRangeStep,
+
+    #[error("The frame selection would leave the animated image without any frames")]
+    NoFramesSelected,
+
     #[error("A static image was required, but an animated image was given")]
     RequiresStaticImage,
 }
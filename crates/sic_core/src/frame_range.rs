@@ -0,0 +1,170 @@
+//! A selection of frames of an animated image, by range and stride.
+
+use crate::errors::SicCoreError;
+
+/// A selection of zero-indexed frames of an animated image.
+///
+/// A frame range can be written in one of the following notations:
+/// * a single frame index, e.g. `3`
+/// * a range with an exclusive end, e.g. `5..20`, or an inclusive end, e.g. `5..=20`
+/// * a slice with an (optional) step, e.g. `0:10`, `::2` or `1:20:3`
+///
+/// In all notations except the single frame index, the start and end may be omitted, in which case
+/// the range starts at the first frame, or ends at the last frame respectively.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FrameRange {
+    start: usize,
+    // exclusive
+    end: Option<usize>,
+    step: usize,
+}
+
+impl FrameRange {
+    /// Create a frame range from `start` (inclusive) up to `end` (exclusive), which selects every
+    /// `step`th frame. If `end` is `None`, the range continues up to and including the last frame.
+    pub fn new(start: usize, end: Option<usize>, step: usize) -> Result<Self, SicCoreError> {
+        if step == 0 {
+            return Err(SicCoreError::InvalidFrameRangeStep);
+        }
+
+        Ok(Self { start, end, step })
+    }
+
+    /// A frame range which selects a single frame.
+    pub fn single(index: usize) -> Self {
+        Self {
+            start: index,
+            end: Some(index.saturating_add(1)),
+            step: 1,
+        }
+    }
+
+    pub fn try_from_str(input: &str) -> Result<Self, SicCoreError> {
+        let invalid = || SicCoreError::InvalidFrameRange(input.to_string());
+
+        let parse_bound = |bound: &str| -> Result<Option<usize>, SicCoreError> {
+            if bound.is_empty() {
+                Ok(None)
+            } else {
+                bound.parse::<usize>().map(Some).map_err(|_| invalid())
+            }
+        };
+
+        let input = input.trim();
+
+        if let Some((start, end)) = input.split_once("..") {
+            let start = parse_bound(start)?.unwrap_or_default();
+
+            let end = match end.strip_prefix('=') {
+                Some(inclusive) => Some(
+                    parse_bound(inclusive)?
+                        .ok_or_else(invalid)?
+                        .checked_add(1)
+                        .ok_or_else(invalid)?,
+                ),
+                None => parse_bound(end)?,
+            };
+
+            Self::new(start, end, 1)
+        } else if input.contains(':') {
+            let mut parts = input.split(':');
+
+            // SAFETY(unwrap): split always yields at least one element
+            let start = parse_bound(parts.next().unwrap())?.unwrap_or_default();
+            let end = parts.next().map(parse_bound).transpose()?.flatten();
+            let step = parts.next().map(parse_bound).transpose()?.flatten();
+
+            if parts.next().is_some() {
+                return Err(invalid());
+            }
+
+            Self::new(start, end, step.unwrap_or(1))
+        } else {
+            parse_bound(input)?.map(Self::single).ok_or_else(invalid)
+        }
+    }
+
+    /// Returns whether the frame at the given index is part of this range.
+    pub fn contains(&self, index: usize) -> bool {
+        index >= self.start
+            && self.end.map_or(true, |end| index < end)
+            && (index - self.start) % self.step == 0
+    }
+
+    /// Returns the indices of the frames within this range, for an animated image with
+    /// `frame_count` frames.
+    pub fn indices(&self, frame_count: usize) -> impl Iterator<Item = usize> {
+        let end = self.end.map_or(frame_count, |end| end.min(frame_count));
+
+        (self.start..end).step_by(self.step)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_single_index() {
+        let range = FrameRange::try_from_str("3").unwrap();
+
+        assert_eq!(range, FrameRange::single(3));
+        assert_eq!(range.indices(10).collect::<Vec<_>>(), vec![3]);
+    }
+
+    #[test]
+    fn parse_ranges() {
+        let cases: [(&str, usize, Vec<usize>); 7] = [
+            ("0..3", 10, vec![0, 1, 2]),
+            ("2..=4", 10, vec![2, 3, 4]),
+            ("7..", 10, vec![7, 8, 9]),
+            ("..2", 10, vec![0, 1]),
+            ("..", 3, vec![0, 1, 2]),
+            ("5..20", 8, vec![5, 6, 7]),
+            ("20..30", 8, vec![]),
+        ];
+
+        for (input, frame_count, expected) in cases {
+            let range = FrameRange::try_from_str(input).unwrap();
+            assert_eq!(range.indices(frame_count).collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn parse_slices() {
+        let cases: [(&str, usize, Vec<usize>); 6] = [
+            ("::2", 7, vec![0, 2, 4, 6]),
+            ("1::2", 7, vec![1, 3, 5]),
+            ("0:4", 7, vec![0, 1, 2, 3]),
+            ("1:7:3", 10, vec![1, 4]),
+            (":", 2, vec![0, 1]),
+            ("2:", 4, vec![2, 3]),
+        ];
+
+        for (input, frame_count, expected) in cases {
+            let range = FrameRange::try_from_str(input).unwrap();
+            assert_eq!(range.indices(frame_count).collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn contains_agrees_with_indices() {
+        for input in ["3", "0..3", "2..=4", "7..", "::2", "1::3", "1:7:3"] {
+            let range = FrameRange::try_from_str(input).unwrap();
+            let indices = range.indices(12).collect::<Vec<_>>();
+
+            for index in 0..12 {
+                assert_eq!(range.contains(index), indices.contains(&index), "{}", input);
+            }
+        }
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for input in [
+            "", "a", "-1", "1..=", "1..2..3", "::0", "1:2:3:4", "1...2", "1.5",
+        ] {
+            assert!(FrameRange::try_from_str(input).is_err(), "{}", input);
+        }
+    }
+}
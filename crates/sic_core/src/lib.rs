@@ -16,8 +16,12 @@ use std::convert::TryFrom;
 
 mod animated;
 mod errors;
+mod frame_range;
+mod metadata;
 
 pub use animated::AnimatedImage;
+pub use frame_range::FrameRange;
+pub use metadata::ImageMetadata;
 
 pub use errors::SicCoreError;
 
@@ -30,6 +34,20 @@ pub enum SicImage {
     Static(DynamicImage),
 }
 
+impl SicImage {
+    /// The width and height of the image. The dimensions of an animated image are those of its
+    /// first frame, or zero if it has no frames.
+    pub fn dimensions(&self) -> (u32, u32) {
+        match self {
+            Self::Animated(animated) => animated
+                .frames()
+                .first()
+                .map_or((0, 0), |frame| frame.buffer().dimensions()),
+            Self::Static(image) => (image.width(), image.height()),
+        }
+    }
+}
+
 // Should not be used outside of tests, as it doesn't support animated images
 #[doc(hidden)]
 impl AsRef<DynamicImage> for SicImage {
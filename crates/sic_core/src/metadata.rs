@@ -0,0 +1,75 @@
+//! Metadata which accompanies the pixel data of an image, for the `sic` project.
+
+use image::metadata::Orientation;
+
+/// Metadata of an image which is not part of its pixel data, such as Exif and XMP metadata
+/// and an embedded ICC color profile.
+///
+/// The metadata is stored in its raw form: the Exif metadata as a TIFF structure (without the
+/// `Exif\0\0` header), the XMP metadata as serialized XML packet and the ICC profile as is.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImageMetadata {
+    icc_profile: Option<Vec<u8>>,
+    exif: Option<Vec<u8>>,
+    xmp: Option<Vec<u8>>,
+    orientation: Orientation,
+}
+
+impl Default for ImageMetadata {
+    fn default() -> Self {
+        Self {
+            icc_profile: None,
+            exif: None,
+            xmp: None,
+            orientation: Orientation::NoTransforms,
+        }
+    }
+}
+
+impl ImageMetadata {
+    /// Returns the embedded ICC color profile, if any
+    pub fn icc_profile(&self) -> Option<&[u8]> {
+        self.icc_profile.as_deref()
+    }
+
+    /// Replaces the embedded ICC color profile
+    pub fn set_icc_profile(&mut self, profile: Option<Vec<u8>>) {
+        self.icc_profile = profile;
+    }
+
+    /// Returns the raw Exif metadata, if any
+    pub fn exif(&self) -> Option<&[u8]> {
+        self.exif.as_deref()
+    }
+
+    /// Replaces the raw Exif metadata
+    pub fn set_exif(&mut self, exif: Option<Vec<u8>>) {
+        self.exif = exif;
+    }
+
+    /// Returns the raw XMP packet, if any
+    pub fn xmp(&self) -> Option<&[u8]> {
+        self.xmp.as_deref()
+    }
+
+    /// Replaces the raw XMP packet
+    pub fn set_xmp(&mut self, xmp: Option<Vec<u8>>) {
+        self.xmp = xmp;
+    }
+
+    /// Returns the orientation which should still be applied to the pixel data, to display the
+    /// image upright.
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Replaces the orientation which should still be applied to the pixel data
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+    }
+
+    /// Returns true if neither Exif, XMP nor ICC profile is present
+    pub fn is_empty(&self) -> bool {
+        self.icc_profile.is_none() && self.exif.is_none() && self.xmp.is_none()
+    }
+}
@@ -0,0 +1,400 @@
+//! Comparison of an image with a reference image, by perceptual and pixel error metrics, unlike
+//! the `diff` image operation, which only marks pixels as equal or different.
+//!
+//! The metrics are computed over the 8 bit RGB samples of both images, and the alpha samples if
+//! either image has an alpha channel. The frames of animated images are always RGBA, and are
+//! compared with the frame of the reference image at the same index.
+//!
+//! * The peak signal-to-noise ratio (PSNR), in decibels; infinite if the images are equal.
+//! * The structural similarity (SSIM) of the luma of the images, between -1 and 1, where 1 means
+//!   the images are structurally equal. It is the mean of the SSIM of 8x8 windows, which overlap
+//!   by half a window.
+//! * The mean and maximum absolute error of the samples.
+//!
+//! A heat map visualizes the error of each pixel, which is the maximum absolute error of its
+//! samples: from black (no error), via red and yellow, to white (the maximum error of the images).
+
+use crate::errors::SicImageEngineError;
+use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
+use sic_core::image::{DynamicImage, Rgba, RgbaImage};
+use sic_core::{image, AnimatedImage, SicImage};
+
+/// The result of comparing an image with a reference image.
+#[derive(Clone, Debug)]
+pub struct Comparison {
+    /// Peak signal-to-noise ratio in decibels; infinite if the images are equal.
+    pub psnr: f64,
+    /// Mean structural similarity, between -1 and 1.
+    pub ssim: f64,
+    pub mean_absolute_error: f64,
+    pub max_absolute_error: u8,
+    /// Error of each pixel, scaled by the maximum absolute error. For animated images, each
+    /// frame of the heat map keeps the delay of the compared frame.
+    pub heat_map: SicImage,
+}
+
+/// Compares the image with the reference image. Both images should have the same dimensions, and
+/// be either static, or animated with the same number of frames.
+pub fn compare(image: &SicImage, reference: &SicImage) -> Result<Comparison, SicImageEngineError> {
+    match (image, reference) {
+        (SicImage::Static(image), SicImage::Static(reference)) => {
+            let include_alpha = image.color().has_alpha() || reference.color().has_alpha();
+            let errors = compare_frame(&image.to_rgba8(), &reference.to_rgba8(), include_alpha)?;
+
+            let max = errors.max_absolute_error;
+            let heat_map = SicImage::Static(DynamicImage::ImageRgba8(errors.heat_map(max)));
+
+            Ok(errors.into_comparison(1, heat_map))
+        }
+        (SicImage::Animated(image), SicImage::Animated(reference)) => {
+            let (frames, reference_frames) = (image.frames(), reference.frames());
+
+            if frames.len() != reference_frames.len() {
+                return Err(SicImageEngineError::CompareFrameCountMismatch(
+                    frames.len(),
+                    reference_frames.len(),
+                ));
+            }
+
+            let errors = frames
+                .par_iter()
+                .zip(reference_frames)
+                .map(|(frame, reference)| compare_frame(frame.buffer(), reference.buffer(), true))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let max = errors
+                .iter()
+                .map(|errors| errors.max_absolute_error)
+                .max()
+                .unwrap_or(0);
+
+            let heat_map = SicImage::Animated(AnimatedImage::from_frames(
+                errors.iter().zip(frames).map(|(errors, frame)| {
+                    image::Frame::from_parts(errors.heat_map(max), 0, 0, frame.delay())
+                }),
+            ));
+
+            let frame_count = errors.len();
+            let total = errors
+                .into_iter()
+                .reduce(FrameErrors::merge)
+                .unwrap_or_default();
+
+            Ok(total.into_comparison(frame_count, heat_map))
+        }
+        _ => Err(SicImageEngineError::CompareStaticWithAnimated),
+    }
+}
+
+/// Errors of a single frame, or, once merged, of all frames.
+#[derive(Debug, Default)]
+struct FrameErrors {
+    width: u32,
+    height: u32,
+    samples: u64,
+    sum_absolute_error: u64,
+    sum_squared_error: u64,
+    max_absolute_error: u8,
+    /// Sum of the SSIM of the frames.
+    ssim: f64,
+    /// The maximum absolute error of the samples of each pixel.
+    pixel_errors: Vec<u8>,
+}
+
+impl FrameErrors {
+    fn merge(self, other: Self) -> Self {
+        Self {
+            width: self.width,
+            height: self.height,
+            samples: self.samples + other.samples,
+            sum_absolute_error: self.sum_absolute_error + other.sum_absolute_error,
+            sum_squared_error: self.sum_squared_error + other.sum_squared_error,
+            max_absolute_error: self.max_absolute_error.max(other.max_absolute_error),
+            ssim: self.ssim + other.ssim,
+            pixel_errors: Vec::new(),
+        }
+    }
+
+    fn into_comparison(self, frame_count: usize, heat_map: SicImage) -> Comparison {
+        let samples = self.samples.max(1) as f64;
+        let mse = self.sum_squared_error as f64 / samples;
+
+        Comparison {
+            psnr: if mse == 0.0 {
+                f64::INFINITY
+            } else {
+                10.0 * (255.0 * 255.0 / mse).log10()
+            },
+            ssim: self.ssim / frame_count.max(1) as f64,
+            mean_absolute_error: self.sum_absolute_error as f64 / samples,
+            max_absolute_error: self.max_absolute_error,
+            heat_map,
+        }
+    }
+
+    /// Colors each pixel by its error, relative to the given maximum error.
+    fn heat_map(&self, max: u8) -> RgbaImage {
+        RgbaImage::from_fn(self.width, self.height, |x, y| {
+            let error = self.pixel_errors[(y * self.width + x) as usize];
+            heat_color(if max == 0 {
+                0.0
+            } else {
+                f64::from(error) / f64::from(max)
+            })
+        })
+    }
+}
+
+/// Black (0.0), via red and yellow, to white (1.0).
+fn heat_color(t: f64) -> Rgba<u8> {
+    let channel = |offset: f64| ((t * 3.0 - offset).clamp(0.0, 1.0) * 255.0).round() as u8;
+
+    Rgba([channel(0.0), channel(1.0), channel(2.0), 255])
+}
+
+fn compare_frame(
+    image: &RgbaImage,
+    reference: &RgbaImage,
+    include_alpha: bool,
+) -> Result<FrameErrors, SicImageEngineError> {
+    if image.dimensions() != reference.dimensions() {
+        return Err(SicImageEngineError::CompareDimensionMismatch(
+            image.dimensions(),
+            reference.dimensions(),
+        ));
+    }
+
+    let channels = if include_alpha { 4 } else { 3 };
+    let (width, height) = image.dimensions();
+
+    let mut errors = FrameErrors {
+        width,
+        height,
+        pixel_errors: Vec::with_capacity((width * height) as usize),
+        ..FrameErrors::default()
+    };
+
+    for (lhs, rhs) in image.pixels().zip(reference.pixels()) {
+        let mut pixel_error = 0;
+
+        for (&l, &r) in lhs.0.iter().zip(rhs.0.iter()).take(channels) {
+            let error = l.abs_diff(r);
+
+            errors.sum_absolute_error += u64::from(error);
+            errors.sum_squared_error += u64::from(error) * u64::from(error);
+            pixel_error = pixel_error.max(error);
+        }
+
+        errors.max_absolute_error = errors.max_absolute_error.max(pixel_error);
+        errors.pixel_errors.push(pixel_error);
+    }
+
+    errors.samples = u64::from(width) * u64::from(height) * channels as u64;
+    errors.ssim = ssim(&luma(image), &luma(reference), width, height);
+
+    Ok(errors)
+}
+
+fn luma(image: &RgbaImage) -> Vec<f64> {
+    image
+        .pixels()
+        .map(|pixel| {
+            0.299 * f64::from(pixel[0]) + 0.587 * f64::from(pixel[1]) + 0.114 * f64::from(pixel[2])
+        })
+        .collect()
+}
+
+const SSIM_WINDOW: u32 = 8;
+const SSIM_STEP: u32 = SSIM_WINDOW / 2;
+const SSIM_C1: f64 = (0.01 * 255.0) * (0.01 * 255.0);
+const SSIM_C2: f64 = (0.03 * 255.0) * (0.03 * 255.0);
+
+/// The mean SSIM of the windows of the luma planes. Images smaller than a window are compared
+/// as a single window.
+fn ssim(lhs: &[f64], rhs: &[f64], width: u32, height: u32) -> f64 {
+    if width == 0 || height == 0 {
+        return 1.0;
+    }
+
+    let window_width = width.min(SSIM_WINDOW);
+    let window_height = height.min(SSIM_WINDOW);
+
+    let offsets = |size: u32, window: u32| {
+        (0..=size - window)
+            .step_by(SSIM_STEP as usize)
+            .collect::<Vec<_>>()
+    };
+
+    let xs = offsets(width, window_width);
+    let ys = offsets(height, window_height);
+
+    let total = ys
+        .iter()
+        .flat_map(|&y| xs.iter().map(move |&x| (x, y)))
+        .map(|(x, y)| {
+            let index = |dx: u32, dy: u32| ((y + dy) * width + x + dx) as usize;
+            let pixels = (0..window_height)
+                .flat_map(|dy| (0..window_width).map(move |dx| (dx, dy)))
+                .map(|(dx, dy)| (lhs[index(dx, dy)], rhs[index(dx, dy)]))
+                .collect::<Vec<_>>();
+
+            window_ssim(&pixels)
+        })
+        .sum::<f64>();
+
+    total / (xs.len() * ys.len()) as f64
+}
+
+fn window_ssim(pixels: &[(f64, f64)]) -> f64 {
+    let n = pixels.len() as f64;
+    let mean_l = pixels.iter().map(|(l, _)| l).sum::<f64>() / n;
+    let mean_r = pixels.iter().map(|(_, r)| r).sum::<f64>() / n;
+
+    let (var_l, var_r, covariance) =
+        pixels
+            .iter()
+            .fold((0.0, 0.0, 0.0), |(var_l, var_r, covariance), (l, r)| {
+                let (dl, dr) = (l - mean_l, r - mean_r);
+                (var_l + dl * dl, var_r + dr * dr, covariance + dl * dr)
+            });
+    let (var_l, var_r, covariance) = (var_l / n, var_r / n, covariance / n);
+
+    ((2.0 * mean_l * mean_r + SSIM_C1) * (2.0 * covariance + SSIM_C2))
+        / ((mean_l * mean_l + mean_r * mean_r + SSIM_C1) * (var_l + var_r + SSIM_C2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrapper::image_path::ImageFromPath;
+    use sic_core::image::{GenericImageView, RgbImage};
+    use sic_testing::{open_test_image, setup_test_image};
+
+    fn animated(name: &str) -> SicImage {
+        ImageFromPath::new(setup_test_image(name))
+            .open_image()
+            .unwrap()
+    }
+
+    fn rgb(width: u32, height: u32, f: impl Fn(u32, u32) -> [u8; 3]) -> SicImage {
+        SicImage::Static(DynamicImage::ImageRgb8(RgbImage::from_fn(
+            width,
+            height,
+            |x, y| image::Rgb(f(x, y)),
+        )))
+    }
+
+    #[test]
+    fn equal_images() {
+        let image = open_test_image(setup_test_image("unsplash_763569_cropped.jpg"));
+
+        let comparison = compare(&image, &image).unwrap();
+
+        assert!(comparison.psnr.is_infinite());
+        assert!((comparison.ssim - 1.0).abs() < 1e-9);
+        assert_eq!(comparison.mean_absolute_error, 0.0);
+        assert_eq!(comparison.max_absolute_error, 0);
+    }
+
+    #[test]
+    fn uniform_error() {
+        let image = rgb(16, 16, |x, y| [(x * 8) as u8, (y * 8) as u8, 0]);
+        let reference = rgb(16, 16, |x, y| [(x * 8) as u8 + 4, (y * 8) as u8, 0]);
+
+        let comparison = compare(&image, &reference).unwrap();
+
+        // one of three samples differs by 4: mse = 16 / 3
+        let expected_psnr = 10.0 * (255.0f64 * 255.0 / (16.0 / 3.0)).log10();
+        assert!((comparison.psnr - expected_psnr).abs() < 1e-9);
+        assert!((comparison.mean_absolute_error - 4.0 / 3.0).abs() < 1e-9);
+        assert_eq!(comparison.max_absolute_error, 4);
+        assert!(comparison.ssim > 0.99 && comparison.ssim < 1.0);
+    }
+
+    #[test]
+    fn structural_difference_lowers_ssim() {
+        let image = rgb(16, 16, |x, _| if x % 2 == 0 { [0; 3] } else { [255; 3] });
+        let inverted = rgb(16, 16, |x, _| if x % 2 == 0 { [255; 3] } else { [0; 3] });
+        let shifted = rgb(16, 16, |x, _| if x % 2 == 0 { [16; 3] } else { [255; 3] });
+
+        let ssim_inverted = compare(&image, &inverted).unwrap().ssim;
+        let ssim_shifted = compare(&image, &shifted).unwrap().ssim;
+
+        assert!(ssim_inverted < 0.0);
+        assert!(ssim_shifted > 0.9);
+    }
+
+    #[test]
+    fn heat_map_scaled_by_max_error() {
+        let image = rgb(3, 1, |_, _| [0; 3]);
+        let reference = rgb(3, 1, |x, _| [(x * 50) as u8, 0, 0]);
+
+        let comparison = compare(&image, &reference).unwrap();
+        let heat_map = match comparison.heat_map {
+            SicImage::Static(heat_map) => heat_map,
+            SicImage::Animated(_) => panic!("expected a static heat map"),
+        };
+
+        assert_eq!(heat_map.get_pixel(0, 0), Rgba([0, 0, 0, 255]));
+        // half of the maximum error
+        assert_eq!(heat_map.get_pixel(1, 0), Rgba([255, 128, 0, 255]));
+        assert_eq!(heat_map.get_pixel(2, 0), Rgba([255, 255, 255, 255]));
+    }
+
+    #[test]
+    fn alpha_is_compared_if_present() {
+        let image = SicImage::Static(DynamicImage::ImageRgba8(RgbaImage::from_pixel(
+            2,
+            2,
+            Rgba([0, 0, 0, 255]),
+        )));
+        let reference = SicImage::Static(DynamicImage::ImageRgba8(RgbaImage::from_pixel(
+            2,
+            2,
+            Rgba([0, 0, 0, 0]),
+        )));
+
+        let comparison = compare(&image, &reference).unwrap();
+
+        assert_eq!(comparison.max_absolute_error, 255);
+        assert!((comparison.mean_absolute_error - 255.0 / 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn animated_images() {
+        let image = animated("loop.gif");
+        let reference = animated("loop-diff.gif");
+
+        let comparison = compare(&image, &reference).unwrap();
+
+        assert!(comparison.max_absolute_error > 0);
+        match comparison.heat_map {
+            SicImage::Animated(heat_map) => assert_eq!(heat_map.frames().len(), 8),
+            SicImage::Static(_) => panic!("expected an animated heat map"),
+        }
+    }
+
+    #[test]
+    fn dimensions_mismatch() {
+        let result = compare(&rgb(2, 2, |_, _| [0; 3]), &rgb(2, 3, |_, _| [0; 3]));
+
+        assert!(matches!(
+            result,
+            Err(SicImageEngineError::CompareDimensionMismatch(
+                (2, 2),
+                (2, 3)
+            ))
+        ));
+    }
+
+    #[test]
+    fn static_with_animated() {
+        let image = animated("loop.gif");
+        let result = compare(&image, &rgb(100, 100, |_, _| [0; 3]));
+
+        assert!(matches!(
+            result,
+            Err(SicImageEngineError::CompareStaticWithAnimated)
+        ));
+    }
+}
@@ -2,12 +2,14 @@ use std::collections::HashMap;
 use std::hash::Hash;
 
 use sic_core::image::imageops::FilterType;
+use sic_core::image::metadata::Orientation;
 
 use crate::errors::SicImageEngineError;
 use crate::operations::ImageOperation;
 use crate::wrapper::filter_type::FilterTypeWrap;
+use crate::wrapper::resize_mode::ResizeMode;
 use crate::{operations, ImgOp};
-use sic_core::SicImage;
+use sic_core::{AnimatedImage, FrameRange, ImageMetadata, SicImage};
 
 trait EnvironmentKey {
     fn key(&self) -> ItemName;
@@ -18,6 +20,7 @@ trait EnvironmentKey {
 pub enum EnvItem {
     CustomSamplingFilter(FilterTypeWrap),
     PreserveAspectRatio(bool),
+    ShrinkOnly(bool),
 }
 
 impl EnvItem {
@@ -34,6 +37,13 @@ impl EnvItem {
             _ => None,
         }
     }
+
+    pub fn shrink_only(self) -> Option<bool> {
+        match self {
+            EnvItem::ShrinkOnly(k) => Some(k),
+            _ => None,
+        }
+    }
 }
 
 impl EnvironmentKey for EnvItem {
@@ -41,6 +51,7 @@ impl EnvironmentKey for EnvItem {
         match self {
             EnvItem::CustomSamplingFilter(_) => ItemName::CustomSamplingFilter,
             EnvItem::PreserveAspectRatio(_) => ItemName::PreserveAspectRatio,
+            EnvItem::ShrinkOnly(_) => ItemName::ShrinkOnly,
         }
     }
 }
@@ -77,6 +88,9 @@ pub enum Instr {
 pub struct ImageEngine {
     environment: Box<Env>,
     image: Box<SicImage>,
+    /// Metadata of the image, such as its Exif metadata and the orientation which has not been
+    /// applied yet.
+    metadata: ImageMetadata,
 }
 
 impl ImageEngine {
@@ -84,10 +98,29 @@ impl ImageEngine {
         Self {
             environment: Box::from(Env::default()),
             image: Box::from(image),
+            metadata: ImageMetadata::default(),
         }
     }
 
-    pub fn ignite(mut self, instructions: &[Instr]) -> Result<SicImage, SicImageEngineError> {
+    /// Set the metadata of the image, as obtained by the decoder.
+    /// The orientation of the metadata will be applied by the `auto-orient` image operation.
+    pub fn with_metadata(mut self, metadata: ImageMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    pub fn ignite(self, instructions: &[Instr]) -> Result<SicImage, SicImageEngineError> {
+        self.ignite_with_metadata(instructions)
+            .map(|(image, _)| image)
+    }
+
+    /// Like [`ImageEngine::ignite`], but also returns the metadata of the image, which reflects
+    /// the operations applied to the image. For example, after the `auto-orient` operation, the
+    /// orientation of the metadata no longer needs to be applied.
+    pub fn ignite_with_metadata(
+        mut self,
+        instructions: &[Instr],
+    ) -> Result<(SicImage, ImageMetadata), SicImageEngineError> {
         for instruction in instructions {
             match self.process_instruction(instruction) {
                 Ok(_) => continue,
@@ -95,7 +128,7 @@ impl ImageEngine {
             }
         }
 
-        Ok(*self.image)
+        Ok((*self.image, self.metadata))
     }
 
     fn process_instruction(&mut self, instruction: &Instr) -> Result<(), SicImageEngineError> {
@@ -108,6 +141,15 @@ impl ImageEngine {
 
     fn process_operation(&mut self, operation: &ImgOp) -> Result<(), SicImageEngineError> {
         match operation {
+            ImgOp::AutoOrient => {
+                operations::auto_orient::AutoOrient::new(self.metadata.orientation())
+                    .apply_operation(&mut self.image)?;
+
+                // The orientation has been applied, so applying it again should be a no-op.
+                self.metadata.set_orientation(Orientation::NoTransforms);
+
+                Ok(())
+            }
             ImgOp::Blur(sigma) => {
                 operations::blur::Blur::new(*sigma).apply_operation(&mut self.image)
             }
@@ -117,14 +159,36 @@ impl ImageEngine {
             ImgOp::Contrast(f) => {
                 operations::contrast::Contrast::new(*f).apply_operation(&mut self.image)
             }
+            ImgOp::ConvertProfile(target) => {
+                let target = target.load()?;
+
+                operations::convert_profile::ConvertProfile::new(
+                    self.metadata.icc_profile(),
+                    target.profile(),
+                )
+                .apply_operation(&mut self.image)?;
+
+                // The pixels are now in the color space of the target profile.
+                self.metadata.set_icc_profile(target.into_raw());
+
+                Ok(())
+            }
             ImgOp::Crop((lx, ly, rx, ry)) => {
                 operations::crop::Crop::new((*lx, *ly), (*rx, *ry)).apply_operation(&mut self.image)
             }
-            ImgOp::Diff(path) => operations::diff::Diff::new(path).apply_operation(&mut self.image),
+            ImgOp::Curves(points) => {
+                operations::curves::Curves::new(points).apply_operation(&mut self.image)
+            }
+            ImgOp::Diff(inputs) => {
+                operations::diff::Diff::new(inputs).apply_operation(&mut self.image)
+            }
             #[cfg(feature = "imageproc-ops")]
             ImgOp::DrawText(inner) => {
                 operations::draw_text::DrawText::new(inner).apply_operation(&mut self.image)
             }
+            ImgOp::DropFrames(range) => {
+                operations::drop_frames::DropFrames::new(*range).apply_operation(&mut self.image)
+            }
             ImgOp::Filter3x3(ref kernel) => {
                 operations::filter3x3::Filter3x3::new(kernel).apply_operation(&mut self.image)
             }
@@ -134,6 +198,9 @@ impl ImageEngine {
             ImgOp::FlipVertical => {
                 operations::flip_vertical::FlipVertical::new().apply_operation(&mut self.image)
             }
+            ImgOp::Gamma(gamma) => {
+                operations::gamma::Gamma::new(*gamma).apply_operation(&mut self.image)
+            }
             ImgOp::Grayscale => {
                 operations::grayscale::Grayscale::new().apply_operation(&mut self.image)
             }
@@ -145,14 +212,33 @@ impl ImageEngine {
                     .apply_operation(&mut self.image)
             }
             ImgOp::Invert => operations::invert::Invert::new().apply_operation(&mut self.image),
+            ImgOp::Levels(inputs) => {
+                operations::levels::Levels::new(inputs).apply_operation(&mut self.image)
+            }
+            ImgOp::OnFrames(range, instructions) => self.process_on_frames(range, instructions),
             ImgOp::Overlay(inputs) => {
                 operations::overlay::Overlay::new(inputs).apply_operation(&mut self.image)
             }
+            ImgOp::Pad(inputs) => {
+                operations::pad::Pad::new(inputs).apply_operation(&mut self.image)
+            }
             ImgOp::Resize((x, y)) => {
-                let aspect_ratio = should_preserve_aspect_ratio(&self.environment);
-                let sampling_filter = resize_filter_or_default(&self.environment);
-                let op = operations::resize::Resize::new(*x, *y, aspect_ratio, sampling_filter);
-                op.apply_operation(&mut self.image)
+                // without an explicit mode, the preserve-aspect-ratio modifier decides the mode
+                let mode = if should_preserve_aspect_ratio(&self.environment) {
+                    ResizeMode::Fit((*x, *y))
+                } else {
+                    ResizeMode::Exact((*x, *y))
+                };
+
+                self.process_resize(mode)
+            }
+            ImgOp::ResizeWithMode(mode) => self.process_resize(*mode),
+            ImgOp::ReverseFrames => {
+                operations::reverse_frames::ReverseFrames::new().apply_operation(&mut self.image)
+            }
+            #[cfg(feature = "imageproc-ops")]
+            ImgOp::Rotate(inputs) => {
+                operations::rotate::Rotate::new(inputs).apply_operation(&mut self.image)
             }
             ImgOp::Rotate90 => {
                 operations::rotate90::Rotate90::new().apply_operation(&mut self.image)
@@ -163,10 +249,23 @@ impl ImageEngine {
             ImgOp::Rotate270 => {
                 operations::rotate270::Rotate270::new().apply_operation(&mut self.image)
             }
+            ImgOp::SetFrameDelay(delay) => operations::set_frame_delay::SetFrameDelay::new(*delay)
+                .apply_operation(&mut self.image),
+            ImgOp::SmartCrop((width, height)) => {
+                let sampling_filter = resize_filter_or_default(&self.environment);
+                operations::smart_crop::SmartCrop::new(*width, *height, sampling_filter)
+                    .apply_operation(&mut self.image)
+            }
+            ImgOp::Speed(factor) => {
+                operations::speed::Speed::new(*factor).apply_operation(&mut self.image)
+            }
             #[cfg(feature = "imageproc-ops")]
             ImgOp::Threshold => {
                 operations::threshold::Threshold::new().apply_operation(&mut self.image)
             }
+            ImgOp::Trim(inputs) => {
+                operations::trim::Trim::new(inputs).apply_operation(&mut self.image)
+            }
             ImgOp::Unsharpen((sigma, threshold)) => {
                 operations::unsharpen::Unsharpen::new(*sigma, *threshold)
                     .apply_operation(&mut self.image)
@@ -178,6 +277,101 @@ impl ImageEngine {
         }
     }
 
+    // Applies the instructions to the selected frames only. The selected frames are processed as
+    // an animated image of their own, by an engine which starts with a copy of the current
+    // environment, so modifiers set within the block don't affect operations outside of it.
+    // A static image is treated as an image with a single frame.
+    //
+    // The metadata describes the whole image, so changes to the metadata made within the block
+    // (e.g. by auto-orient or convert-profile) are only kept when the block applies to every frame;
+    // otherwise they are refused, like operations which leave frames of different dimensions.
+    fn process_on_frames(
+        &mut self,
+        range: &FrameRange,
+        instructions: &[Instr],
+    ) -> Result<(), SicImageEngineError> {
+        let nested = |image: SicImage| Self {
+            environment: self.environment.clone(),
+            image: Box::from(image),
+            metadata: self.metadata.clone(),
+        };
+
+        match self.image.as_mut() {
+            SicImage::Static(_) if range.contains(0) => {
+                let (image, metadata) =
+                    nested(*self.image.clone()).ignite_with_metadata(instructions)?;
+                self.image = Box::from(image);
+                self.metadata = metadata;
+            }
+            SicImage::Static(_) => {}
+            SicImage::Animated(animated) => {
+                let frame_count = animated.frames().len();
+                let indices = range.indices(frame_count).collect::<Vec<_>>();
+
+                let selection = AnimatedImage::from_frames(
+                    indices
+                        .iter()
+                        .map(|&index| animated.frames()[index].clone()),
+                );
+
+                let (processed, metadata) =
+                    nested(SicImage::Animated(selection)).ignite_with_metadata(instructions)?;
+                let processed = match processed {
+                    SicImage::Animated(processed) => processed.into_frames(),
+                    SicImage::Static(_) => {
+                        return Err(SicImageEngineError::OnFramesFrameCountChanged(
+                            indices.len(),
+                            1,
+                        ))
+                    }
+                };
+
+                if processed.len() != indices.len() {
+                    return Err(SicImageEngineError::OnFramesFrameCountChanged(
+                        indices.len(),
+                        processed.len(),
+                    ));
+                }
+
+                let selects_all_frames = indices.len() == frame_count;
+                if !selects_all_frames && metadata != self.metadata {
+                    return Err(SicImageEngineError::OnFramesMetadataChanged);
+                }
+
+                let frames = animated.frames_mut();
+                for (index, frame) in indices.into_iter().zip(processed) {
+                    frames[index] = frame;
+                }
+
+                if let Some(first) = frames.first() {
+                    let expected = first.buffer().dimensions();
+
+                    if let Some(frame) = frames
+                        .iter()
+                        .find(|frame| frame.buffer().dimensions() != expected)
+                    {
+                        let (width, height) = frame.buffer().dimensions();
+                        return Err(SicImageEngineError::OnFramesDimensionsChanged(
+                            expected.0, expected.1, width, height,
+                        ));
+                    }
+                }
+
+                self.metadata = metadata;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn process_resize(&mut self, mode: ResizeMode) -> Result<(), SicImageEngineError> {
+        let shrink_only = should_shrink_only(&self.environment);
+        let sampling_filter = resize_filter_or_default(&self.environment);
+
+        operations::resize::Resize::new(mode, shrink_only, sampling_filter)
+            .apply_operation(&mut self.image)
+    }
+
     fn insert_env(&mut self, item: EnvItem) -> Result<(), SicImageEngineError> {
         self.environment.insert_or_update(item);
 
@@ -211,6 +405,12 @@ fn should_preserve_aspect_ratio(env: &Env) -> bool {
         .unwrap_or_default()
 }
 
+fn should_shrink_only(env: &Env) -> bool {
+    env.get(ItemName::ShrinkOnly)
+        .and_then(|item| item.shrink_only())
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod compatibility {
     use sic_core::SicImage;
@@ -314,10 +514,19 @@ mod tests {
     use super::*;
     use crate::engine::compatibility::*;
     use crate::operations::diff::{DIFF_PX_DIFF, DIFF_PX_NO_OVERLAP, DIFF_PX_SAME};
+    use crate::test_helpers::{animated_image, WHITE};
+    use crate::wrapper::diff::{DiffInputs, DiffOptions};
     use crate::wrapper::gradient_input::GradientInput;
+    use crate::wrapper::icc_profile::IccProfileTarget;
     use crate::wrapper::image_path::ImageFromPath;
+    use crate::wrapper::levels::LevelsInputs;
+    use crate::wrapper::pad::{Margins, PadInputs, PadSize};
+    #[cfg(feature = "imageproc-ops")]
+    use crate::wrapper::rotate::RotateInputs;
+    use crate::wrapper::trim::TrimInputs;
     use sic_core::image::imageops::FilterType;
-    use sic_core::image::Rgba;
+    use sic_core::image::metadata::Orientation;
+    use sic_core::image::{DynamicImage, GrayImage, Luma, Pixel, Rgb, Rgba, RgbaImage};
     use sic_testing::*;
     use std::path::PathBuf;
 
@@ -340,8 +549,8 @@ mod tests {
         const RIGHT: &str = "3x2_wbaaba.png";
 
         let engine = ImageEngine::new(left);
-        let out = engine.ignite(&[Instr::Operation(ImgOp::Diff(ImageFromPath::new(
-            PathBuf::from(in_!(RIGHT)),
+        let out = engine.ignite(&[Instr::Operation(ImgOp::Diff(DiffInputs::new(
+            ImageFromPath::new(PathBuf::from(in_!(RIGHT))),
         )))]);
 
         let out = out.unwrap();
@@ -362,6 +571,94 @@ mod tests {
         output_test_image_for_manual_inspection(&out, out_!("test_diff_3x3.png"));
     }
 
+    fn diff_with_options(options: DiffOptions) -> SicImage {
+        let left = sic_testing::open_test_image(sic_testing::in_!("2x3_wrabaa.png"));
+        let right = ImageFromPath::new(PathBuf::from(in_!("3x2_wbaaba.png")));
+
+        let engine = ImageEngine::new(left);
+        engine
+            .ignite(&[Instr::Operation(ImgOp::Diff(DiffInputs::with_options(
+                right, options,
+            )))])
+            .unwrap()
+    }
+
+    #[test]
+    fn diff_with_tolerance() {
+        let out = diff_with_options(DiffOptions {
+            tolerance: [255; 4],
+            ..DiffOptions::default()
+        });
+
+        // all overlapping pixels are within the tolerance
+        assert_eq!(out.get_pixel(0, 0), DIFF_PX_SAME);
+        assert_eq!(out.get_pixel(1, 0), DIFF_PX_SAME);
+        assert_eq!(out.get_pixel(0, 1), DIFF_PX_SAME);
+        assert_eq!(out.get_pixel(1, 1), DIFF_PX_SAME);
+        // pixels which are part of one image only still differ
+        assert_eq!(out.get_pixel(2, 0), DIFF_PX_DIFF);
+        assert_eq!(out.get_pixel(0, 2), DIFF_PX_DIFF);
+        assert_eq!(out.get_pixel(2, 2), DIFF_PX_NO_OVERLAP);
+    }
+
+    #[test]
+    fn diff_with_colors() {
+        let same = Rgba([0, 0, 0, 255]);
+        let different = Rgba([0, 255, 0, 255]);
+        let no_overlap = Rgba([0, 0, 255, 255]);
+
+        let out = diff_with_options(DiffOptions {
+            same,
+            different,
+            no_overlap,
+            ..DiffOptions::default()
+        });
+
+        assert_eq!(out.get_pixel(0, 0), same);
+        assert_eq!(out.get_pixel(1, 0), different);
+        assert_eq!(out.get_pixel(2, 0), different);
+        assert_eq!(out.get_pixel(2, 2), no_overlap);
+    }
+
+    #[test]
+    fn diff_with_dimmed_original() {
+        let left = sic_testing::open_test_image(sic_testing::in_!("2x3_wrabaa.png"));
+        let out = diff_with_options(DiffOptions {
+            dim: Some(0.5),
+            ..DiffOptions::default()
+        });
+
+        let dimmed = |x, y| {
+            left.get_pixel(x, y)
+                .map_without_alpha(|channel| (f32::from(channel) * 0.5).round() as u8)
+        };
+
+        // the same pixels show the dimmed original
+        assert_eq!(out.get_pixel(0, 0), dimmed(0, 0));
+        assert_eq!(out.get_pixel(1, 1), dimmed(1, 1));
+        // the opaque colour of differing pixels covers the original
+        assert_eq!(out.get_pixel(1, 0), DIFF_PX_DIFF);
+        assert_eq!(out.get_pixel(2, 0), DIFF_PX_DIFF);
+        assert_eq!(out.get_pixel(2, 2), DIFF_PX_NO_OVERLAP);
+    }
+
+    #[test]
+    fn diff_with_dimmed_original_blends_translucent_color() {
+        let left = sic_testing::open_test_image(sic_testing::in_!("2x3_wrabaa.png"));
+        let different = Rgba([0, 0, 255, 128]);
+        let out = diff_with_options(DiffOptions {
+            different,
+            dim: Some(1.0),
+            ..DiffOptions::default()
+        });
+
+        let mut expected = left.get_pixel(1, 0).map_without_alpha(|_| 0);
+        expected.blend(&different);
+
+        assert_eq!(out.get_pixel(1, 0), expected);
+        assert_ne!(expected, different);
+    }
+
     mod sizes {
         use super::*;
 
@@ -402,8 +699,8 @@ mod tests {
             let left_img = sic_testing::open_test_image(sic_testing::in_!(left));
 
             let engine = ImageEngine::new(left_img);
-            let out = engine.ignite(&[Instr::Operation(ImgOp::Diff(ImageFromPath::new(
-                PathBuf::from(in_!(right)),
+            let out = engine.ignite(&[Instr::Operation(ImgOp::Diff(DiffInputs::new(
+                ImageFromPath::new(PathBuf::from(in_!(right))),
             )))]);
 
             let out = out.unwrap();
@@ -603,6 +900,202 @@ mod tests {
         );
     }
 
+    fn rotated_metadata() -> ImageMetadata {
+        let mut metadata = ImageMetadata::default();
+        metadata.set_orientation(Orientation::Rotate90);
+        metadata
+    }
+
+    #[test]
+    fn test_auto_orient() {
+        let img = open_test_image(in_!("rainbow_8x6.bmp"));
+        let expected = img.as_ref().rotate90();
+
+        let operator = ImageEngine::new(img).with_metadata(rotated_metadata());
+        let done = operator.ignite(&[Instr::Operation(ImgOp::AutoOrient)]);
+
+        assert!(done.is_ok());
+
+        let img_result = done.unwrap();
+        assert_eq!(img_result.dimensions(), (6, 8));
+        assert_eq!(img_result.raw_pixels(), expected.into_bytes());
+
+        output_test_image_for_manual_inspection(&img_result, out_!("test_auto_orient.png"));
+    }
+
+    #[test]
+    fn test_auto_orient_applied_once() {
+        let img = open_test_image(in_!("rainbow_8x6.bmp"));
+
+        let operator = ImageEngine::new(img).with_metadata(rotated_metadata());
+        let done = operator.ignite(&[
+            Instr::Operation(ImgOp::AutoOrient),
+            Instr::Operation(ImgOp::AutoOrient),
+        ]);
+
+        assert_eq!(done.unwrap().dimensions(), (6, 8));
+    }
+
+    #[test]
+    fn test_auto_orient_resets_orientation() {
+        let img = open_test_image(in_!("rainbow_8x6.bmp"));
+
+        let operator = ImageEngine::new(img).with_metadata(rotated_metadata());
+        let (_, metadata) = operator
+            .ignite_with_metadata(&[Instr::Operation(ImgOp::AutoOrient)])
+            .unwrap();
+
+        assert_eq!(metadata.orientation(), Orientation::NoTransforms);
+    }
+
+    #[test]
+    fn test_auto_orient_no_orientation() {
+        let img = open_test_image(in_!("rainbow_8x6.bmp"));
+        let expected = img.raw_pixels();
+
+        let operator = ImageEngine::new(img);
+        let done = operator.ignite(&[Instr::Operation(ImgOp::AutoOrient)]);
+
+        assert_eq!(done.unwrap().raw_pixels(), expected);
+    }
+
+    fn with_icc_profile(profile: Option<Vec<u8>>) -> ImageMetadata {
+        let mut metadata = ImageMetadata::default();
+        metadata.set_icc_profile(profile);
+        metadata
+    }
+
+    #[test]
+    fn test_convert_profile_srgb_without_profile() {
+        let img = open_test_image(in_!("rainbow_8x6.bmp"));
+        let expected = img.raw_pixels();
+
+        let operator = ImageEngine::new(img);
+        let (image, metadata) = operator
+            .ignite_with_metadata(&[Instr::Operation(ImgOp::ConvertProfile(
+                IccProfileTarget::Srgb,
+            ))])
+            .unwrap();
+
+        // sRGB to sRGB should leave the pixels (nearly) as is
+        let max_difference = image
+            .raw_pixels()
+            .iter()
+            .zip(expected.iter())
+            .map(|(lhs, rhs)| lhs.abs_diff(*rhs))
+            .max();
+
+        assert!(max_difference <= Some(1));
+        assert!(metadata.icc_profile().is_none());
+    }
+
+    #[test]
+    fn test_convert_profile_to_srgb() {
+        let img = open_test_image(in_!("rainbow_8x6.bmp"));
+        let expected = img.raw_pixels();
+        let display_p3 = std::fs::read(in_!("display_p3.icc")).unwrap();
+
+        let operator = ImageEngine::new(img).with_metadata(with_icc_profile(Some(display_p3)));
+        let (image, metadata) = operator
+            .ignite_with_metadata(&[Instr::Operation(ImgOp::ConvertProfile(
+                IccProfileTarget::Srgb,
+            ))])
+            .unwrap();
+
+        assert_ne!(image.raw_pixels(), expected);
+        assert!(metadata.icc_profile().is_none());
+
+        output_test_image_for_manual_inspection(&image, out_!("test_convert_profile_to_srgb.png"));
+    }
+
+    #[test]
+    fn test_convert_profile_to_path() {
+        let img = open_test_image(in_!("rainbow_8x6.bmp"));
+        let display_p3 = std::fs::read(in_!("display_p3.icc")).unwrap();
+
+        let operator = ImageEngine::new(img);
+        let (image, metadata) = operator
+            .ignite_with_metadata(&[Instr::Operation(ImgOp::ConvertProfile(
+                IccProfileTarget::Path(PathBuf::from(in_!("display_p3.icc"))),
+            ))])
+            .unwrap();
+
+        // Fully saturated sRGB red is less saturated in the wider Display P3 color space
+        let red = image.get_pixel(0, 0);
+        assert!(red[0] < 255);
+        assert!(red[1] > 0);
+
+        assert_eq!(metadata.icc_profile(), Some(display_p3.as_slice()));
+    }
+
+    fn gray_profile() -> Vec<u8> {
+        moxcms::ColorProfile::new_gray_with_gamma(2.2)
+            .encode()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_convert_profile_gray_to_srgb() {
+        let ramp = GrayImage::from_fn(16, 1, |x, _| Luma([(x * 17) as u8]));
+        let img = SicImage::Static(DynamicImage::ImageLuma8(ramp));
+
+        let operator = ImageEngine::new(img).with_metadata(with_icc_profile(Some(gray_profile())));
+        let image = operator
+            .ignite(&[Instr::Operation(ImgOp::ConvertProfile(
+                IccProfileTarget::Srgb,
+            ))])
+            .unwrap();
+
+        // the gray pixels stay gray, from black to white
+        let image = image.as_ref().as_rgb8().unwrap();
+        assert_eq!(image.dimensions(), (16, 1));
+        assert!(image.pixels().all(|Rgb([r, g, b])| r == g && g == b));
+        assert_eq!(image.get_pixel(0, 0), &Rgb([0, 0, 0]));
+        assert_eq!(image.get_pixel(15, 0), &Rgb([255, 255, 255]));
+    }
+
+    #[test]
+    fn test_convert_profile_gray_animated_image() {
+        let img = animated_image([
+            RgbaImage::from_pixel(2, 2, Rgba([0, 0, 0, 255])),
+            RgbaImage::from_pixel(2, 2, Rgba([255, 255, 255, 128])),
+        ]);
+
+        let operator = ImageEngine::new(img).with_metadata(with_icc_profile(Some(gray_profile())));
+        let image = operator
+            .ignite(&[Instr::Operation(ImgOp::ConvertProfile(
+                IccProfileTarget::Srgb,
+            ))])
+            .unwrap();
+
+        match image {
+            SicImage::Animated(image) => {
+                let frames = image.frames();
+                assert_eq!(frames[0].buffer().get_pixel(0, 0), &Rgba([0, 0, 0, 255]));
+                assert_eq!(
+                    frames[1].buffer().get_pixel(0, 0),
+                    &Rgba([255, 255, 255, 128])
+                );
+            }
+            SicImage::Static(_) => panic!("Expected an animated image"),
+        }
+    }
+
+    #[test]
+    fn test_convert_profile_invalid_source_profile() {
+        let img = open_test_image(in_!("rainbow_8x6.bmp"));
+
+        let operator = ImageEngine::new(img).with_metadata(with_icc_profile(Some(vec![0; 16])));
+        let done = operator.ignite(&[Instr::Operation(ImgOp::ConvertProfile(
+            IccProfileTarget::Srgb,
+        ))]);
+
+        assert!(matches!(
+            done,
+            Err(SicImageEngineError::InvalidIccProfile(_))
+        ));
+    }
+
     #[test]
     fn test_blur() {
         let img = setup_default_test_image();
@@ -860,6 +1353,26 @@ mod tests {
         assert!(done.is_err());
     }
 
+    #[test]
+    fn test_curves() {
+        let img = setup_default_test_image();
+        assert_eq!(img.get_pixel(100, 100), Rgba([90, 99, 99, 255]));
+
+        let operation = ImgOp::Curves(vec![(0, 0), (128, 160), (255, 255)]);
+
+        let operator = ImageEngine::new(img);
+        let done = operator.ignite(&[Instr::Operation(operation)]);
+
+        assert!(done.is_ok());
+
+        let result_img = done.unwrap();
+
+        // the curve rises above the diagonal, so the midtones are brightened
+        assert_eq!(result_img.get_pixel(100, 100), Rgba([117, 128, 128, 255]));
+
+        output_test_image_for_manual_inspection(&result_img, out_!("test_curves.png"));
+    }
+
     #[test]
     fn test_filter3x3() {
         let img = setup_default_test_image();
@@ -919,6 +1432,26 @@ mod tests {
         output_test_image_for_manual_inspection(&img_result, out_!("test_flipv.png"));
     }
 
+    #[test]
+    fn test_gamma() {
+        let img = setup_default_test_image();
+        assert_eq!(img.get_pixel(100, 100), Rgba([90, 99, 99, 255]));
+
+        let operation = ImgOp::Gamma(2.0);
+
+        let operator = ImageEngine::new(img);
+        let done = operator.ignite(&[Instr::Operation(operation)]);
+
+        assert!(done.is_ok());
+
+        let result_img = done.unwrap();
+
+        // round((90 / 255)^(1 / 2) * 255) = 151
+        assert_eq!(result_img.get_pixel(100, 100), Rgba([151, 159, 159, 255]));
+
+        output_test_image_for_manual_inspection(&result_img, out_!("test_gamma_2.png"));
+    }
+
     #[test]
     fn test_gray_scale() {
         use sic_core::image::Pixel;
@@ -1069,6 +1602,50 @@ mod tests {
         output_test_image_for_manual_inspection(&result_img, out_!("test_invert.png"));
     }
 
+    #[test]
+    fn test_levels() {
+        let img = setup_default_test_image();
+        assert_eq!(img.get_pixel(100, 100), Rgba([90, 99, 99, 255]));
+
+        let operation = ImgOp::Levels(LevelsInputs::new(30, 225));
+
+        let operator = ImageEngine::new(img);
+        let done = operator.ignite(&[Instr::Operation(operation)]);
+
+        assert!(done.is_ok());
+
+        let result_img = done.unwrap();
+
+        // round((90 - 30) / (225 - 30) * 255) = 78
+        assert_eq!(result_img.get_pixel(100, 100), Rgba([78, 90, 90, 255]));
+
+        output_test_image_for_manual_inspection(&result_img, out_!("test_levels_30_225.png"));
+    }
+
+    #[test]
+    fn test_pad() {
+        // 217x447px => 227x467px
+        let img = setup_default_test_image();
+        let size = PadSize::Margins(Margins {
+            top: 10,
+            right: 5,
+            bottom: 10,
+            left: 5,
+        });
+        let operation = ImgOp::Pad(PadInputs::with_fill(size, WHITE));
+
+        let operator = ImageEngine::new(img);
+        let done = operator.ignite(&[Instr::Operation(operation)]);
+
+        assert!(done.is_ok());
+
+        let img_result = done.unwrap();
+        assert_eq!(img_result.dimensions(), (227, 467));
+        assert_eq!(img_result.get_pixel(0, 0), WHITE);
+
+        output_test_image_for_manual_inspection(&img_result, out_!("test_pad.png"));
+    }
+
     mod overlay {
         use super::*;
         use crate::wrapper::overlay::OverlayInputs;
@@ -1191,6 +1768,41 @@ mod tests {
         output_test_image_for_manual_inspection(&img_result, out_!("test_scale_250x500.png"));
     }
 
+    #[test]
+    fn test_resize_with_mode() {
+        // 217x447px => 100x447, as the exact mode ignores the aspect ratio, and only shrinks
+        let img = setup_default_test_image();
+
+        let operator = ImageEngine::new(img);
+        let done = operator.ignite(&[
+            Instr::EnvAdd(EnvItem::PreserveAspectRatio(true)),
+            Instr::EnvAdd(EnvItem::ShrinkOnly(true)),
+            Instr::Operation(ImgOp::ResizeWithMode(ResizeMode::Exact((100, 1000)))),
+        ]);
+
+        assert!(done.is_ok());
+
+        let img_result = done.unwrap();
+        assert_eq!(img_result.dimensions(), (100, 447));
+    }
+
+    #[test]
+    fn test_resize_shrink_only() {
+        // 217x447px => 100x447
+        let img = setup_default_test_image();
+
+        let operator = ImageEngine::new(img);
+        let done = operator.ignite(&[
+            Instr::EnvAdd(EnvItem::ShrinkOnly(true)),
+            Instr::Operation(ImgOp::Resize((100, 1000))),
+        ]);
+
+        assert!(done.is_ok());
+
+        let img_result = done.unwrap();
+        assert_eq!(img_result.dimensions(), (100, 447));
+    }
+
     #[test]
     fn test_rotate90() {
         let img = setup_default_test_image();
@@ -1251,6 +1863,24 @@ mod tests {
         output_test_image_for_manual_inspection(&img_result, out_!("test_rotate270.png"));
     }
 
+    #[cfg(feature = "imageproc-ops")]
+    #[test]
+    fn test_rotate() {
+        // 217x447px => 447x217px, as a right angle is rotated exactly
+        let img = setup_default_test_image();
+        let operation = ImgOp::Rotate(RotateInputs::new(90.0));
+
+        let operator = ImageEngine::new(img);
+        let done = operator.ignite(&[Instr::Operation(operation)]);
+
+        assert!(done.is_ok());
+
+        let img_result = done.unwrap();
+        assert_eq!(img_result.dimensions(), (447, 217));
+
+        output_test_image_for_manual_inspection(&img_result, out_!("test_rotate_90.png"));
+    }
+
     #[test]
     fn test_unsharpen_pos() {
         let img = setup_default_test_image();
@@ -1309,6 +1939,414 @@ mod tests {
         output_test_image_for_manual_inspection(&result_img, out_!("test_threshold.png"));
     }
 
+    #[test]
+    fn test_trim() {
+        // 217x447px => 227x457px => 217x447px, as the white border is cropped away
+        let img = setup_default_test_image();
+        let operation = ImgOp::Pad(PadInputs::with_fill(
+            PadSize::Margins(Margins::uniform(5)),
+            WHITE,
+        ));
+
+        let operator = ImageEngine::new(img);
+        let done = operator.ignite(&[
+            Instr::Operation(operation),
+            Instr::Operation(ImgOp::Trim(TrimInputs::default())),
+        ]);
+
+        assert!(done.is_ok());
+
+        let img_result = done.unwrap();
+        assert_eq!(img_result.dimensions(), (217, 447));
+
+        output_test_image_for_manual_inspection(&img_result, out_!("test_trim.png"));
+    }
+
+    #[test]
+    fn test_smart_crop() {
+        // 217x447px => 217x217px (most interesting square) => 100x100px
+        let img = setup_default_test_image();
+        let operation = ImgOp::SmartCrop((100, 100));
+
+        let operator = ImageEngine::new(img);
+        let done = operator.ignite(&[Instr::Operation(operation)]);
+
+        assert!(done.is_ok());
+
+        let img_result = done.unwrap();
+        assert_eq!(img_result.dimensions(), (100, 100));
+
+        output_test_image_for_manual_inspection(&img_result, out_!("test_smart_crop.png"));
+    }
+
+    mod frame_timing {
+        use super::*;
+        use crate::wrapper::frame_delay::FrameDelay;
+        use sic_core::image::{Delay, Frame, RgbaImage};
+        use sic_core::AnimatedImage;
+        use std::time::Duration;
+
+        pub(super) fn animated_image(delays_ms: &[u32]) -> SicImage {
+            let frames = delays_ms.iter().enumerate().map(|(i, ms)| {
+                Frame::from_parts(
+                    RgbaImage::from_pixel(4, 2, Rgba([i as u8, 0, 0, 255])),
+                    0,
+                    0,
+                    Delay::from_numer_denom_ms(*ms, 1),
+                )
+            });
+
+            SicImage::Animated(AnimatedImage::from_frames(frames))
+        }
+
+        pub(super) fn delays_ms(image: &SicImage) -> Vec<u128> {
+            match image {
+                SicImage::Animated(image) => image
+                    .frames()
+                    .iter()
+                    .map(|frame| Duration::from(frame.delay()).as_millis())
+                    .collect(),
+                SicImage::Static(_) => panic!("Expected an animated image"),
+            }
+        }
+
+        pub(super) fn first_pixels(image: &SicImage) -> Vec<u8> {
+            match image {
+                SicImage::Animated(image) => image
+                    .frames()
+                    .iter()
+                    .map(|frame| frame.buffer().get_pixel(0, 0).0[0])
+                    .collect(),
+                SicImage::Static(_) => panic!("Expected an animated image"),
+            }
+        }
+
+        #[test]
+        fn set_frame_delay() {
+            let engine = ImageEngine::new(animated_image(&[10, 20, 30]));
+            let delay = FrameDelay::new(Duration::from_millis(50));
+
+            let image = engine
+                .ignite(&[Instr::Operation(ImgOp::SetFrameDelay(delay))])
+                .unwrap();
+
+            assert_eq!(delays_ms(&image), vec![50, 50, 50]);
+        }
+
+        #[test]
+        fn speed_up() {
+            let engine = ImageEngine::new(animated_image(&[10, 20, 30]));
+
+            let image = engine
+                .ignite(&[Instr::Operation(ImgOp::Speed(2.0))])
+                .unwrap();
+
+            assert_eq!(delays_ms(&image), vec![5, 10, 15]);
+        }
+
+        #[test]
+        fn slow_down() {
+            let engine = ImageEngine::new(animated_image(&[10, 20, 30]));
+
+            let image = engine
+                .ignite(&[Instr::Operation(ImgOp::Speed(0.5))])
+                .unwrap();
+
+            assert_eq!(delays_ms(&image), vec![20, 40, 60]);
+        }
+
+        #[test]
+        fn speed_invalid_factor() {
+            for factor in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+                let engine = ImageEngine::new(animated_image(&[10]));
+                let result = engine.ignite(&[Instr::Operation(ImgOp::Speed(factor))]);
+
+                assert!(matches!(
+                    result,
+                    Err(SicImageEngineError::InvalidSpeedFactor(_))
+                ));
+            }
+        }
+
+        #[test]
+        fn reverse_frames() {
+            let engine = ImageEngine::new(animated_image(&[10, 20, 30]));
+
+            let image = engine
+                .ignite(&[Instr::Operation(ImgOp::ReverseFrames)])
+                .unwrap();
+
+            // Each frame keeps its own delay.
+            assert_eq!(first_pixels(&image), vec![2, 1, 0]);
+            assert_eq!(delays_ms(&image), vec![30, 20, 10]);
+        }
+
+        #[test]
+        fn static_image_unchanged() {
+            let img = setup_default_test_image();
+            let cmp = setup_default_test_image();
+
+            let engine = ImageEngine::new(img);
+            let image = engine
+                .ignite(&[
+                    Instr::Operation(ImgOp::SetFrameDelay(FrameDelay::new(
+                        Duration::from_millis(50),
+                    ))),
+                    Instr::Operation(ImgOp::Speed(2.0)),
+                    Instr::Operation(ImgOp::ReverseFrames),
+                ])
+                .unwrap();
+
+            assert_eq!(cmp.raw_pixels(), image.raw_pixels());
+        }
+
+        #[test]
+        fn delays_preserved_by_operations() {
+            let engine = ImageEngine::new(animated_image(&[10, 20, 30]));
+
+            let image = engine
+                .ignite(&[
+                    Instr::Operation(ImgOp::AutoOrient),
+                    Instr::Operation(ImgOp::Blur(1.0)),
+                    Instr::Operation(ImgOp::Brighten(10)),
+                    Instr::Operation(ImgOp::Contrast(1.0)),
+                    Instr::Operation(ImgOp::ConvertProfile(IccProfileTarget::Srgb)),
+                    Instr::Operation(ImgOp::Crop((0, 0, 3, 2))),
+                    Instr::Operation(ImgOp::Filter3x3([0.0; 9])),
+                    Instr::Operation(ImgOp::FlipHorizontal),
+                    Instr::Operation(ImgOp::FlipVertical),
+                    Instr::Operation(ImgOp::Grayscale),
+                    Instr::Operation(ImgOp::HueRotate(90)),
+                    Instr::Operation(ImgOp::Invert),
+                    Instr::Operation(ImgOp::Resize((8, 4))),
+                    Instr::Operation(ImgOp::Rotate90),
+                    Instr::Operation(ImgOp::Rotate180),
+                    Instr::Operation(ImgOp::Rotate270),
+                    Instr::Operation(ImgOp::Unsharpen((1.0, 1))),
+                ])
+                .unwrap();
+
+            assert_eq!(delays_ms(&image), vec![10, 20, 30]);
+        }
+    }
+
+    mod frame_selection {
+        use super::frame_timing::{animated_image, delays_ms, first_pixels};
+        use super::*;
+        use crate::wrapper::frame_delay::FrameDelay;
+        use sic_core::FrameRange;
+        use std::time::Duration;
+
+        fn range(input: &str) -> FrameRange {
+            FrameRange::try_from_str(input).unwrap()
+        }
+
+        #[test]
+        fn drop_frames() {
+            let engine = ImageEngine::new(animated_image(&[10, 20, 30, 40, 50]));
+
+            let image = engine
+                .ignite(&[Instr::Operation(ImgOp::DropFrames(range("::2")))])
+                .unwrap();
+
+            assert_eq!(first_pixels(&image), vec![1, 3]);
+            assert_eq!(delays_ms(&image), vec![20, 40]);
+        }
+
+        #[test]
+        fn drop_all_frames() {
+            let engine = ImageEngine::new(animated_image(&[10, 20, 30]));
+            let result = engine.ignite(&[Instr::Operation(ImgOp::DropFrames(range("..")))]);
+
+            assert!(matches!(
+                result,
+                Err(SicImageEngineError::SicCoreError(
+                    sic_core::SicCoreError::NoFramesSelected
+                ))
+            ));
+        }
+
+        #[test]
+        fn on_frames() {
+            let engine = ImageEngine::new(animated_image(&[10, 20, 30, 40]));
+
+            let image = engine
+                .ignite(&[Instr::Operation(ImgOp::OnFrames(
+                    range("1..3"),
+                    vec![
+                        Instr::Operation(ImgOp::Invert),
+                        Instr::Operation(ImgOp::SetFrameDelay(FrameDelay::new(
+                            Duration::from_millis(100),
+                        ))),
+                    ],
+                ))])
+                .unwrap();
+
+            assert_eq!(first_pixels(&image), vec![0, 254, 253, 3]);
+            assert_eq!(delays_ms(&image), vec![10, 100, 100, 40]);
+        }
+
+        #[test]
+        fn on_frames_nested() {
+            let engine = ImageEngine::new(animated_image(&[10, 20, 30, 40, 50]));
+
+            // The nested range is relative to the frames selected by the outer range.
+            let image = engine
+                .ignite(&[Instr::Operation(ImgOp::OnFrames(
+                    range("1.."),
+                    vec![Instr::Operation(ImgOp::OnFrames(
+                        range("::2"),
+                        vec![Instr::Operation(ImgOp::Invert)],
+                    ))],
+                ))])
+                .unwrap();
+
+            assert_eq!(first_pixels(&image), vec![0, 254, 2, 252, 4]);
+        }
+
+        #[test]
+        fn on_frames_environment_is_scoped() {
+            let engine = ImageEngine::new(animated_image(&[10, 20]));
+
+            let image = engine
+                .ignite(&[
+                    Instr::Operation(ImgOp::OnFrames(
+                        range("0"),
+                        vec![Instr::EnvAdd(EnvItem::PreserveAspectRatio(true))],
+                    )),
+                    Instr::Operation(ImgOp::Resize((2, 2))),
+                ])
+                .unwrap();
+
+            match image {
+                SicImage::Animated(image) => {
+                    assert!(image
+                        .frames()
+                        .iter()
+                        .all(|frame| frame.buffer().dimensions() == (2, 2)));
+                }
+                SicImage::Static(_) => panic!("Expected an animated image"),
+            }
+        }
+
+        #[test]
+        fn on_frames_changing_frame_count() {
+            let engine = ImageEngine::new(animated_image(&[10, 20, 30]));
+
+            let result = engine.ignite(&[Instr::Operation(ImgOp::OnFrames(
+                range("0..2"),
+                vec![Instr::Operation(ImgOp::DropFrames(range("0")))],
+            ))]);
+
+            assert!(matches!(
+                result,
+                Err(SicImageEngineError::OnFramesFrameCountChanged(2, 1))
+            ));
+        }
+
+        #[test]
+        fn on_frames_changing_dimensions_of_some_frames() {
+            let engine = ImageEngine::new(animated_image(&[10, 20, 30]));
+
+            let result = engine.ignite(&[Instr::Operation(ImgOp::OnFrames(
+                range("0..2"),
+                vec![Instr::Operation(ImgOp::Resize((2, 2)))],
+            ))]);
+
+            assert!(matches!(
+                result,
+                Err(SicImageEngineError::OnFramesDimensionsChanged(2, 2, 4, 2))
+            ));
+        }
+
+        #[test]
+        fn on_frames_changing_dimensions_of_all_frames() {
+            let engine = ImageEngine::new(animated_image(&[10, 20, 30]));
+
+            let image = engine
+                .ignite(&[Instr::Operation(ImgOp::OnFrames(
+                    range(".."),
+                    vec![Instr::Operation(ImgOp::Resize((2, 2)))],
+                ))])
+                .unwrap();
+
+            assert_eq!(image.dimensions(), (2, 2));
+        }
+
+        fn rotated_metadata() -> ImageMetadata {
+            let mut metadata = ImageMetadata::default();
+            metadata.set_orientation(Orientation::Rotate90);
+            metadata
+        }
+
+        #[test]
+        fn on_frames_metadata_of_all_frames_is_kept() {
+            let engine =
+                ImageEngine::new(animated_image(&[10, 20])).with_metadata(rotated_metadata());
+
+            let (_, metadata) = engine
+                .ignite_with_metadata(&[Instr::Operation(ImgOp::OnFrames(
+                    range(".."),
+                    vec![Instr::Operation(ImgOp::AutoOrient)],
+                ))])
+                .unwrap();
+
+            assert_eq!(metadata.orientation(), Orientation::NoTransforms);
+        }
+
+        #[test]
+        fn on_frames_metadata_of_some_frames_is_refused() {
+            let engine =
+                ImageEngine::new(animated_image(&[10, 20])).with_metadata(rotated_metadata());
+
+            let result = engine.ignite(&[Instr::Operation(ImgOp::OnFrames(
+                range("0"),
+                vec![Instr::Operation(ImgOp::AutoOrient)],
+            ))]);
+
+            assert!(matches!(
+                result,
+                Err(SicImageEngineError::OnFramesMetadataChanged)
+            ));
+        }
+
+        #[test]
+        fn on_frames_static_image_metadata_is_kept() {
+            let engine =
+                ImageEngine::new(setup_default_test_image()).with_metadata(rotated_metadata());
+
+            let (_, metadata) = engine
+                .ignite_with_metadata(&[Instr::Operation(ImgOp::OnFrames(
+                    range("0"),
+                    vec![Instr::Operation(ImgOp::AutoOrient)],
+                ))])
+                .unwrap();
+
+            assert_eq!(metadata.orientation(), Orientation::NoTransforms);
+        }
+
+        #[test]
+        fn on_frames_static_image() {
+            let cmp = setup_default_test_image();
+
+            let selected = ImageEngine::new(setup_default_test_image())
+                .ignite(&[Instr::Operation(ImgOp::OnFrames(
+                    range("0"),
+                    vec![Instr::Operation(ImgOp::Invert)],
+                ))])
+                .unwrap();
+            assert_ne!(cmp.raw_pixels(), selected.raw_pixels());
+
+            let not_selected = ImageEngine::new(setup_default_test_image())
+                .ignite(&[Instr::Operation(ImgOp::OnFrames(
+                    range("1.."),
+                    vec![Instr::Operation(ImgOp::Invert)],
+                ))])
+                .unwrap();
+            assert_eq!(cmp.raw_pixels(), not_selected.raw_pixels());
+        }
+    }
+
     #[test]
     fn test_multi() {
         // 217x447px original
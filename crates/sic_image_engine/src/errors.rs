@@ -9,18 +9,84 @@ pub enum SicImageEngineError {
     #[error("Could not get frame {0} from animated image")]
     AnimatedFrameUnobtainable(usize),
 
+    #[error("Unable to compare the images; both images should have the same dimensions, but the image is {}x{} pixels, while the reference image is {}x{} pixels", .0.0, .0.1, .1.0, .1.1)]
+    CompareDimensionMismatch((u32, u32), (u32, u32)),
+
+    #[error("Unable to compare the images; both images should have the same number of frames, but the image has {0} frames, while the reference image has {1} frames")]
+    CompareFrameCountMismatch(usize, usize),
+
+    #[error("Unable to compare the images; a static image can only be compared with a static image, and an animated image with an animated image")]
+    CompareStaticWithAnimated,
+
     #[error("Unable to crop; required top-left anchor < bottom-right anchor; note that (x=0,y=0) is the smallest top-left coordinate; [top-left anchor: (x={0}, y={1}), bottom-right anchor: (x={2}, y={3})]")]
     CropInvalidSelection(u32, u32, u32, u32),
 
     #[error("Unable to crop; anchor coordinates should be within image bounds [image size: (x={0}, y={1}), top-left anchor: (x={2}, y={3}), bottom-right anchor: (x={4}, y={5})]")]
     CropCoordinateOutOfBounds(u32, u32, u32, u32, u32, u32),
 
+    #[error(
+        "Unable to apply curves; at least two control points are required, but {0} were given"
+    )]
+    CurvesTooFewPoints(usize),
+
+    #[error("Unable to apply curves; each control point should have a different input value, but {0} was given more than once")]
+    CurvesDuplicateInput(u8),
+
+    #[error("Unable to adjust the gamma; the gamma should be a positive number, but was {0}")]
+    InvalidGamma(f32),
+
+    #[error("Unable to adjust the levels; the black point should be smaller than the white point, but were (black={0}, white={1})")]
+    LevelsInvalidRange(u8, u8),
+
+    #[error("Unable to pad; the padded canvas of the {0}x{1} pixels image would be too large")]
+    PadCanvasTooLarge(u32, u32),
+
+    #[error("Unable to smart crop; the width and height should be larger than zero, but were (width={0}, height={1})")]
+    SmartCropInvalidSize(u32, u32),
+
     #[error("Unable to load image argument from given path")]
     LoadImageFromPath,
 
+    #[error("Unable to load ICC profile from path '{0}'")]
+    LoadIccProfileFromPath(std::path::PathBuf),
+
+    #[error("Unable to read ICC profile: {0}")]
+    InvalidIccProfile(String),
+
+    #[error("Unable to convert the image between color profiles: {0}")]
+    ColorProfileConversion(String),
+
+    #[error("Unable to parse frame delay '{0}'; expected a non-negative number followed by 'ms' (milliseconds) or 's' (seconds)")]
+    InvalidFrameDelay(String),
+
+    #[error("Unable to parse scale factor '{0}'; expected a positive percentage, e.g. '50%'")]
+    InvalidScaleFactor(String),
+
+    #[error("Unable to change the speed of an animated image; the speed factor should be a positive number, but was {0}")]
+    InvalidSpeedFactor(f32),
+
+    #[error("Unable to apply operations to the selected frames; operations within an on-frames block should not change the number of frames (expected {0} frames, but got {1})")]
+    OnFramesFrameCountChanged(usize, usize),
+
+    #[error("Unable to apply operations to the selected frames; all frames of an animated image should have the same dimensions, but operations within an on-frames block left frames of {0}x{1} and {2}x{3} pixels")]
+    OnFramesDimensionsChanged(u32, u32, u32, u32),
+
+    #[error("Unable to apply operations to the selected frames; operations which change the metadata of the image, such as auto-orient and convert-profile, can only be used in an on-frames block which selects all frames")]
+    OnFramesMetadataChanged,
+
     #[error("Filter type '{0}' not found")]
     UnknownFilterType(String),
 
+    #[error("Color channel '{0}' not found; expected one of: red, green or blue")]
+    UnknownColorChannel(String),
+
+    #[error("Gravity '{0}' not found; expected one of: center, north, north-east, east, south-east, south, south-west, west or north-west")]
+    UnknownGravity(String),
+
+    #[cfg(feature = "imageproc-ops")]
+    #[error("Rotate canvas '{0}' not found; expected either expand or crop")]
+    UnknownRotateCanvas(String),
+
     #[cfg(feature = "imageproc-ops")]
     #[error("Unable to load font: '{0}'")]
     FontError(sic_core::ab_glyph::InvalidFont),
@@ -0,0 +1,128 @@
+use sic_core::image::{DynamicImage, Rgba};
+use std::borrow::Cow;
+
+/// Applies an expression to the image buffer of a static image, while keeping its color type,
+/// so for example 16 bit images keep their precision. Within the expression, `$buffer` is the
+/// image buffer, and `$fill` the fill color converted to the pixel type of that buffer.
+///
+/// Images without an alpha channel gain one if the fill color is not fully opaque, and grayscale
+/// images become color images if the fill color is not gray.
+macro_rules! map_with_fill {
+    ($image:expr, $fill:expr, |$buffer:ident, $px:ident| $map:expr) => {{
+        let fill: sic_core::image::Rgba<u8> = $fill;
+        let image = $crate::helper::fill::with_fill_channels($image, fill);
+
+        // A single pixel image of the fill color, which is converted to the pixel type of the image
+        let fill_image = sic_core::image::DynamicImage::ImageRgba8(
+            sic_core::image::RgbaImage::from_pixel(1, 1, fill),
+        );
+
+        $crate::helper::fill::map_with_fill!(
+            @variants image, fill_image, fill, |$buffer, $px| $map,
+            ImageLuma8 => to_luma8,
+            ImageLumaA8 => to_luma_alpha8,
+            ImageRgb8 => to_rgb8,
+            ImageRgba8 => to_rgba8,
+            ImageLuma16 => to_luma16,
+            ImageLumaA16 => to_luma_alpha16,
+            ImageRgb16 => to_rgb16,
+            ImageRgba16 => to_rgba16,
+            ImageRgb32F => to_rgb32f,
+            ImageRgba32F => to_rgba32f,
+        )
+    }};
+    (@variants $image:ident, $fill_image:ident, $fill:ident, |$buffer:ident, $px:ident| $map:expr,
+        $($variant:ident => $convert:ident),* $(,)?) => {
+        match &*$image {
+            $(
+                sic_core::image::DynamicImage::$variant(buffer) => {
+                    let $buffer = buffer;
+                    let $px = *$fill_image.$convert().get_pixel(0, 0);
+                    sic_core::image::DynamicImage::$variant($map)
+                }
+            )*
+            other => {
+                let $buffer = &other.to_rgba8();
+                let $px = $fill;
+                sic_core::image::DynamicImage::ImageRgba8($map)
+            }
+        }
+    };
+}
+
+pub(crate) use map_with_fill;
+
+/// Adds the channels which the image needs to be filled with the fill color, if it lacks them:
+/// an alpha channel if the fill color is not fully opaque, and color channels if the fill color
+/// is not gray. The bit depth of the image is kept.
+pub(crate) fn with_fill_channels(image: &DynamicImage, fill: Rgba<u8>) -> Cow<'_, DynamicImage> {
+    let color_type = image.color();
+    let has_color = color_type.has_color() || fill[0] != fill[1] || fill[1] != fill[2];
+    let has_alpha = color_type.has_alpha() || fill[3] != u8::MAX;
+
+    if has_color == color_type.has_color() && has_alpha == color_type.has_alpha() {
+        return Cow::Borrowed(image);
+    }
+
+    let is_16_bit = matches!(
+        image,
+        DynamicImage::ImageLuma16(_)
+            | DynamicImage::ImageLumaA16(_)
+            | DynamicImage::ImageRgb16(_)
+            | DynamicImage::ImageRgba16(_)
+    );
+
+    Cow::Owned(match (has_color, has_alpha) {
+        (true, true) if matches!(image, DynamicImage::ImageRgb32F(_)) => image.to_rgba32f().into(),
+        (true, true) if is_16_bit => image.to_rgba16().into(),
+        (true, true) => image.to_rgba8().into(),
+        (true, false) if is_16_bit => image.to_rgb16().into(),
+        (true, false) => image.to_rgb8().into(),
+        (false, _) if is_16_bit => image.to_luma_alpha16().into(),
+        (false, _) => image.to_luma_alpha8().into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sic_core::image::{ColorType, GrayImage, ImageBuffer, Luma, RgbImage};
+
+    #[test]
+    fn channels_are_added_for_fill() {
+        let luma8 = DynamicImage::ImageLuma8(GrayImage::new(1, 1));
+        let luma16 = DynamicImage::ImageLuma16(ImageBuffer::new(1, 1));
+        let rgb8 = DynamicImage::ImageRgb8(RgbImage::new(1, 1));
+
+        let cases = [
+            (&luma8, Rgba([128, 128, 128, 255]), ColorType::L8),
+            (&luma8, Rgba([128, 128, 128, 0]), ColorType::La8),
+            (&luma8, Rgba([255, 0, 0, 255]), ColorType::Rgb8),
+            (&luma8, Rgba([255, 0, 0, 128]), ColorType::Rgba8),
+            (&luma16, Rgba([255, 0, 0, 255]), ColorType::Rgb16),
+            (&luma16, Rgba([0, 0, 0, 0]), ColorType::La16),
+            (&rgb8, Rgba([255, 0, 0, 255]), ColorType::Rgb8),
+            (&rgb8, Rgba([255, 0, 0, 0]), ColorType::Rgba8),
+        ];
+
+        for (image, fill, expected) in cases {
+            assert_eq!(
+                with_fill_channels(image, fill).color(),
+                expected,
+                "{:?} {:?}",
+                image.color(),
+                fill
+            );
+        }
+    }
+
+    #[test]
+    fn image_is_borrowed_if_no_channels_are_added() {
+        let image = DynamicImage::ImageLuma8(GrayImage::from_pixel(1, 1, Luma([1])));
+
+        assert!(matches!(
+            with_fill_channels(&image, Rgba([0, 0, 0, 255])),
+            Cow::Borrowed(_)
+        ));
+    }
+}
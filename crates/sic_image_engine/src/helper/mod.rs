@@ -1 +1,3 @@
+pub(crate) mod fill;
 pub(crate) mod resize;
+pub(crate) mod tone;
@@ -1,4 +1,4 @@
-//! Module copied from: https://docs.rs/crate/image/0.23.13/source/src/math/utils.rs
+//! Module copied from: https://docs.rs/crate/image/0.25.5/source/src/math/utils.rs
 //! Module licensed under MIT License, reproduced below:
 //!
 //! ```text
@@ -25,12 +25,14 @@
 //! SOFTWARE.
 //! ```
 
+use std::cmp::max;
+
 /// Calculates the width and height an image should be resized to.
 /// This preserves aspect ratio, and based on the `fill` parameter
 /// will either fill the dimensions to fit inside the smaller constraint
 /// (will overflow the specified bounds on one axis to preserve
 /// aspect ratio), or will shrink so that both dimensions are
-/// completely contained with in the given `width` and `height`,
+/// completely contained within the given `width` and `height`,
 /// with empty space on one axis.
 pub(crate) fn resize_dimensions(
     width: u32,
@@ -39,35 +41,25 @@ pub(crate) fn resize_dimensions(
     nheight: u32,
     fill: bool,
 ) -> (u32, u32) {
-    let ratio = u64::from(width) * u64::from(nheight);
-    let nratio = u64::from(nwidth) * u64::from(height);
+    let wratio = f64::from(nwidth) / f64::from(width);
+    let hratio = f64::from(nheight) / f64::from(height);
 
-    let use_width = if fill {
-        nratio > ratio
-    } else {
-        nratio <= ratio
-    };
-    let intermediate = if use_width {
-        u64::from(height) * u64::from(nwidth) / u64::from(width)
+    let ratio = if fill {
+        f64::max(wratio, hratio)
     } else {
-        u64::from(width) * u64::from(nheight) / u64::from(height)
+        f64::min(wratio, hratio)
     };
-    let intermediate = std::cmp::max(1, intermediate);
-    if use_width {
-        if intermediate <= u64::from(u32::MAX) {
-            (nwidth, intermediate as u32)
-        } else {
-            (
-                (u64::from(nwidth) * u64::from(u32::MAX) / intermediate) as u32,
-                u32::MAX,
-            )
-        }
-    } else if intermediate <= u64::from(u32::MAX) {
-        (intermediate as u32, nheight)
+
+    let nw = max((f64::from(width) * ratio).round() as u64, 1);
+    let nh = max((f64::from(height) * ratio).round() as u64, 1);
+
+    if nw > u64::from(u32::MAX) {
+        let ratio = f64::from(u32::MAX) / f64::from(width);
+        (u32::MAX, max((f64::from(height) * ratio).round() as u32, 1))
+    } else if nh > u64::from(u32::MAX) {
+        let ratio = f64::from(u32::MAX) / f64::from(height);
+        (max((f64::from(width) * ratio).round() as u32, 1), u32::MAX)
     } else {
-        (
-            u32::MAX,
-            (u64::from(nheight) * u64::from(u32::MAX) / intermediate) as u32,
-        )
+        (nw as u32, nh as u32)
     }
 }
@@ -0,0 +1,141 @@
+use crate::wrapper::color_channels::ColorChannels;
+use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
+use sic_core::image::{DynamicImage, ImageBuffer, Pixel};
+use sic_core::SicImage;
+
+/// Maps the selected color channels of each pixel with a tone curve, which maps a normalized
+/// channel value (from 0 to 1) to a new normalized value. For 8 and 16 bit images, the curve is
+/// evaluated once for each possible value of a channel, and stored in a lookup table. The alpha
+/// channel is never changed.
+pub(crate) fn apply_tone_curve<F>(image: &mut SicImage, channels: ColorChannels, curve: F)
+where
+    F: Fn(f32) -> f32 + Sync,
+{
+    match image {
+        SicImage::Static(image) => apply_static(image, channels, &curve),
+        SicImage::Animated(image) => {
+            let lut = lut_u8(&curve);
+
+            image.frames_mut().par_iter_mut().for_each(|frame| {
+                map_channels(frame.buffer_mut(), channels.mask(), |value| {
+                    lut[usize::from(value)]
+                });
+            });
+        }
+    }
+}
+
+fn apply_static<F>(image: &mut DynamicImage, channels: ColorChannels, curve: &F)
+where
+    F: Fn(f32) -> f32,
+{
+    // A single color channel can only be changed once a grayscale image has three of them
+    if channels != ColorChannels::All && !image.color().has_color() {
+        *image = match image {
+            DynamicImage::ImageLuma16(_) => image.to_rgb16().into(),
+            DynamicImage::ImageLumaA16(_) => image.to_rgba16().into(),
+            _ if image.color().has_alpha() => image.to_rgba8().into(),
+            _ => image.to_rgb8().into(),
+        };
+    }
+
+    // The single color channel of a grayscale image is its first channel
+    let mask = if image.color().has_color() {
+        channels.mask()
+    } else {
+        [true, false, false, false]
+    };
+
+    match image {
+        DynamicImage::ImageLuma8(buffer) => map_u8(buffer, mask, curve),
+        DynamicImage::ImageLumaA8(buffer) => map_u8(buffer, mask, curve),
+        DynamicImage::ImageRgb8(buffer) => map_u8(buffer, mask, curve),
+        DynamicImage::ImageRgba8(buffer) => map_u8(buffer, mask, curve),
+        DynamicImage::ImageLuma16(buffer) => map_u16(buffer, mask, curve),
+        DynamicImage::ImageLumaA16(buffer) => map_u16(buffer, mask, curve),
+        DynamicImage::ImageRgb16(buffer) => map_u16(buffer, mask, curve),
+        DynamicImage::ImageRgba16(buffer) => map_u16(buffer, mask, curve),
+        DynamicImage::ImageRgb32F(buffer) => map_f32(buffer, mask, curve),
+        DynamicImage::ImageRgba32F(buffer) => map_f32(buffer, mask, curve),
+        _ => {
+            let mut buffer = image.to_rgba8();
+            map_u8(&mut buffer, mask, curve);
+            *image = buffer.into();
+        }
+    }
+}
+
+fn map_channels<P, M>(buffer: &mut ImageBuffer<P, Vec<P::Subpixel>>, mask: [bool; 4], map: M)
+where
+    P: Pixel,
+    M: Fn(P::Subpixel) -> P::Subpixel,
+{
+    for pixel in buffer.pixels_mut() {
+        pixel
+            .channels_mut()
+            .iter_mut()
+            .zip(mask)
+            .filter(|(_, selected)| *selected)
+            .for_each(|(channel, _)| *channel = map(*channel));
+    }
+}
+
+fn map_u8<P, F>(buffer: &mut ImageBuffer<P, Vec<u8>>, mask: [bool; 4], curve: &F)
+where
+    P: Pixel<Subpixel = u8>,
+    F: Fn(f32) -> f32,
+{
+    let lut = lut_u8(curve);
+    map_channels(buffer, mask, |value| lut[usize::from(value)]);
+}
+
+fn map_u16<P, F>(buffer: &mut ImageBuffer<P, Vec<u16>>, mask: [bool; 4], curve: &F)
+where
+    P: Pixel<Subpixel = u16>,
+    F: Fn(f32) -> f32,
+{
+    let lut = (0..=u16::MAX)
+        .map(|value| quantize(curve(f32::from(value) / f32::from(u16::MAX)), u16::MAX))
+        .collect::<Vec<_>>();
+
+    map_channels(buffer, mask, |value| lut[usize::from(value)]);
+}
+
+// Floating point channels have too many values for a lookup table, so the curve is evaluated for
+// each channel instead.
+fn map_f32<P, F>(buffer: &mut ImageBuffer<P, Vec<f32>>, mask: [bool; 4], curve: &F)
+where
+    P: Pixel<Subpixel = f32>,
+    F: Fn(f32) -> f32,
+{
+    map_channels(buffer, mask, |value| {
+        curve(value.clamp(0.0, 1.0)).clamp(0.0, 1.0)
+    });
+}
+
+fn lut_u8<F: Fn(f32) -> f32>(curve: &F) -> Vec<u8> {
+    (0..=u8::MAX)
+        .map(|value| quantize(curve(f32::from(value) / 255.0), u16::from(u8::MAX)) as u8)
+        .collect()
+}
+
+// Converts a normalized value to the nearest integer value between 0 and max.
+fn quantize(value: f32, max: u16) -> u16 {
+    (value.clamp(0.0, 1.0) * f32::from(max)).round() as u16
+}
+
+/// Fixtures of the tests of the tone operations: gamma, levels and curves.
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use sic_core::image::{Rgba, RgbaImage};
+
+    /// A single row of 18 translucent gray pixels, of which the values increase from 0 to 255 in
+    /// steps of 15.
+    pub(crate) fn gray_ramp() -> RgbaImage {
+        RgbaImage::from_fn(18, 1, |x, _| translucent_gray(x as u8 * 15))
+    }
+
+    pub(crate) fn translucent_gray(value: u8) -> Rgba<u8> {
+        Rgba([value, value, value, 128])
+    }
+}
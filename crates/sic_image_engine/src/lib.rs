@@ -4,41 +4,71 @@
 #[macro_use]
 extern crate strum_macros;
 
+use crate::engine::Instr;
+use crate::wrapper::diff::DiffInputs;
 #[cfg(feature = "imageproc-ops")]
 use crate::wrapper::draw_text_inner::DrawTextInner;
+use crate::wrapper::frame_delay::FrameDelay;
 use crate::wrapper::gradient_input::GradientInput;
-use crate::wrapper::image_path::ImageFromPath;
+use crate::wrapper::icc_profile::IccProfileTarget;
+use crate::wrapper::levels::LevelsInputs;
 use crate::wrapper::overlay::OverlayInputs;
+use crate::wrapper::pad::PadInputs;
+use crate::wrapper::resize_mode::ResizeMode;
+#[cfg(feature = "imageproc-ops")]
+use crate::wrapper::rotate::RotateInputs;
+use crate::wrapper::trim::TrimInputs;
+use sic_core::FrameRange;
 
+pub mod compare;
 pub mod engine;
 pub mod errors;
 pub(crate) mod helper;
 pub mod operations;
+pub mod stats;
+#[cfg(test)]
+pub(crate) mod test_helpers;
 pub mod wrapper;
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum ImgOp {
+    AutoOrient,
     Blur(f32),
     Brighten(i32),
     Contrast(f32),
+    ConvertProfile(IccProfileTarget),
     Crop((u32, u32, u32, u32)),
-    Diff(ImageFromPath),
+    Curves(Vec<(u8, u8)>),
+    Diff(DiffInputs),
     #[cfg(feature = "imageproc-ops")]
     DrawText(DrawTextInner),
+    DropFrames(FrameRange),
     Filter3x3([f32; 9]),
     FlipHorizontal,
     FlipVertical,
+    Gamma(f32),
     Grayscale,
     HueRotate(i32),
     HorizontalGradient(GradientInput),
     Invert,
+    Levels(LevelsInputs),
+    OnFrames(FrameRange, Vec<Instr>),
     Overlay(OverlayInputs),
+    Pad(PadInputs),
     Resize((u32, u32)),
+    ResizeWithMode(ResizeMode),
+    ReverseFrames,
+    #[cfg(feature = "imageproc-ops")]
+    Rotate(RotateInputs),
     Rotate90,
     Rotate180,
     Rotate270,
+    SetFrameDelay(FrameDelay),
+    SmartCrop((u32, u32)),
+    Speed(f32),
     #[cfg(feature = "imageproc-ops")]
     Threshold,
+    Trim(TrimInputs),
     Unsharpen((f32, i32)),
     VerticalGradient(GradientInput),
 }
@@ -0,0 +1,61 @@
+use crate::errors::SicImageEngineError;
+use crate::operations::ImageOperation;
+use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
+use sic_core::image::metadata::Orientation;
+use sic_core::image::RgbaImage;
+use sic_core::{image, SicImage};
+
+pub struct AutoOrient {
+    orientation: Orientation,
+}
+
+impl AutoOrient {
+    pub fn new(orientation: Orientation) -> Self {
+        Self { orientation }
+    }
+}
+
+impl ImageOperation for AutoOrient {
+    fn apply_operation(&self, image: &mut SicImage) -> Result<(), SicImageEngineError> {
+        // Most images are already upright, in which case the frames don't need to be touched
+        if self.orientation == Orientation::NoTransforms {
+            return Ok(());
+        }
+
+        match image {
+            SicImage::Static(image) => image.apply_orientation(self.orientation),
+            SicImage::Animated(image) => {
+                auto_orient_animated_image(image.frames_mut(), self.orientation)
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn auto_orient_animated_image(frames: &mut [image::Frame], orientation: Orientation) {
+    frames.par_iter_mut().for_each(|frame| {
+        orient_buffer(frame.buffer_mut(), orientation);
+    });
+}
+
+fn orient_buffer(buffer: &mut RgbaImage, orientation: Orientation) {
+    use image::imageops;
+
+    match orientation {
+        Orientation::NoTransforms => {}
+        Orientation::Rotate90 => *buffer = imageops::rotate90(buffer),
+        Orientation::Rotate180 => imageops::rotate180_in_place(buffer),
+        Orientation::Rotate270 => *buffer = imageops::rotate270(buffer),
+        Orientation::FlipHorizontal => imageops::flip_horizontal_in_place(buffer),
+        Orientation::FlipVertical => imageops::flip_vertical_in_place(buffer),
+        Orientation::Rotate90FlipH => {
+            *buffer = imageops::rotate90(buffer);
+            imageops::flip_horizontal_in_place(buffer);
+        }
+        Orientation::Rotate270FlipH => {
+            *buffer = imageops::rotate270(buffer);
+            imageops::flip_horizontal_in_place(buffer);
+        }
+    }
+}
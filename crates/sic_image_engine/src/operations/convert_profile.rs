@@ -0,0 +1,221 @@
+use crate::errors::SicImageEngineError;
+use crate::operations::ImageOperation;
+use moxcms::{ColorProfile, DataColorSpace, Layout, TransformExecutor, TransformOptions};
+use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
+use sic_core::image::{imageops, DynamicImage, ImageBuffer, Pixel};
+use sic_core::{image, SicImage};
+
+pub struct ConvertProfile<'profile> {
+    /// The embedded ICC profile of the image; images without profile are assumed to be sRGB.
+    source: Option<&'profile [u8]>,
+    target: &'profile ColorProfile,
+}
+
+impl<'profile> ConvertProfile<'profile> {
+    pub fn new(source: Option<&'profile [u8]>, target: &'profile ColorProfile) -> Self {
+        Self { source, target }
+    }
+}
+
+impl ImageOperation for ConvertProfile<'_> {
+    fn apply_operation(&self, image: &mut SicImage) -> Result<(), SicImageEngineError> {
+        let source = match self.source {
+            Some(profile) => ColorProfile::new_from_slice(profile)
+                .map_err(|err| SicImageEngineError::InvalidIccProfile(err.to_string()))?,
+            None => ColorProfile::new_srgb(),
+        };
+
+        match image {
+            SicImage::Static(image) => convert_static_image(image, &source, self.target),
+            SicImage::Animated(image) => {
+                convert_animated_image(image.frames_mut(), &source, self.target)
+            }
+        }
+    }
+}
+
+fn convert_static_image(
+    image: &mut DynamicImage,
+    source: &ColorProfile,
+    target: &ColorProfile,
+) -> Result<(), SicImageEngineError> {
+    if source.color_space == DataColorSpace::Gray {
+        return convert_gray_static_image(image, source, target);
+    }
+
+    let options = TransformOptions::default();
+
+    // Grayscale images are converted to RGB, since the target profile may map gray pixels
+    // to colors.
+    match image {
+        DynamicImage::ImageLuma8(_) => *image = DynamicImage::ImageRgb8(image.to_rgb8()),
+        DynamicImage::ImageLumaA8(_) => *image = DynamicImage::ImageRgba8(image.to_rgba8()),
+        DynamicImage::ImageLuma16(_) => *image = DynamicImage::ImageRgb16(image.to_rgb16()),
+        DynamicImage::ImageLumaA16(_) => *image = DynamicImage::ImageRgba16(image.to_rgba16()),
+        _ => {}
+    }
+
+    match image {
+        DynamicImage::ImageRgb8(buffer) => transform(
+            buffer,
+            source.create_transform_8bit(Layout::Rgb, target, Layout::Rgb, options),
+        ),
+        DynamicImage::ImageRgba8(buffer) => transform(
+            buffer,
+            source.create_transform_8bit(Layout::Rgba, target, Layout::Rgba, options),
+        ),
+        DynamicImage::ImageRgb16(buffer) => transform(
+            buffer,
+            source.create_transform_16bit(Layout::Rgb, target, Layout::Rgb, options),
+        ),
+        DynamicImage::ImageRgba16(buffer) => transform(
+            buffer,
+            source.create_transform_16bit(Layout::Rgba, target, Layout::Rgba, options),
+        ),
+        DynamicImage::ImageRgb32F(buffer) => transform(
+            buffer,
+            source.create_transform_f32(Layout::Rgb, target, Layout::Rgb, options),
+        ),
+        DynamicImage::ImageRgba32F(buffer) => transform(
+            buffer,
+            source.create_transform_f32(Layout::Rgba, target, Layout::Rgba, options),
+        ),
+        _ => {
+            let mut buffer = image.to_rgba8();
+            transform(
+                &mut buffer,
+                source.create_transform_8bit(Layout::Rgba, target, Layout::Rgba, options),
+            )?;
+            *image = DynamicImage::ImageRgba8(buffer);
+
+            Ok(())
+        }
+    }
+}
+
+// Images with a gray profile are transformed from their gray channel to RGB, since the target
+// profile may map gray pixels to colors.
+fn convert_gray_static_image(
+    image: &mut DynamicImage,
+    source: &ColorProfile,
+    target: &ColorProfile,
+) -> Result<(), SicImageEngineError> {
+    let options = TransformOptions::default();
+
+    *image = match image {
+        DynamicImage::ImageLuma8(_) | DynamicImage::ImageRgb8(_) => {
+            let executor = source
+                .create_transform_8bit(Layout::Gray, target, Layout::Rgb, options)
+                .map_err(conversion_error)?;
+            DynamicImage::ImageRgb8(transform_from_gray(&image.to_luma8(), &*executor)?)
+        }
+        DynamicImage::ImageLuma16(_) | DynamicImage::ImageRgb16(_) => {
+            let executor = source
+                .create_transform_16bit(Layout::Gray, target, Layout::Rgb, options)
+                .map_err(conversion_error)?;
+            DynamicImage::ImageRgb16(transform_from_gray(&image.to_luma16(), &*executor)?)
+        }
+        DynamicImage::ImageLumaA16(_) | DynamicImage::ImageRgba16(_) => {
+            let executor = source
+                .create_transform_16bit(Layout::Gray, target, Layout::Rgb, options)
+                .map_err(conversion_error)?;
+            DynamicImage::ImageRgba16(transform_from_gray(&image.to_luma_alpha16(), &*executor)?)
+        }
+        _ => {
+            let executor = source
+                .create_transform_8bit(Layout::Gray, target, Layout::Rgb, options)
+                .map_err(conversion_error)?;
+            DynamicImage::ImageRgba8(transform_from_gray(&image.to_luma_alpha8(), &*executor)?)
+        }
+    };
+
+    Ok(())
+}
+
+fn convert_animated_image(
+    frames: &mut [image::Frame],
+    source: &ColorProfile,
+    target: &ColorProfile,
+) -> Result<(), SicImageEngineError> {
+    let is_gray = source.color_space == DataColorSpace::Gray;
+    let source_layout = if is_gray { Layout::Gray } else { Layout::Rgba };
+    let target_layout = if is_gray { Layout::Rgb } else { Layout::Rgba };
+
+    let executor = source
+        .create_transform_8bit(
+            source_layout,
+            target,
+            target_layout,
+            TransformOptions::default(),
+        )
+        .map_err(conversion_error)?;
+
+    frames.par_iter_mut().try_for_each(|frame| {
+        if is_gray {
+            let gray = imageops::grayscale_alpha(frame.buffer());
+            *frame.buffer_mut() = transform_from_gray(&gray, &*executor)?;
+
+            return Ok(());
+        }
+
+        let pixels = frame.buffer().as_raw().clone();
+        executor
+            .transform(&pixels, frame.buffer_mut())
+            .map_err(conversion_error)
+    })
+}
+
+// Transform the pixels in place, using the given transform executor.
+fn transform<T: Copy + Default>(
+    pixels: &mut [T],
+    executor: Result<Box<dyn TransformExecutor<T> + Send + Sync>, moxcms::CmsError>,
+) -> Result<(), SicImageEngineError> {
+    let executor = executor.map_err(conversion_error)?;
+    let source = pixels.to_vec();
+
+    executor
+        .transform(&source, pixels)
+        .map_err(conversion_error)
+}
+
+// Transform the pixels of a gray image into a new RGB image, using the given gray to RGB transform
+// executor. Only the gray channel is transformed; the alpha channel, if any, is copied as is.
+fn transform_from_gray<G, P>(
+    gray: &ImageBuffer<G, Vec<G::Subpixel>>,
+    executor: &dyn TransformExecutor<G::Subpixel>,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, SicImageEngineError>
+where
+    G: Pixel,
+    G::Subpixel: Default,
+    P: Pixel<Subpixel = G::Subpixel>,
+{
+    let luma = gray
+        .pixels()
+        .map(|pixel| pixel.channels()[0])
+        .collect::<Vec<_>>();
+    let mut rgb = vec![G::Subpixel::default(); luma.len() * 3];
+
+    executor
+        .transform(&luma, &mut rgb)
+        .map_err(conversion_error)?;
+
+    let mut buffer = ImageBuffer::<P, _>::new(gray.width(), gray.height());
+    for ((pixel, rgb), gray) in buffer
+        .pixels_mut()
+        .zip(rgb.chunks_exact(3))
+        .zip(gray.pixels())
+    {
+        let channels = pixel.channels_mut();
+        channels[..3].copy_from_slice(rgb);
+
+        if let (Some(alpha), [_, source_alpha]) = (channels.get_mut(3), gray.channels()) {
+            *alpha = *source_alpha;
+        }
+    }
+
+    Ok(buffer)
+}
+
+fn conversion_error(err: moxcms::CmsError) -> SicImageEngineError {
+    SicImageEngineError::ColorProfileConversion(err.to_string())
+}
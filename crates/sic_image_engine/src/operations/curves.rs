@@ -0,0 +1,186 @@
+use crate::errors::SicImageEngineError;
+use crate::helper::tone::apply_tone_curve;
+use crate::operations::ImageOperation;
+use crate::wrapper::color_channels::ColorChannels;
+use sic_core::SicImage;
+
+/// Maps the color channels of an image with a smooth curve through the given control points,
+/// where each point maps an input value (from 0 to 255) to an output value. The curve is a
+/// monotone cubic spline, so it doesn't overshoot between the points: it only rises (or falls)
+/// where the points do. Values before the first point and after the last point are mapped to the
+/// output value of that point.
+pub struct Curves<'curves> {
+    points: &'curves [(u8, u8)],
+}
+
+impl<'curves> Curves<'curves> {
+    pub fn new(points: &'curves [(u8, u8)]) -> Self {
+        Self { points }
+    }
+}
+
+impl ImageOperation for Curves<'_> {
+    fn apply_operation(&self, image: &mut SicImage) -> Result<(), SicImageEngineError> {
+        let spline = Spline::new(self.points)?;
+        apply_tone_curve(image, ColorChannels::All, |value| spline.evaluate(value));
+
+        Ok(())
+    }
+}
+
+/// A monotone cubic Hermite spline, of which the tangents are chosen with the Fritsch-Carlson
+/// method.
+struct Spline {
+    // normalized (from 0 to 1) control points, sorted by their input value
+    points: Vec<(f32, f32)>,
+    tangents: Vec<f32>,
+}
+
+impl Spline {
+    fn new(points: &[(u8, u8)]) -> Result<Self, SicImageEngineError> {
+        if points.len() < 2 {
+            return Err(SicImageEngineError::CurvesTooFewPoints(points.len()));
+        }
+
+        let mut sorted = points.to_vec();
+        sorted.sort_by_key(|(x, _)| *x);
+
+        if let Some(window) = sorted.windows(2).find(|window| window[0].0 == window[1].0) {
+            return Err(SicImageEngineError::CurvesDuplicateInput(window[0].0));
+        }
+
+        let points = sorted
+            .iter()
+            .map(|(x, y)| (f32::from(*x) / 255.0, f32::from(*y) / 255.0))
+            .collect::<Vec<_>>();
+
+        let secants = points
+            .windows(2)
+            .map(|window| (window[1].1 - window[0].1) / (window[1].0 - window[0].0))
+            .collect::<Vec<_>>();
+
+        // The tangent at each inner point is the mean of the adjacent secants, or zero at a local
+        // extremum; the tangents at the outer points are the secants of the outer segments.
+        let last = secants.len() - 1;
+        let mut tangents = std::iter::once(secants[0])
+            .chain(secants.windows(2).map(|window| {
+                if window[0] * window[1] <= 0.0 {
+                    0.0
+                } else {
+                    (window[0] + window[1]) / 2.0
+                }
+            }))
+            .chain(std::iter::once(secants[last]))
+            .collect::<Vec<_>>();
+
+        // Limit the tangents of each segment, so the segment is monotone
+        for (k, secant) in secants.iter().enumerate() {
+            if *secant == 0.0 {
+                tangents[k] = 0.0;
+                tangents[k + 1] = 0.0;
+                continue;
+            }
+
+            let alpha = tangents[k] / secant;
+            let beta = tangents[k + 1] / secant;
+            let length = alpha.hypot(beta);
+
+            if length > 3.0 {
+                let tau = 3.0 / length;
+                tangents[k] = tau * alpha * secant;
+                tangents[k + 1] = tau * beta * secant;
+            }
+        }
+
+        Ok(Self { points, tangents })
+    }
+
+    fn evaluate(&self, x: f32) -> f32 {
+        let (first, last) = (self.points[0], self.points[self.points.len() - 1]);
+
+        if x <= first.0 {
+            return first.1;
+        } else if x >= last.0 {
+            return last.1;
+        }
+
+        // the segment which contains x
+        let k = self
+            .points
+            .windows(2)
+            .position(|window| x < window[1].0)
+            .unwrap_or(self.points.len() - 2);
+
+        let ((x0, y0), (x1, y1)) = (self.points[k], self.points[k + 1]);
+        let h = x1 - x0;
+        let t = (x - x0) / h;
+        let (t2, t3) = (t * t, t * t * t);
+
+        let h00 = 2.0 * t3 - 3.0 * t2 + 1.0;
+        let h10 = t3 - 2.0 * t2 + t;
+        let h01 = -2.0 * t3 + 3.0 * t2;
+        let h11 = t3 - t2;
+
+        h00 * y0 + h10 * h * self.tangents[k] + h01 * y1 + h11 * h * self.tangents[k + 1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helper::tone::fixtures::{gray_ramp, translucent_gray};
+    use crate::test_helpers::static_image;
+    use sic_core::image::Rgba;
+
+    fn curves_of_ramp(points: &[(u8, u8)]) -> Vec<Rgba<u8>> {
+        let mut image = static_image(gray_ramp());
+        Curves::new(points).apply_operation(&mut image).unwrap();
+
+        image.as_ref().to_rgba8().pixels().copied().collect()
+    }
+
+    #[test]
+    fn through_points() {
+        let identity = curves_of_ramp(&[(0, 0), (255, 255)]);
+        assert_eq!(identity, gray_ramp().pixels().copied().collect::<Vec<_>>());
+
+        let inverted = curves_of_ramp(&[(255, 0), (0, 255)]);
+        assert_eq!(inverted[1], translucent_gray(240));
+        assert_eq!(inverted[16], translucent_gray(15));
+
+        // the points don't need to be sorted
+        let pixels = curves_of_ramp(&[(255, 255), (0, 0), (120, 150)]);
+        assert_eq!(pixels[8], translucent_gray(150));
+        assert_eq!(pixels[17], translucent_gray(255));
+    }
+
+    #[test]
+    fn monotone() {
+        let pixels = curves_of_ramp(&[(0, 0), (60, 200), (120, 210), (255, 255)]);
+
+        assert!(pixels.windows(2).all(|pair| pair[0][0] <= pair[1][0]));
+        assert!(pixels.iter().all(|px| px[3] == 128));
+    }
+
+    #[test]
+    fn flat_outside_of_points() {
+        let pixels = curves_of_ramp(&[(30, 20), (225, 240)]);
+
+        assert!(pixels[..=2].iter().all(|px| *px == translucent_gray(20)));
+        assert!(pixels[15..].iter().all(|px| *px == translucent_gray(240)));
+    }
+
+    #[test]
+    fn invalid_curves() {
+        let mut image = static_image(gray_ramp());
+
+        assert!(matches!(
+            Curves::new(&[(0, 0)]).apply_operation(&mut image),
+            Err(SicImageEngineError::CurvesTooFewPoints(1))
+        ));
+        assert!(matches!(
+            Curves::new(&[(0, 0), (128, 10), (128, 20)]).apply_operation(&mut image),
+            Err(SicImageEngineError::CurvesDuplicateInput(128))
+        ));
+    }
+}
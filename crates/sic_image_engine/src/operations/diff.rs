@@ -1,27 +1,31 @@
 use crate::errors::SicImageEngineError;
 use crate::operations::ImageOperation;
+use crate::wrapper::diff::{DiffInputs, DiffOptions};
 use crate::wrapper::image_path::ImageFromPath;
 use rayon::iter::{IndexedParallelIterator, IntoParallelRefMutIterator, ParallelIterator};
-use sic_core::image::{DynamicImage, GenericImageView, ImageBuffer, Rgba, RgbaImage};
+use sic_core::image::{DynamicImage, GenericImageView, ImageBuffer, Pixel, Rgba, RgbaImage};
 use sic_core::{image, SicImage};
 use std::cmp;
 use std::convert::TryFrom;
 
 pub struct Diff<'image> {
-    path: &'image ImageFromPath,
+    inputs: &'image DiffInputs,
 }
 
 impl<'image> Diff<'image> {
-    pub fn new(path: &'image ImageFromPath) -> Self {
-        Self { path }
+    pub fn new(inputs: &'image DiffInputs) -> Self {
+        Self { inputs }
     }
 }
 
 impl ImageOperation for Diff<'_> {
     fn apply_operation(&self, image: &mut SicImage) -> Result<(), SicImageEngineError> {
+        let path = self.inputs.image_path();
+        let options = self.inputs.options();
+
         match image {
-            SicImage::Static(image) => diff_impl(image, self.path),
-            SicImage::Animated(image) => diff_animated_image(image.frames_mut(), self.path),
+            SicImage::Static(image) => diff_impl(image, path, options),
+            SicImage::Animated(image) => diff_animated_image(image.frames_mut(), path, options),
         }
     }
 }
@@ -29,47 +33,65 @@ impl ImageOperation for Diff<'_> {
 fn diff_animated_image(
     frames: &mut [image::Frame],
     path: &ImageFromPath,
+    options: &DiffOptions,
 ) -> Result<(), SicImageEngineError> {
     // Open matching image
     let other = path.open_image()?;
 
     match other {
-        SicImage::Static(image) => diff_animated_with_static(frames, &image),
-        SicImage::Animated(other) => diff_animated_with_animated(frames, other.frames()),
+        SicImage::Static(image) => diff_animated_with_static(frames, &image, options),
+        SicImage::Animated(other) => diff_animated_with_animated(frames, other.frames(), options),
     }
 
     Ok(())
 }
 
 //
-fn diff_animated_with_animated(frames: &mut [image::Frame], other: &[image::Frame]) {
+fn diff_animated_with_animated(
+    frames: &mut [image::Frame],
+    other: &[image::Frame],
+    options: &DiffOptions,
+) {
     frames.par_iter_mut().zip(other).for_each(|(lhs, rhs)| {
         *lhs.buffer_mut() = produce_image_diff(
             &DynamicImage::ImageRgba8(lhs.buffer().clone()),
             &DynamicImage::ImageRgba8(rhs.buffer().clone()),
+            options,
         );
     });
 }
 
-fn diff_animated_with_static(frames: &mut [image::Frame], other: &DynamicImage) {
+fn diff_animated_with_static(
+    frames: &mut [image::Frame],
+    other: &DynamicImage,
+    options: &DiffOptions,
+) {
     frames.par_iter_mut().for_each(|frame| {
-        *frame.buffer_mut() =
-            produce_image_diff(&DynamicImage::ImageRgba8(frame.buffer().clone()), other);
+        *frame.buffer_mut() = produce_image_diff(
+            &DynamicImage::ImageRgba8(frame.buffer().clone()),
+            other,
+            options,
+        );
     });
 }
 
-fn diff_impl(image: &mut DynamicImage, path: &ImageFromPath) -> Result<(), SicImageEngineError> {
+fn diff_impl(
+    image: &mut DynamicImage,
+    path: &ImageFromPath,
+    options: &DiffOptions,
+) -> Result<(), SicImageEngineError> {
     let cmp = path.open_image()?;
     // NB: Diffing a static image currently requires the right hand side image to be a static image
     //      We could do the same as we do on loading an image: simply pick the first frame
     //      Right now we error instead.
     let cmp = DynamicImage::try_from(cmp)?;
 
-    *image = DynamicImage::ImageRgba8(produce_image_diff(image, &cmp));
+    *image = DynamicImage::ImageRgba8(produce_image_diff(image, &cmp, options));
 
     Ok(())
 }
 
+// The default colours of the diff:
 // same -> white pixel
 pub(crate) const DIFF_PX_SAME: Rgba<u8> = Rgba([255, 255, 255, 255]);
 // different -> coloured pixel
@@ -80,8 +102,13 @@ pub(crate) const DIFF_PX_NO_OVERLAP: Rgba<u8> = Rgba([0, 0, 0, 0]);
 /// Takes the diff of two images.
 ///
 /// If a pixel at `(x, y)` in the image `this` (`P`) compared to the pixel at `(x, y)` in the image `that` (`Q`):
-/// * is the same: the output image will colour that pixel white.
-/// * differs: the output image will colour that pixel red.
+/// * is the same: the output image will colour that pixel white (by default).
+/// * differs: the output image will colour that pixel red (by default).
+///
+/// Two pixels are the same if none of their channels differ more than the tolerance of
+/// `options`, which also define the colours of the output image. If `options` has a dim factor,
+/// pixels which are the same show the dimmed pixel of `P` instead, and the colour of differing
+/// pixels is blended over it.
 ///
 /// The output image (`R`) will have width `w=max(width(this), width(that))` and height
 /// `h=max(height(this), height(that))`.
@@ -92,7 +119,11 @@ pub(crate) const DIFF_PX_NO_OVERLAP: Rgba<u8> = Rgba([0, 0, 0, 0]);
 /// That is, the part of output image which isn't part of either of the two original input images.
 /// These pixels will be 'coloured' black but with an alpha value of 0, so they will be transparent
 /// as to show they were not part of the input images.
-fn produce_image_diff(this: &DynamicImage, other: &DynamicImage) -> RgbaImage {
+fn produce_image_diff(
+    this: &DynamicImage,
+    other: &DynamicImage,
+    options: &DiffOptions,
+) -> RgbaImage {
     let (lw, lh) = this.dimensions();
     let (rw, rh) = other.dimensions();
 
@@ -102,18 +133,35 @@ fn produce_image_diff(this: &DynamicImage, other: &DynamicImage) -> RgbaImage {
     let mut buffer = ImageBuffer::new(w, h);
 
     for (x, y, pixel) in buffer.enumerate_pixels_mut() {
-        if this.in_bounds(x, y) && other.in_bounds(x, y) {
-            if this.get_pixel(x, y) == other.get_pixel(x, y) {
-                *pixel = DIFF_PX_SAME;
-            } else {
-                *pixel = DIFF_PX_DIFF;
+        let pixel_at = |image: &DynamicImage| image.in_bounds(x, y).then(|| image.get_pixel(x, y));
+
+        *pixel = match (pixel_at(this), pixel_at(other)) {
+            (Some(p), Some(q)) if is_within_tolerance(p, q, options.tolerance) => {
+                options.dim.map_or(options.same, |factor| dimmed(p, factor))
             }
-        } else if this.in_bounds(x, y) || other.in_bounds(x, y) {
-            *pixel = DIFF_PX_DIFF;
-        } else {
-            *pixel = DIFF_PX_NO_OVERLAP;
-        }
+            (Some(p), _) => options.dim.map_or(options.different, |factor| {
+                let mut background = dimmed(p, factor);
+                background.blend(&options.different);
+                background
+            }),
+            (None, Some(_)) => options.different,
+            (None, None) => options.no_overlap,
+        };
     }
 
     buffer
 }
+
+pub(crate) fn is_within_tolerance(p: Rgba<u8>, q: Rgba<u8>, tolerance: [u8; 4]) -> bool {
+    p.0.iter()
+        .zip(q.0.iter())
+        .zip(tolerance.iter())
+        .all(|((lhs, rhs), tolerance)| lhs.abs_diff(*rhs) <= *tolerance)
+}
+
+// Darkens the colour channels of the pixel by the given factor, and keeps its alpha channel.
+fn dimmed(pixel: Rgba<u8>, factor: f32) -> Rgba<u8> {
+    let keep = 1.0 - factor.clamp(0.0, 1.0);
+
+    pixel.map_without_alpha(|channel| (f32::from(channel) * keep).round() as u8)
+}
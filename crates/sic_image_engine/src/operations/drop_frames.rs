@@ -0,0 +1,24 @@
+use crate::errors::SicImageEngineError;
+use crate::operations::ImageOperation;
+use sic_core::{FrameRange, SicImage};
+
+pub struct DropFrames {
+    range: FrameRange,
+}
+
+impl DropFrames {
+    pub fn new(range: FrameRange) -> Self {
+        Self { range }
+    }
+}
+
+impl ImageOperation for DropFrames {
+    fn apply_operation(&self, image: &mut SicImage) -> Result<(), SicImageEngineError> {
+        match image {
+            SicImage::Static(_) => Ok(()),
+            SicImage::Animated(image) => image
+                .remove_frames(&self.range)
+                .map_err(SicImageEngineError::SicCoreError),
+        }
+    }
+}
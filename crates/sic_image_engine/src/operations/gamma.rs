@@ -0,0 +1,114 @@
+use crate::errors::SicImageEngineError;
+use crate::helper::tone::apply_tone_curve;
+use crate::operations::ImageOperation;
+use crate::wrapper::color_channels::ColorChannels;
+use sic_core::SicImage;
+
+/// Adjusts the gamma of the color channels of an image; a gamma larger than 1 brightens the
+/// midtones, and a gamma smaller than 1 darkens them. Black and white are not changed.
+pub struct Gamma {
+    gamma: f32,
+}
+
+impl Gamma {
+    pub fn new(gamma: f32) -> Self {
+        Self { gamma }
+    }
+}
+
+impl ImageOperation for Gamma {
+    fn apply_operation(&self, image: &mut SicImage) -> Result<(), SicImageEngineError> {
+        let exponent = gamma_exponent(self.gamma)?;
+        apply_tone_curve(image, ColorChannels::All, |value| value.powf(exponent));
+
+        Ok(())
+    }
+}
+
+/// The exponent by which normalized channel values are raised to apply the gamma.
+pub(crate) fn gamma_exponent(gamma: f32) -> Result<f32, SicImageEngineError> {
+    if gamma.is_finite() && gamma > 0.0 {
+        Ok(gamma.recip())
+    } else {
+        Err(SicImageEngineError::InvalidGamma(gamma))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helper::tone::fixtures::{gray_ramp, translucent_gray};
+    use crate::test_helpers::{animated_image, static_image};
+    use sic_core::image::{Delay, DynamicImage, ImageBuffer, Luma, Rgba};
+
+    fn gamma(mut image: SicImage, gamma: f32) -> SicImage {
+        Gamma::new(gamma).apply_operation(&mut image).unwrap();
+        image
+    }
+
+    fn gamma_of_ramp(value: f32) -> Vec<Rgba<u8>> {
+        let image = gamma(static_image(gray_ramp()), value);
+        image.as_ref().to_rgba8().pixels().copied().collect()
+    }
+
+    #[test]
+    fn adjusts_midtones() {
+        let cases = [(1.0, 120), (2.0, 175), (0.5, 56)];
+
+        for (value, expected) in cases {
+            let pixels = gamma_of_ramp(value);
+
+            assert_eq!(pixels[0], translucent_gray(0), "{}", value);
+            assert_eq!(pixels[8], translucent_gray(expected), "{}", value);
+            assert_eq!(pixels[17], translucent_gray(255), "{}", value);
+        }
+    }
+
+    #[test]
+    fn keeps_precision_of_16_bit_image() {
+        let image: ImageBuffer<Luma<u16>, Vec<u16>> = ImageBuffer::from_pixel(1, 1, Luma([30000]));
+
+        let image = gamma(SicImage::Static(DynamicImage::ImageLuma16(image)), 2.0);
+
+        match image {
+            SicImage::Static(DynamicImage::ImageLuma16(buffer)) => {
+                assert_eq!(*buffer.get_pixel(0, 0), Luma([44340]));
+            }
+            other => panic!(
+                "Expected a Luma16 image, but got: {:?}",
+                other.as_ref().color()
+            ),
+        }
+    }
+
+    #[test]
+    fn invalid_gamma() {
+        for value in [0.0, -1.0, f32::NAN] {
+            let mut image = static_image(gray_ramp());
+            let result = Gamma::new(value).apply_operation(&mut image);
+
+            assert!(
+                matches!(result, Err(SicImageEngineError::InvalidGamma(_))),
+                "{}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn maps_each_frame() {
+        let image = gamma(animated_image((0..3).map(|_| gray_ramp())), 2.0);
+
+        match image {
+            SicImage::Animated(animated) => {
+                assert_eq!(animated.frames().len(), 3);
+
+                for frame in animated.frames() {
+                    assert_eq!(*frame.buffer().get_pixel(8, 0), translucent_gray(175));
+                    assert_eq!(frame.delay(), Delay::from_numer_denom_ms(40, 1));
+                }
+            }
+            SicImage::Static(_) => panic!("Expected an animated image"),
+        }
+    }
+}
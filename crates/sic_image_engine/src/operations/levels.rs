@@ -0,0 +1,113 @@
+use crate::errors::SicImageEngineError;
+use crate::helper::tone::apply_tone_curve;
+use crate::operations::gamma::gamma_exponent;
+use crate::operations::ImageOperation;
+use crate::wrapper::levels::LevelsInputs;
+use sic_core::SicImage;
+
+/// Maps the values of the selected color channels from the black point up to the white point to
+/// the full range of values, and adjusts the midtones by the gamma. Values below the black point
+/// become black, and values above the white point become white.
+pub struct Levels<'levels> {
+    inputs: &'levels LevelsInputs,
+}
+
+impl<'levels> Levels<'levels> {
+    pub fn new(inputs: &'levels LevelsInputs) -> Self {
+        Self { inputs }
+    }
+}
+
+impl ImageOperation for Levels<'_> {
+    fn apply_operation(&self, image: &mut SicImage) -> Result<(), SicImageEngineError> {
+        let (black, white) = (self.inputs.black(), self.inputs.white());
+
+        if black >= white {
+            return Err(SicImageEngineError::LevelsInvalidRange(black, white));
+        }
+
+        let exponent = gamma_exponent(self.inputs.gamma())?;
+        let black = f32::from(black) / 255.0;
+        let range = f32::from(white) / 255.0 - black;
+
+        apply_tone_curve(image, self.inputs.channels(), |value| {
+            ((value - black) / range).clamp(0.0, 1.0).powf(exponent)
+        });
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helper::tone::fixtures::{gray_ramp, translucent_gray};
+    use crate::test_helpers::static_image;
+    use crate::wrapper::color_channels::ColorChannels;
+    use sic_core::image::{DynamicImage, GrayImage, Luma, Rgba};
+
+    fn levels(mut image: SicImage, inputs: LevelsInputs) -> SicImage {
+        Levels::new(&inputs).apply_operation(&mut image).unwrap();
+        image
+    }
+
+    fn levels_of_ramp(inputs: LevelsInputs) -> Vec<Rgba<u8>> {
+        let image = levels(static_image(gray_ramp()), inputs);
+        image.as_ref().to_rgba8().pixels().copied().collect()
+    }
+
+    #[test]
+    fn stretches_range() {
+        let pixels = levels_of_ramp(LevelsInputs::new(30, 225));
+
+        // values up to the black point become black, and from the white point become white
+        assert!(pixels[..=2].iter().all(|px| *px == translucent_gray(0)));
+        assert!(pixels[15..].iter().all(|px| *px == translucent_gray(255)));
+        assert_eq!(pixels[8], translucent_gray(118));
+
+        let pixels = levels_of_ramp(LevelsInputs::new(30, 225).with_gamma(2.0));
+        assert_eq!(pixels[8], translucent_gray(173));
+    }
+
+    #[test]
+    fn single_channel() {
+        let inputs = LevelsInputs::new(30, 225).with_channels(ColorChannels::Green);
+        let pixels = levels_of_ramp(inputs);
+
+        assert_eq!(pixels[8], Rgba([120, 118, 120, 128]));
+    }
+
+    #[test]
+    fn single_channel_of_grayscale_image() {
+        let image = GrayImage::from_pixel(1, 1, Luma([120]));
+        let inputs = LevelsInputs::new(30, 225).with_channels(ColorChannels::Blue);
+
+        let image = levels(SicImage::Static(DynamicImage::ImageLuma8(image)), inputs);
+
+        match image {
+            SicImage::Static(DynamicImage::ImageRgb8(buffer)) => {
+                assert_eq!(buffer.get_pixel(0, 0).0, [120, 120, 118]);
+            }
+            other => panic!(
+                "Expected an Rgb8 image, but got: {:?}",
+                other.as_ref().color()
+            ),
+        }
+    }
+
+    #[test]
+    fn invalid_levels() {
+        let cases = [
+            LevelsInputs::new(200, 100),
+            LevelsInputs::new(100, 100),
+            LevelsInputs::new(0, 255).with_gamma(0.0),
+        ];
+
+        for inputs in cases {
+            let mut image = static_image(gray_ramp());
+            let result = Levels::new(&inputs).apply_operation(&mut image);
+
+            assert!(result.is_err(), "{:?}", inputs);
+        }
+    }
+}
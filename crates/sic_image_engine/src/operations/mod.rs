@@ -2,27 +2,41 @@
 use crate::errors::SicImageEngineError;
 use sic_core::SicImage;
 
+pub mod auto_orient;
 pub mod blur;
 pub mod brighten;
 pub mod contrast;
+pub mod convert_profile;
 pub mod crop;
+pub mod curves;
 pub mod diff;
 #[cfg(feature = "imageproc-ops")]
 pub mod draw_text;
+pub mod drop_frames;
 pub mod filter3x3;
 pub mod flip_horizontal;
 pub mod flip_vertical;
+pub mod gamma;
 pub mod grayscale;
 pub mod horizontal_gradient;
 pub mod hue_rotate;
 pub mod invert;
+pub mod levels;
 pub mod overlay;
+pub mod pad;
 pub mod resize;
+pub mod reverse_frames;
+#[cfg(feature = "imageproc-ops")]
+pub mod rotate;
 pub mod rotate180;
 pub mod rotate270;
 pub mod rotate90;
+pub mod set_frame_delay;
+pub mod smart_crop;
+pub mod speed;
 #[cfg(feature = "imageproc-ops")]
 pub mod threshold;
+pub mod trim;
 pub mod unsharpen;
 pub mod vertical_gradient;
 
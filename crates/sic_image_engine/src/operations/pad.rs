@@ -0,0 +1,309 @@
+use crate::errors::SicImageEngineError;
+use crate::helper::fill::map_with_fill;
+use crate::operations::ImageOperation;
+use crate::wrapper::pad::{PadInputs, PadSize};
+use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
+use sic_core::image::{imageops, DynamicImage, ImageBuffer, Pixel, Rgba};
+use sic_core::SicImage;
+
+pub struct Pad<'pad> {
+    inputs: &'pad PadInputs,
+}
+
+impl<'pad> Pad<'pad> {
+    pub fn new(inputs: &'pad PadInputs) -> Self {
+        Self { inputs }
+    }
+}
+
+impl ImageOperation for Pad<'_> {
+    fn apply_operation(&self, image: &mut SicImage) -> Result<(), SicImageEngineError> {
+        let fill = self.inputs.fill();
+
+        match image {
+            SicImage::Static(image) => {
+                let placement = self.placement(image.width(), image.height())?;
+                *image = pad_static(image, &placement, fill);
+            }
+            SicImage::Animated(image) => {
+                let frames = image.frames_mut();
+
+                // Frames of a decoded animated image all have the same dimensions
+                if let Some(first) = frames.first() {
+                    let buffer = first.buffer();
+                    let placement = self.placement(buffer.width(), buffer.height())?;
+
+                    frames.par_iter_mut().for_each(|frame| {
+                        *frame.buffer_mut() = pad_buffer(frame.buffer(), &placement, fill);
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The dimensions of the padded canvas, and the position of the top left corner of the image on
+/// that canvas.
+struct Placement {
+    canvas: (u32, u32),
+    offset: (u32, u32),
+}
+
+impl Pad<'_> {
+    fn placement(&self, width: u32, height: u32) -> Result<Placement, SicImageEngineError> {
+        match self.inputs.size() {
+            PadSize::Canvas((w, h), gravity) => {
+                let canvas = (w.max(width), h.max(height));
+
+                Ok(Placement {
+                    canvas,
+                    offset: gravity.offset(canvas, (width, height)),
+                })
+            }
+            PadSize::Margins(margins) => {
+                let canvas = width
+                    .checked_add(margins.left)
+                    .and_then(|w| w.checked_add(margins.right))
+                    .zip(
+                        height
+                            .checked_add(margins.top)
+                            .and_then(|h| h.checked_add(margins.bottom)),
+                    )
+                    .ok_or(SicImageEngineError::PadCanvasTooLarge(width, height))?;
+
+                Ok(Placement {
+                    canvas,
+                    offset: (margins.left, margins.top),
+                })
+            }
+        }
+    }
+}
+
+fn pad_buffer<P: Pixel>(
+    buffer: &ImageBuffer<P, Vec<P::Subpixel>>,
+    placement: &Placement,
+    fill: P,
+) -> ImageBuffer<P, Vec<P::Subpixel>> {
+    let (width, height) = placement.canvas;
+    let (x, y) = placement.offset;
+
+    let mut canvas = ImageBuffer::from_pixel(width, height, fill);
+    imageops::replace(&mut canvas, buffer, i64::from(x), i64::from(y));
+    canvas
+}
+
+fn pad_static(image: &DynamicImage, placement: &Placement, fill: Rgba<u8>) -> DynamicImage {
+    map_with_fill!(image, fill, |buffer, fill| pad_buffer(
+        buffer, placement, fill
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{animated_image, filled_image, RED, TRANSPARENT, WHITE};
+    use crate::wrapper::gravity::Gravity;
+    use crate::wrapper::pad::Margins;
+    use sic_core::image::{Delay, GrayImage, Luma, Rgb, RgbImage, RgbaImage};
+
+    fn pad(mut image: SicImage, inputs: PadInputs) -> SicImage {
+        Pad::new(&inputs).apply_operation(&mut image).unwrap();
+        image
+    }
+
+    // The bounding box (x, y, width, height) of the red pixels
+    fn red_region(image: &RgbaImage) -> (u32, u32, u32, u32) {
+        let red = image
+            .enumerate_pixels()
+            .filter(|(_, _, px)| **px == RED)
+            .map(|(x, y, _)| (x, y))
+            .collect::<Vec<_>>();
+
+        let min_x = red.iter().map(|(x, _)| *x).min().unwrap();
+        let min_y = red.iter().map(|(_, y)| *y).min().unwrap();
+        let max_x = red.iter().map(|(x, _)| *x).max().unwrap();
+        let max_y = red.iter().map(|(_, y)| *y).max().unwrap();
+
+        (min_x, min_y, max_x - min_x + 1, max_y - min_y + 1)
+    }
+
+    #[test]
+    fn places_image_by_gravity() {
+        let cases = [
+            (Gravity::Center, (2, 1)),
+            (Gravity::NorthWest, (0, 0)),
+            (Gravity::East, (4, 1)),
+            (Gravity::South, (2, 2)),
+            (Gravity::SouthEast, (4, 2)),
+        ];
+
+        for (gravity, (x, y)) in cases {
+            let size = PadSize::Canvas((6, 4), gravity);
+            let image = pad(filled_image(2, 2, RED), PadInputs::new(size));
+
+            let buffer = image.as_ref().to_rgba8();
+            assert_eq!(buffer.dimensions(), (6, 4), "{:?}", gravity);
+            assert_eq!(red_region(&buffer), (x, y, 2, 2), "{:?}", gravity);
+            assert_eq!(buffer.pixels().filter(|px| **px == TRANSPARENT).count(), 20);
+        }
+    }
+
+    #[test]
+    fn does_not_crop_larger_image() {
+        let size = PadSize::Canvas((4, 4), Gravity::Center);
+        let image = pad(filled_image(6, 2, RED), PadInputs::new(size));
+
+        let buffer = image.as_ref().to_rgba8();
+        assert_eq!(buffer.dimensions(), (6, 4));
+        assert_eq!(red_region(&buffer), (0, 1, 6, 2));
+    }
+
+    #[test]
+    fn extends_by_margins() {
+        let margins = PadSize::Margins(Margins {
+            top: 1,
+            right: 2,
+            bottom: 3,
+            left: 4,
+        });
+        let image = pad(
+            filled_image(2, 2, RED),
+            PadInputs::with_fill(margins, WHITE),
+        );
+
+        let buffer = image.as_ref().to_rgba8();
+        assert_eq!(buffer.dimensions(), (8, 6));
+        assert_eq!(red_region(&buffer), (4, 1, 2, 2));
+        assert_eq!(buffer.pixels().filter(|px| **px == WHITE).count(), 44);
+    }
+
+    #[test]
+    fn opaque_fill_keeps_color_type() {
+        let image = RgbImage::from_pixel(2, 2, Rgb([255, 0, 0]));
+        let size = PadSize::Margins(Margins::uniform(1));
+
+        let image = pad(
+            SicImage::Static(DynamicImage::ImageRgb8(image)),
+            PadInputs::with_fill(size, WHITE),
+        );
+
+        match image {
+            SicImage::Static(DynamicImage::ImageRgb8(buffer)) => {
+                assert_eq!(buffer.dimensions(), (4, 4));
+                assert_eq!(*buffer.get_pixel(0, 0), Rgb([255, 255, 255]));
+                assert_eq!(*buffer.get_pixel(1, 1), Rgb([255, 0, 0]));
+            }
+            other => panic!(
+                "Expected an Rgb8 image, but got: {:?}",
+                other.as_ref().color()
+            ),
+        }
+    }
+
+    #[test]
+    fn translucent_fill_adds_alpha_channel() {
+        let image = RgbImage::from_pixel(2, 2, Rgb([255, 0, 0]));
+        let size = PadSize::Margins(Margins::uniform(1));
+
+        let image = pad(
+            SicImage::Static(DynamicImage::ImageRgb8(image)),
+            PadInputs::new(size),
+        );
+
+        match image {
+            SicImage::Static(DynamicImage::ImageRgba8(buffer)) => {
+                assert_eq!(*buffer.get_pixel(0, 0), TRANSPARENT);
+                assert_eq!(*buffer.get_pixel(1, 1), RED);
+            }
+            other => panic!(
+                "Expected an Rgba8 image, but got: {:?}",
+                other.as_ref().color()
+            ),
+        }
+    }
+
+    #[test]
+    fn colored_fill_adds_color_channels() {
+        let image = GrayImage::from_pixel(2, 2, Luma([128]));
+        let size = PadSize::Margins(Margins::uniform(1));
+
+        let image = pad(
+            SicImage::Static(DynamicImage::ImageLuma8(image)),
+            PadInputs::with_fill(size, RED),
+        );
+
+        match image {
+            SicImage::Static(DynamicImage::ImageRgb8(buffer)) => {
+                assert_eq!(*buffer.get_pixel(0, 0), Rgb([255, 0, 0]));
+                assert_eq!(*buffer.get_pixel(1, 1), Rgb([128, 128, 128]));
+            }
+            other => panic!(
+                "Expected an Rgb8 image, but got: {:?}",
+                other.as_ref().color()
+            ),
+        }
+    }
+
+    #[test]
+    fn keeps_precision_of_16_bit_image() {
+        let image: ImageBuffer<Rgb<u16>, Vec<u16>> =
+            ImageBuffer::from_pixel(2, 2, Rgb([1000, 2000, 3000]));
+        let size = PadSize::Margins(Margins::uniform(1));
+
+        let image = pad(
+            SicImage::Static(DynamicImage::ImageRgb16(image)),
+            PadInputs::with_fill(size, WHITE),
+        );
+
+        match image {
+            SicImage::Static(DynamicImage::ImageRgb16(buffer)) => {
+                assert_eq!(*buffer.get_pixel(0, 0), Rgb([u16::MAX; 3]));
+                assert_eq!(*buffer.get_pixel(1, 1), Rgb([1000, 2000, 3000]));
+            }
+            other => panic!(
+                "Expected an Rgb16 image, but got: {:?}",
+                other.as_ref().color()
+            ),
+        }
+    }
+
+    #[test]
+    fn pads_each_frame() {
+        let image = animated_image((0..3).map(|_| RgbaImage::from_pixel(2, 2, RED)));
+
+        let size = PadSize::Canvas((4, 4), Gravity::NorthWest);
+        let image = pad(image, PadInputs::with_fill(size, WHITE));
+
+        match image {
+            SicImage::Animated(animated) => {
+                assert_eq!(animated.frames().len(), 3);
+
+                for frame in animated.frames() {
+                    assert_eq!(red_region(frame.buffer()), (0, 0, 2, 2));
+                    assert_eq!(frame.buffer().dimensions(), (4, 4));
+                    assert_eq!(frame.delay(), Delay::from_numer_denom_ms(40, 1));
+                }
+            }
+            SicImage::Static(_) => panic!("Expected an animated image"),
+        }
+    }
+
+    #[test]
+    fn canvas_too_large() {
+        let size = PadSize::Margins(Margins {
+            left: u32::MAX,
+            ..Margins::default()
+        });
+
+        let mut image = filled_image(2, 2, RED);
+        let result = Pad::new(&PadInputs::new(size)).apply_operation(&mut image);
+
+        assert!(matches!(
+            result,
+            Err(SicImageEngineError::PadCanvasTooLarge(2, 2))
+        ));
+    }
+}
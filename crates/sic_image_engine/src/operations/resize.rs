@@ -1,26 +1,24 @@
 use crate::errors::SicImageEngineError;
 use crate::helper;
 use crate::operations::ImageOperation;
+use crate::wrapper::resize_mode::ResizeMode;
 use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
 use sic_core::image::imageops;
 use sic_core::image::imageops::FilterType;
-use sic_core::image::DynamicImage;
-use sic_core::{image, SicImage};
+use sic_core::SicImage;
 
 #[derive(Debug)]
 pub struct Resize {
-    x: u32,
-    y: u32,
-    preserve_aspect_ratio: bool,
+    mode: ResizeMode,
+    shrink_only: bool,
     filter_type: FilterType,
 }
 
 impl Resize {
-    pub fn new(x: u32, y: u32, preserve_aspect_ratio: bool, filter_type: FilterType) -> Self {
+    pub fn new(mode: ResizeMode, shrink_only: bool, filter_type: FilterType) -> Self {
         Self {
-            x,
-            y,
-            preserve_aspect_ratio,
+            mode,
+            shrink_only,
             filter_type,
         }
     }
@@ -29,49 +27,274 @@ impl Resize {
 impl ImageOperation for Resize {
     fn apply_operation(&self, image: &mut SicImage) -> Result<(), SicImageEngineError> {
         match image {
-            SicImage::Static(image) => resize_static(image, self),
-            SicImage::Animated(image) => resize_animated(image.frames_mut(), self),
+            SicImage::Static(image) => {
+                let target = self.target(image.width(), image.height());
+
+                if let Some((width, height)) = target.size {
+                    *image = image.resize_exact(width, height, self.filter_type);
+                }
+
+                if let Some(region) = target.crop {
+                    *image = image.crop_imm(region.x, region.y, region.width, region.height);
+                }
+            }
+            SicImage::Animated(image) => {
+                image.frames_mut().par_iter_mut().for_each(|frame| {
+                    let buffer = frame.buffer_mut();
+                    let target = self.target(buffer.width(), buffer.height());
+
+                    if let Some((width, height)) = target.size {
+                        *buffer = imageops::resize(buffer, width, height, self.filter_type);
+                    }
+
+                    if let Some(region) = target.crop {
+                        *buffer = imageops::crop_imm(
+                            buffer,
+                            region.x,
+                            region.y,
+                            region.width,
+                            region.height,
+                        )
+                        .to_image();
+                    }
+                });
+            }
         }
 
         Ok(())
     }
 }
 
-fn resize_animated(frames: &mut [image::Frame], cfg: &Resize) {
-    frames.par_iter_mut().for_each(|frame| {
-        let (x, y) = if cfg.preserve_aspect_ratio {
-            helper::resize::resize_dimensions(
-                frame.buffer().width(),
-                frame.buffer().height(),
-                cfg.x,
-                cfg.y,
-                false,
-            )
+/// The dimensions to which an image is resized, and the region of the resized image which is
+/// kept, if any.
+#[derive(Debug, PartialEq)]
+struct Target {
+    size: Option<(u32, u32)>,
+    crop: Option<Region>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Region {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+impl Resize {
+    fn target(&self, width: u32, height: u32) -> Target {
+        let size = match self.mode {
+            ResizeMode::Exact(size) => size,
+            ResizeMode::Fit((w, h)) => {
+                helper::resize::resize_dimensions(width, height, w, h, false)
+            }
+            ResizeMode::Cover((w, h), _) => {
+                helper::resize::resize_dimensions(width, height, w, h, true)
+            }
+            ResizeMode::Scale(scale) => {
+                let scaled = |dimension: u32| {
+                    let scaled = (f64::from(dimension) * f64::from(scale.factor())).round();
+                    scaled.clamp(1.0, f64::from(u32::MAX)) as u32
+                };
+
+                (scaled(width), scaled(height))
+            }
+            ResizeMode::MaxEdge(edge) if width.max(height) > edge => {
+                helper::resize::resize_dimensions(width, height, edge, edge, false)
+            }
+            ResizeMode::MaxEdge(_) => (width, height),
+        };
+
+        let size = if !self.shrink_only {
+            size
+        } else if let ResizeMode::Exact(_) = self.mode {
+            // the aspect ratio isn't preserved, so each dimension is limited separately
+            (size.0.min(width), size.1.min(height))
+        } else if size.0 > width || size.1 > height {
+            (width, height)
         } else {
-            (cfg.x, cfg.y)
+            size
         };
 
-        *frame.buffer_mut() = imageops::resize(frame.buffer_mut(), x, y, cfg.filter_type);
-    });
-}
+        let crop = match self.mode {
+            ResizeMode::Cover((w, h), gravity) => {
+                let region = (w.min(size.0), h.min(size.1));
+                let (x, y) = gravity.offset(size, region);
 
-fn resize_static(image: &mut DynamicImage, cfg: &Resize) {
-    if cfg.preserve_aspect_ratio {
-        resize_with_preserve_aspect_ratio(image, cfg.x, cfg.y, cfg.filter_type)
-    } else {
-        resize_regularly(image, cfg.x, cfg.y, cfg.filter_type)
+                (region != size).then(|| Region {
+                    x,
+                    y,
+                    width: region.0,
+                    height: region.1,
+                })
+            }
+            _ => None,
+        };
+
+        Target {
+            size: (size != (width, height)).then(|| size),
+            crop,
+        }
     }
 }
 
-fn resize_regularly(image: &mut DynamicImage, x: u32, y: u32, filter_type: FilterType) {
-    *image = image.resize_exact(x, y, filter_type);
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{animated_image, static_image, RED};
+    use crate::wrapper::gravity::Gravity;
+    use crate::wrapper::resize_mode::ScaleFactor;
+    use sic_core::image::{Rgba, RgbaImage};
 
-fn resize_with_preserve_aspect_ratio(
-    image: &mut DynamicImage,
-    x: u32,
-    y: u32,
-    filter_type: FilterType,
-) {
-    *image = image.resize(x, y, filter_type);
+    const BLUE: Rgba<u8> = Rgba([0, 0, 255, 255]);
+
+    // A 40x20 image, of which the left half is red and the right half is blue.
+    fn red_and_blue() -> RgbaImage {
+        RgbaImage::from_fn(40, 20, |x, _| if x < 20 { RED } else { BLUE })
+    }
+
+    fn resize(mut image: SicImage, mode: ResizeMode, shrink_only: bool) -> SicImage {
+        Resize::new(mode, shrink_only, FilterType::Nearest)
+            .apply_operation(&mut image)
+            .unwrap();
+
+        image
+    }
+
+    fn scale(percentage: &str) -> ResizeMode {
+        ResizeMode::Scale(ScaleFactor::try_from_str(percentage).unwrap())
+    }
+
+    fn colors(image: &SicImage) -> Vec<Rgba<u8>> {
+        let mut colors = image
+            .as_ref()
+            .to_rgba8()
+            .pixels()
+            .copied()
+            .collect::<Vec<_>>();
+        colors.sort_unstable_by_key(|px| px.0);
+        colors.dedup();
+        colors
+    }
+
+    #[test]
+    fn exact() {
+        let cases = [
+            (ResizeMode::Exact((10, 10)), false, (10, 10)),
+            (ResizeMode::Exact((80, 10)), false, (80, 10)),
+            (ResizeMode::Exact((80, 10)), true, (40, 10)),
+            (ResizeMode::Exact((80, 40)), true, (40, 20)),
+        ];
+
+        for (mode, shrink_only, expected) in cases {
+            let image = resize(static_image(red_and_blue()), mode, shrink_only);
+            assert_eq!(image.dimensions(), expected, "{:?}", mode);
+        }
+    }
+
+    #[test]
+    fn fit() {
+        let cases = [
+            (ResizeMode::Fit((10, 10)), false, (10, 5)),
+            (ResizeMode::Fit((80, 80)), false, (80, 40)),
+            (ResizeMode::Fit((80, 80)), true, (40, 20)),
+        ];
+
+        for (mode, shrink_only, expected) in cases {
+            let image = resize(static_image(red_and_blue()), mode, shrink_only);
+            assert_eq!(image.dimensions(), expected, "{:?}", mode);
+        }
+    }
+
+    #[test]
+    fn cover() {
+        let mode = ResizeMode::Cover((10, 10), Gravity::Center);
+        let image = resize(static_image(red_and_blue()), mode, false);
+
+        assert_eq!(image.dimensions(), (10, 10));
+        assert_eq!(colors(&image).len(), 2);
+    }
+
+    #[test]
+    fn cover_with_gravity() {
+        let cases = [
+            (Gravity::West, RED),
+            (Gravity::NorthWest, RED),
+            (Gravity::East, BLUE),
+            (Gravity::SouthEast, BLUE),
+        ];
+
+        for (gravity, expected) in cases {
+            let mode = ResizeMode::Cover((10, 10), gravity);
+            let image = resize(static_image(red_and_blue()), mode, false);
+
+            assert_eq!(image.dimensions(), (10, 10));
+            assert_eq!(colors(&image), vec![expected], "{:?}", gravity);
+        }
+    }
+
+    #[test]
+    fn cover_shrink_only() {
+        // the image isn't enlarged, but the part which doesn't fit is still cropped
+        let mode = ResizeMode::Cover((20, 40), Gravity::West);
+        let image = resize(static_image(red_and_blue()), mode, true);
+
+        assert_eq!(image.dimensions(), (20, 20));
+        assert_eq!(colors(&image), vec![RED]);
+    }
+
+    #[test]
+    fn scale_by_percentage() {
+        let cases = [
+            (scale("50%"), false, (20, 10)),
+            (scale("150%"), false, (60, 30)),
+            (scale("1%"), false, (1, 1)),
+            (scale("150%"), true, (40, 20)),
+        ];
+
+        for (mode, shrink_only, expected) in cases {
+            let image = resize(static_image(red_and_blue()), mode, shrink_only);
+            assert_eq!(image.dimensions(), expected, "{:?}", mode);
+        }
+    }
+
+    #[test]
+    fn max_edge() {
+        let cases = [
+            (ResizeMode::MaxEdge(10), (10, 5)),
+            (ResizeMode::MaxEdge(40), (40, 20)),
+            (ResizeMode::MaxEdge(80), (40, 20)),
+        ];
+
+        for (mode, expected) in cases {
+            let image = resize(static_image(red_and_blue()), mode, false);
+            assert_eq!(image.dimensions(), expected, "{:?}", mode);
+        }
+    }
+
+    #[test]
+    fn max_edge_of_portrait_image() {
+        let portrait = static_image(imageops::rotate90(&red_and_blue()));
+        let image = resize(portrait, ResizeMode::MaxEdge(10), false);
+
+        assert_eq!(image.dimensions(), (5, 10));
+    }
+
+    #[test]
+    fn resizes_each_frame() {
+        let image = animated_image((0..3).map(|_| red_and_blue()));
+        let image = resize(image, ResizeMode::Cover((10, 10), Gravity::East), false);
+
+        match image {
+            SicImage::Animated(image) => {
+                assert_eq!(image.frames().len(), 3);
+
+                for frame in image.frames() {
+                    assert_eq!(frame.buffer().dimensions(), (10, 10));
+                    assert!(frame.buffer().pixels().all(|px| *px == BLUE));
+                }
+            }
+            SicImage::Static(_) => panic!("Expected an animated image"),
+        }
+    }
 }
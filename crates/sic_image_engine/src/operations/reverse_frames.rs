@@ -0,0 +1,22 @@
+use crate::errors::SicImageEngineError;
+use crate::operations::ImageOperation;
+use sic_core::SicImage;
+
+pub struct ReverseFrames;
+
+impl ReverseFrames {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl ImageOperation for ReverseFrames {
+    fn apply_operation(&self, image: &mut SicImage) -> Result<(), SicImageEngineError> {
+        match image {
+            SicImage::Static(_) => {}
+            SicImage::Animated(image) => image.reverse_frames(),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,315 @@
+use crate::errors::SicImageEngineError;
+use crate::helper::fill::map_with_fill;
+use crate::operations::ImageOperation;
+use crate::wrapper::filter_type::FilterTypeWrap;
+use crate::wrapper::rotate::{RotateCanvas, RotateInputs};
+use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
+use sic_core::image::imageops::{self, FilterType};
+use sic_core::image::{ImageBuffer, Pixel};
+use sic_core::imageproc::definitions::Clamp;
+use sic_core::imageproc::geometric_transformations::{warp_into, Interpolation, Projection};
+use sic_core::SicImage;
+
+// The number of pixels by which the image is padded with the fill color before it is rotated.
+// Interpolation yields the fill color for pixels of which a neighbouring pixel used for the
+// interpolation is out of bounds (bicubic interpolation uses two neighbours on each side), so
+// without padding, the pixels at the edges of the image would be lost. It also smooths the
+// edges of the rotated image.
+const MARGIN: u32 = 3;
+
+pub struct Rotate<'rotate> {
+    inputs: &'rotate RotateInputs,
+}
+
+impl<'rotate> Rotate<'rotate> {
+    pub fn new(inputs: &'rotate RotateInputs) -> Self {
+        Self { inputs }
+    }
+}
+
+impl ImageOperation for Rotate<'_> {
+    fn apply_operation(&self, image: &mut SicImage) -> Result<(), SicImageEngineError> {
+        let rotation = self.rotation();
+        let fill = self.inputs.fill();
+
+        match image {
+            SicImage::Static(image) => {
+                *image = match rotation {
+                    Rotation::None => return Ok(()),
+                    Rotation::Rotate90 => image.rotate90(),
+                    Rotation::Rotate180 => image.rotate180(),
+                    Rotation::Rotate270 => image.rotate270(),
+                    Rotation::Interpolated(theta) => {
+                        map_with_fill!(image, fill, |buffer, fill| self
+                            .rotate_buffer(buffer, theta, fill))
+                    }
+                }
+            }
+            SicImage::Animated(image) => {
+                image.frames_mut().par_iter_mut().for_each(|frame| {
+                    let buffer = frame.buffer_mut();
+
+                    *buffer = match rotation {
+                        Rotation::None => return,
+                        Rotation::Rotate90 => imageops::rotate90(buffer),
+                        Rotation::Rotate180 => imageops::rotate180(buffer),
+                        Rotation::Rotate270 => imageops::rotate270(buffer),
+                        Rotation::Interpolated(theta) => self.rotate_buffer(buffer, theta, fill),
+                    };
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Rotation {
+    None,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    // clockwise, in radians
+    Interpolated(f32),
+}
+
+impl Rotate<'_> {
+    // Rotations by a right angle which don't change the dimensions of the canvas, or which expand
+    // it anyways, are done without interpolation, so they are lossless.
+    fn rotation(&self) -> Rotation {
+        let degrees = self.inputs.degrees().rem_euclid(360.0);
+        let expand = self.inputs.canvas() == RotateCanvas::Expand;
+
+        if degrees == 0.0 {
+            Rotation::None
+        } else if degrees == 90.0 && expand {
+            Rotation::Rotate90
+        } else if degrees == 180.0 {
+            Rotation::Rotate180
+        } else if degrees == 270.0 && expand {
+            Rotation::Rotate270
+        } else {
+            Rotation::Interpolated(degrees.to_radians())
+        }
+    }
+
+    fn rotate_buffer<P>(
+        &self,
+        buffer: &ImageBuffer<P, Vec<P::Subpixel>>,
+        theta: f32,
+        fill: P,
+    ) -> ImageBuffer<P, Vec<P::Subpixel>>
+    where
+        P: Pixel + Send + Sync,
+        P::Subpixel: Into<f32> + Clamp<f32> + Send + Sync,
+    {
+        let (width, height) = buffer.dimensions();
+        let (canvas_width, canvas_height) = match self.inputs.canvas() {
+            RotateCanvas::Expand => expanded_dimensions(width, height, theta),
+            RotateCanvas::Crop => (width, height),
+        };
+
+        let mut source = ImageBuffer::from_pixel(width + 2 * MARGIN, height + 2 * MARGIN, fill);
+        imageops::replace(&mut source, buffer, i64::from(MARGIN), i64::from(MARGIN));
+
+        // Rotate about the center of the image, and move that center to the center of the canvas
+        let (cx, cy) = center(width, height);
+        let (canvas_cx, canvas_cy) = center(canvas_width, canvas_height);
+        let margin = MARGIN as f32;
+
+        let projection = Projection::translate(canvas_cx, canvas_cy)
+            * Projection::rotate(theta)
+            * Projection::translate(-(cx + margin), -(cy + margin));
+
+        let mut canvas = ImageBuffer::new(canvas_width, canvas_height);
+        warp_into(
+            &source,
+            &projection,
+            interpolation(self.inputs.interpolation()),
+            fill,
+            &mut canvas,
+        );
+
+        canvas
+    }
+}
+
+// The center of an image, where the center of the top left pixel is (0, 0)
+fn center(width: u32, height: u32) -> (f32, f32) {
+    ((width as f32 - 1.0) / 2.0, (height as f32 - 1.0) / 2.0)
+}
+
+// The smallest dimensions which fit the image after it is rotated by theta radians
+fn expanded_dimensions(width: u32, height: u32, theta: f32) -> (u32, u32) {
+    let (sin, cos) = f64::from(theta).sin_cos();
+    let (sin, cos) = (sin.abs(), cos.abs());
+    let (width, height) = (f64::from(width), f64::from(height));
+
+    // Allow for rounding errors, which would otherwise add a pixel to the canvas
+    let fit = |length: f64| (length - 1e-3).ceil().clamp(1.0, f64::from(u32::MAX)) as u32;
+
+    (
+        fit(width * cos + height * sin),
+        fit(width * sin + height * cos),
+    )
+}
+
+// imageproc has no equivalent of the Gaussian and Lanczos filters, so bicubic interpolation is
+// used instead.
+fn interpolation(filter: FilterTypeWrap) -> Interpolation {
+    match FilterType::from(filter) {
+        FilterType::Nearest => Interpolation::Nearest,
+        FilterType::Triangle => Interpolation::Bilinear,
+        FilterType::CatmullRom | FilterType::Gaussian | FilterType::Lanczos3 => {
+            Interpolation::Bicubic
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{animated_image, filled_image, static_image, RED, WHITE};
+    use sic_core::image::{Delay, DynamicImage, Rgb, RgbImage, Rgba, RgbaImage};
+
+    fn rotate(mut image: SicImage, inputs: RotateInputs) -> SicImage {
+        Rotate::new(&inputs).apply_operation(&mut image).unwrap();
+        image
+    }
+
+    // An image of which each pixel is unique
+    fn gradient(width: u32, height: u32) -> SicImage {
+        static_image(RgbaImage::from_fn(width, height, |x, y| {
+            Rgba([x as u8 * 20, y as u8 * 20, 0, 255])
+        }))
+    }
+
+    #[test]
+    fn right_angles_are_exact() {
+        type ExactRotation = fn(&DynamicImage) -> DynamicImage;
+
+        let cases: [(f32, ExactRotation); 5] = [
+            (90.0, DynamicImage::rotate90),
+            (180.0, DynamicImage::rotate180),
+            (270.0, DynamicImage::rotate270),
+            (-90.0, DynamicImage::rotate270),
+            (450.0, DynamicImage::rotate90),
+        ];
+
+        for (degrees, expected) in cases {
+            let image = rotate(gradient(4, 3), RotateInputs::new(degrees));
+
+            assert_eq!(
+                image.as_ref(),
+                &expected(gradient(4, 3).as_ref()),
+                "{}",
+                degrees
+            );
+        }
+    }
+
+    #[test]
+    fn full_rotation_is_unchanged() {
+        for degrees in [0.0, 360.0, -720.0] {
+            let image = rotate(gradient(4, 3), RotateInputs::new(degrees));
+
+            assert_eq!(image.as_ref(), gradient(4, 3).as_ref(), "{}", degrees);
+        }
+    }
+
+    #[test]
+    fn expands_canvas() {
+        let cases = [(45.0, (15, 15)), (30.0, (14, 14)), (-45.0, (15, 15))];
+
+        for (degrees, expected) in cases {
+            let image = rotate(filled_image(10, 10, RED), RotateInputs::new(degrees));
+
+            assert_eq!(image.dimensions(), expected, "{}", degrees);
+        }
+    }
+
+    #[test]
+    fn expanded_canvas_of_rectangle() {
+        let image = rotate(filled_image(20, 10, RED), RotateInputs::new(90.5));
+
+        assert_eq!(image.dimensions(), (11, 21));
+    }
+
+    #[test]
+    fn fills_exposed_corners() {
+        for canvas in [RotateCanvas::Expand, RotateCanvas::Crop] {
+            let inputs = RotateInputs::new(45.0).with_canvas(canvas).with_fill(WHITE);
+            let image = rotate(filled_image(10, 10, RED), inputs);
+
+            let buffer = image.as_ref().to_rgba8();
+            let (width, height) = buffer.dimensions();
+
+            assert_eq!(*buffer.get_pixel(0, 0), WHITE, "{:?}", canvas);
+            assert_eq!(*buffer.get_pixel(width - 1, height - 1), WHITE);
+            assert_eq!(*buffer.get_pixel(width / 2, height / 2), RED);
+        }
+    }
+
+    #[test]
+    fn crop_keeps_dimensions() {
+        for degrees in [45.0, 90.0, 270.0] {
+            let inputs = RotateInputs::new(degrees).with_canvas(RotateCanvas::Crop);
+            let image = rotate(filled_image(20, 10, RED), inputs);
+
+            assert_eq!(image.dimensions(), (20, 10), "{}", degrees);
+        }
+    }
+
+    #[test]
+    fn keeps_edges_of_image() {
+        // A rotation by a tiny angle shouldn't lose the pixels at the edges of the image
+        let inputs = RotateInputs::new(0.01).with_canvas(RotateCanvas::Crop);
+        let image = rotate(filled_image(10, 10, RED), inputs);
+
+        // The pixels are blended slightly with the fill color
+        let buffer = image.as_ref().to_rgba8();
+        assert!(buffer.pixels().all(|px| px[0] >= 250 && px[3] >= 250));
+    }
+
+    #[test]
+    fn translucent_fill_adds_alpha_channel() {
+        let image = SicImage::Static(DynamicImage::ImageRgb8(RgbImage::from_pixel(
+            4,
+            4,
+            Rgb([255, 0, 0]),
+        )));
+
+        let image = rotate(image, RotateInputs::new(45.0));
+
+        match image {
+            SicImage::Static(DynamicImage::ImageRgba8(buffer)) => {
+                assert_eq!(buffer.get_pixel(0, 0)[3], 0);
+            }
+            other => panic!(
+                "Expected an Rgba8 image, but got: {:?}",
+                other.as_ref().color()
+            ),
+        }
+    }
+
+    #[test]
+    fn rotates_each_frame() {
+        let image = animated_image((0..3).map(|_| RgbaImage::from_pixel(10, 10, RED)));
+        let image = rotate(image, RotateInputs::new(45.0).with_fill(WHITE));
+
+        match image {
+            SicImage::Animated(animated) => {
+                assert_eq!(animated.frames().len(), 3);
+
+                for frame in animated.frames() {
+                    assert_eq!(frame.buffer().dimensions(), (15, 15));
+                    assert_eq!(*frame.buffer().get_pixel(0, 0), WHITE);
+                    assert_eq!(*frame.buffer().get_pixel(7, 7), RED);
+                    assert_eq!(frame.delay(), Delay::from_numer_denom_ms(40, 1));
+                }
+            }
+            SicImage::Static(_) => panic!("Expected an animated image"),
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use crate::errors::SicImageEngineError;
+use crate::operations::ImageOperation;
+use crate::wrapper::frame_delay::FrameDelay;
+use sic_core::SicImage;
+
+pub struct SetFrameDelay {
+    delay: FrameDelay,
+}
+
+impl SetFrameDelay {
+    pub fn new(delay: FrameDelay) -> Self {
+        Self { delay }
+    }
+}
+
+impl ImageOperation for SetFrameDelay {
+    fn apply_operation(&self, image: &mut SicImage) -> Result<(), SicImageEngineError> {
+        match image {
+            // Static images have no frame delay.
+            SicImage::Static(_) => {}
+            SicImage::Animated(image) => image.map_delays(|_| self.delay.into()),
+        }
+
+        Ok(())
+    }
+}
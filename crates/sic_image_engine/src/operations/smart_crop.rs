@@ -0,0 +1,494 @@
+use crate::errors::SicImageEngineError;
+use crate::operations::ImageOperation;
+use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
+use sic_core::image::imageops::{self, FilterType};
+use sic_core::image::RgbaImage;
+use sic_core::SicImage;
+use std::borrow::Cow;
+
+/// Crops the image to its most interesting region with the aspect ratio `width:height`, and
+/// scales the region down to `width` by `height` pixels. Images which are smaller than `width`
+/// by `height` are cropped to the aspect ratio, but not scaled up.
+///
+/// The region is the largest region with the aspect ratio which fits within the image, placed
+/// where the image is most interesting, which is estimated by the amount of edges (detail),
+/// saturated colors and skin tones. The frames of an animated image share a single region.
+pub struct SmartCrop {
+    width: u32,
+    height: u32,
+    filter_type: FilterType,
+}
+
+impl SmartCrop {
+    pub fn new(width: u32, height: u32, filter_type: FilterType) -> Self {
+        Self {
+            width,
+            height,
+            filter_type,
+        }
+    }
+}
+
+impl ImageOperation for SmartCrop {
+    fn apply_operation(&self, image: &mut SicImage) -> Result<(), SicImageEngineError> {
+        if self.width == 0 || self.height == 0 {
+            return Err(SicImageEngineError::SmartCropInvalidSize(
+                self.width,
+                self.height,
+            ));
+        }
+
+        match image {
+            SicImage::Static(image) => {
+                let buffer = match image.as_rgba8() {
+                    Some(buffer) => Cow::Borrowed(buffer),
+                    None => Cow::Owned(image.to_rgba8()),
+                };
+                let window = self.find_window(&[&buffer]);
+                *image = image.crop_imm(window.x, window.y, window.width, window.height);
+
+                let (width, height) = self.scaled_dimensions(&window);
+                if (width, height) != (window.width, window.height) {
+                    *image = image.resize_exact(width, height, self.filter_type);
+                }
+            }
+            SicImage::Animated(image) => {
+                let frames = image.frames_mut();
+                let buffers = frames
+                    .iter()
+                    .map(|frame| frame.buffer())
+                    .collect::<Vec<_>>();
+
+                let window = self.find_window(&buffers);
+                let (width, height) = self.scaled_dimensions(&window);
+
+                frames.par_iter_mut().for_each(|frame| {
+                    let buffer = frame.buffer_mut();
+                    let cropped =
+                        imageops::crop(buffer, window.x, window.y, window.width, window.height)
+                            .to_image();
+
+                    *buffer = if (width, height) != (window.width, window.height) {
+                        imageops::resize(&cropped, width, height, self.filter_type)
+                    } else {
+                        cropped
+                    };
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Window {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+impl SmartCrop {
+    /// Finds the most interesting window over all frames, which all have the same dimensions.
+    fn find_window(&self, frames: &[&RgbaImage]) -> Window {
+        let (image_width, image_height) = frames
+            .first()
+            .map(|frame| frame.dimensions())
+            .unwrap_or((0, 0));
+
+        let (width, height) = largest_window(image_width, image_height, self.width, self.height);
+
+        // The window can only move along a single axis, since it spans the other axis entirely.
+        let horizontal = width < image_width;
+        let slack = if horizontal {
+            image_width - width
+        } else {
+            image_height - height
+        };
+
+        if slack == 0 {
+            return Window {
+                x: 0,
+                y: 0,
+                width,
+                height,
+            };
+        }
+
+        // The importance of each column (or row) of the image, summed over all frames.
+        let mut profile = Vec::new();
+        for frame in frames {
+            let analysed = analysis_image(frame);
+            let importance = importance_profile(&analysed, horizontal);
+
+            profile.resize(importance.len(), 0.0);
+            profile
+                .iter_mut()
+                .zip(importance)
+                .for_each(|(total, value)| *total += value);
+        }
+
+        let offset = best_offset(
+            &profile,
+            slack,
+            if horizontal {
+                image_width
+            } else {
+                image_height
+            },
+        );
+
+        if horizontal {
+            Window {
+                x: offset,
+                y: 0,
+                width,
+                height,
+            }
+        } else {
+            Window {
+                x: 0,
+                y: offset,
+                width,
+                height,
+            }
+        }
+    }
+
+    /// The dimensions to which the window is scaled; the window is not scaled up.
+    fn scaled_dimensions(&self, window: &Window) -> (u32, u32) {
+        if window.width > self.width || window.height > self.height {
+            (self.width, self.height)
+        } else {
+            (window.width, window.height)
+        }
+    }
+}
+
+/// The largest window with the aspect ratio `ratio_width:ratio_height`, which fits within an
+/// image of `width` by `height` pixels.
+fn largest_window(width: u32, height: u32, ratio_width: u32, ratio_height: u32) -> (u32, u32) {
+    let (width, height) = (u64::from(width), u64::from(height));
+    let (ratio_width, ratio_height) = (u64::from(ratio_width), u64::from(ratio_height));
+
+    let (window_width, window_height) = if width * ratio_height >= height * ratio_width {
+        // the image is wider than the aspect ratio
+        (
+            (height * ratio_width + ratio_height / 2) / ratio_height,
+            height,
+        )
+    } else {
+        (
+            width,
+            (width * ratio_height + ratio_width / 2) / ratio_width,
+        )
+    };
+
+    (
+        window_width.max(1).min(width) as u32,
+        window_height.max(1).min(height) as u32,
+    )
+}
+
+/// The longest edge of the image with which the importance of pixels is estimated; larger images
+/// are scaled down first, which is faster, and reduces the effect of noise.
+const ANALYSIS_SIZE: u32 = 256;
+
+fn analysis_image(frame: &RgbaImage) -> Cow<'_, RgbaImage> {
+    let (width, height) = frame.dimensions();
+    let longest = width.max(height);
+
+    if longest <= ANALYSIS_SIZE {
+        return Cow::Borrowed(frame);
+    }
+
+    let scale = f64::from(ANALYSIS_SIZE) / f64::from(longest);
+    let scaled = |dimension: u32| ((f64::from(dimension) * scale).round() as u32).max(1);
+
+    Cow::Owned(imageops::resize(
+        frame,
+        scaled(width),
+        scaled(height),
+        FilterType::Triangle,
+    ))
+}
+
+// The weights of the heuristics of the importance of a pixel.
+const EDGE_WEIGHT: f32 = 1.0;
+const SATURATION_WEIGHT: f32 = 0.3;
+const SKIN_WEIGHT: f32 = 1.0;
+
+/// Sums the importance of the pixels of each column (`horizontal`) or row of the image.
+fn importance_profile(image: &RgbaImage, horizontal: bool) -> Vec<f32> {
+    let (width, height) = image.dimensions();
+    let mut profile = vec![0.0; if horizontal { width } else { height } as usize];
+
+    let luma = |x: u32, y: u32| luma(image.get_pixel(x, y).0);
+
+    for y in 0..height {
+        for x in 0..width {
+            let [r, g, b, a] = image.get_pixel(x, y).0;
+
+            // Laplacian of the luma; pixels at the border of the image lack neighbours, and
+            // are not considered to be part of an edge
+            let center = luma(x, y);
+            let is_border = x == 0 || y == 0 || x + 1 == width || y + 1 == height;
+            let edge = if is_border {
+                0.0
+            } else {
+                (4.0 * center - luma(x - 1, y) - luma(x + 1, y) - luma(x, y - 1) - luma(x, y + 1))
+                    .abs()
+            };
+
+            let importance = EDGE_WEIGHT * edge
+                + SATURATION_WEIGHT * saturation([r, g, b], center)
+                + SKIN_WEIGHT * skin([r, g, b], center);
+
+            // transparent pixels are not interesting
+            let index = if horizontal { x } else { y } as usize;
+            profile[index] += importance * f32::from(a) / 255.0;
+        }
+    }
+
+    profile
+}
+
+fn luma([r, g, b, _]: [u8; 4]) -> f32 {
+    (0.2126 * f32::from(r) + 0.7152 * f32::from(g) + 0.0722 * f32::from(b)) / 255.0
+}
+
+/// The saturation of the pixel, ignoring very dark and very bright pixels, of which the
+/// saturation is mostly noise.
+fn saturation([r, g, b]: [u8; 3], luma: f32) -> f32 {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+
+    if max == 0 || !(0.05..=0.9).contains(&luma) {
+        return 0.0;
+    }
+
+    f32::from(max - min) / f32::from(max)
+}
+
+/// The likeness of the color of the pixel to a skin tone, from 0 (not alike) to 1.
+fn skin([r, g, b]: [u8; 3], luma: f32) -> f32 {
+    const SKIN_TONE: [f32; 3] = [0.78, 0.57, 0.44];
+    const THRESHOLD: f32 = 0.8;
+
+    let rgb = [f32::from(r), f32::from(g), f32::from(b)];
+    let length = rgb.iter().map(|c| c * c).sum::<f32>().sqrt();
+
+    if length == 0.0 || !(0.2..=1.0).contains(&luma) {
+        return 0.0;
+    }
+
+    let distance = rgb
+        .iter()
+        .zip(SKIN_TONE)
+        .map(|(c, skin)| (c / length - skin).powi(2))
+        .sum::<f32>()
+        .sqrt();
+
+    let likeness = 1.0 - distance;
+
+    if likeness > THRESHOLD {
+        (likeness - THRESHOLD) / (1.0 - THRESHOLD)
+    } else {
+        0.0
+    }
+}
+
+/// Windows of which the importance differs less than this fraction are considered equally
+/// important, so rounding errors don't decide which window is chosen.
+const SCORE_TOLERANCE: f64 = 1e-4;
+
+/// The offset (in pixels of the original image) of the window which covers the most important
+/// part of the profile. The profile may have been computed from a scaled down image, of which the
+/// original size along the axis was `size` pixels. Of windows which are equally important, the
+/// one which is closest to the center is chosen.
+fn best_offset(profile: &[f32], slack: u32, size: u32) -> u32 {
+    let scale = profile.len() as f64 / f64::from(size);
+    let window = ((f64::from(size - slack) * scale).round() as usize).clamp(1, profile.len());
+    let profile_slack = profile.len() - window;
+
+    // prefix sums, to sum the importance of each window in constant time
+    let prefix = std::iter::once(0.0)
+        .chain(profile.iter().scan(0.0f64, |sum, value| {
+            *sum += f64::from(*value);
+            Some(*sum)
+        }))
+        .collect::<Vec<_>>();
+    let score = |offset: usize| prefix[offset + window] - prefix[offset];
+
+    // visit the offsets from the center outwards, so only a more important window replaces
+    // a more central one
+    let mut offsets = (0..=profile_slack).collect::<Vec<_>>();
+    offsets.sort_by_key(|offset| (2 * offset).abs_diff(profile_slack));
+
+    let (best, _) = offsets.into_iter().fold((0, f64::MIN), |best, offset| {
+        let (_, best_score) = best;
+        let score = score(offset);
+
+        if score - best_score > SCORE_TOLERANCE * best_score.abs().max(1.0) {
+            (offset, score)
+        } else {
+            best
+        }
+    });
+
+    ((best as f64 / scale).round() as u32).min(slack)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{animated_image, static_image, RED};
+    use sic_core::image::Rgba;
+
+    const BLUE: Rgba<u8> = Rgba([0, 0, 255, 255]);
+    const GRAY: Rgba<u8> = Rgba([128, 128, 128, 255]);
+
+    // A gray image with a detailed (checkered red and blue) square at the given position.
+    fn image_with_detail(width: u32, height: u32, (x, y): (u32, u32), size: u32) -> RgbaImage {
+        RgbaImage::from_fn(width, height, |px, py| {
+            let in_detail = (x..x + size).contains(&px) && (y..y + size).contains(&py);
+
+            match (in_detail, (px + py) % 2 == 0) {
+                (true, true) => RED,
+                (true, false) => BLUE,
+                (false, _) => GRAY,
+            }
+        })
+    }
+
+    fn smart_crop(mut image: SicImage, width: u32, height: u32) -> SicImage {
+        SmartCrop::new(width, height, FilterType::Nearest)
+            .apply_operation(&mut image)
+            .unwrap();
+        image
+    }
+
+    fn detail_of(image: &SicImage) -> usize {
+        image
+            .as_ref()
+            .to_rgba8()
+            .pixels()
+            .filter(|px| **px != GRAY)
+            .count()
+    }
+
+    #[test]
+    fn crops_to_detail_horizontally() {
+        let image = smart_crop(static_image(image_with_detail(40, 10, (30, 2), 6)), 10, 10);
+
+        assert_eq!(image.dimensions(), (10, 10));
+        assert_eq!(detail_of(&image), 36);
+    }
+
+    #[test]
+    fn crops_to_detail_vertically() {
+        let image = smart_crop(static_image(image_with_detail(10, 40, (2, 4), 6)), 10, 10);
+
+        assert_eq!(image.dimensions(), (10, 10));
+        assert_eq!(detail_of(&image), 36);
+    }
+
+    #[test]
+    fn crops_to_detail_of_large_image() {
+        // large images are scaled down to find the most interesting region
+        let image = smart_crop(
+            static_image(image_with_detail(1200, 300, (100, 100), 80)),
+            400,
+            300,
+        );
+
+        assert_eq!(image.dimensions(), (400, 300));
+        assert_eq!(detail_of(&image), 80 * 80);
+    }
+
+    #[test]
+    fn uniform_image_is_cropped_at_the_center() {
+        // a gray gradient, which is equally interesting everywhere, except at its borders
+        let image = RgbaImage::from_fn(30, 10, |x, _| {
+            let value = 100 + x as u8;
+            Rgba([value, value, value, 255])
+        });
+
+        let image = smart_crop(static_image(image), 10, 10);
+
+        assert_eq!(
+            image.as_ref().to_rgba8().get_pixel(0, 0),
+            &Rgba([110, 110, 110, 255])
+        );
+    }
+
+    #[test]
+    fn is_scaled_down() {
+        let image = smart_crop(
+            static_image(image_with_detail(400, 100, (300, 20), 50)),
+            20,
+            10,
+        );
+
+        assert_eq!(image.dimensions(), (20, 10));
+    }
+
+    #[test]
+    fn is_not_scaled_up() {
+        let image = smart_crop(
+            static_image(image_with_detail(40, 10, (30, 2), 6)),
+            200,
+            100,
+        );
+
+        assert_eq!(image.dimensions(), (20, 10));
+    }
+
+    #[test]
+    fn is_deterministic() {
+        let image = static_image(image_with_detail(300, 200, (120, 40), 30));
+
+        let left = smart_crop(image.clone(), 100, 100);
+        let right = smart_crop(image, 100, 100);
+
+        assert_eq!(left.dimensions(), (100, 100));
+        assert_eq!(left.as_ref().as_bytes(), right.as_ref().as_bytes());
+    }
+
+    #[test]
+    fn animated_image_shares_the_crop_window() {
+        // the detail moves along the frames; the window should contain both positions
+        let image = animated_image(
+            [(24, 2), (32, 2)].map(|position| image_with_detail(40, 10, position, 6)),
+        );
+
+        let image = smart_crop(image, 20, 10);
+
+        match image {
+            SicImage::Animated(image) => {
+                let frames = image.frames();
+                assert_eq!(frames.len(), 2);
+
+                for frame in frames {
+                    assert_eq!(frame.buffer().dimensions(), (20, 10));
+                    let detail = frame.buffer().pixels().filter(|px| **px != GRAY);
+                    assert_eq!(detail.count(), 36);
+                }
+            }
+            SicImage::Static(_) => panic!("Expected an animated image"),
+        }
+    }
+
+    #[test]
+    fn invalid_size() {
+        let mut image = static_image(RgbaImage::new(4, 4));
+
+        let result = SmartCrop::new(0, 10, FilterType::Nearest).apply_operation(&mut image);
+
+        assert!(matches!(
+            result,
+            Err(SicImageEngineError::SmartCropInvalidSize(0, 10))
+        ));
+    }
+}
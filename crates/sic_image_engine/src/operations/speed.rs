@@ -0,0 +1,33 @@
+use crate::errors::SicImageEngineError;
+use crate::operations::ImageOperation;
+use sic_core::{image, SicImage};
+use std::time::Duration;
+
+pub struct Speed {
+    factor: f32,
+}
+
+impl Speed {
+    pub fn new(factor: f32) -> Self {
+        Self { factor }
+    }
+}
+
+impl ImageOperation for Speed {
+    fn apply_operation(&self, image: &mut SicImage) -> Result<(), SicImageEngineError> {
+        if !(self.factor.is_finite() && self.factor > 0.0) {
+            return Err(SicImageEngineError::InvalidSpeedFactor(self.factor));
+        }
+
+        match image {
+            // Static images have no frame delay.
+            SicImage::Static(_) => {}
+            SicImage::Animated(image) => image.map_delays(|delay| {
+                let duration = Duration::from(delay).div_f64(f64::from(self.factor));
+                image::Delay::from_saturating_duration(duration)
+            }),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,305 @@
+use crate::errors::SicImageEngineError;
+use crate::operations::diff::is_within_tolerance;
+use crate::operations::ImageOperation;
+use crate::wrapper::trim::TrimInputs;
+use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
+use sic_core::image::{imageops, Rgba, RgbaImage};
+use sic_core::SicImage;
+
+/// Crops the uniform border away from an image. The border consists of the rows and columns at
+/// the edges of the image of which each pixel has the color of the border (within the tolerance).
+/// Fully transparent pixels match a fully transparent border color, regardless of their color
+/// channels.
+///
+/// The frames of an animated image are cropped to the union of the content of all frames, so the
+/// frames stay aligned. Images which consist of nothing but the border are left unchanged.
+pub struct Trim<'trim> {
+    inputs: &'trim TrimInputs,
+}
+
+impl<'trim> Trim<'trim> {
+    pub fn new(inputs: &'trim TrimInputs) -> Self {
+        Self { inputs }
+    }
+}
+
+impl ImageOperation for Trim<'_> {
+    fn apply_operation(&self, image: &mut SicImage) -> Result<(), SicImageEngineError> {
+        match image {
+            SicImage::Static(image) => {
+                let buffer = image.to_rgba8();
+
+                if let Some(region) = self.content(&buffer).map(|r| self.with_margin(r, &buffer)) {
+                    *image = image.crop_imm(region.x, region.y, region.width, region.height);
+                }
+            }
+            SicImage::Animated(image) => {
+                let frames = image.frames_mut();
+                let content = frames
+                    .iter()
+                    .filter_map(|frame| self.content(frame.buffer()))
+                    .reduce(Region::union);
+
+                if let (Some(region), Some(first)) = (content, frames.first()) {
+                    let region = self.with_margin(region, first.buffer());
+
+                    frames.par_iter_mut().for_each(|frame| {
+                        let buffer = frame.buffer_mut();
+                        *buffer =
+                            imageops::crop(buffer, region.x, region.y, region.width, region.height)
+                                .to_image();
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Region {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+impl Region {
+    /// The smallest region which contains both regions.
+    fn union(self, other: Self) -> Self {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        let right = (self.x + self.width).max(other.x + other.width);
+        let bottom = (self.y + self.height).max(other.y + other.height);
+
+        Region {
+            x,
+            y,
+            width: right - x,
+            height: bottom - y,
+        }
+    }
+}
+
+impl Trim<'_> {
+    /// The smallest region which contains every pixel which is not part of the border, or `None`
+    /// if every pixel is part of the border.
+    fn content(&self, buffer: &RgbaImage) -> Option<Region> {
+        let color = self
+            .inputs
+            .color
+            .or_else(|| buffer.get_pixel_checked(0, 0).copied())?;
+
+        let is_border = |pixel: Rgba<u8>| {
+            is_within_tolerance(pixel, color, self.inputs.tolerance)
+                || (pixel[3] == 0 && color[3] == 0)
+        };
+
+        // (left, top, right, bottom), where right and bottom are inclusive
+        let bounds = buffer
+            .enumerate_pixels()
+            .filter(|(_, _, pixel)| !is_border(**pixel))
+            .fold(None, |bounds, (x, y, _)| match bounds {
+                None => Some((x, y, x, y)),
+                Some((left, top, right, bottom)) => {
+                    Some((left.min(x), top.min(y), right.max(x), bottom.max(y)))
+                }
+            });
+
+        bounds.map(|(left, top, right, bottom)| Region {
+            x: left,
+            y: top,
+            width: right - left + 1,
+            height: bottom - top + 1,
+        })
+    }
+
+    /// Grows the region by the margin on each side, within the bounds of the image.
+    fn with_margin(&self, region: Region, buffer: &RgbaImage) -> Region {
+        let margin = self.inputs.margin;
+        let x = region.x.saturating_sub(margin);
+        let y = region.y.saturating_sub(margin);
+        let right = (region.x + region.width)
+            .saturating_add(margin)
+            .min(buffer.width());
+        let bottom = (region.y + region.height)
+            .saturating_add(margin)
+            .min(buffer.height());
+
+        Region {
+            x,
+            y,
+            width: right - x,
+            height: bottom - y,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{animated_image, filled_image, static_image, RED, WHITE};
+    use sic_core::image::{Delay, DynamicImage, Rgb, RgbImage};
+
+    fn trim(mut image: SicImage, inputs: TrimInputs) -> SicImage {
+        Trim::new(&inputs).apply_operation(&mut image).unwrap();
+        image
+    }
+
+    // A 10x8 image with a white border, and a red rectangle of `width` by `height` pixels at
+    // (x, y)
+    fn bordered(x: u32, y: u32, width: u32, height: u32) -> RgbaImage {
+        RgbaImage::from_fn(10, 8, |px, py| {
+            if (x..x + width).contains(&px) && (y..y + height).contains(&py) {
+                RED
+            } else {
+                WHITE
+            }
+        })
+    }
+
+    #[test]
+    fn trims_border_of_corner_color() {
+        let image = trim(static_image(bordered(2, 1, 3, 4)), TrimInputs::default());
+
+        let buffer = image.as_ref().to_rgba8();
+        assert_eq!(buffer.dimensions(), (3, 4));
+        assert!(buffer.pixels().all(|px| *px == RED));
+    }
+
+    #[test]
+    fn trims_border_of_given_color() {
+        // The top left pixel is part of the content, so the corner color is not the border
+        let image = RgbaImage::from_fn(6, 6, |x, y| if x < 2 && y < 2 { RED } else { WHITE });
+
+        let inputs = TrimInputs {
+            color: Some(WHITE),
+            ..TrimInputs::default()
+        };
+        let image = trim(static_image(image), inputs);
+        assert_eq!(image.dimensions(), (2, 2));
+
+        let inputs = TrimInputs {
+            color: Some(RED),
+            ..TrimInputs::default()
+        };
+        let image = trim(static_image(bordered(2, 1, 3, 4)), inputs);
+        assert_eq!(image.dimensions(), (10, 8));
+    }
+
+    #[test]
+    fn border_within_tolerance() {
+        // A noisy border, of which the pixels differ slightly from the corner color
+        let image = RgbaImage::from_fn(10, 8, |x, y| {
+            if (3..6).contains(&x) && (2..5).contains(&y) {
+                RED
+            } else {
+                Rgba([255, 250 + ((x + y) % 5) as u8, 255, 255])
+            }
+        });
+
+        let cases = [([0; 4], (10, 8)), ([3; 4], (10, 8)), ([4; 4], (3, 3))];
+
+        for (tolerance, expected) in cases {
+            let inputs = TrimInputs {
+                tolerance,
+                ..TrimInputs::default()
+            };
+
+            let image = trim(static_image(image.clone()), inputs);
+            assert_eq!(image.dimensions(), expected, "{:?}", tolerance);
+        }
+    }
+
+    #[test]
+    fn keeps_margin_within_bounds() {
+        let cases = [(0, (2, 1, 3, 4)), (1, (1, 0, 5, 6)), (3, (0, 0, 8, 8))];
+
+        for (margin, (x, y, width, height)) in cases {
+            let inputs = TrimInputs {
+                margin,
+                ..TrimInputs::default()
+            };
+
+            let image = trim(static_image(bordered(2, 1, 3, 4)), inputs);
+            let buffer = image.as_ref().to_rgba8();
+
+            assert_eq!(buffer.dimensions(), (width, height), "margin {}", margin);
+            assert_eq!(*buffer.get_pixel(2 - x, 1 - y), RED, "margin {}", margin);
+        }
+    }
+
+    #[test]
+    fn transparent_border_of_any_color() {
+        let image = RgbaImage::from_fn(6, 6, |x, y| {
+            if (1..3).contains(&x) && (2..5).contains(&y) {
+                RED
+            } else {
+                Rgba([x as u8 * 40, y as u8 * 40, 0, 0])
+            }
+        });
+
+        let image = trim(static_image(image), TrimInputs::default());
+        assert_eq!(image.dimensions(), (2, 3));
+    }
+
+    #[test]
+    fn uniform_image_is_unchanged() {
+        let image = trim(filled_image(4, 4, WHITE), TrimInputs::default());
+        assert_eq!(image.dimensions(), (4, 4));
+    }
+
+    #[test]
+    fn keeps_color_type() {
+        let image = RgbImage::from_fn(4, 4, |x, y| {
+            if x == 1 && y == 2 {
+                Rgb([255, 0, 0])
+            } else {
+                Rgb([255, 255, 255])
+            }
+        });
+
+        let image = trim(
+            SicImage::Static(DynamicImage::ImageRgb8(image)),
+            TrimInputs::default(),
+        );
+
+        match image {
+            SicImage::Static(DynamicImage::ImageRgb8(buffer)) => {
+                assert_eq!(buffer.dimensions(), (1, 1));
+                assert_eq!(*buffer.get_pixel(0, 0), Rgb([255, 0, 0]));
+            }
+            other => panic!(
+                "Expected an Rgb8 image, but got: {:?}",
+                other.as_ref().color()
+            ),
+        }
+    }
+
+    #[test]
+    fn trims_frames_to_union_of_content() {
+        let regions = [(2, 1, 2, 2), (5, 3, 1, 1), (3, 4, 2, 3)];
+        let image = animated_image(
+            regions
+                .iter()
+                .map(|&(x, y, width, height)| bordered(x, y, width, height)),
+        );
+
+        let image = trim(image, TrimInputs::default());
+
+        match image {
+            SicImage::Animated(animated) => {
+                assert_eq!(animated.frames().len(), 3);
+
+                for (frame, (x, y, _, _)) in animated.frames().iter().zip(regions) {
+                    // the union spans (2, 1) to (5, 6), inclusive
+                    assert_eq!(frame.buffer().dimensions(), (4, 6));
+                    assert_eq!(*frame.buffer().get_pixel(x - 2, y - 1), RED);
+                    assert_eq!(frame.delay(), Delay::from_numer_denom_ms(40, 1));
+                }
+            }
+            SicImage::Static(_) => panic!("Expected an animated image"),
+        }
+    }
+}
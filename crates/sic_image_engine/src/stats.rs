@@ -0,0 +1,254 @@
+//! Statistics of the pixels of an image, such as a histogram, the minimum, maximum, mean and
+//! standard deviation of each channel, and the number of unique colors.
+//!
+//! Statistics are computed over the 8 bit samples of an image: images with a higher bit depth
+//! are converted to 8 bits per channel first. The channels are those of the color type of the
+//! image; for example, for a grayscale image, only the luma channel is included. The frames of
+//! an animated image are always RGBA.
+
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use sic_core::image::{ColorType, DynamicImage};
+use sic_core::SicImage;
+use std::collections::HashSet;
+use std::fmt;
+
+/// Statistics of a static image, or of a single frame of an animated image.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImageStats {
+    pub width: u32,
+    pub height: u32,
+    /// Statistics of each channel, in the order of the channels of the color type.
+    pub channels: Vec<ChannelStats>,
+    /// Number of distinct pixel values, over all channels.
+    pub unique_colors: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChannelStats {
+    pub channel: Channel,
+    /// Number of samples of each of the 256 possible values.
+    pub histogram: Vec<u64>,
+    pub min: u8,
+    pub max: u8,
+    pub mean: f64,
+    /// The (population) standard deviation.
+    pub stddev: f64,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Channel {
+    Luma,
+    Red,
+    Green,
+    Blue,
+    Alpha,
+}
+
+impl Channel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Luma => "luma",
+            Self::Red => "red",
+            Self::Green => "green",
+            Self::Blue => "blue",
+            Self::Alpha => "alpha",
+        }
+    }
+}
+
+impl fmt::Display for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Computes the statistics of the image. A static image results in a single item, while an
+/// animated image results in one item for each of its frames.
+pub fn image_stats(image: &SicImage) -> Vec<ImageStats> {
+    match image {
+        SicImage::Static(image) => vec![static_image_stats(image)],
+        SicImage::Animated(animated) => animated
+            .frames()
+            .par_iter()
+            .map(|frame| {
+                let buffer = frame.buffer();
+                stats_of_samples(buffer.width(), buffer.height(), buffer.as_raw(), RGBA)
+            })
+            .collect(),
+    }
+}
+
+const LUMA: &[Channel] = &[Channel::Luma];
+const LUMA_ALPHA: &[Channel] = &[Channel::Luma, Channel::Alpha];
+const RGB: &[Channel] = &[Channel::Red, Channel::Green, Channel::Blue];
+const RGBA: &[Channel] = &[Channel::Red, Channel::Green, Channel::Blue, Channel::Alpha];
+
+fn static_image_stats(image: &DynamicImage) -> ImageStats {
+    let (width, height) = (image.width(), image.height());
+
+    match image.color() {
+        ColorType::L8 | ColorType::L16 => {
+            stats_of_samples(width, height, image.to_luma8().as_raw(), LUMA)
+        }
+        ColorType::La8 | ColorType::La16 => {
+            stats_of_samples(width, height, image.to_luma_alpha8().as_raw(), LUMA_ALPHA)
+        }
+        ColorType::Rgb8 | ColorType::Rgb16 | ColorType::Rgb32F => {
+            stats_of_samples(width, height, image.to_rgb8().as_raw(), RGB)
+        }
+        _ => stats_of_samples(width, height, image.to_rgba8().as_raw(), RGBA),
+    }
+}
+
+/// Computes the statistics of interleaved 8 bit samples, with one sample for each of the given
+/// channels per pixel.
+fn stats_of_samples(width: u32, height: u32, samples: &[u8], channels: &[Channel]) -> ImageStats {
+    let mut histograms = vec![vec![0u64; 256]; channels.len()];
+    let mut unique_colors = HashSet::new();
+
+    for pixel in samples.chunks_exact(channels.len()) {
+        let mut color = [0u8; 4];
+
+        for (index, &sample) in pixel.iter().enumerate() {
+            histograms[index][usize::from(sample)] += 1;
+            color[index] = sample;
+        }
+
+        unique_colors.insert(u32::from_le_bytes(color));
+    }
+
+    ImageStats {
+        width,
+        height,
+        channels: channels
+            .iter()
+            .zip(histograms)
+            .map(|(&channel, histogram)| channel_stats(channel, histogram))
+            .collect(),
+        unique_colors: unique_colors.len(),
+    }
+}
+
+fn channel_stats(channel: Channel, histogram: Vec<u64>) -> ChannelStats {
+    let count = histogram.iter().sum::<u64>();
+
+    let values = || {
+        histogram
+            .iter()
+            .enumerate()
+            .filter(|(_, &n)| n > 0)
+            .map(|(value, &n)| (value as f64, n as f64))
+    };
+
+    let (mean, stddev) = if count == 0 {
+        (0.0, 0.0)
+    } else {
+        let count = count as f64;
+        let mean = values().map(|(value, n)| value * n).sum::<f64>() / count;
+        let variance = values()
+            .map(|(value, n)| (value - mean).powi(2) * n)
+            .sum::<f64>()
+            / count;
+
+        (mean, variance.sqrt())
+    };
+
+    let min = histogram.iter().position(|&n| n > 0).unwrap_or(0) as u8;
+    let max = histogram.iter().rposition(|&n| n > 0).unwrap_or(0) as u8;
+
+    ChannelStats {
+        channel,
+        histogram,
+        min,
+        max,
+        mean,
+        stddev,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sic_core::image::{GrayImage, Rgba, RgbaImage};
+    use sic_core::AnimatedImage;
+    use sic_testing::{open_test_image, setup_test_image};
+
+    #[test]
+    fn rgb_image() {
+        let image = open_test_image(setup_test_image("2x3_rrgrbb.bmp"));
+        let stats = image_stats(&image);
+
+        assert_eq!(stats.len(), 1);
+
+        let stats = &stats[0];
+        assert_eq!((stats.width, stats.height), (2, 3));
+        assert_eq!(
+            stats
+                .channels
+                .iter()
+                .map(|channel| channel.channel)
+                .collect::<Vec<_>>(),
+            RGB
+        );
+        // red, green and blue pixels
+        assert_eq!(stats.unique_colors, 3);
+
+        // three of the six pixels are red
+        let red = &stats.channels[0];
+        assert_eq!((red.min, red.max), (0, 255));
+        assert_eq!(red.histogram[255], 3);
+        assert_eq!(red.histogram[0], 3);
+        assert!((red.mean - 127.5).abs() < f64::EPSILON);
+        assert!((red.stddev - 127.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn grayscale_image() {
+        let image = GrayImage::from_raw(2, 2, vec![10, 20, 30, 40]).unwrap();
+        let stats = &image_stats(&SicImage::Static(DynamicImage::ImageLuma8(image)))[0];
+
+        assert_eq!(stats.channels.len(), 1);
+        assert_eq!(stats.unique_colors, 4);
+
+        let luma = &stats.channels[0];
+        assert_eq!(luma.channel, Channel::Luma);
+        assert_eq!((luma.min, luma.max), (10, 40));
+        assert!((luma.mean - 25.0).abs() < f64::EPSILON);
+        assert!((luma.stddev - 125f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn uniform_image() {
+        let image = RgbaImage::from_pixel(4, 4, Rgba([1, 2, 3, 4]));
+        let stats = &image_stats(&SicImage::Static(DynamicImage::ImageRgba8(image)))[0];
+
+        assert_eq!(stats.unique_colors, 1);
+
+        for (channel, expected) in stats.channels.iter().zip([1u8, 2, 3, 4]) {
+            assert_eq!((channel.min, channel.max), (expected, expected));
+            assert_eq!(channel.histogram[usize::from(expected)], 16);
+            assert_eq!(channel.stddev, 0.0);
+        }
+    }
+
+    #[test]
+    fn animated_image_has_stats_per_frame() {
+        let frames = [Rgba([255, 0, 0, 255]), Rgba([0, 0, 255, 255])]
+            .map(|color| sic_core::image::Frame::new(RgbaImage::from_pixel(2, 2, color)));
+        let stats = image_stats(&SicImage::Animated(AnimatedImage::from_frames(frames)));
+
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].channels[0].max, 255);
+        assert_eq!(stats[1].channels[0].max, 0);
+        assert_eq!(stats[1].channels[2].max, 255);
+    }
+
+    #[test]
+    fn empty_image() {
+        let image = RgbaImage::new(0, 0);
+        let stats = &image_stats(&SicImage::Static(DynamicImage::ImageRgba8(image)))[0];
+
+        assert_eq!(stats.unique_colors, 0);
+        assert_eq!(stats.channels[0].mean, 0.0);
+    }
+}
@@ -0,0 +1,26 @@
+//! Builders of small images, for the tests of the image operations.
+
+use sic_core::image::{Delay, DynamicImage, Frame, Rgba, RgbaImage};
+use sic_core::{AnimatedImage, SicImage};
+
+pub const RED: Rgba<u8> = Rgba([255, 0, 0, 255]);
+pub const WHITE: Rgba<u8> = Rgba([255, 255, 255, 255]);
+pub const TRANSPARENT: Rgba<u8> = Rgba([0, 0, 0, 0]);
+
+/// A static image of `width` by `height` pixels, each of the given color.
+pub fn filled_image(width: u32, height: u32, color: Rgba<u8>) -> SicImage {
+    static_image(RgbaImage::from_pixel(width, height, color))
+}
+
+pub fn static_image(buffer: RgbaImage) -> SicImage {
+    SicImage::Static(DynamicImage::ImageRgba8(buffer))
+}
+
+/// An animated image of the given frames, each of which is shown for 40 ms.
+pub fn animated_image<I: IntoIterator<Item = RgbaImage>>(buffers: I) -> SicImage {
+    let frames = buffers
+        .into_iter()
+        .map(|buffer| Frame::from_parts(buffer, 0, 0, Delay::from_numer_denom_ms(40, 1)));
+
+    SicImage::Animated(AnimatedImage::from_frames(frames))
+}
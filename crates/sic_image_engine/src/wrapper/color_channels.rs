@@ -0,0 +1,38 @@
+use crate::errors::SicImageEngineError;
+
+/// The color channels of an image which are changed by an operation; the alpha channel is never
+/// changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorChannels {
+    All,
+    Red,
+    Green,
+    Blue,
+}
+
+impl Default for ColorChannels {
+    fn default() -> Self {
+        Self::All
+    }
+}
+
+impl ColorChannels {
+    pub fn try_from_str(val: &str) -> Result<ColorChannels, SicImageEngineError> {
+        match val.to_lowercase().as_str() {
+            "red" => Ok(ColorChannels::Red),
+            "green" => Ok(ColorChannels::Green),
+            "blue" => Ok(ColorChannels::Blue),
+            fail => Err(SicImageEngineError::UnknownColorChannel(fail.to_string())),
+        }
+    }
+
+    /// Whether each of the red, green, blue and alpha channels of an RGBA pixel is selected.
+    pub(crate) fn mask(self) -> [bool; 4] {
+        match self {
+            ColorChannels::All => [true, true, true, false],
+            ColorChannels::Red => [true, false, false, false],
+            ColorChannels::Green => [false, true, false, false],
+            ColorChannels::Blue => [false, false, true, false],
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use crate::operations::diff::{DIFF_PX_DIFF, DIFF_PX_NO_OVERLAP, DIFF_PX_SAME};
+use crate::wrapper::image_path::ImageFromPath;
+use sic_core::image::Rgba;
+
+/// The image to diff against, and the options which decide how its pixels are compared and
+/// coloured.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiffInputs {
+    image_path: ImageFromPath,
+    options: DiffOptions,
+}
+
+impl DiffInputs {
+    pub fn new(image_path: ImageFromPath) -> Self {
+        Self::with_options(image_path, DiffOptions::default())
+    }
+
+    pub fn with_options(image_path: ImageFromPath, options: DiffOptions) -> Self {
+        Self {
+            image_path,
+            options,
+        }
+    }
+
+    pub fn image_path(&self) -> &ImageFromPath {
+        &self.image_path
+    }
+
+    pub fn options(&self) -> &DiffOptions {
+        &self.options
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DiffOptions {
+    /// The largest difference of each of the red, green, blue and alpha channels, for which two
+    /// pixels are still considered the same.
+    pub tolerance: [u8; 4],
+    /// Colour of the pixels which are the same in both images.
+    pub same: Rgba<u8>,
+    /// Colour of the pixels which differ, or which are part of only one of the images.
+    pub different: Rgba<u8>,
+    /// Colour of the pixels which are part of neither image.
+    pub no_overlap: Rgba<u8>,
+    /// If set, pixels which are the same show the original image, of which the colour channels
+    /// are darkened by this factor (from 0 to 1), instead of the `same` colour. The `different`
+    /// colour is blended over the darkened original.
+    pub dim: Option<f32>,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            tolerance: [0; 4],
+            same: DIFF_PX_SAME,
+            different: DIFF_PX_DIFF,
+            no_overlap: DIFF_PX_NO_OVERLAP,
+            dim: None,
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use crate::errors::SicImageEngineError;
+use sic_core::image;
+use std::time::Duration;
+
+/// The time for which a frame of an animated image is displayed.
+///
+/// Parsed from a non-negative number with a unit, either milliseconds (e.g. `50ms`) or
+/// seconds (e.g. `1.5s`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameDelay {
+    duration: Duration,
+}
+
+impl FrameDelay {
+    pub fn new(duration: Duration) -> Self {
+        Self { duration }
+    }
+
+    pub fn try_from_str(val: &str) -> Result<FrameDelay, SicImageEngineError> {
+        let lowercase = val.trim().to_lowercase();
+
+        let (value, to_seconds) = if let Some(ms) = lowercase.strip_suffix("ms") {
+            (ms, 0.001)
+        } else if let Some(s) = lowercase.strip_suffix('s') {
+            (s, 1.0)
+        } else {
+            return Err(SicImageEngineError::InvalidFrameDelay(val.to_string()));
+        };
+
+        value
+            .parse::<f64>()
+            .ok()
+            .map(|value| value * to_seconds)
+            .filter(|secs| secs.is_finite() && *secs >= 0.0 && *secs <= u64::MAX as f64)
+            .map(Duration::from_secs_f64)
+            .map(FrameDelay::new)
+            .ok_or_else(|| SicImageEngineError::InvalidFrameDelay(val.to_string()))
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+}
+
+impl From<FrameDelay> for image::Delay {
+    fn from(delay: FrameDelay) -> Self {
+        image::Delay::from_saturating_duration(delay.duration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_valid() {
+        let cases = [
+            ("50ms", 50_000),
+            ("0ms", 0),
+            ("1.5s", 1_500_000),
+            ("2S", 2_000_000),
+            ("20MS", 20_000),
+            ("0.5ms", 500),
+        ];
+
+        for (input, expected_micros) in cases {
+            let delay = FrameDelay::try_from_str(input).unwrap();
+            assert_eq!(delay.duration(), Duration::from_micros(expected_micros));
+        }
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for input in ["", "50", "ms", "-1ms", "1.5m", "fast"] {
+            assert!(FrameDelay::try_from_str(input).is_err());
+        }
+    }
+}
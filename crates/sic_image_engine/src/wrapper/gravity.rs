@@ -0,0 +1,133 @@
+use crate::errors::SicImageEngineError;
+
+/// Where an image is placed within a larger area, for example which region of an image is kept
+/// when it is cropped to a smaller size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Gravity {
+    Center,
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Default for Gravity {
+    fn default() -> Self {
+        Self::Center
+    }
+}
+
+impl Gravity {
+    pub fn try_from_str(val: &str) -> Result<Gravity, SicImageEngineError> {
+        match val.to_lowercase().as_str() {
+            "center" => Ok(Gravity::Center),
+            "north" => Ok(Gravity::North),
+            "north-east" => Ok(Gravity::NorthEast),
+            "east" => Ok(Gravity::East),
+            "south-east" => Ok(Gravity::SouthEast),
+            "south" => Ok(Gravity::South),
+            "south-west" => Ok(Gravity::SouthWest),
+            "west" => Ok(Gravity::West),
+            "north-west" => Ok(Gravity::NorthWest),
+            fail => Err(SicImageEngineError::UnknownGravity(fail.to_string())),
+        }
+    }
+
+    /// The offset of the top left corner of an area with dimensions `inner`, when it is placed
+    /// within an area with dimensions `outer`. If the inner area is larger than the outer area
+    /// along an axis, the offset along that axis is 0.
+    pub fn offset(self, outer: (u32, u32), inner: (u32, u32)) -> (u32, u32) {
+        let (horizontal, vertical) = match self {
+            Gravity::Center => (Align::Middle, Align::Middle),
+            Gravity::North => (Align::Middle, Align::Start),
+            Gravity::NorthEast => (Align::End, Align::Start),
+            Gravity::East => (Align::End, Align::Middle),
+            Gravity::SouthEast => (Align::End, Align::End),
+            Gravity::South => (Align::Middle, Align::End),
+            Gravity::SouthWest => (Align::Start, Align::End),
+            Gravity::West => (Align::Start, Align::Middle),
+            Gravity::NorthWest => (Align::Start, Align::Start),
+        };
+
+        (
+            horizontal.offset(outer.0.saturating_sub(inner.0)),
+            vertical.offset(outer.1.saturating_sub(inner.1)),
+        )
+    }
+}
+
+enum Align {
+    Start,
+    Middle,
+    End,
+}
+
+impl Align {
+    fn offset(self, slack: u32) -> u32 {
+        match self {
+            Align::Start => 0,
+            Align::Middle => slack / 2,
+            Align::End => slack,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_valid() {
+        let cases = [
+            ("center", Gravity::Center),
+            ("north", Gravity::North),
+            ("north-east", Gravity::NorthEast),
+            ("east", Gravity::East),
+            ("south-east", Gravity::SouthEast),
+            ("south", Gravity::South),
+            ("south-west", Gravity::SouthWest),
+            ("west", Gravity::West),
+            ("north-west", Gravity::NorthWest),
+            ("NORTH-WEST", Gravity::NorthWest),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(Gravity::try_from_str(input).unwrap(), expected, "{}", input);
+        }
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for input in ["", "centre", "northwest"] {
+            assert!(Gravity::try_from_str(input).is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn offset() {
+        let cases = [
+            (Gravity::Center, (3, 2)),
+            (Gravity::North, (3, 0)),
+            (Gravity::NorthEast, (6, 0)),
+            (Gravity::East, (6, 2)),
+            (Gravity::SouthEast, (6, 4)),
+            (Gravity::South, (3, 4)),
+            (Gravity::SouthWest, (0, 4)),
+            (Gravity::West, (0, 2)),
+            (Gravity::NorthWest, (0, 0)),
+        ];
+
+        for (gravity, expected) in cases {
+            assert_eq!(gravity.offset((10, 8), (4, 4)), expected, "{:?}", gravity);
+        }
+    }
+
+    #[test]
+    fn offset_of_larger_inner_area() {
+        assert_eq!(Gravity::SouthEast.offset((4, 4), (10, 2)), (0, 2));
+    }
+}
@@ -0,0 +1,95 @@
+use std::path::PathBuf;
+
+use crate::errors::SicImageEngineError;
+use moxcms::ColorProfile;
+
+/// The color profile into which the pixels of an image are converted by the `convert-profile`
+/// image operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IccProfileTarget {
+    /// The built-in sRGB color profile.
+    Srgb,
+    /// An ICC color profile loaded from the given path.
+    Path(PathBuf),
+}
+
+impl IccProfileTarget {
+    /// Load the target color profile.
+    pub fn load(&self) -> Result<IccProfile, SicImageEngineError> {
+        match self {
+            Self::Srgb => Ok(IccProfile {
+                profile: ColorProfile::new_srgb(),
+                raw: None,
+            }),
+            Self::Path(path) => {
+                let raw = std::fs::read(path)
+                    .map_err(|_err| SicImageEngineError::LoadIccProfileFromPath(path.clone()))?;
+
+                Ok(IccProfile {
+                    profile: ColorProfile::new_from_slice(&raw)
+                        .map_err(|err| SicImageEngineError::InvalidIccProfile(err.to_string()))?,
+                    raw: Some(raw),
+                })
+            }
+        }
+    }
+}
+
+/// A loaded ICC color profile.
+pub struct IccProfile {
+    profile: ColorProfile,
+    /// The serialized profile, which should be embedded in the output image.
+    /// Images without embedded profile are assumed to be sRGB, so for sRGB, this is `None`.
+    raw: Option<Vec<u8>>,
+}
+
+impl IccProfile {
+    pub fn profile(&self) -> &ColorProfile {
+        &self.profile
+    }
+
+    pub fn into_raw(self) -> Option<Vec<u8>> {
+        self.raw
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sic_testing::in_;
+
+    #[test]
+    fn load_srgb() {
+        let profile = IccProfileTarget::Srgb.load().unwrap();
+
+        assert!(profile.into_raw().is_none());
+    }
+
+    #[test]
+    fn load_from_path() {
+        let path = PathBuf::from(in_!("display_p3.icc"));
+        let profile = IccProfileTarget::Path(path.clone()).load().unwrap();
+
+        assert_eq!(profile.into_raw().unwrap(), std::fs::read(path).unwrap());
+    }
+
+    #[test]
+    fn load_from_missing_path() {
+        let result = IccProfileTarget::Path(PathBuf::from("missing.icc")).load();
+
+        assert!(matches!(
+            result,
+            Err(SicImageEngineError::LoadIccProfileFromPath(_))
+        ));
+    }
+
+    #[test]
+    fn load_invalid_profile() {
+        let result = IccProfileTarget::Path(PathBuf::from(in_!("rainbow_8x6.bmp"))).load();
+
+        assert!(matches!(
+            result,
+            Err(SicImageEngineError::InvalidIccProfile(_))
+        ));
+    }
+}
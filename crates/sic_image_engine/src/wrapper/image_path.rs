@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use sic_io::decode::{file_reader, SicImageDecoder};
 
@@ -15,6 +15,10 @@ impl ImageFromPath {
         Self { path }
     }
 
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
     pub fn open_image(&self) -> Result<SicImage, SicImageEngineError> {
         file_reader(self.path.as_path())
             .and_then(|mut file| SicImageDecoder::default().decode(&mut file))
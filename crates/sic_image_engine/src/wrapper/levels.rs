@@ -0,0 +1,47 @@
+use crate::wrapper::color_channels::ColorChannels;
+
+/// The inputs of the `levels` operation, which stretches the range of values from the black point
+/// to the white point to the full range, and adjusts the midtones by a gamma.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelsInputs {
+    black: u8,
+    white: u8,
+    gamma: f32,
+    channels: ColorChannels,
+}
+
+impl LevelsInputs {
+    /// Adjusts the levels of all color channels, without changing the midtones.
+    pub fn new(black: u8, white: u8) -> Self {
+        Self {
+            black,
+            white,
+            gamma: 1.0,
+            channels: ColorChannels::default(),
+        }
+    }
+
+    pub fn with_gamma(self, gamma: f32) -> Self {
+        Self { gamma, ..self }
+    }
+
+    pub fn with_channels(self, channels: ColorChannels) -> Self {
+        Self { channels, ..self }
+    }
+
+    pub fn black(&self) -> u8 {
+        self.black
+    }
+
+    pub fn white(&self) -> u8 {
+        self.white
+    }
+
+    pub fn gamma(&self) -> f32 {
+        self.gamma
+    }
+
+    pub fn channels(&self) -> ColorChannels {
+        self.channels
+    }
+}
@@ -1,11 +1,23 @@
+pub mod color_channels;
+pub mod diff;
 pub mod filter_type;
+pub mod frame_delay;
 pub mod gradient_fn;
 pub mod gradient_input;
+pub mod gravity;
+pub mod icc_profile;
 pub mod image_path;
+pub mod levels;
 pub mod overlay;
+pub mod pad;
+pub mod resize_mode;
+pub mod trim;
 
 #[cfg(feature = "imageproc-ops")]
 pub mod font_options;
 
 #[cfg(feature = "imageproc-ops")]
 pub mod draw_text_inner;
+
+#[cfg(feature = "imageproc-ops")]
+pub mod rotate;
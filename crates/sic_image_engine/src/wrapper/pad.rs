@@ -0,0 +1,58 @@
+use crate::wrapper::gravity::Gravity;
+use sic_core::image::Rgba;
+
+/// The size of the canvas to which an image is padded, and the color with which the area around
+/// the image is filled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PadInputs {
+    size: PadSize,
+    fill: Rgba<u8>,
+}
+
+impl PadInputs {
+    /// Pads the image with transparent pixels.
+    pub fn new(size: PadSize) -> Self {
+        Self::with_fill(size, Rgba([0, 0, 0, 0]))
+    }
+
+    pub fn with_fill(size: PadSize, fill: Rgba<u8>) -> Self {
+        Self { size, fill }
+    }
+
+    pub fn size(&self) -> PadSize {
+        self.size
+    }
+
+    pub fn fill(&self) -> Rgba<u8> {
+        self.fill
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PadSize {
+    /// Grow the canvas to (at least) width by height pixels; the gravity decides where the image
+    /// is placed on the canvas. Images which are already larger along an axis are not cropped.
+    Canvas((u32, u32), Gravity),
+    /// Grow the canvas by a margin on each side.
+    Margins(Margins),
+}
+
+/// The number of pixels added to each side of an image.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Margins {
+    pub top: u32,
+    pub right: u32,
+    pub bottom: u32,
+    pub left: u32,
+}
+
+impl Margins {
+    pub fn uniform(margin: u32) -> Self {
+        Self {
+            top: margin,
+            right: margin,
+            bottom: margin,
+            left: margin,
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use crate::errors::SicImageEngineError;
+use crate::wrapper::gravity::Gravity;
+
+/// How the `resize` operation computes the dimensions of the resized image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResizeMode {
+    /// Resize to exactly width by height pixels; the aspect ratio is not preserved.
+    Exact((u32, u32)),
+    /// Resize to the largest dimensions which fit within width by height pixels, while
+    /// preserving the aspect ratio.
+    Fit((u32, u32)),
+    /// Resize to the smallest dimensions which cover width by height pixels, while preserving the
+    /// aspect ratio, and crop the part which doesn't fit. The gravity decides which part of the
+    /// image is kept.
+    Cover((u32, u32), Gravity),
+    /// Scale the width and height by a factor.
+    Scale(ScaleFactor),
+    /// Resize such that the longest edge is at most the given number of pixels, while preserving
+    /// the aspect ratio. Images of which the longest edge is already short enough are not resized.
+    MaxEdge(u32),
+}
+
+/// The factor by which an image is scaled, parsed from a positive percentage, e.g. `50%`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScaleFactor {
+    factor: f32,
+}
+
+impl ScaleFactor {
+    pub fn try_from_str(val: &str) -> Result<ScaleFactor, SicImageEngineError> {
+        val.trim()
+            .strip_suffix('%')
+            .and_then(|percentage| percentage.parse::<f32>().ok())
+            .map(|percentage| percentage / 100.0)
+            .filter(|factor| factor.is_finite() && *factor > 0.0)
+            .map(|factor| ScaleFactor { factor })
+            .ok_or_else(|| SicImageEngineError::InvalidScaleFactor(val.to_string()))
+    }
+
+    pub fn factor(&self) -> f32 {
+        self.factor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_valid() {
+        let cases = [("50%", 0.5), ("100%", 1.0), ("250%", 2.5), ("12.5%", 0.125)];
+
+        for (input, expected) in cases {
+            let scale = ScaleFactor::try_from_str(input).unwrap();
+            assert!(
+                (scale.factor() - expected).abs() < f32::EPSILON,
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for input in ["", "50", "%", "0%", "-50%", "fifty%", "inf%"] {
+            assert!(ScaleFactor::try_from_str(input).is_err(), "{}", input);
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use crate::errors::SicImageEngineError;
+use crate::wrapper::filter_type::FilterTypeWrap;
+use sic_core::image::Rgba;
+
+/// The inputs of the `rotate` operation, which rotates an image clockwise by an arbitrary angle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RotateInputs {
+    degrees: f32,
+    canvas: RotateCanvas,
+    interpolation: FilterTypeWrap,
+    fill: Rgba<u8>,
+}
+
+impl RotateInputs {
+    /// Rotates by the given number of degrees, expands the canvas to fit the rotated image,
+    /// interpolates with the default sampling filter, and fills the exposed corners with
+    /// transparent pixels.
+    pub fn new(degrees: f32) -> Self {
+        Self {
+            degrees,
+            canvas: RotateCanvas::default(),
+            interpolation: FilterTypeWrap::default(),
+            fill: Rgba([0, 0, 0, 0]),
+        }
+    }
+
+    pub fn with_canvas(self, canvas: RotateCanvas) -> Self {
+        Self { canvas, ..self }
+    }
+
+    pub fn with_interpolation(self, interpolation: FilterTypeWrap) -> Self {
+        Self {
+            interpolation,
+            ..self
+        }
+    }
+
+    pub fn with_fill(self, fill: Rgba<u8>) -> Self {
+        Self { fill, ..self }
+    }
+
+    pub fn degrees(&self) -> f32 {
+        self.degrees
+    }
+
+    pub fn canvas(&self) -> RotateCanvas {
+        self.canvas
+    }
+
+    pub fn interpolation(&self) -> FilterTypeWrap {
+        self.interpolation
+    }
+
+    pub fn fill(&self) -> Rgba<u8> {
+        self.fill
+    }
+}
+
+/// The dimensions of a rotated image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RotateCanvas {
+    /// Expand the canvas, such that the whole rotated image fits on it.
+    Expand,
+    /// Keep the dimensions of the original image; the corners of the rotated image are cropped.
+    Crop,
+}
+
+impl Default for RotateCanvas {
+    fn default() -> Self {
+        Self::Expand
+    }
+}
+
+impl RotateCanvas {
+    pub fn try_from_str(val: &str) -> Result<RotateCanvas, SicImageEngineError> {
+        match val.to_lowercase().as_str() {
+            "expand" => Ok(RotateCanvas::Expand),
+            "crop" => Ok(RotateCanvas::Crop),
+            fail => Err(SicImageEngineError::UnknownRotateCanvas(fail.to_string())),
+        }
+    }
+}
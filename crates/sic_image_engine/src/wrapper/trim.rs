@@ -0,0 +1,14 @@
+use sic_core::image::Rgba;
+
+/// The options which decide which border is trimmed from an image, and how much of it is kept.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TrimInputs {
+    /// Color of the border; if not set, the color of the top left pixel is used.
+    pub color: Option<Rgba<u8>>,
+    /// The largest difference of each of the red, green, blue and alpha channels, for which a
+    /// pixel is still considered part of the border.
+    pub tolerance: [u8; 4],
+    /// The number of pixels of the border which are kept on each side of the trimmed image,
+    /// insofar the border is wide enough.
+    pub margin: u32,
+}
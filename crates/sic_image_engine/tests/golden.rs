@@ -0,0 +1,537 @@
+//! Golden image tests of the image operations. The references are stored in
+//! `resources/golden/operations`; run with `SIC_BLESS=1` to (re)create them after an intended
+//! change of an operation.
+//!
+//! Operations which only change the frame delays of an animated image, `set-frame-delay` and
+//! `speed`, don't change any pixels, and are not included.
+
+use sic_core::image::metadata::Orientation;
+use sic_core::image::Rgba;
+use sic_core::{FrameRange, ImageMetadata, SicImage};
+use sic_image_engine::engine::{EnvItem, ImageEngine, Instr};
+use sic_image_engine::wrapper::color_channels::ColorChannels;
+use sic_image_engine::wrapper::diff::{DiffInputs, DiffOptions};
+use sic_image_engine::wrapper::filter_type::FilterTypeWrap;
+use sic_image_engine::wrapper::gradient_input::GradientInput;
+use sic_image_engine::wrapper::gravity::Gravity;
+use sic_image_engine::wrapper::icc_profile::IccProfileTarget;
+use sic_image_engine::wrapper::image_path::ImageFromPath;
+use sic_image_engine::wrapper::levels::LevelsInputs;
+use sic_image_engine::wrapper::overlay::OverlayInputs;
+use sic_image_engine::wrapper::pad::{Margins, PadInputs, PadSize};
+use sic_image_engine::wrapper::resize_mode::{ResizeMode, ScaleFactor};
+use sic_image_engine::wrapper::trim::TrimInputs;
+use sic_image_engine::ImgOp;
+use sic_testing::{golden, in_, open_test_image};
+use std::path::PathBuf;
+
+fn static_image() -> SicImage {
+    open_test_image(in_!("rainbow_8x6.bmp"))
+}
+
+// The frames of the animated image are resized first, to keep the references small.
+fn animated_image() -> SicImage {
+    let image = ImageFromPath::new(PathBuf::from(in_!("loop.gif")))
+        .open_image()
+        .unwrap();
+
+    ImageEngine::new(image)
+        .ignite(&[op(ImgOp::Resize((10, 10)))])
+        .unwrap()
+}
+
+fn op(operation: ImgOp) -> Instr {
+    Instr::Operation(operation)
+}
+
+fn assert_golden(name: &str, image: SicImage, metadata: ImageMetadata, program: &[Instr]) {
+    let (image, _) = ImageEngine::new(image)
+        .with_metadata(metadata)
+        .ignite_with_metadata(program)
+        .unwrap();
+
+    // Allow rounding differences of operations which compute with floating point numbers.
+    golden()
+        .with_tolerance(1)
+        .assert_matches(&format!("operations/{}", name), &image);
+}
+
+fn assert_golden_static(name: &str, operation: ImgOp) {
+    assert_golden(
+        name,
+        static_image(),
+        ImageMetadata::default(),
+        &[op(operation)],
+    );
+}
+
+fn assert_golden_animated(name: &str, operation: ImgOp) {
+    assert_golden(
+        name,
+        animated_image(),
+        ImageMetadata::default(),
+        &[op(operation)],
+    );
+}
+
+fn range(input: &str) -> FrameRange {
+    FrameRange::try_from_str(input).unwrap()
+}
+
+#[test]
+fn auto_orient() {
+    let mut metadata = ImageMetadata::default();
+    metadata.set_orientation(Orientation::Rotate90);
+
+    assert_golden(
+        "auto-orient",
+        static_image(),
+        metadata,
+        &[op(ImgOp::AutoOrient)],
+    );
+}
+
+#[test]
+fn blur() {
+    assert_golden_static("blur", ImgOp::Blur(1.0));
+}
+
+#[test]
+fn brighten() {
+    assert_golden_static("brighten", ImgOp::Brighten(40));
+}
+
+#[test]
+fn contrast() {
+    assert_golden_static("contrast", ImgOp::Contrast(25.0));
+}
+
+#[test]
+fn convert_profile() {
+    let mut metadata = ImageMetadata::default();
+    metadata.set_icc_profile(Some(std::fs::read(in_!("display_p3.icc")).unwrap()));
+
+    assert_golden(
+        "convert-profile",
+        static_image(),
+        metadata,
+        &[op(ImgOp::ConvertProfile(IccProfileTarget::Srgb))],
+    );
+}
+
+#[test]
+fn crop() {
+    assert_golden_static("crop", ImgOp::Crop((1, 1, 6, 5)));
+}
+
+#[test]
+fn curves() {
+    assert_golden_static(
+        "curves",
+        ImgOp::Curves(vec![(0, 0), (64, 40), (192, 220), (255, 255)]),
+    );
+}
+
+#[test]
+fn curves_animated() {
+    assert_golden_animated(
+        "curves-animated",
+        ImgOp::Curves(vec![(0, 20), (128, 150), (255, 235)]),
+    );
+}
+
+#[test]
+fn diff() {
+    assert_golden_static(
+        "diff",
+        ImgOp::Diff(DiffInputs::new(ImageFromPath::new(PathBuf::from(in_!(
+            "2x3_rrgrbb.bmp"
+        ))))),
+    );
+}
+
+#[test]
+fn diff_with_options() {
+    let options = DiffOptions {
+        tolerance: [64; 4],
+        different: Rgba([255, 0, 0, 128]),
+        dim: Some(0.5),
+        ..DiffOptions::default()
+    };
+
+    assert_golden_static(
+        "diff-with-options",
+        ImgOp::Diff(DiffInputs::with_options(
+            ImageFromPath::new(PathBuf::from(in_!("rainbow_8x6_orientation_6.png"))),
+            options,
+        )),
+    );
+}
+
+#[cfg(feature = "imageproc-ops")]
+#[test]
+fn draw_text() {
+    use sic_image_engine::wrapper::draw_text_inner::DrawTextInner;
+    use sic_image_engine::wrapper::font_options::{FontOptions, FontScale};
+
+    let image = ImageEngine::new(static_image())
+        .ignite(&[op(ImgOp::Resize((32, 24)))])
+        .unwrap();
+
+    assert_golden(
+        "draw-text",
+        image,
+        ImageMetadata::default(),
+        &[op(ImgOp::DrawText(DrawTextInner::new(
+            "<3".to_string(),
+            (4, 4),
+            FontOptions::new(
+                PathBuf::from(in_!("font/Lato-Regular.ttf")),
+                Rgba([255, 255, 255, 255]),
+                FontScale::Uniform(14.0),
+            ),
+        )))],
+    );
+}
+
+#[test]
+fn drop_frames() {
+    assert_golden_animated("drop-frames", ImgOp::DropFrames(range("::2")));
+}
+
+#[test]
+fn extend() {
+    let margins = PadSize::Margins(Margins {
+        top: 1,
+        right: 2,
+        bottom: 3,
+        left: 4,
+    });
+
+    assert_golden_static(
+        "extend",
+        ImgOp::Pad(PadInputs::with_fill(margins, Rgba([255, 255, 255, 255]))),
+    );
+}
+
+#[test]
+fn filter3x3() {
+    assert_golden_static(
+        "filter3x3",
+        ImgOp::Filter3x3([0.0, -1.0, 0.0, -1.0, 5.0, -1.0, 0.0, -1.0, 0.0]),
+    );
+}
+
+#[test]
+fn flip_horizontal() {
+    assert_golden_static("flip-horizontal", ImgOp::FlipHorizontal);
+}
+
+#[test]
+fn flip_vertical() {
+    assert_golden_static("flip-vertical", ImgOp::FlipVertical);
+}
+
+#[test]
+fn gamma() {
+    assert_golden_static("gamma", ImgOp::Gamma(2.2));
+}
+
+#[test]
+fn grayscale() {
+    assert_golden_static("grayscale", ImgOp::Grayscale);
+}
+
+#[test]
+fn horizontal_gradient() {
+    assert_golden_static(
+        "horizontal-gradient",
+        ImgOp::HorizontalGradient(GradientInput::new((
+            Rgba([255, 0, 0, 255]),
+            Rgba([0, 0, 255, 255]),
+        ))),
+    );
+}
+
+#[test]
+fn hue_rotate() {
+    assert_golden_static("hue-rotate", ImgOp::HueRotate(90));
+}
+
+#[test]
+fn invert() {
+    assert_golden_static("invert", ImgOp::Invert);
+}
+
+#[test]
+fn levels() {
+    assert_golden_static(
+        "levels",
+        ImgOp::Levels(LevelsInputs::new(40, 200).with_gamma(0.8)),
+    );
+}
+
+#[test]
+fn levels_of_single_channel() {
+    assert_golden_static(
+        "levels-red",
+        ImgOp::Levels(LevelsInputs::new(60, 255).with_channels(ColorChannels::Red)),
+    );
+}
+
+#[test]
+fn on_frames() {
+    assert_golden_animated(
+        "on-frames",
+        ImgOp::OnFrames(range("0..2"), vec![op(ImgOp::Invert)]),
+    );
+}
+
+#[test]
+fn overlay() {
+    assert_golden_static(
+        "overlay",
+        ImgOp::Overlay(OverlayInputs::new(
+            ImageFromPath::new(PathBuf::from(in_!("2x3_rrgrbb.bmp"))),
+            (3, 2),
+        )),
+    );
+}
+
+#[test]
+fn pad() {
+    assert_golden_static(
+        "pad",
+        ImgOp::Pad(PadInputs::new(PadSize::Canvas((12, 12), Gravity::Center))),
+    );
+}
+
+#[test]
+fn pad_with_gravity_and_fill() {
+    assert_golden_static(
+        "pad-south-east-fill",
+        ImgOp::Pad(PadInputs::with_fill(
+            PadSize::Canvas((10, 9), Gravity::SouthEast),
+            Rgba([0, 0, 255, 128]),
+        )),
+    );
+}
+
+// Resizing to fit, followed by padding to the same size, gives an image of exactly that size.
+#[test]
+fn pad_after_resize_fit() {
+    assert_golden(
+        "pad-after-resize-fit",
+        static_image(),
+        ImageMetadata::default(),
+        &[
+            op(ImgOp::ResizeWithMode(ResizeMode::Fit((10, 10)))),
+            op(ImgOp::Pad(PadInputs::new(PadSize::Canvas(
+                (10, 10),
+                Gravity::Center,
+            )))),
+        ],
+    );
+}
+
+#[test]
+fn pad_animated() {
+    assert_golden_animated(
+        "pad-animated",
+        ImgOp::Pad(PadInputs::with_fill(
+            PadSize::Canvas((12, 14), Gravity::North),
+            Rgba([0, 0, 0, 255]),
+        )),
+    );
+}
+
+#[test]
+fn resize() {
+    assert_golden_static("resize", ImgOp::Resize((16, 9)));
+}
+
+#[test]
+fn resize_with_environment() {
+    assert_golden(
+        "resize-nearest-preserve-aspect-ratio",
+        static_image(),
+        ImageMetadata::default(),
+        &[
+            Instr::EnvAdd(EnvItem::CustomSamplingFilter(
+                FilterTypeWrap::try_from_str("nearest").unwrap(),
+            )),
+            Instr::EnvAdd(EnvItem::PreserveAspectRatio(true)),
+            op(ImgOp::Resize((16, 16))),
+        ],
+    );
+}
+
+#[test]
+fn resize_fit() {
+    assert_golden_static("resize-fit", ImgOp::ResizeWithMode(ResizeMode::Fit((6, 6))));
+}
+
+#[test]
+fn resize_cover() {
+    assert_golden_static(
+        "resize-cover-north-west",
+        ImgOp::ResizeWithMode(ResizeMode::Cover((6, 6), Gravity::NorthWest)),
+    );
+}
+
+#[test]
+fn resize_cover_animated() {
+    assert_golden_animated(
+        "resize-cover-animated",
+        ImgOp::ResizeWithMode(ResizeMode::Cover((8, 4), Gravity::Center)),
+    );
+}
+
+#[test]
+fn resize_scale() {
+    assert_golden_static(
+        "resize-scale",
+        ImgOp::ResizeWithMode(ResizeMode::Scale(
+            ScaleFactor::try_from_str("150%").unwrap(),
+        )),
+    );
+}
+
+#[test]
+fn resize_max_edge() {
+    assert_golden_static(
+        "resize-max-edge",
+        ImgOp::ResizeWithMode(ResizeMode::MaxEdge(4)),
+    );
+}
+
+#[test]
+fn reverse_frames() {
+    assert_golden_animated("reverse-frames", ImgOp::ReverseFrames);
+}
+
+#[cfg(feature = "imageproc-ops")]
+#[test]
+fn rotate() {
+    use sic_image_engine::wrapper::rotate::RotateInputs;
+
+    assert_golden_static("rotate", ImgOp::Rotate(RotateInputs::new(30.0)));
+}
+
+#[cfg(feature = "imageproc-ops")]
+#[test]
+fn rotate_crop_with_fill() {
+    use sic_image_engine::wrapper::rotate::{RotateCanvas, RotateInputs};
+
+    assert_golden_static(
+        "rotate-crop-nearest-fill",
+        ImgOp::Rotate(
+            RotateInputs::new(-45.0)
+                .with_canvas(RotateCanvas::Crop)
+                .with_interpolation(FilterTypeWrap::try_from_str("nearest").unwrap())
+                .with_fill(Rgba([255, 255, 255, 255])),
+        ),
+    );
+}
+
+#[cfg(feature = "imageproc-ops")]
+#[test]
+fn rotate_animated() {
+    use sic_image_engine::wrapper::rotate::RotateInputs;
+
+    assert_golden_animated(
+        "rotate-animated",
+        ImgOp::Rotate(RotateInputs::new(60.0).with_fill(Rgba([0, 0, 0, 255]))),
+    );
+}
+
+#[test]
+fn rotate90() {
+    assert_golden_static("rotate90", ImgOp::Rotate90);
+}
+
+#[test]
+fn rotate180() {
+    assert_golden_static("rotate180", ImgOp::Rotate180);
+}
+
+#[test]
+fn rotate270() {
+    assert_golden_static("rotate270", ImgOp::Rotate270);
+}
+
+#[test]
+fn smart_crop() {
+    assert_golden_static("smart-crop", ImgOp::SmartCrop((4, 4)));
+}
+
+#[test]
+fn smart_crop_animated() {
+    assert_golden_animated("smart-crop-animated", ImgOp::SmartCrop((4, 6)));
+}
+
+#[cfg(feature = "imageproc-ops")]
+#[test]
+fn threshold() {
+    assert_golden_static("threshold", ImgOp::Threshold);
+}
+
+// The test image has no border, so a border is added first.
+#[test]
+fn trim() {
+    let white = Rgba([255, 255, 255, 255]);
+
+    assert_golden(
+        "trim-after-extend",
+        static_image(),
+        ImageMetadata::default(),
+        &[
+            op(ImgOp::Pad(PadInputs::with_fill(
+                PadSize::Margins(Margins {
+                    top: 1,
+                    right: 2,
+                    bottom: 3,
+                    left: 4,
+                }),
+                white,
+            ))),
+            op(ImgOp::Trim(TrimInputs {
+                color: Some(white),
+                margin: 1,
+                ..TrimInputs::default()
+            })),
+        ],
+    );
+}
+
+#[test]
+fn trim_animated() {
+    assert_golden(
+        "trim-animated",
+        animated_image(),
+        ImageMetadata::default(),
+        &[
+            op(ImgOp::Pad(PadInputs::with_fill(
+                PadSize::Canvas((14, 14), Gravity::Center),
+                Rgba([0, 0, 0, 255]),
+            ))),
+            op(ImgOp::Trim(TrimInputs::default())),
+        ],
+    );
+}
+
+#[test]
+fn unsharpen() {
+    assert_golden_static("unsharpen", ImgOp::Unsharpen((1.0, 2)));
+}
+
+#[test]
+fn vertical_gradient() {
+    assert_golden_static(
+        "vertical-gradient",
+        ImgOp::VerticalGradient(GradientInput::new((
+            Rgba([255, 255, 0, 255]),
+            Rgba([0, 255, 255, 128]),
+        ))),
+    );
+}
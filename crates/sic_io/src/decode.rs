@@ -1,43 +1,223 @@
 use std::fs::File;
 use std::io::{BufRead, BufReader, Cursor, Read, Seek};
 use std::path::Path;
+use std::time::Duration;
 
 use crate::errors::SicIoError;
-use sic_core::{image, AnimatedImage, SicImage};
+use crate::info::{ImageInfo, LoopCount};
+use crate::metadata::read_metadata;
+use sic_core::image::ImageDecoder;
+use sic_core::{image, AnimatedImage, FrameRange, ImageMetadata, SicImage};
 
 #[derive(Default)]
 pub struct SicImageDecoder {
     /// For animated images, this frame will be used if we can only decode into a static image.
     selected_frame: Option<FrameIndex>,
+    /// For animated images, only the frames within this range will be kept.
+    frame_range: Option<FrameRange>,
 }
 
 impl SicImageDecoder {
     pub fn new(selected_frame: Option<FrameIndex>) -> Self {
-        Self { selected_frame }
+        Self {
+            selected_frame,
+            frame_range: None,
+        }
+    }
+
+    /// Only keep the frames within the given range, if the decoded image is an animated image.
+    /// The range is applied before a single frame is selected, so the selected frame is
+    /// indexed relative to the kept frames.
+    pub fn with_frame_range(mut self, frame_range: Option<FrameRange>) -> Self {
+        self.frame_range = frame_range;
+        self
     }
 }
 
 impl SicImageDecoder {
     /// Load an image using a reader.
     /// All images are currently loaded from memory.
+    ///
+    /// The orientation stored in the metadata of the image (if any) is not applied to the
+    /// decoded pixels. Use [`SicImageDecoder::decode_with_metadata`] to obtain it.
     pub fn decode<R: Read>(&self, reader: &mut R) -> Result<SicImage, SicIoError> {
-        let reader = image::ImageReader::new(Cursor::new(read_image_to_buffer(reader)?))
+        self.decode_with_metadata(reader).map(|(image, _)| image)
+    }
+
+    /// Load an image using a reader, and read its metadata, such as Exif and XMP metadata,
+    /// and its embedded ICC profile.
+    ///
+    /// The orientation of the metadata is read from the Exif Orientation tag. If the image has
+    /// no (valid) orientation tag, the orientation is [`Orientation::NoTransforms`].
+    /// The orientation can be applied with the `auto-orient` image operation.
+    ///
+    /// [`Orientation::NoTransforms`]: sic_core::image::metadata::Orientation::NoTransforms
+    pub fn decode_with_metadata<R: Read>(
+        &self,
+        reader: &mut R,
+    ) -> Result<(SicImage, ImageMetadata), SicIoError> {
+        let buffer = read_image_to_buffer(reader)?;
+        let metadata = read_metadata(&buffer);
+
+        let reader = image::ImageReader::new(Cursor::new(buffer))
             .with_guessed_format()
             .map_err(SicIoError::Io)?;
 
-        match reader.format() {
-            Some(image::ImageFormat::Png) => decode_png(reader, self.selected_frame),
-            Some(image::ImageFormat::Gif) => decode_gif(reader, self.selected_frame),
+        let image = match reader.format() {
+            Some(image::ImageFormat::Png) => decode_png(reader),
+            Some(image::ImageFormat::Gif) => decode_gif(reader),
+            Some(image::ImageFormat::WebP) => decode_webp(reader),
             Some(_) => reader
                 .decode()
                 .map_err(SicIoError::ImageError)
                 .map(SicImage::from),
-            None => Err(SicIoError::ImageError(image::error::ImageError::Decoding(
-                image::error::DecodingError::from_format_hint(
-                    image::error::ImageFormatHint::Unknown,
-                ),
-            ))),
+            None => Err(unknown_format_error()),
+        }?;
+
+        let image = select_frames(image, self.frame_range.as_ref(), self.selected_frame)?;
+
+        Ok((image, metadata))
+    }
+
+    /// Read the properties of an image, such as its format, dimensions, color type and the
+    /// presence of metadata, without processing it. For animated images, the delay of each
+    /// frame and the number of times the animation is played are read as well.
+    ///
+    /// The selected frame and frame range of the decoder are not applied: all frames of an
+    /// animated image are inspected.
+    pub fn inspect<R: Read>(&self, reader: &mut R) -> Result<ImageInfo, SicIoError> {
+        let buffer = read_image_to_buffer(reader)?;
+        let metadata = read_metadata(&buffer);
+
+        let reader = image::ImageReader::new(Cursor::new(buffer.as_slice()))
+            .with_guessed_format()
+            .map_err(SicIoError::Io)?;
+        let format = reader.format().ok_or_else(unknown_format_error)?;
+
+        let decoder = reader.into_decoder().map_err(SicIoError::ImageError)?;
+        let (width, height) = decoder.dimensions();
+        let color_type = decoder.color_type();
+        let bit_depth = bit_depth(decoder.original_color_type()).unwrap_or_else(|| {
+            (color_type.bits_per_pixel() / u16::from(color_type.channel_count())) as u8
+        });
+
+        let frame_delays = match format {
+            image::ImageFormat::Png => {
+                let decoder = image::codecs::png::PngDecoder::new(Cursor::new(buffer.as_slice()))
+                    .map_err(SicIoError::ImageError)?;
+
+                if decoder.is_apng().map_err(SicIoError::ImageError)? {
+                    frame_delays(decoder.apng().map_err(SicIoError::ImageError)?)?
+                } else {
+                    Vec::new()
+                }
+            }
+            image::ImageFormat::Gif => frame_delays(
+                image::codecs::gif::GifDecoder::new(Cursor::new(buffer.as_slice()))
+                    .map_err(SicIoError::ImageError)?,
+            )?,
+            image::ImageFormat::WebP => {
+                let decoder = image::codecs::webp::WebPDecoder::new(Cursor::new(buffer.as_slice()))
+                    .map_err(SicIoError::ImageError)?;
+
+                if decoder.has_animation() {
+                    frame_delays(decoder)?
+                } else {
+                    Vec::new()
+                }
+            }
+            _ => Vec::new(),
+        };
+
+        // Like the decoder, an animated image with a single frame is considered a still image.
+        let (frame_count, frame_delays, loop_count) = if frame_delays.len() > 1 {
+            (
+                frame_delays.len(),
+                frame_delays,
+                read_loop_count(format, &buffer),
+            )
+        } else {
+            (1, Vec::new(), None)
+        };
+
+        Ok(ImageInfo {
+            format,
+            width,
+            height,
+            color_type,
+            bit_depth,
+            frame_count,
+            frame_delays,
+            loop_count,
+            has_exif: metadata.exif().is_some(),
+            has_xmp: metadata.xmp().is_some(),
+            has_icc_profile: metadata.icc_profile().is_some(),
+        })
+    }
+}
+
+fn unknown_format_error() -> SicIoError {
+    SicIoError::ImageError(image::error::ImageError::Decoding(
+        image::error::DecodingError::from_format_hint(image::error::ImageFormatHint::Unknown),
+    ))
+}
+
+// Number of bits per channel of the given color type, if its channels are known.
+fn bit_depth(color_type: image::ExtendedColorType) -> Option<u8> {
+    match color_type.channel_count() {
+        0 => None,
+        channels => Some((color_type.bits_per_pixel() / u16::from(channels)) as u8),
+    }
+}
+
+/// Read the delay of each frame of an animated image.
+fn frame_delays<'decoder, D: image::AnimationDecoder<'decoder>>(
+    decoder: D,
+) -> Result<Vec<Duration>, SicIoError> {
+    decoder
+        .into_frames()
+        .map(|frame| {
+            frame
+                .map(|frame| Duration::from(frame.delay()))
+                .map_err(SicIoError::ImageError)
+        })
+        .collect()
+}
+
+/// Read how often an animated image is played. The `image` decoders don't expose the loop
+/// count, so it is read with the decoders of the image formats themselves.
+fn read_loop_count(format: image::ImageFormat, buffer: &[u8]) -> Option<LoopCount> {
+    match format {
+        // The GIF repeat count is the number of times the animation is repeated after it has
+        // been played once.
+        image::ImageFormat::Gif => match gif::DecodeOptions::new().read_info(buffer).ok()?.repeat()
+        {
+            gif::Repeat::Infinite => Some(LoopCount::Infinite),
+            gif::Repeat::Finite(repeat) => Some(LoopCount::Finite(u32::from(repeat) + 1)),
+        },
+        image::ImageFormat::Png => {
+            let reader = png::Decoder::new(buffer).read_info().ok()?;
+
+            reader
+                .info()
+                .animation_control
+                .map(|control| match control.num_plays {
+                    0 => LoopCount::Infinite,
+                    plays => LoopCount::Finite(plays),
+                })
+        }
+        image::ImageFormat::WebP => {
+            match image_webp::WebPDecoder::new(Cursor::new(buffer))
+                .ok()?
+                .loop_count()
+            {
+                image_webp::LoopCount::Forever => Some(LoopCount::Infinite),
+                image_webp::LoopCount::Times(plays) => {
+                    Some(LoopCount::Finite(u32::from(plays.get())))
+                }
+            }
         }
+        _ => None,
     }
 }
 
@@ -79,15 +259,28 @@ fn frames<'decoder, D: image::AnimationDecoder<'decoder>>(
     }
 }
 
-fn select_frame(image: SicImage, frame_index: Option<FrameIndex>) -> Result<SicImage, SicIoError> {
-    match (image, frame_index) {
-        (SicImage::Animated(animated), Some(index)) => {
+fn select_frames(
+    image: SicImage,
+    frame_range: Option<&FrameRange>,
+    frame_index: Option<FrameIndex>,
+) -> Result<SicImage, SicIoError> {
+    let mut animated = match image {
+        SicImage::Animated(animated) => animated,
+        img => return Ok(img),
+    };
+
+    if let Some(range) = frame_range {
+        animated.retain_frames(range)?;
+    }
+
+    match frame_index {
+        Some(index) => {
             let max_frames = animated.frames().len();
             Ok(SicImage::Static(
                 animated.try_into_static_image(index.as_number(max_frames))?,
             ))
         }
-        (img, _) => Ok(img),
+        None => Ok(SicImage::Animated(animated)),
     }
 }
 
@@ -109,25 +302,32 @@ impl FrameIndex {
     }
 }
 
-fn decode_gif<R: BufRead + Seek>(
-    reader: image::ImageReader<R>,
-    frame_index: Option<FrameIndex>,
-) -> Result<SicImage, SicIoError> {
+fn decode_gif<R: BufRead + Seek>(reader: image::ImageReader<R>) -> Result<SicImage, SicIoError> {
     let decoder =
         image::codecs::gif::GifDecoder::new(reader.into_inner()).map_err(SicIoError::ImageError)?;
 
-    frames(decoder).and_then(|image| select_frame(image, frame_index))
+    frames(decoder)
 }
 
-fn decode_png<R: BufRead + Seek>(
-    reader: image::ImageReader<R>,
-    frame: Option<FrameIndex>,
-) -> Result<SicImage, SicIoError> {
+fn decode_png<R: BufRead + Seek>(reader: image::ImageReader<R>) -> Result<SicImage, SicIoError> {
     let decoder =
         image::codecs::png::PngDecoder::new(reader.into_inner()).map_err(SicIoError::ImageError)?;
 
     if decoder.is_apng().map_err(SicIoError::ImageError)? {
-        frames(decoder.apng().map_err(SicIoError::ImageError)?).and_then(|f| select_frame(f, frame))
+        frames(decoder.apng().map_err(SicIoError::ImageError)?)
+    } else {
+        image::DynamicImage::from_decoder(decoder)
+            .map_err(SicIoError::ImageError)
+            .map(SicImage::from)
+    }
+}
+
+fn decode_webp<R: BufRead + Seek>(reader: image::ImageReader<R>) -> Result<SicImage, SicIoError> {
+    let decoder = image::codecs::webp::WebPDecoder::new(reader.into_inner())
+        .map_err(SicIoError::ImageError)?;
+
+    if decoder.has_animation() {
+        frames(decoder)
     } else {
         image::DynamicImage::from_decoder(decoder)
             .map_err(SicIoError::ImageError)
@@ -287,6 +487,58 @@ mod tests {
         assert_eq!(last.get_pixel(XY, XY).0, seven.get_pixel(XY, XY).0);
     }
 
+    #[test]
+    fn load_gif_frame_range() {
+        let load_path = setup_test_image(GIF_LOOP);
+
+        let decoder = SicImageDecoder::default()
+            .with_frame_range(Some(FrameRange::try_from_str("1::3").unwrap()));
+        let image = decoder
+            .decode(&mut file_reader(load_path).unwrap())
+            .unwrap();
+
+        let animated = match image {
+            SicImage::Animated(animated) => animated,
+            SicImage::Static(_) => panic!("expected an animated image"),
+        };
+
+        let colors = animated
+            .frames()
+            .iter()
+            .map(|frame| frame.buffer().get_pixel(XY, XY).0)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            colors,
+            vec![FRAME_COLORS[1], FRAME_COLORS[4], FRAME_COLORS[7]]
+        );
+    }
+
+    // The selected frame is indexed relative to the frames within the frame range.
+    #[test]
+    fn load_gif_frame_range_then_select_frame() {
+        let load_path = setup_test_image(GIF_LOOP);
+
+        let decoder = SicImageDecoder::new(Some(FrameIndex::Last))
+            .with_frame_range(Some(FrameRange::try_from_str("2..5").unwrap()));
+        let image = decoder
+            .decode(&mut file_reader(load_path).unwrap())
+            .unwrap();
+
+        assert_eq!(image.get_pixel(XY, XY).0, FRAME_COLORS[4]);
+    }
+
+    #[test]
+    fn load_gif_frame_range_beyond_length() {
+        let load_path = setup_test_image(GIF_LOOP);
+
+        let decoder = SicImageDecoder::default()
+            .with_frame_range(Some(FrameRange::try_from_str("8..").unwrap()));
+        let result = decoder.decode(&mut file_reader(load_path).unwrap());
+
+        assert!(result.is_err());
+    }
+
     const NOT_GIFS: [&str; 3] = [
         "blackwhite_2x2.bmp",
         "bwlines.png",
@@ -338,4 +590,139 @@ mod tests {
             }
         }
     }
+
+    mod orientation {
+        use super::*;
+        use sic_core::image::metadata::Orientation;
+
+        #[parameterized(
+            path = {
+                "rainbow_8x6_orientation_6.jpg",
+                "rainbow_8x6_orientation_6.png",
+                "rainbow_8x6.bmp",
+                "unsplash_763569_cropped.jpg",
+            },
+            expected = {
+                Orientation::Rotate90,
+                Orientation::Rotate90,
+                Orientation::NoTransforms,
+                Orientation::NoTransforms,
+            }
+        )]
+        fn read_orientation(path: &str, expected: Orientation) {
+            let load_path = setup_test_image(path);
+
+            let decoder = SicImageDecoder::default();
+            let (_, metadata) = decoder
+                .decode_with_metadata(&mut file_reader(load_path).unwrap())
+                .unwrap();
+
+            assert_eq!(metadata.orientation(), expected);
+        }
+
+        #[test]
+        fn decode_does_not_apply_orientation() {
+            let load_path = setup_test_image("rainbow_8x6_orientation_6.png");
+
+            let decoder = SicImageDecoder::default();
+            let image = decoder
+                .decode(&mut file_reader(load_path).unwrap())
+                .unwrap();
+
+            assert_eq!((image.width(), image.height()), (8, 6));
+        }
+    }
+
+    mod inspect {
+        use super::*;
+        use crate::info::LoopCount;
+        use std::time::Duration;
+
+        fn inspect(path: &str) -> ImageInfo {
+            let load_path = setup_test_image(path);
+
+            SicImageDecoder::default()
+                .inspect(&mut file_reader(load_path).unwrap())
+                .unwrap()
+        }
+
+        #[parameterized(
+            path = { "rainbow_8x6.bmp", "unsplash_763569_cropped.jpg", "palette_4x4.png" },
+            expected = {
+                (image::ImageFormat::Bmp, (8, 6), image::ColorType::Rgb8),
+                (image::ImageFormat::Jpeg, (217, 447), image::ColorType::Rgb8),
+                (image::ImageFormat::Png, (4, 4), image::ColorType::Rgba8),
+            }
+        )]
+        fn still_image(path: &str, expected: (image::ImageFormat, (u32, u32), image::ColorType)) {
+            let info = inspect(path);
+
+            assert_eq!(
+                (info.format(), info.dimensions(), info.color_type()),
+                expected
+            );
+            assert_eq!(info.bit_depth(), 8);
+            assert_eq!(info.frame_count(), 1);
+            assert!(info.frame_delays().is_empty());
+            assert_eq!(info.loop_count(), None);
+        }
+
+        #[parameterized(
+            path = { "loop.gif", "noloop.gif", "apng_sample.png" },
+            expected = {
+                (8, Duration::from_millis(720), Some(LoopCount::Infinite)),
+                (8, Duration::from_millis(720), Some(LoopCount::Finite(1))),
+                (3, Duration::from_secs(1), Some(LoopCount::Infinite)),
+            }
+        )]
+        fn animated_image(path: &str, expected: (usize, Duration, Option<LoopCount>)) {
+            let (frame_count, delay, loop_count) = expected;
+            let info = inspect(path);
+
+            assert_eq!(info.frame_count(), frame_count);
+            assert_eq!(info.frame_delays(), vec![delay; frame_count].as_slice());
+            assert_eq!(info.loop_count(), loop_count);
+        }
+
+        #[parameterized(
+            path = {
+                "rainbow_8x6.bmp",
+                "rainbow_8x6_exif_gps.jpg",
+                "unsplash_763569_cropped.jpg",
+            },
+            expected = {
+                (false, false, false),
+                (true, true, false),
+                (true, false, true),
+            }
+        )]
+        fn metadata(path: &str, expected: (bool, bool, bool)) {
+            let info = inspect(path);
+
+            assert_eq!(
+                (info.has_exif(), info.has_xmp(), info.has_icc_profile()),
+                expected
+            );
+        }
+
+        #[test]
+        fn frame_range_is_not_applied() {
+            let load_path = setup_test_image("loop.gif");
+
+            let decoder = SicImageDecoder::new(Some(FrameIndex::First))
+                .with_frame_range(Some(FrameRange::try_from_str("2..5").unwrap()));
+            let info = decoder
+                .inspect(&mut file_reader(load_path).unwrap())
+                .unwrap();
+
+            assert_eq!(info.frame_count(), 8);
+        }
+
+        #[test]
+        fn not_an_image() {
+            let result = SicImageDecoder::default().inspect(&mut "not an image".as_bytes());
+
+            assert!(result.is_err());
+        }
+    }
 }
@@ -11,6 +11,9 @@ use crate::preprocessor::Preprocessors;
 pub mod bmp;
 pub mod dynamic;
 pub mod jpeg;
+pub mod png;
+pub mod tiff;
+pub mod webp;
 
 pub struct SicImageEncoder {
     preprocessors: Preprocessors,
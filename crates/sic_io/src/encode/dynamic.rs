@@ -1,5 +1,8 @@
 use crate::encode::bmp::BmpEncoder;
 use crate::encode::jpeg::JpegEncoder;
+use crate::encode::png::PngEncoder;
+use crate::encode::tiff::TiffEncoder;
+use crate::encode::webp::WebpEncoder;
 use crate::encode_settings::EncodeSettings;
 use crate::errors::{EncodingError, SicIoError, UnknownImageFormatError};
 use sic_core::image;
@@ -87,7 +90,11 @@ impl<W: Write + Seek> IntoImageEncoder<W> for DynamicEncoder<W> {
 
         let (encoder, format) = match id.to_ascii_lowercase().as_str() {
             "avif" => (
-                Avif(image::codecs::avif::AvifEncoder::new(writer)),
+                Avif(image::codecs::avif::AvifEncoder::new_with_speed_quality(
+                    writer,
+                    settings.avif_speed.as_u8(),
+                    settings.avif_quality.as_u8(),
+                )),
                 DynamicImageFormat::Avif,
             ),
             "bmp" => (Bmp(BmpEncoder::new(writer)), DynamicImageFormat::Bmp),
@@ -134,7 +141,12 @@ impl<W: Write + Seek> IntoImageEncoder<W> for DynamicEncoder<W> {
                 (Pnm(enc), DynamicImageFormat::Pnm { subtype })
             }
             "png" => (
-                Png(image::codecs::png::PngEncoder::new(writer)),
+                Png(PngEncoder::new(
+                    writer,
+                    settings.png_compression,
+                    settings.png_filter,
+                    settings.repeat_animation,
+                )),
                 DynamicImageFormat::Png,
             ),
             "ppm" => {
@@ -152,11 +164,15 @@ impl<W: Write + Seek> IntoImageEncoder<W> for DynamicEncoder<W> {
                 DynamicImageFormat::Tga,
             ),
             "tiff" | "tif" => (
-                Tiff(image::codecs::tiff::TiffEncoder::new(writer)),
+                Tiff(TiffEncoder::new(writer, settings.tiff_compression)),
                 DynamicImageFormat::Tiff,
             ),
             "webp" => (
-                Webp(image::codecs::webp::WebPEncoder::new_lossless(writer)),
+                Webp(WebpEncoder::new(
+                    writer,
+                    settings.webp_quality,
+                    settings.repeat_animation,
+                )),
                 DynamicImageFormat::Webp,
             ),
             _ => {
@@ -215,7 +231,13 @@ impl<W: Write + Seek> DynamicEncoder<W> {
             DynamicEncoderInner::Gif(mut enc) => {
                 enc.encode_frames(frames).map_err(SicIoError::ImageError)
             }
-            // Use SingleFramePreprocessor to avoid this error, by picking a single frame
+            DynamicEncoderInner::Png(enc) => {
+                enc.encode_frames(frames).map_err(SicIoError::ImageError)
+            }
+            DynamicEncoderInner::Webp(enc) => {
+                enc.encode_frames(frames).map_err(SicIoError::ImageError)
+            }
+            // Use PickFramePreprocessor to avoid this error, by picking a single frame
             // from the animated image instead.
             enc => Err(SicIoError::Encoding(
                 EncodingError::AnimatedImageUnsupported(enc.image_format()),
@@ -240,11 +262,11 @@ enum DynamicEncoderInner<W: Write + Seek> {
     Ico(image::codecs::ico::IcoEncoder<W>),
     Jpeg(JpegEncoder<W>),
     Pnm(image::codecs::pnm::PnmEncoder<W>),
-    Png(image::codecs::png::PngEncoder<W>),
+    Png(PngEncoder<W>),
     Qoi(image::codecs::qoi::QoiEncoder<W>),
     Tga(image::codecs::tga::TgaEncoder<W>),
-    Tiff(image::codecs::tiff::TiffEncoder<W>),
-    Webp(image::codecs::webp::WebPEncoder<W>),
+    Tiff(TiffEncoder<W>),
+    Webp(WebpEncoder<W>),
 }
 
 impl<W: Write + Seek> fmt::Debug for DynamicEncoderInner<W> {
@@ -309,8 +331,13 @@ pub enum DynamicImageFormat {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::encode_settings::png::{PngCompression, PngFilter};
+    use crate::encode_settings::tiff::TiffCompression;
+    use image::GenericImageView;
     use parameterized::parameterized;
     use std::io::SeekFrom;
+    use tiff::decoder::Decoder;
+    use tiff::tags::{CompressionMethod, Tag};
 
     #[derive(Debug)]
     struct DummyMem;
@@ -349,7 +376,8 @@ mod tests {
             "qoi",
             "tga",
             "tiff",
-            "tif"
+            "tif",
+            "webp"
         },
         expected = {
             image::ImageFormat::Avif,
@@ -369,6 +397,7 @@ mod tests {
             image::ImageFormat::Tga,
             image::ImageFormat::Tiff,
             image::ImageFormat::Tiff,
+            image::ImageFormat::WebP,
         }
     )]
     fn test_with_extensions(ext: &str, expected: image::ImageFormat) {
@@ -400,7 +429,8 @@ mod tests {
             "qoi",
             "tga",
             "tiff",
-            "tif"
+            "tif",
+            "webp"
         },
         expected = {
             image::ImageFormat::Avif,
@@ -420,6 +450,7 @@ mod tests {
             image::ImageFormat::Tga,
             image::ImageFormat::Tiff,
             image::ImageFormat::Tiff,
+            image::ImageFormat::WebP,
         }
     )]
     fn test_with_identifier(identifier: &str, expected: image::ImageFormat) {
@@ -448,7 +479,8 @@ mod tests {
             "QOI",
             "TGA",
             "TIFF",
-            "TIF"
+            "TIF",
+            "WEBP"
         },
         expected = {
             image::ImageFormat::Avif,
@@ -468,6 +500,7 @@ mod tests {
             image::ImageFormat::Tga,
             image::ImageFormat::Tiff,
             image::ImageFormat::Tiff,
+            image::ImageFormat::WebP,
         }
     )]
     fn test_with_identifier_uppercase(identifier: &str, expected: image::ImageFormat) {
@@ -540,10 +573,11 @@ mod tests {
 
     // non default: pnm ascii + "pbm"
     #[test]
-    #[allow(clippy::field_reassign_with_default)]
     fn identifier_custom_pnm_sample_encoding_ascii_pbm() {
-        let mut settings = EncodeSettings::default();
-        settings.pnm_sample_encoding = image::codecs::pnm::SampleEncoding::Ascii;
+        let settings = EncodeSettings {
+            pnm_sample_encoding: image::codecs::pnm::SampleEncoding::Ascii,
+            ..Default::default()
+        };
 
         let mut mem = DummyMem;
         let dynamic_encoder = DynamicEncoder::from_identifier(&mut mem, "pbm", &settings).unwrap();
@@ -559,10 +593,11 @@ mod tests {
             "ppm",
         }
     )]
-    #[allow(clippy::field_reassign_with_default)]
     fn identifier_custom_pnm_sample_encoding_ascii_pgm(identifier: &str) {
-        let mut settings = EncodeSettings::default();
-        settings.pnm_sample_encoding = image::codecs::pnm::SampleEncoding::Ascii;
+        let settings = EncodeSettings {
+            pnm_sample_encoding: image::codecs::pnm::SampleEncoding::Ascii,
+            ..Default::default()
+        };
 
         let mut mem = DummyMem;
         let dynamic_encoder =
@@ -570,4 +605,143 @@ mod tests {
 
         assert_eq!(dynamic_encoder.image_format(), image::ImageFormat::Pnm);
     }
+
+    fn encode_gradient(identifier: &str, settings: &EncodeSettings) -> Vec<u8> {
+        use image::ImageEncoder;
+
+        let image = image::RgbImage::from_fn(32, 32, |x, y| {
+            image::Rgb([(x * 8) as u8, (y * 8) as u8, ((x + y) * 4) as u8])
+        });
+
+        let mut out = std::io::Cursor::new(Vec::new());
+        let encoder = DynamicEncoder::from_identifier(&mut out, identifier, settings).unwrap();
+        encoder
+            .write_image(image.as_raw(), 32, 32, image::ExtendedColorType::Rgb8)
+            .unwrap();
+
+        out.into_inner()
+    }
+
+    #[test]
+    fn identifier_custom_webp_quality_lossy() {
+        use crate::encode_settings::webp::WebpQuality;
+
+        let settings = EncodeSettings {
+            webp_quality: Some(WebpQuality::try_from(50).unwrap()),
+            ..Default::default()
+        };
+
+        let encoded = encode_gradient("webp", &settings);
+
+        // Lossy WebP images store their image data in a 'VP8 ' chunk, while lossless images
+        // use the 'VP8L' chunk.
+        assert_eq!(&encoded[12..16], b"VP8 ");
+        assert_eq!(
+            image::load_from_memory(&encoded).unwrap().dimensions(),
+            (32, 32)
+        );
+    }
+
+    #[test]
+    fn identifier_default_webp_lossless() {
+        let encoded = encode_gradient("webp", &EncodeSettings::default());
+
+        assert_eq!(&encoded[12..16], b"VP8L");
+    }
+
+    #[parameterized(
+        compression = {
+            TiffCompression::Uncompressed,
+            TiffCompression::Lzw,
+            TiffCompression::Deflate,
+            TiffCompression::PackBits,
+        },
+        expected = {
+            CompressionMethod::None,
+            CompressionMethod::LZW,
+            CompressionMethod::Deflate,
+            CompressionMethod::PackBits,
+        }
+    )]
+    fn identifier_custom_tiff_compression(
+        compression: TiffCompression,
+        expected: CompressionMethod,
+    ) {
+        let settings = EncodeSettings {
+            tiff_compression: compression,
+            ..Default::default()
+        };
+
+        let encoded = encode_gradient("tiff", &settings);
+        let uncompressed = encode_gradient("tiff", &EncodeSettings::default());
+
+        let mut decoder = Decoder::new(std::io::Cursor::new(&encoded)).unwrap();
+        let method = decoder.get_tag_u32(Tag::Compression).unwrap();
+        assert_eq!(CompressionMethod::from_u16(method as u16), Some(expected));
+
+        let decoded = image::load_from_memory(&encoded).unwrap();
+        let expected = image::load_from_memory(&uncompressed).unwrap();
+        assert_eq!(decoded.as_bytes(), expected.as_bytes());
+    }
+
+    #[parameterized(
+        compression = { PngCompression::Fast, PngCompression::Default, PngCompression::Best },
+        filter = { PngFilter::NoFilter, PngFilter::Paeth, PngFilter::Adaptive }
+    )]
+    fn identifier_custom_png_settings(compression: PngCompression, filter: PngFilter) {
+        let settings = EncodeSettings {
+            png_compression: compression,
+            png_filter: filter,
+            ..Default::default()
+        };
+
+        let encoded = encode_gradient("png", &settings);
+        let expected = encode_gradient("png", &EncodeSettings::default());
+
+        // none of the combinations is the default (fast, adaptive), so the image data is
+        // compressed differently, but decodes to the same pixels
+        assert_ne!(encoded, expected);
+        assert_eq!(
+            image::load_from_memory(&encoded).unwrap().as_bytes(),
+            image::load_from_memory(&expected).unwrap().as_bytes()
+        );
+    }
+
+    fn frames() -> Vec<image::Frame> {
+        [[255, 0, 0, 255], [0, 0, 255, 128]]
+            .into_iter()
+            .map(|color| {
+                image::Frame::from_parts(
+                    image::RgbaImage::from_pixel(4, 4, image::Rgba(color)),
+                    0,
+                    0,
+                    image::Delay::from_numer_denom_ms(100, 1),
+                )
+            })
+            .collect()
+    }
+
+    #[parameterized(identifier = { "gif", "png", "webp" })]
+    fn write_image_frames_animated(identifier: &str) {
+        let settings = EncodeSettings::default();
+        let mut out = std::io::Cursor::new(Vec::new());
+        let encoder = DynamicEncoder::from_identifier(&mut out, identifier, &settings).unwrap();
+
+        assert!(encoder.write_image_frames(frames()).is_ok());
+        assert!(!out.into_inner().is_empty());
+    }
+
+    #[parameterized(identifier = { "bmp", "jpeg", "tiff" })]
+    fn write_image_frames_unsupported(identifier: &str) {
+        let settings = EncodeSettings::default();
+        let mut out = std::io::Cursor::new(Vec::new());
+        let encoder = DynamicEncoder::from_identifier(&mut out, identifier, &settings).unwrap();
+
+        assert!(matches!(
+            encoder.write_image_frames(frames()),
+            Err(SicIoError::Encoding(
+                EncodingError::AnimatedImageUnsupported(_)
+            ))
+        ));
+    }
 }
@@ -0,0 +1,158 @@
+use crate::encode_settings::gif::RepeatAnimation;
+use crate::encode_settings::png::{PngCompression, PngFilter};
+use sic_core::image;
+use std::io::{Seek, Write};
+
+/// Wrapper for the PNG encoder, which adds support for animated (APNG) images.
+/// The [`PngEncoder`] of `image` can only encode static images, so animated images are encoded
+/// with the `png` crate directly instead.
+///
+/// [`PngEncoder`]: image::codecs::png::PngEncoder
+pub struct PngEncoder<W> {
+    writer: W,
+    compression: PngCompression,
+    filter: PngFilter,
+    repeat: RepeatAnimation,
+}
+
+impl<W: Write + Seek> PngEncoder<W> {
+    pub fn new(
+        writer: W,
+        compression: PngCompression,
+        filter: PngFilter,
+        repeat: RepeatAnimation,
+    ) -> Self {
+        Self {
+            writer,
+            compression,
+            filter,
+            repeat,
+        }
+    }
+
+    /// Encode the frames of an animated image as APNG.
+    ///
+    /// Expects each frame to cover the complete canvas, which is the case for frames produced by
+    /// the decoders of `image`.
+    pub fn encode_frames(self, frames: Vec<image::Frame>) -> image::ImageResult<()> {
+        let (width, height) = frames
+            .first()
+            .map(|frame| frame.buffer().dimensions())
+            .ok_or_else(|| encoding_error("an animated image requires at least one frame"))?;
+
+        if frames
+            .iter()
+            .any(|frame| frame.buffer().dimensions() != (width, height))
+        {
+            return Err(encoding_error(
+                "all frames of an animated image should have the same dimensions",
+            ));
+        }
+
+        let mut encoder = png::Encoder::new(self.writer, width, height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_compression(compression(self.compression));
+        let (filter, adaptive_filter) = filter(self.filter);
+        encoder.set_filter(filter);
+        encoder.set_adaptive_filter(adaptive_filter);
+        encoder
+            .set_animated(frames.len() as u32, self.repeat.num_plays())
+            .map_err(png_error)?;
+
+        let mut writer = encoder.write_header().map_err(png_error)?;
+
+        for frame in frames {
+            let (numerator, denominator) = frame_delay(frame.delay());
+            writer
+                .set_frame_delay(numerator, denominator)
+                .map_err(png_error)?;
+            writer
+                .write_image_data(frame.buffer().as_raw())
+                .map_err(png_error)?;
+        }
+
+        writer.finish().map_err(png_error)
+    }
+}
+
+impl<W: Write + Seek> image::ImageEncoder for PngEncoder<W> {
+    fn write_image(
+        self,
+        buf: &[u8],
+        width: u32,
+        height: u32,
+        color_type: image::ExtendedColorType,
+    ) -> image::ImageResult<()> {
+        image::codecs::png::PngEncoder::new_with_quality(
+            self.writer,
+            self.compression.into(),
+            self.filter.into(),
+        )
+        .write_image(buf, width, height, color_type)
+    }
+}
+
+fn compression(compression: PngCompression) -> png::Compression {
+    match compression {
+        PngCompression::Fast => png::Compression::Fast,
+        PngCompression::Default => png::Compression::Default,
+        PngCompression::Best => png::Compression::Best,
+    }
+}
+
+// Mirrors how the `image` PNG encoder configures its filters.
+fn filter(filter: PngFilter) -> (png::FilterType, png::AdaptiveFilterType) {
+    match filter {
+        PngFilter::NoFilter => (
+            png::FilterType::NoFilter,
+            png::AdaptiveFilterType::NonAdaptive,
+        ),
+        PngFilter::Sub => (png::FilterType::Sub, png::AdaptiveFilterType::NonAdaptive),
+        PngFilter::Up => (png::FilterType::Up, png::AdaptiveFilterType::NonAdaptive),
+        PngFilter::Avg => (png::FilterType::Avg, png::AdaptiveFilterType::NonAdaptive),
+        PngFilter::Paeth => (png::FilterType::Paeth, png::AdaptiveFilterType::NonAdaptive),
+        PngFilter::Adaptive => (png::FilterType::Sub, png::AdaptiveFilterType::Adaptive),
+    }
+}
+
+/// APNG frame delays are stored as a fraction of seconds, with a 16 bit numerator and denominator.
+fn frame_delay(delay: image::Delay) -> (u16, u16) {
+    let (numerator, denominator) = delay.numer_denom_ms();
+    let ms = (f64::from(numerator) / f64::from(denominator.max(1))).round() as u32;
+
+    match u16::try_from(ms) {
+        Ok(ms) => (ms, 1000),
+        Err(_) => (u16::try_from(ms / 10).unwrap_or(u16::MAX), 100),
+    }
+}
+
+fn png_error(err: png::EncodingError) -> image::ImageError {
+    match err {
+        png::EncodingError::IoError(err) => image::ImageError::IoError(err),
+        err => encoding_error(err),
+    }
+}
+
+fn encoding_error(err: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> image::ImageError {
+    image::ImageError::Encoding(image::error::EncodingError::new(
+        image::error::ImageFormatHint::Exact(image::ImageFormat::Png),
+        err,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parameterized::parameterized;
+
+    #[parameterized(
+        delay_ms = { 0, 100, 65535, 65536, 1_000_000 },
+        expected = { (0, 1000), (100, 1000), (65535, 1000), (6553, 100), (65535, 100) }
+    )]
+    fn frame_delay_fraction(delay_ms: u32, expected: (u16, u16)) {
+        let delay = image::Delay::from_numer_denom_ms(delay_ms, 1);
+
+        assert_eq!(frame_delay(delay), expected);
+    }
+}
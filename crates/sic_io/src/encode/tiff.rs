@@ -0,0 +1,114 @@
+use crate::encode_settings::tiff::TiffCompression;
+use sic_core::image;
+use std::io::{Seek, Write};
+use tiff::encoder::colortype;
+use tiff::encoder::compression::{Compression, Deflate, Lzw, Packbits};
+
+/// Wrapper for the TIFF encoder, which adds support for compression.
+/// The [`TiffEncoder`] of `image` always writes uncompressed images, so compressed images are
+/// written with the `tiff` crate directly instead.
+///
+/// [`TiffEncoder`]: image::codecs::tiff::TiffEncoder
+pub struct TiffEncoder<W> {
+    writer: W,
+    compression: TiffCompression,
+}
+
+impl<W: Write + Seek> TiffEncoder<W> {
+    pub fn new(writer: W, compression: TiffCompression) -> Self {
+        Self {
+            writer,
+            compression,
+        }
+    }
+}
+
+impl<W: Write + Seek> image::ImageEncoder for TiffEncoder<W> {
+    fn write_image(
+        self,
+        buf: &[u8],
+        width: u32,
+        height: u32,
+        color_type: image::ExtendedColorType,
+    ) -> image::ImageResult<()> {
+        let Self {
+            writer,
+            compression,
+        } = self;
+
+        match compression {
+            TiffCompression::Uncompressed => image::codecs::tiff::TiffEncoder::new(writer)
+                .write_image(buf, width, height, color_type),
+            TiffCompression::Lzw => write(writer, buf, width, height, color_type, Lzw),
+            TiffCompression::Deflate => {
+                write(writer, buf, width, height, color_type, Deflate::default())
+            }
+            TiffCompression::PackBits => write(writer, buf, width, height, color_type, Packbits),
+        }
+    }
+}
+
+fn write<W: Write + Seek, D: Compression>(
+    writer: W,
+    buf: &[u8],
+    width: u32,
+    height: u32,
+    color_type: image::ExtendedColorType,
+    compression: D,
+) -> image::ImageResult<()> {
+    let mut encoder = tiff::encoder::TiffEncoder::new(writer).map_err(encoding_error)?;
+
+    let result = match color_type {
+        image::ExtendedColorType::L8 => encoder
+            .write_image_with_compression::<colortype::Gray8, D>(width, height, compression, buf),
+        image::ExtendedColorType::Rgb8 => encoder
+            .write_image_with_compression::<colortype::RGB8, D>(width, height, compression, buf),
+        image::ExtendedColorType::Rgba8 => encoder
+            .write_image_with_compression::<colortype::RGBA8, D>(width, height, compression, buf),
+        image::ExtendedColorType::L16 => encoder
+            .write_image_with_compression::<colortype::Gray16, D>(
+                width,
+                height,
+                compression,
+                &u16_samples(buf),
+            ),
+        image::ExtendedColorType::Rgb16 => encoder
+            .write_image_with_compression::<colortype::RGB16, D>(
+                width,
+                height,
+                compression,
+                &u16_samples(buf),
+            ),
+        image::ExtendedColorType::Rgba16 => encoder
+            .write_image_with_compression::<colortype::RGBA16, D>(
+                width,
+                height,
+                compression,
+                &u16_samples(buf),
+            ),
+        _ => {
+            return Err(image::ImageError::Unsupported(
+                image::error::UnsupportedError::from_format_and_kind(
+                    image::error::ImageFormatHint::Exact(image::ImageFormat::Tiff),
+                    image::error::UnsupportedErrorKind::Color(color_type),
+                ),
+            ))
+        }
+    };
+
+    result.map_err(encoding_error)
+}
+
+// The sample buffers of `image` are stored in native endian byte order.
+fn u16_samples(buf: &[u8]) -> Vec<u16> {
+    buf.chunks_exact(2)
+        .map(|bytes| u16::from_ne_bytes([bytes[0], bytes[1]]))
+        .collect()
+}
+
+fn encoding_error(err: tiff::TiffError) -> image::ImageError {
+    image::ImageError::Encoding(image::error::EncodingError::new(
+        image::error::ImageFormatHint::Exact(image::ImageFormat::Tiff),
+        err,
+    ))
+}
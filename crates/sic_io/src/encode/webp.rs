@@ -0,0 +1,279 @@
+use crate::encode_settings::gif::RepeatAnimation;
+use crate::encode_settings::webp::WebpQuality;
+use sic_core::image;
+use std::io::{Seek, Write};
+
+/// Wrapper for the WebP encoder, which adds support for lossy encoding and animated images.
+/// The [`WebPEncoder`] of `image` can only encode static lossless images, so lossy and animated
+/// images are encoded with `libwebp` instead.
+///
+/// [`WebPEncoder`]: image::codecs::webp::WebPEncoder
+pub struct WebpEncoder<W> {
+    writer: W,
+    quality: Option<WebpQuality>,
+    repeat: RepeatAnimation,
+}
+
+impl<W: Write + Seek> WebpEncoder<W> {
+    /// Create a new WebP encoder. If no quality is given, the image will be encoded lossless.
+    pub fn new(writer: W, quality: Option<WebpQuality>, repeat: RepeatAnimation) -> Self {
+        Self {
+            writer,
+            quality,
+            repeat,
+        }
+    }
+
+    /// Encode the frames of an animated image.
+    ///
+    /// Expects each frame to cover the complete canvas, which is the case for frames produced by
+    /// the decoders of `image`.
+    pub fn encode_frames(mut self, frames: Vec<image::Frame>) -> image::ImageResult<()> {
+        let (width, height) = frames
+            .first()
+            .map(|frame| frame.buffer().dimensions())
+            .ok_or_else(|| encoding_error("an animated image requires at least one frame"))?;
+
+        if frames
+            .iter()
+            .any(|frame| frame.buffer().dimensions() != (width, height))
+        {
+            return Err(encoding_error(
+                "all frames of an animated image should have the same dimensions",
+            ));
+        }
+
+        let mut config = webp::WebPConfig::new()
+            .map_err(|_| encoding_error("unable to initialize the WebP encoder configuration"))?;
+        match self.quality {
+            Some(quality) => config.quality = f32::from(quality.as_u8()),
+            None => {
+                config.lossless = 1;
+                // Preserve the color values of transparent pixels.
+                config.exact = 1;
+            }
+        }
+
+        let loop_count = i32::try_from(self.repeat.num_plays()).unwrap_or(i32::MAX);
+        let memory =
+            encode_animation(&frames, width, height, &config, loop_count).map_err(|err| {
+                encoding_error(format!(
+                    "sic: Unable to encode animated WebP image ({})",
+                    err
+                ))
+            })?;
+
+        self.writer
+            .write_all(&memory)
+            .map_err(image::ImageError::IoError)
+    }
+}
+
+impl<W: Write + Seek> image::ImageEncoder for WebpEncoder<W> {
+    fn write_image(
+        mut self,
+        buf: &[u8],
+        width: u32,
+        height: u32,
+        color_type: image::ExtendedColorType,
+    ) -> image::ImageResult<()> {
+        let Some(quality) = self.quality else {
+            return image::codecs::webp::WebPEncoder::new_lossless(self.writer)
+                .write_image(buf, width, height, color_type);
+        };
+
+        let expanded;
+        let (buf, has_alpha) = match color_type {
+            image::ExtendedColorType::Rgb8 => (buf, false),
+            image::ExtendedColorType::Rgba8 => (buf, true),
+            image::ExtendedColorType::L8 => {
+                expanded = buf.iter().flat_map(|&l| [l, l, l]).collect::<Vec<_>>();
+                (expanded.as_slice(), false)
+            }
+            image::ExtendedColorType::La8 => {
+                expanded = buf
+                    .chunks_exact(2)
+                    .flat_map(|la| [la[0], la[0], la[0], la[1]])
+                    .collect::<Vec<_>>();
+                (expanded.as_slice(), true)
+            }
+            _ => {
+                return Err(image::ImageError::Unsupported(
+                    image::error::UnsupportedError::from_format_and_kind(
+                        image::error::ImageFormatHint::Exact(image::ImageFormat::WebP),
+                        image::error::UnsupportedErrorKind::Color(color_type),
+                    ),
+                ))
+            }
+        };
+
+        let encoder = if has_alpha {
+            webp::Encoder::from_rgba(buf, width, height)
+        } else {
+            webp::Encoder::from_rgb(buf, width, height)
+        };
+
+        let memory = encoder
+            .encode_simple(false, f32::from(quality.as_u8()))
+            .map_err(|err| {
+                encoding_error(format!(
+                    "sic: Unable to encode lossy WebP image ({:?})",
+                    err
+                ))
+            })?;
+
+        self.writer
+            .write_all(&memory)
+            .map_err(image::ImageError::IoError)
+    }
+}
+
+/// Encodes the frames with the animation encoder of the `webp` crate.
+///
+/// The `AnimEncoder` of the `webp` crate ends the animation at timestamp 0, after which `libwebp`
+/// makes up the duration of the last frame, so the duration of the last frame is corrected in
+/// the encoded image afterwards.
+fn encode_animation(
+    frames: &[image::Frame],
+    width: u32,
+    height: u32,
+    config: &webp::WebPConfig,
+    loop_count: i32,
+) -> Result<Vec<u8>, String> {
+    let mut encoder = webp::AnimEncoder::new(width, height, config);
+    encoder.set_loop_count(loop_count);
+
+    // Frames are shown at a timestamp, instead of for a duration.
+    let mut timestamp = 0u32;
+    for frame in frames {
+        let start =
+            i32::try_from(timestamp).map_err(|_| String::from("the animation is too long"))?;
+        encoder.add_frame(webp::AnimFrame::from_rgba(
+            frame.buffer().as_raw(),
+            width,
+            height,
+            start,
+        ));
+
+        timestamp = timestamp.saturating_add(delay_ms(frame.delay()));
+    }
+
+    let memory = encoder.try_encode().map_err(|err| format!("{:?}", err))?;
+
+    let mut bytes = memory.to_vec();
+    set_duration_of_last_frame(&mut bytes, timestamp)?;
+
+    Ok(bytes)
+}
+
+fn delay_ms(delay: image::Delay) -> u32 {
+    let (numerator, denominator) = delay.numer_denom_ms();
+    (f64::from(numerator) / f64::from(denominator.max(1))).round() as u32
+}
+
+/// Sets the duration of the last frame (`ANMF` chunk) of an encoded animation, such that the
+/// animation lasts `total` milliseconds. The encoder may have merged the last frames into one, if
+/// they're identical, so the duration of the last frame is what remains of `total` after the
+/// preceding frames.
+fn set_duration_of_last_frame(webp: &mut [u8], total: u32) -> Result<(), String> {
+    // The 'RIFF' header: fourcc, size and 'WEBP'.
+    const HEADER: usize = 12;
+    // The fourcc and size of a chunk.
+    const CHUNK_HEADER: usize = 8;
+    // The offset of the 24-bit duration within the payload of an 'ANMF' chunk, which follows the
+    // position and dimensions of the frame.
+    const DURATION: usize = 12;
+
+    let mut frames = Vec::new();
+    let mut offset = HEADER;
+
+    while let Some(header) = webp.get(offset..offset + CHUNK_HEADER) {
+        let size = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+
+        if &header[..4] == b"ANMF" {
+            frames.push(offset + CHUNK_HEADER + DURATION);
+        }
+
+        // chunks are padded to an even size
+        offset += CHUNK_HEADER + size + size % 2;
+    }
+
+    let read = |at: usize| u32::from_le_bytes([webp[at], webp[at + 1], webp[at + 2], 0]);
+
+    let (&last, preceding) = frames
+        .split_last()
+        .ok_or_else(|| String::from("the encoded animation has no frames"))?;
+
+    if webp.len() < last + 3 {
+        return Err(String::from("the encoded animation is truncated"));
+    }
+
+    let elapsed = preceding.iter().map(|&at| read(at)).sum::<u32>();
+    let duration = total.saturating_sub(elapsed).min(0xFF_FFFF);
+
+    webp[last..last + 3].copy_from_slice(&duration.to_le_bytes()[..3]);
+
+    Ok(())
+}
+
+fn encoding_error(err: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> image::ImageError {
+    image::ImageError::Encoding(image::error::EncodingError::new(
+        image::error::ImageFormatHint::Exact(image::ImageFormat::WebP),
+        err,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::AnimationDecoder;
+    use std::io::Cursor;
+    use std::time::Duration;
+
+    // Encodes frames of which the red channel has the given values, and decodes the delays of the
+    // frames of the encoded animation.
+    fn encoded_delays(frames: &[(u8, u32)]) -> Vec<u128> {
+        let frames = frames
+            .iter()
+            .map(|&(red, delay)| {
+                image::Frame::from_parts(
+                    image::RgbaImage::from_pixel(2, 2, image::Rgba([red, 0, 0, 255])),
+                    0,
+                    0,
+                    image::Delay::from_numer_denom_ms(delay, 1),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut memory = Cursor::new(Vec::new());
+        WebpEncoder::new(&mut memory, None, RepeatAnimation::Infinite)
+            .encode_frames(frames)
+            .unwrap();
+        memory.set_position(0);
+
+        image::codecs::webp::WebPDecoder::new(memory)
+            .unwrap()
+            .into_frames()
+            .collect_frames()
+            .unwrap()
+            .iter()
+            .map(|frame| Duration::from(frame.delay()).as_millis())
+            .collect()
+    }
+
+    #[test]
+    fn animation_keeps_delay_of_last_frame() {
+        let delays = encoded_delays(&[(0, 100), (100, 100), (200, 1000)]);
+
+        assert_eq!(delays, [100, 100, 1000]);
+    }
+
+    #[test]
+    fn animation_keeps_delay_of_identical_last_frames() {
+        let delays = encoded_delays(&[(0, 100), (100, 200), (100, 300)]);
+
+        // identical frames may be merged into one frame by the encoder
+        assert_eq!(delays.first(), Some(&100));
+        assert_eq!(delays.iter().sum::<u128>(), 600);
+    }
+}
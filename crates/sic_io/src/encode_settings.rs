@@ -1,14 +1,29 @@
+use crate::encode_settings::avif::{AvifQuality, AvifSpeed};
 use crate::encode_settings::gif::RepeatAnimation;
 use crate::encode_settings::jpeg::JpegQuality;
+use crate::encode_settings::png::{PngCompression, PngFilter};
+use crate::encode_settings::tiff::TiffCompression;
+use crate::encode_settings::webp::WebpQuality;
 use sic_core::image;
 
+pub mod avif;
 pub mod gif;
 pub mod jpeg;
+pub mod png;
+pub mod tiff;
+pub mod webp;
 
 pub struct EncodeSettings {
     pub pnm_sample_encoding: image::codecs::pnm::SampleEncoding,
     pub jpeg_quality: JpegQuality,
     pub repeat_animation: RepeatAnimation,
+    pub avif_quality: AvifQuality,
+    pub avif_speed: AvifSpeed,
+    pub png_compression: PngCompression,
+    pub png_filter: PngFilter,
+    pub tiff_compression: TiffCompression,
+    /// If set, WebP images are encoded lossy with the given quality, and lossless otherwise.
+    pub webp_quality: Option<WebpQuality>,
 }
 
 impl Default for EncodeSettings {
@@ -17,6 +32,12 @@ impl Default for EncodeSettings {
             pnm_sample_encoding: image::codecs::pnm::SampleEncoding::Binary,
             jpeg_quality: JpegQuality::default(),
             repeat_animation: RepeatAnimation::default(),
+            avif_quality: AvifQuality::default(),
+            avif_speed: AvifSpeed::default(),
+            png_compression: PngCompression::default(),
+            png_filter: PngFilter::default(),
+            tiff_compression: TiffCompression::default(),
+            webp_quality: None,
         }
     }
 }
@@ -0,0 +1,92 @@
+/// This struct ensures no invalid AVIF qualities can be stored.
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub struct AvifQuality(u8);
+
+impl Default for AvifQuality {
+    /// The default AVIF quality is `80`.
+    fn default() -> Self {
+        Self(80)
+    }
+}
+
+impl AvifQuality {
+    /// Returns an Ok result if the quality requested is between 1 and 100 (inclusive).
+    pub fn try_from(quality: u8) -> Result<Self, AvifQualityError> {
+        if (1u8..=100u8).contains(&quality) {
+            Ok(AvifQuality(quality))
+        } else {
+            Err(AvifQualityError { value: quality })
+        }
+    }
+
+    /// Return the valid quality value.
+    pub fn as_u8(self) -> u8 {
+        self.0
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("AVIF quality should range between 1 and 100 (inclusive), but was {}", .value)]
+pub struct AvifQualityError {
+    pub value: u8,
+}
+
+/// This struct ensures no invalid AVIF encoding speeds can be stored.
+/// A lower speed results in a smaller image, but takes longer to encode.
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub struct AvifSpeed(u8);
+
+impl Default for AvifSpeed {
+    /// The default AVIF encoding speed is `4`.
+    fn default() -> Self {
+        Self(4)
+    }
+}
+
+impl AvifSpeed {
+    /// Returns an Ok result if the speed requested is between 1 and 10 (inclusive).
+    pub fn try_from(speed: u8) -> Result<Self, AvifSpeedError> {
+        if (1u8..=10u8).contains(&speed) {
+            Ok(AvifSpeed(speed))
+        } else {
+            Err(AvifSpeedError { value: speed })
+        }
+    }
+
+    /// Return the valid speed value.
+    pub fn as_u8(self) -> u8 {
+        self.0
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("AVIF encoding speed should range between 1 and 10 (inclusive), but was {}", .value)]
+pub struct AvifSpeedError {
+    pub value: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parameterized::parameterized;
+
+    #[parameterized(quality = { 1, 80, 100 })]
+    fn avif_quality_in_range(quality: u8) {
+        assert_eq!(AvifQuality::try_from(quality).unwrap().as_u8(), quality);
+    }
+
+    #[parameterized(quality = { 0, 101, 255 })]
+    fn avif_quality_out_of_range(quality: u8) {
+        assert!(AvifQuality::try_from(quality).is_err());
+    }
+
+    #[parameterized(speed = { 1, 4, 10 })]
+    fn avif_speed_in_range(speed: u8) {
+        assert_eq!(AvifSpeed::try_from(speed).unwrap().as_u8(), speed);
+    }
+
+    #[parameterized(speed = { 0, 11, 255 })]
+    fn avif_speed_out_of_range(speed: u8) {
+        assert!(AvifSpeed::try_from(speed).is_err());
+    }
+}
@@ -21,6 +21,18 @@ impl RepeatAnimation {
     }
 }
 
+impl RepeatAnimation {
+    /// The number of times an animation is played, as used by the APNG and WebP formats,
+    /// where `0` means the animation repeats infinitely.
+    pub fn num_plays(self) -> u32 {
+        match self {
+            Self::Finite(v) => u32::from(v) + 1,
+            Self::Infinite => 0,
+            Self::Never => 1,
+        }
+    }
+}
+
 impl Default for RepeatAnimation {
     fn default() -> Self {
         Self::Infinite
@@ -36,3 +48,23 @@ impl From<RepeatAnimation> for image::codecs::gif::Repeat {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parameterized::parameterized;
+
+    #[parameterized(
+        repeat = {
+            RepeatAnimation::Infinite,
+            RepeatAnimation::Never,
+            RepeatAnimation::Finite(0),
+            RepeatAnimation::Finite(2),
+            RepeatAnimation::Finite(u16::MAX),
+        },
+        expected = { 0, 1, 1, 3, 65536 }
+    )]
+    fn num_plays(repeat: RepeatAnimation, expected: u32) {
+        assert_eq!(repeat.num_plays(), expected);
+    }
+}
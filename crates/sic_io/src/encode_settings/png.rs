@@ -0,0 +1,116 @@
+use crate::errors::{FormatError, SicIoError};
+use sic_core::image;
+
+/// The compression level used by the PNG encoder.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum PngCompression {
+    /// Fast, minimal compression.
+    #[default]
+    Fast,
+    /// A balance between encoding speed and compression.
+    Default,
+    /// High compression, but slow to encode.
+    Best,
+}
+
+impl PngCompression {
+    pub fn try_from_str(input: &str) -> Result<Self, SicIoError> {
+        match input {
+            "fast" => Ok(Self::Fast),
+            "default" => Ok(Self::Default),
+            "best" => Ok(Self::Best),
+            _ => Err(SicIoError::FormatError(
+                FormatError::PNGCompressionInvalidValue,
+            )),
+        }
+    }
+}
+
+impl From<PngCompression> for image::codecs::png::CompressionType {
+    fn from(value: PngCompression) -> Self {
+        match value {
+            PngCompression::Fast => image::codecs::png::CompressionType::Fast,
+            PngCompression::Default => image::codecs::png::CompressionType::Default,
+            PngCompression::Best => image::codecs::png::CompressionType::Best,
+        }
+    }
+}
+
+/// The filter applied to the image data by the PNG encoder, prior to compression.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum PngFilter {
+    NoFilter,
+    Sub,
+    Up,
+    Avg,
+    Paeth,
+    /// Select a filter per scanline.
+    #[default]
+    Adaptive,
+}
+
+impl PngFilter {
+    pub fn try_from_str(input: &str) -> Result<Self, SicIoError> {
+        match input {
+            "none" => Ok(Self::NoFilter),
+            "sub" => Ok(Self::Sub),
+            "up" => Ok(Self::Up),
+            "avg" => Ok(Self::Avg),
+            "paeth" => Ok(Self::Paeth),
+            "adaptive" => Ok(Self::Adaptive),
+            _ => Err(SicIoError::FormatError(FormatError::PNGFilterInvalidValue)),
+        }
+    }
+}
+
+impl From<PngFilter> for image::codecs::png::FilterType {
+    fn from(value: PngFilter) -> Self {
+        match value {
+            PngFilter::NoFilter => image::codecs::png::FilterType::NoFilter,
+            PngFilter::Sub => image::codecs::png::FilterType::Sub,
+            PngFilter::Up => image::codecs::png::FilterType::Up,
+            PngFilter::Avg => image::codecs::png::FilterType::Avg,
+            PngFilter::Paeth => image::codecs::png::FilterType::Paeth,
+            PngFilter::Adaptive => image::codecs::png::FilterType::Adaptive,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parameterized::parameterized;
+
+    #[parameterized(
+        input = { "fast", "default", "best" },
+        expected = { PngCompression::Fast, PngCompression::Default, PngCompression::Best }
+    )]
+    fn compression(input: &str, expected: PngCompression) {
+        assert_eq!(PngCompression::try_from_str(input).unwrap(), expected);
+    }
+
+    #[parameterized(input = { "", "Fast", "9" })]
+    fn compression_invalid(input: &str) {
+        assert!(PngCompression::try_from_str(input).is_err());
+    }
+
+    #[parameterized(
+        input = { "none", "sub", "up", "avg", "paeth", "adaptive" },
+        expected = {
+            PngFilter::NoFilter,
+            PngFilter::Sub,
+            PngFilter::Up,
+            PngFilter::Avg,
+            PngFilter::Paeth,
+            PngFilter::Adaptive,
+        }
+    )]
+    fn filter(input: &str, expected: PngFilter) {
+        assert_eq!(PngFilter::try_from_str(input).unwrap(), expected);
+    }
+
+    #[parameterized(input = { "", "nofilter", "average" })]
+    fn filter_invalid(input: &str) {
+        assert!(PngFilter::try_from_str(input).is_err());
+    }
+}
@@ -0,0 +1,49 @@
+use crate::errors::{FormatError, SicIoError};
+
+/// The compression algorithm used by the TIFF encoder.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TiffCompression {
+    #[default]
+    Uncompressed,
+    Lzw,
+    Deflate,
+    PackBits,
+}
+
+impl TiffCompression {
+    pub fn try_from_str(input: &str) -> Result<Self, SicIoError> {
+        match input {
+            "none" => Ok(Self::Uncompressed),
+            "lzw" => Ok(Self::Lzw),
+            "deflate" => Ok(Self::Deflate),
+            "packbits" => Ok(Self::PackBits),
+            _ => Err(SicIoError::FormatError(
+                FormatError::TIFFCompressionInvalidValue,
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parameterized::parameterized;
+
+    #[parameterized(
+        input = { "none", "lzw", "deflate", "packbits" },
+        expected = {
+            TiffCompression::Uncompressed,
+            TiffCompression::Lzw,
+            TiffCompression::Deflate,
+            TiffCompression::PackBits,
+        }
+    )]
+    fn compression(input: &str, expected: TiffCompression) {
+        assert_eq!(TiffCompression::try_from_str(input).unwrap(), expected);
+    }
+
+    #[parameterized(input = { "", "LZW", "jpeg" })]
+    fn compression_invalid(input: &str) {
+        assert!(TiffCompression::try_from_str(input).is_err());
+    }
+}
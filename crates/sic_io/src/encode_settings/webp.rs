@@ -0,0 +1,42 @@
+/// This struct ensures no invalid WebP qualities can be stored.
+/// The quality is only used for lossy encoding.
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub struct WebpQuality(u8);
+
+impl WebpQuality {
+    /// Returns an Ok result if the quality requested is between 0 and 100 (inclusive).
+    pub fn try_from(quality: u8) -> Result<Self, WebpQualityError> {
+        if quality <= 100 {
+            Ok(WebpQuality(quality))
+        } else {
+            Err(WebpQualityError { value: quality })
+        }
+    }
+
+    /// Return the valid quality value.
+    pub fn as_u8(self) -> u8 {
+        self.0
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("WebP quality should range between 0 and 100 (inclusive), but was {}", .value)]
+pub struct WebpQualityError {
+    pub value: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parameterized::parameterized;
+
+    #[parameterized(quality = { 0, 75, 100 })]
+    fn webp_quality_in_range(quality: u8) {
+        assert_eq!(WebpQuality::try_from(quality).unwrap().as_u8(), quality);
+    }
+
+    #[parameterized(quality = { 101, 255 })]
+    fn webp_quality_out_of_range(quality: u8) {
+        assert!(WebpQuality::try_from(quality).is_err());
+    }
+}
@@ -21,6 +21,9 @@ pub enum SicIoError {
     #[error(transparent)]
     FormatError(FormatError),
 
+    #[error(transparent)]
+    Metadata(#[from] MetadataError),
+
     #[error(
         "An input image should be given by providing a path using the input argument or by \
          piping an image to the stdin."
@@ -53,6 +56,26 @@ pub enum FormatError {
         "The GIF repeat value has to be either a positive integer < 65536, 'infinite' or 'never'"
     )]
     GIFRepeatInvalidValue,
+
+    #[error(transparent)]
+    AVIFQuality(encode_settings::avif::AvifQualityError),
+
+    #[error(transparent)]
+    AVIFSpeed(encode_settings::avif::AvifSpeedError),
+
+    #[error("The PNG compression value has to be one of 'fast', 'default' or 'best'")]
+    PNGCompressionInvalidValue,
+
+    #[error(
+        "The PNG filter value has to be one of 'none', 'sub', 'up', 'avg', 'paeth' or 'adaptive'"
+    )]
+    PNGFilterInvalidValue,
+
+    #[error("The TIFF compression value has to be one of 'none', 'lzw', 'deflate' or 'packbits'")]
+    TIFFCompressionInvalidValue,
+
+    #[error(transparent)]
+    WebPQuality(encode_settings::webp::WebpQualityError),
 }
 
 #[derive(Debug, Error)]
@@ -69,3 +92,21 @@ pub enum EncodingError {
     #[error("Unable to encode animated image using '{0:?}' image format")]
     AnimatedImageUnsupported(image::ImageFormat),
 }
+
+#[derive(Debug, Error)]
+pub enum MetadataError {
+    #[error("Unable to read or write the metadata of the image: {0}")]
+    Container(#[from] img_parts::Error),
+
+    #[error("Unable to rewrite the Exif metadata of the image: {0}")]
+    Exif(#[from] ::exif::Error),
+
+    #[error("The ICC profile is too large to be embedded in a JPEG image")]
+    IccProfileTooLarge,
+
+    #[error("Unknown metadata retention mode '{0}'; supported modes are 'keep-all', 'keep-icc', 'strip-gps' and 'strip-all'")]
+    UnknownRetention(String),
+
+    #[error("Unable to remove the location information from the XMP metadata of the image, since it is malformed")]
+    XmpGps,
+}
@@ -0,0 +1,79 @@
+//! Properties of an image, which can be read without processing the image.
+//!
+//! See [`SicImageDecoder::inspect`](crate::decode::SicImageDecoder::inspect).
+
+use sic_core::image;
+use std::time::Duration;
+
+/// The properties of an (animated) image, as stored in the input image.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImageInfo {
+    pub(crate) format: image::ImageFormat,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) color_type: image::ColorType,
+    pub(crate) bit_depth: u8,
+    pub(crate) frame_count: usize,
+    pub(crate) frame_delays: Vec<Duration>,
+    pub(crate) loop_count: Option<LoopCount>,
+    pub(crate) has_exif: bool,
+    pub(crate) has_xmp: bool,
+    pub(crate) has_icc_profile: bool,
+}
+
+impl ImageInfo {
+    pub fn format(&self) -> image::ImageFormat {
+        self.format
+    }
+
+    /// Dimensions of the image; for animated images, the dimensions of the canvas.
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// The color type into which the image is decoded.
+    pub fn color_type(&self) -> image::ColorType {
+        self.color_type
+    }
+
+    /// Number of bits per channel, as stored in the input image.
+    pub fn bit_depth(&self) -> u8 {
+        self.bit_depth
+    }
+
+    /// Number of frames; 1 for still images.
+    pub fn frame_count(&self) -> usize {
+        self.frame_count
+    }
+
+    /// Delay of each frame of an animated image; empty for still images.
+    pub fn frame_delays(&self) -> &[Duration] {
+        &self.frame_delays
+    }
+
+    /// How often an animated image is played, if known; `None` for still images.
+    pub fn loop_count(&self) -> Option<LoopCount> {
+        self.loop_count
+    }
+
+    pub fn has_exif(&self) -> bool {
+        self.has_exif
+    }
+
+    pub fn has_xmp(&self) -> bool {
+        self.has_xmp
+    }
+
+    pub fn has_icc_profile(&self) -> bool {
+        self.has_icc_profile
+    }
+}
+
+/// How often an animated image is played.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LoopCount {
+    Infinite,
+    /// The animation is played the given number of times, i.e. 1 means it is played once, and
+    /// not repeated.
+    Finite(u32),
+}
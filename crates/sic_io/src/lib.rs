@@ -4,4 +4,6 @@ pub mod decode;
 pub mod encode;
 pub mod encode_settings;
 pub mod errors;
+pub mod info;
+pub mod metadata;
 pub mod preprocessor;
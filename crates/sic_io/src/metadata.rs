@@ -0,0 +1,345 @@
+//! Reading, filtering and embedding of image metadata, such as Exif and XMP metadata and
+//! embedded ICC color profiles.
+//!
+//! Metadata is read from the raw bytes of the input image, and embedded into the raw bytes of
+//! an encoded output image. Embedding is supported for JPEG, PNG and WebP output images.
+
+use crate::errors::{MetadataError, SicIoError};
+use sic_core::{image, ImageMetadata};
+use std::io::Cursor;
+
+mod exif;
+mod jpeg;
+mod png;
+mod webp;
+mod xmp;
+
+/// Which metadata of the input image should be retained in the output image.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MetadataRetention {
+    /// Keep the Exif and XMP metadata, and the ICC color profile.
+    #[default]
+    KeepAll,
+    /// Keep only the ICC color profile.
+    KeepIcc,
+    /// Keep all metadata, except for location (GPS) information.
+    StripGps,
+    /// Keep no metadata at all.
+    StripAll,
+}
+
+impl MetadataRetention {
+    pub fn try_from_str(input: &str) -> Result<Self, SicIoError> {
+        match input {
+            "keep-all" => Ok(Self::KeepAll),
+            "keep-icc" => Ok(Self::KeepIcc),
+            "strip-gps" => Ok(Self::StripGps),
+            "strip-all" => Ok(Self::StripAll),
+            elsy => Err(SicIoError::Metadata(MetadataError::UnknownRetention(
+                elsy.to_string(),
+            ))),
+        }
+    }
+
+    /// Filter the given metadata, so only the metadata which should be retained remains.
+    ///
+    /// The Orientation tag of the Exif metadata is updated to match the orientation of the
+    /// metadata, since the orientation may have been applied to the pixels already.
+    /// Returns an error if the Exif metadata needs to be changed but can't be parsed, or if the
+    /// location information can't be removed from the XMP metadata, instead of discarding the
+    /// other metadata along with it.
+    pub fn retain(self, metadata: ImageMetadata) -> Result<ImageMetadata, SicIoError> {
+        let mut retained = ImageMetadata::default();
+        retained.set_orientation(metadata.orientation());
+
+        match self {
+            Self::KeepAll | Self::StripGps => {
+                let strip_gps = self == Self::StripGps;
+
+                let exif = metadata
+                    .exif()
+                    .map(|raw| exif::rewrite(raw, metadata.orientation(), strip_gps))
+                    .transpose()?;
+                let xmp = match metadata.xmp() {
+                    Some(packet) if strip_gps => {
+                        Some(xmp::strip_gps(packet).ok_or(MetadataError::XmpGps)?)
+                    }
+                    packet => packet.map(<[u8]>::to_vec),
+                };
+
+                retained.set_icc_profile(metadata.icc_profile().map(<[u8]>::to_vec));
+                retained.set_exif(exif);
+                retained.set_xmp(xmp);
+            }
+            Self::KeepIcc => {
+                retained.set_icc_profile(metadata.icc_profile().map(<[u8]>::to_vec));
+            }
+            Self::StripAll => {}
+        }
+
+        Ok(retained)
+    }
+}
+
+/// Read the metadata from the raw bytes of an image.
+///
+/// Exif, XMP and ICC profiles are read from JPEG, PNG and WebP images. For other formats, only
+/// the ICC profile is read, if supported by the decoder of the format. The orientation is read
+/// from the Exif metadata of JPEG, PNG, WebP, TIFF and HEIF images.
+///
+/// Metadata which can't be read is ignored, since it isn't required to decode the image.
+pub fn read_metadata(buffer: &[u8]) -> ImageMetadata {
+    let metadata = match image::guess_format(buffer) {
+        Ok(image::ImageFormat::Jpeg) => jpeg::read(buffer).ok(),
+        Ok(image::ImageFormat::Png) => png::read(buffer).ok(),
+        Ok(image::ImageFormat::WebP) => webp::read(buffer).ok(),
+        _ => read_icc_profile(buffer).map(|profile| {
+            let mut metadata = ImageMetadata::default();
+            metadata.set_icc_profile(Some(profile));
+            metadata
+        }),
+    };
+
+    let mut metadata = metadata.unwrap_or_default();
+    metadata.set_orientation(exif::read_orientation(buffer));
+    metadata
+}
+
+// Read the ICC profile using the decoder of the image format, if supported.
+fn read_icc_profile(buffer: &[u8]) -> Option<Vec<u8>> {
+    use image::ImageDecoder;
+
+    image::ImageReader::new(Cursor::new(buffer))
+        .with_guessed_format()
+        .ok()?
+        .into_decoder()
+        .ok()?
+        .icc_profile()
+        .ok()
+        .flatten()
+}
+
+/// An encoded image, into which the metadata has been embedded, if its format supports it.
+#[derive(Debug)]
+pub struct EmbeddedImage {
+    pub encoded: Vec<u8>,
+    /// Whether metadata was discarded, because the format does not support embedding it.
+    pub metadata_discarded: bool,
+}
+
+/// Embed the metadata into the raw bytes of an encoded image of the given format.
+///
+/// If the metadata is empty, or if the format does not support embedding metadata, the encoded
+/// image is returned as is. In the latter case, the metadata is reported as discarded, so the
+/// caller can decide whether to warn about it.
+pub fn embed_metadata(
+    encoded: Vec<u8>,
+    format: image::ImageFormat,
+    metadata: &ImageMetadata,
+) -> Result<EmbeddedImage, SicIoError> {
+    let embedded = |encoded| EmbeddedImage {
+        encoded,
+        metadata_discarded: false,
+    };
+
+    if metadata.is_empty() {
+        return Ok(embedded(encoded));
+    }
+
+    match format {
+        image::ImageFormat::Jpeg => jpeg::embed(encoded, metadata).map(embedded),
+        image::ImageFormat::Png => png::embed(encoded, metadata).map(embedded),
+        image::ImageFormat::WebP => webp::embed(encoded, metadata).map(embedded),
+        _ => Ok(EmbeddedImage {
+            encoded,
+            metadata_discarded: true,
+        }),
+    }
+    .map_err(SicIoError::Metadata)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parameterized::parameterized;
+    use sic_core::image::metadata::Orientation;
+    use sic_testing::*;
+
+    const EXIF_GPS: &str = "rainbow_8x6_exif_gps.jpg";
+
+    fn read_test_metadata(path: &str) -> ImageMetadata {
+        let buffer = std::fs::read(setup_test_image(path)).unwrap();
+        read_metadata(&buffer)
+    }
+
+    fn exif_fields(metadata: &ImageMetadata) -> Vec<::exif::Tag> {
+        let exif = ::exif::Reader::new()
+            .read_raw(metadata.exif().unwrap().to_vec())
+            .unwrap();
+
+        exif.fields().map(|field| field.tag).collect()
+    }
+
+    fn xmp(metadata: &ImageMetadata) -> &str {
+        std::str::from_utf8(metadata.xmp().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn read_jpeg_metadata() {
+        let metadata = read_test_metadata(EXIF_GPS);
+
+        assert_eq!(metadata.orientation(), Orientation::Rotate90);
+        assert!(exif_fields(&metadata).contains(&::exif::Tag::GPSLatitudeRef));
+        assert!(xmp(&metadata).contains("exif:GPSLatitude"));
+        assert!(metadata.icc_profile().is_none());
+    }
+
+    #[test]
+    fn read_without_metadata() {
+        let metadata = read_test_metadata("rainbow_8x6.bmp");
+
+        assert!(metadata.is_empty());
+    }
+
+    #[parameterized(
+        retention = {
+            MetadataRetention::KeepAll,
+            MetadataRetention::KeepIcc,
+            MetadataRetention::StripGps,
+            MetadataRetention::StripAll,
+        },
+        expected = {
+            (true, true, true),
+            (true, false, false),
+            (true, true, true),
+            (false, false, false),
+        }
+    )]
+    fn retain(retention: MetadataRetention, expected: (bool, bool, bool)) {
+        let mut metadata = read_test_metadata(EXIF_GPS);
+        metadata.set_icc_profile(Some(vec![0; 16]));
+
+        let retained = retention.retain(metadata).unwrap();
+        let present = (
+            retained.icc_profile().is_some(),
+            retained.exif().is_some(),
+            retained.xmp().is_some(),
+        );
+
+        assert_eq!(present, expected);
+    }
+
+    #[test]
+    fn retain_strip_gps() {
+        let retained = MetadataRetention::StripGps
+            .retain(read_test_metadata(EXIF_GPS))
+            .unwrap();
+        let fields = exif_fields(&retained);
+
+        assert!(fields.contains(&::exif::Tag::Copyright));
+        assert!(fields.contains(&::exif::Tag::Orientation));
+        assert!(!fields.contains(&::exif::Tag::GPSLatitudeRef));
+        assert!(!fields.contains(&::exif::Tag::GPSVersionID));
+        assert!(!xmp(&retained).contains("GPS"));
+        assert!(xmp(&retained).contains("dc:format"));
+    }
+
+    #[test]
+    fn retain_keep_all_is_unchanged() {
+        let metadata = read_test_metadata(EXIF_GPS);
+        let retained = MetadataRetention::KeepAll.retain(metadata.clone()).unwrap();
+
+        assert_eq!(retained, metadata);
+    }
+
+    #[test]
+    fn retain_updates_applied_orientation() {
+        let mut metadata = read_test_metadata(EXIF_GPS);
+        metadata.set_orientation(Orientation::NoTransforms);
+
+        let retained = MetadataRetention::KeepAll.retain(metadata).unwrap();
+        let exif = ::exif::Reader::new()
+            .read_raw(retained.exif().unwrap().to_vec())
+            .unwrap();
+        let orientation = exif
+            .get_field(::exif::Tag::Orientation, ::exif::In::PRIMARY)
+            .and_then(|field| field.value.get_uint(0));
+
+        assert_eq!(orientation, Some(1));
+        assert!(exif_fields(&retained).contains(&::exif::Tag::GPSLatitudeRef));
+    }
+
+    #[test]
+    fn retain_malformed_exif() {
+        let mut metadata = ImageMetadata::default();
+        metadata.set_exif(Some(b"not exif".to_vec()));
+
+        // nothing needs to be changed, so the Exif metadata is kept as is
+        let retained = MetadataRetention::KeepAll.retain(metadata.clone()).unwrap();
+        assert_eq!(retained.exif(), Some(&b"not exif"[..]));
+
+        // the absence of location information can't be verified
+        assert!(MetadataRetention::StripGps
+            .retain(metadata.clone())
+            .is_err());
+
+        metadata.set_orientation(Orientation::Rotate90);
+        assert!(MetadataRetention::KeepAll.retain(metadata).is_err());
+    }
+
+    #[test]
+    fn retain_malformed_xmp() {
+        let mut metadata = ImageMetadata::default();
+        metadata.set_xmp(Some(vec![0xFF, 0xFE]));
+
+        assert!(MetadataRetention::KeepAll.retain(metadata.clone()).is_ok());
+        assert!(MetadataRetention::StripGps.retain(metadata).is_err());
+    }
+
+    #[test]
+    fn unknown_retention() {
+        assert!(MetadataRetention::try_from_str("keep-some").is_err());
+    }
+
+    #[parameterized(
+        format = {
+            image::ImageFormat::Jpeg,
+            image::ImageFormat::Png,
+            image::ImageFormat::WebP,
+        }
+    )]
+    fn embed_round_trip(format: image::ImageFormat) {
+        let mut metadata = read_test_metadata(EXIF_GPS);
+        // Large enough to be split over multiple segments in a JPEG image.
+        metadata.set_icc_profile(Some((0..70_000).map(|i| i as u8).collect()));
+
+        let image = image::open(setup_test_image("rainbow_8x6.bmp")).unwrap();
+        let mut encoded = Vec::new();
+        image
+            .to_rgb8()
+            .write_to(&mut Cursor::new(&mut encoded), format)
+            .unwrap();
+
+        let embedded = embed_metadata(encoded, format, &metadata).unwrap();
+        assert!(!embedded.metadata_discarded);
+
+        let embedded = embedded.encoded;
+        let read = read_metadata(&embedded);
+
+        assert_eq!(read, metadata);
+
+        let decoded = image::load_from_memory_with_format(&embedded, format).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (8, 6));
+    }
+
+    #[test]
+    fn embed_unsupported_format_discards_metadata() {
+        let encoded = std::fs::read(setup_test_image("rainbow_8x6.bmp")).unwrap();
+        let metadata = read_test_metadata(EXIF_GPS);
+
+        let embedded = embed_metadata(encoded.clone(), image::ImageFormat::Bmp, &metadata).unwrap();
+
+        assert!(embedded.metadata_discarded);
+        assert_eq!(embedded.encoded, encoded);
+    }
+}
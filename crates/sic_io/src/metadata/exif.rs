@@ -0,0 +1,75 @@
+use crate::errors::MetadataError;
+use sic_core::image::metadata::Orientation;
+use std::io::Cursor;
+
+/// Read the orientation from the Exif metadata of an image container.
+///
+/// The Exif Orientation tag is read from JPEG, TIFF, WebP, HEIF and PNG (eXIf chunk) images.
+/// If the image has no (valid) orientation tag, [`Orientation::NoTransforms`] is returned.
+pub(crate) fn read_orientation(buffer: &[u8]) -> Orientation {
+    exif::Reader::new()
+        .read_from_container(&mut Cursor::new(buffer))
+        .ok()
+        .and_then(|exif| orientation_tag(&exif))
+        .and_then(|value| Orientation::from_exif(u8::try_from(value).ok()?))
+        .unwrap_or(Orientation::NoTransforms)
+}
+
+/// Rewrite raw Exif metadata, such that its Orientation tag matches the given orientation, and
+/// optionally without its GPS fields.
+///
+/// If nothing needs to be changed, the raw Exif metadata is returned as is. Otherwise, only the
+/// fields of the primary image are retained; the thumbnail is dropped. Metadata which can't be
+/// parsed is only returned as is if it doesn't need to be changed; it can't contain a (valid)
+/// Orientation tag, since the orientation is read from the same metadata.
+pub(crate) fn rewrite(
+    raw: &[u8],
+    orientation: Orientation,
+    strip_gps: bool,
+) -> Result<Vec<u8>, MetadataError> {
+    let exif = match exif::Reader::new().read_raw(raw.to_vec()) {
+        Ok(exif) => exif,
+        Err(_) if !strip_gps && orientation == Orientation::NoTransforms => return Ok(raw.to_vec()),
+        Err(err) => return Err(MetadataError::Exif(err)),
+    };
+
+    let current = orientation_tag(&exif);
+    let expected = u32::from(orientation.to_exif());
+    let orientation_changed = current.unwrap_or(1) != expected;
+    let gps_stripped = strip_gps
+        && exif
+            .fields()
+            .any(|field| field.tag.context() == exif::Context::Gps);
+
+    if !(orientation_changed || gps_stripped) {
+        return Ok(raw.to_vec());
+    }
+
+    let orientation_field = exif::Field {
+        tag: exif::Tag::Orientation,
+        ifd_num: exif::In::PRIMARY,
+        value: exif::Value::Short(vec![orientation.to_exif().into()]),
+    };
+
+    let mut writer = exif::experimental::Writer::new();
+
+    exif.fields()
+        .filter(|field| field.ifd_num == exif::In::PRIMARY)
+        .filter(|field| field.tag != exif::Tag::Orientation)
+        .filter(|field| !(strip_gps && field.tag.context() == exif::Context::Gps))
+        .for_each(|field| writer.push_field(field));
+
+    if current.is_some() || orientation_changed {
+        writer.push_field(&orientation_field);
+    }
+
+    let mut buffer = Cursor::new(Vec::new());
+    writer.write(&mut buffer, exif.little_endian())?;
+
+    Ok(buffer.into_inner())
+}
+
+fn orientation_tag(exif: &exif::Exif) -> Option<u32> {
+    exif.get_field(exif::Tag::Orientation, exif::In::PRIMARY)
+        .and_then(|field| field.value.get_uint(0))
+}
@@ -0,0 +1,104 @@
+use crate::errors::MetadataError;
+use img_parts::jpeg::{markers, Jpeg, JpegSegment};
+use img_parts::Bytes;
+use sic_core::ImageMetadata;
+
+const EXIF_PREFIX: &[u8] = b"Exif\0\0";
+const XMP_PREFIX: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+const ICC_PREFIX: &[u8] = b"ICC_PROFILE\0";
+
+// The maximum size of a segment is 65535 bytes, of which 2 bytes are used by the segment
+// length, and 14 by the ICC prefix, sequence number and chunk count.
+const ICC_CHUNK_SIZE: usize = 65535 - 2 - ICC_PREFIX.len() - 2;
+
+/// Read the Exif and XMP metadata, and the ICC profile from a JPEG image.
+pub(crate) fn read(buffer: &[u8]) -> Result<ImageMetadata, MetadataError> {
+    let jpeg = Jpeg::from_bytes(Bytes::copy_from_slice(buffer))?;
+    let mut metadata = ImageMetadata::default();
+
+    let app1 = jpeg.segments_by_marker(markers::APP1);
+
+    for segment in app1 {
+        let contents = segment.contents();
+
+        if let Some(exif) = contents.strip_prefix(EXIF_PREFIX) {
+            metadata.set_exif(Some(exif.to_vec()));
+        } else if let Some(xmp) = contents.strip_prefix(XMP_PREFIX) {
+            metadata.set_xmp(Some(xmp.to_vec()));
+        }
+    }
+
+    // The ICC profile may be split over multiple segments, which are numbered from 1.
+    let mut icc_chunks = jpeg
+        .segments_by_marker(markers::APP2)
+        .filter_map(|segment| segment.contents().strip_prefix(ICC_PREFIX))
+        .filter(|chunk| chunk.len() > 2)
+        .map(|chunk| (chunk[0], &chunk[2..]))
+        .collect::<Vec<_>>();
+
+    if !icc_chunks.is_empty() {
+        icc_chunks.sort_by_key(|(sequence_number, _)| *sequence_number);
+        metadata.set_icc_profile(Some(
+            icc_chunks
+                .into_iter()
+                .flat_map(|(_, chunk)| chunk)
+                .copied()
+                .collect(),
+        ));
+    }
+
+    Ok(metadata)
+}
+
+/// Embed the Exif and XMP metadata, and the ICC profile into an encoded JPEG image.
+///
+/// Any such metadata already present in the encoded image is replaced.
+pub(crate) fn embed(encoded: Vec<u8>, metadata: &ImageMetadata) -> Result<Vec<u8>, MetadataError> {
+    let mut jpeg = Jpeg::from_bytes(Bytes::from(encoded))?;
+    let segments = jpeg.segments_mut();
+
+    segments.retain(|segment| !is_metadata_segment(segment));
+
+    let mut metadata_segments = Vec::new();
+
+    if let Some(exif) = metadata.exif() {
+        metadata_segments.push(segment_with_prefix(markers::APP1, EXIF_PREFIX, exif));
+    }
+
+    if let Some(xmp) = metadata.xmp() {
+        metadata_segments.push(segment_with_prefix(markers::APP1, XMP_PREFIX, xmp));
+    }
+
+    if let Some(profile) = metadata.icc_profile() {
+        let chunks = profile.chunks(ICC_CHUNK_SIZE);
+        let count = u8::try_from(chunks.len()).map_err(|_| MetadataError::IccProfileTooLarge)?;
+
+        for (sequence_number, chunk) in (1..=count).zip(chunks) {
+            let prefix = [ICC_PREFIX, &[sequence_number, count]].concat();
+            metadata_segments.push(segment_with_prefix(markers::APP2, &prefix, chunk));
+        }
+    }
+
+    // The Exif segment should directly follow the JFIF (APP0) segment, if present.
+    let position = segments
+        .iter()
+        .take_while(|segment| segment.marker() == markers::APP0)
+        .count();
+    segments.splice(position..position, metadata_segments);
+
+    Ok(jpeg.encoder().bytes().to_vec())
+}
+
+fn is_metadata_segment(segment: &JpegSegment) -> bool {
+    let contents = segment.contents();
+
+    match segment.marker() {
+        markers::APP1 => contents.starts_with(EXIF_PREFIX) || contents.starts_with(XMP_PREFIX),
+        markers::APP2 => contents.starts_with(ICC_PREFIX),
+        _ => false,
+    }
+}
+
+fn segment_with_prefix(marker: u8, prefix: &[u8], data: &[u8]) -> JpegSegment {
+    JpegSegment::new_with_contents(marker, Bytes::from([prefix, data].concat()))
+}
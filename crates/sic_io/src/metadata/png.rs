@@ -0,0 +1,68 @@
+use crate::errors::MetadataError;
+use img_parts::png::{Png, PngChunk};
+use img_parts::{Bytes, ImageICC};
+use sic_core::ImageMetadata;
+
+const CHUNK_IHDR: [u8; 4] = *b"IHDR";
+const CHUNK_ICCP: [u8; 4] = *b"iCCP";
+const CHUNK_EXIF: [u8; 4] = *b"eXIf";
+const CHUNK_ITXT: [u8; 4] = *b"iTXt";
+
+// An uncompressed international text chunk, with the keyword reserved for XMP, and without
+// language tag and translated keyword.
+const XMP_PREFIX: &[u8] = b"XML:com.adobe.xmp\0\0\0\0\0";
+
+/// Read the Exif and XMP metadata, and the ICC profile from a PNG image.
+pub(crate) fn read(buffer: &[u8]) -> Result<ImageMetadata, MetadataError> {
+    let png = Png::from_bytes(Bytes::copy_from_slice(buffer))?;
+    let mut metadata = ImageMetadata::default();
+
+    metadata.set_icc_profile(png.icc_profile().map(|profile| profile.to_vec()));
+    metadata.set_exif(
+        png.chunk_by_type(CHUNK_EXIF)
+            .map(|chunk| chunk.contents().to_vec()),
+    );
+    metadata.set_xmp(
+        png.chunks_by_type(CHUNK_ITXT)
+            .find_map(|chunk| chunk.contents().strip_prefix(XMP_PREFIX))
+            .map(<[u8]>::to_vec),
+    );
+
+    Ok(metadata)
+}
+
+/// Embed the Exif and XMP metadata, and the ICC profile into an encoded PNG image.
+///
+/// Any such metadata already present in the encoded image is replaced.
+pub(crate) fn embed(encoded: Vec<u8>, metadata: &ImageMetadata) -> Result<Vec<u8>, MetadataError> {
+    let mut png = Png::from_bytes(Bytes::from(encoded))?;
+
+    // Inserts the (compressed) iCCP chunk directly after the IHDR chunk.
+    png.set_icc_profile(metadata.icc_profile().map(Bytes::copy_from_slice));
+
+    let chunks = png.chunks_mut();
+    chunks.retain(|chunk| {
+        chunk.kind() != CHUNK_EXIF
+            && !(chunk.kind() == CHUNK_ITXT && chunk.contents().starts_with(XMP_PREFIX))
+    });
+
+    let mut metadata_chunks = Vec::new();
+
+    if let Some(exif) = metadata.exif() {
+        metadata_chunks.push(PngChunk::new(CHUNK_EXIF, Bytes::copy_from_slice(exif)));
+    }
+
+    if let Some(xmp) = metadata.xmp() {
+        let contents = [XMP_PREFIX, xmp].concat();
+        metadata_chunks.push(PngChunk::new(CHUNK_ITXT, Bytes::from(contents)));
+    }
+
+    // The chunks are placed directly after the IHDR and iCCP chunks, before the image data.
+    let position = chunks
+        .iter()
+        .position(|chunk| chunk.kind() != CHUNK_IHDR && chunk.kind() != CHUNK_ICCP)
+        .unwrap_or(chunks.len());
+    chunks.splice(position..position, metadata_chunks);
+
+    Ok(png.encoder().bytes().to_vec())
+}
@@ -0,0 +1,109 @@
+use crate::errors::MetadataError;
+use img_parts::riff::{RiffChunk, RiffContent};
+use img_parts::webp::{
+    WebP, CHUNK_ALPH, CHUNK_ANIM, CHUNK_EXIF, CHUNK_ICCP, CHUNK_VP8L, CHUNK_VP8X, CHUNK_XMP,
+};
+use img_parts::Bytes;
+use sic_core::ImageMetadata;
+
+// Some writers prefix the Exif metadata with the same header as used in JPEG images.
+const EXIF_PREFIX: &[u8] = b"Exif\0\0";
+
+// Flags of the extended file format (VP8X) chunk.
+const FLAG_ICC: u8 = 0x20;
+const FLAG_ALPHA: u8 = 0x10;
+const FLAG_EXIF: u8 = 0x08;
+const FLAG_XMP: u8 = 0x04;
+const FLAG_ANIMATION: u8 = 0x02;
+
+// Bit of the lossless (VP8L) bitstream header which signals that the alpha channel is used.
+const VP8L_ALPHA_IS_USED: u32 = 1 << 28;
+
+/// Read the Exif and XMP metadata, and the ICC profile from a WebP image.
+pub(crate) fn read(buffer: &[u8]) -> Result<ImageMetadata, MetadataError> {
+    let webp = WebP::from_bytes(Bytes::copy_from_slice(buffer))?;
+    let mut metadata = ImageMetadata::default();
+
+    let data = |id| {
+        webp.chunk_by_id(id)
+            .and_then(|chunk| chunk.content().data())
+            .map(|data| data.to_vec())
+    };
+
+    metadata.set_icc_profile(data(CHUNK_ICCP));
+    metadata.set_exif(
+        data(CHUNK_EXIF).map(|exif| match exif.strip_prefix(EXIF_PREFIX) {
+            Some(stripped) => stripped.to_vec(),
+            None => exif,
+        }),
+    );
+    metadata.set_xmp(data(CHUNK_XMP));
+
+    Ok(metadata)
+}
+
+/// Embed the Exif and XMP metadata, and the ICC profile into an encoded WebP image.
+///
+/// Any such metadata already present in the encoded image is replaced. Since metadata can only
+/// be stored in the extended file format, the VP8X chunk is (re)created.
+pub(crate) fn embed(encoded: Vec<u8>, metadata: &ImageMetadata) -> Result<Vec<u8>, MetadataError> {
+    let mut webp = WebP::from_bytes(Bytes::from(encoded))?;
+    let (width, height) = webp.dimensions().ok_or(img_parts::Error::Truncated)?;
+
+    let mut flags = match webp.chunk_by_id(CHUNK_VP8X) {
+        Some(vp8x) => vp8x
+            .content()
+            .data()
+            .and_then(|data| data.first().copied())
+            .unwrap_or_default(),
+        None => 0,
+    } & (FLAG_ALPHA | FLAG_ANIMATION);
+
+    if webp.has_chunk(CHUNK_ALPH) || has_lossless_alpha(&webp) {
+        flags |= FLAG_ALPHA;
+    }
+
+    if webp.has_chunk(CHUNK_ANIM) {
+        flags |= FLAG_ANIMATION;
+    }
+
+    let chunk =
+        |id, data: &[u8]| RiffChunk::new(id, RiffContent::Data(Bytes::copy_from_slice(data)));
+
+    let chunks = webp.chunks_mut();
+    chunks.retain(|chunk| ![CHUNK_VP8X, CHUNK_ICCP, CHUNK_EXIF, CHUNK_XMP].contains(&chunk.id()));
+
+    // The ICC profile must precede the image data, while the Exif and XMP metadata follow it.
+    if let Some(profile) = metadata.icc_profile() {
+        flags |= FLAG_ICC;
+        chunks.insert(0, chunk(CHUNK_ICCP, profile));
+    }
+
+    if let Some(exif) = metadata.exif() {
+        flags |= FLAG_EXIF;
+        chunks.push(chunk(CHUNK_EXIF, exif));
+    }
+
+    if let Some(xmp) = metadata.xmp() {
+        flags |= FLAG_XMP;
+        chunks.push(chunk(CHUNK_XMP, xmp));
+    }
+
+    let mut vp8x = vec![flags, 0, 0, 0];
+    vp8x.extend_from_slice(&(width - 1).to_le_bytes()[..3]);
+    vp8x.extend_from_slice(&(height - 1).to_le_bytes()[..3]);
+    chunks.insert(0, chunk(CHUNK_VP8X, &vp8x));
+
+    Ok(webp.encoder().bytes().to_vec())
+}
+
+fn has_lossless_alpha(webp: &WebP) -> bool {
+    webp.chunk_by_id(CHUNK_VP8L)
+        .and_then(|chunk| chunk.content().data())
+        .and_then(|data| data.get(1..5))
+        .map(|header| {
+            let header = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+            header & VP8L_ALPHA_IS_USED != 0
+        })
+        .unwrap_or(false)
+}
@@ -0,0 +1,242 @@
+/// The namespaces of the Exif schemas, of which the properties whose names start with `GPS`
+/// contain location information.
+const EXIF_NAMESPACES: [&str; 2] = ["http://ns.adobe.com/exif/1.0/", "http://cipa.jp/exif/1.0/"];
+
+/// Remove the location (GPS) properties from an XMP packet.
+///
+/// The prefixes of the properties are resolved from the `xmlns` declarations of the Exif
+/// namespaces, so the properties are found whichever prefix the packet uses. Both properties
+/// which are serialized as attribute (e.g. `exif:GPSLatitude="..."`) and properties which are
+/// serialized as element (e.g. `<exif:GPSLatitude>...</exif:GPSLatitude>`) are removed. Returns
+/// `None` if the packet is not valid UTF-8, or is otherwise malformed.
+pub(crate) fn strip_gps(packet: &[u8]) -> Option<Vec<u8>> {
+    let mut xmp = std::str::from_utf8(packet).ok()?.to_string();
+
+    for prefix in exif_prefixes(&xmp)? {
+        strip_properties(&mut xmp, &prefix)?;
+    }
+
+    Some(xmp.into_bytes())
+}
+
+// The prefixes which are bound to an Exif namespace. The empty prefix is used when an Exif
+// namespace is the default namespace of an element.
+fn exif_prefixes(xmp: &str) -> Option<Vec<String>> {
+    let mut prefixes = Vec::new();
+    let mut from = 0;
+
+    while let Some(offset) = xmp[from..].find("xmlns") {
+        let position = from + offset;
+        from = position + "xmlns".len();
+
+        if !xmp[..position].ends_with(char::is_whitespace) {
+            continue;
+        }
+
+        let rest = &xmp[from..];
+        let (prefix, rest) = match rest.strip_prefix(':') {
+            Some(rest) => rest.split_at(rest.find(|c: char| c == '=' || c.is_whitespace())?),
+            None => ("", rest),
+        };
+
+        let value = rest.trim_start().strip_prefix('=')?.trim_start();
+        let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let namespace = &value[1..1 + value[1..].find(quote)?];
+
+        if EXIF_NAMESPACES.contains(&namespace) && !prefixes.iter().any(|p| p == prefix) {
+            prefixes.push(prefix.to_string());
+        }
+    }
+
+    Some(prefixes)
+}
+
+// Remove the properties with the given prefix, of which the name starts with `GPS`. Unprefixed
+// attributes are not part of the default namespace, so only elements are removed for the empty
+// prefix.
+fn strip_properties(xmp: &mut String, prefix: &str) -> Option<()> {
+    let name = if prefix.is_empty() {
+        String::from("GPS")
+    } else {
+        format!("{}:GPS", prefix)
+    };
+    let mut from = 0;
+
+    while let Some(offset) = xmp[from..].find(&name) {
+        let position = from + offset;
+        let preceding = xmp[..position].chars().next_back();
+
+        let removed = match preceding {
+            Some('<') => element_span(xmp, position - 1)?,
+            Some(c) if c.is_whitespace() && !prefix.is_empty() => attribute_span(xmp, position)?,
+            _ => {
+                from = position + name.len();
+                continue;
+            }
+        };
+
+        xmp.replace_range(removed.clone(), "");
+        from = removed.start;
+    }
+
+    Some(())
+}
+
+// The span of an element which starts at `start` (the position of its `<`), including the
+// indentation which precedes it.
+fn element_span(xmp: &str, start: usize) -> Option<std::ops::Range<usize>> {
+    let name_end = xmp[start + 1..]
+        .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+        .map(|end| start + 1 + end)?;
+    let name = &xmp[start + 1..name_end];
+    let tag_end = end_of_tag(xmp, name_end)?;
+
+    let end = if xmp[..tag_end].ends_with("/>") {
+        tag_end
+    } else {
+        let closing = format!("</{}>", name);
+        xmp[tag_end..]
+            .find(&closing)
+            .map(|offset| tag_end + offset + closing.len())?
+    };
+
+    Some(leading_indentation(xmp, start)..end)
+}
+
+// The span of an attribute which starts at `start`, including the whitespace which precedes it.
+fn attribute_span(xmp: &str, start: usize) -> Option<std::ops::Range<usize>> {
+    let equals = start + xmp[start..].find('=')?;
+    let quote_position = equals + xmp[equals + 1..].find(|c: char| !c.is_whitespace())? + 1;
+    let quote = xmp[quote_position..].chars().next()?;
+
+    if quote != '"' && quote != '\'' {
+        return None;
+    }
+
+    let end = quote_position + 1 + xmp[quote_position + 1..].find(quote)? + 1;
+    let whitespace = xmp[..start].len() - xmp[..start].trim_end().len();
+
+    Some(start - whitespace..end)
+}
+
+// The position directly after the `>` which ends the tag, skipping over quoted attribute values.
+fn end_of_tag(xmp: &str, from: usize) -> Option<usize> {
+    let mut quote = None;
+
+    for (offset, c) in xmp[from..].char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            (None, '>') => return Some(from + offset + 1),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+// The position of the start of the line, if only spaces and tabs precede `position` on its line.
+fn leading_indentation(xmp: &str, position: usize) -> usize {
+    let indentation = xmp[..position].trim_end_matches([' ', '\t']);
+
+    if indentation.ends_with('\n') {
+        indentation.len() - 1
+    } else {
+        position
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PACKET: &str = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+ <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+  <rdf:Description rdf:about=""
+    xmlns:exif="http://ns.adobe.com/exif/1.0/"
+    xmlns:dc="http://purl.org/dc/elements/1.1/"
+    exif:GPSLatitude="51,55.2N"
+    exif:GPSLongitude='4,28.8E'
+    exif:ExposureTime="1/60">
+   <exif:GPSAltitude>12/1</exif:GPSAltitude>
+   <exif:GPSVersionID/>
+   <dc:rights>sic</dc:rights>
+  </rdf:Description>
+ </rdf:RDF>
+</x:xmpmeta>"#;
+
+    const EXPECTED: &str = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+ <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+  <rdf:Description rdf:about=""
+    xmlns:exif="http://ns.adobe.com/exif/1.0/"
+    xmlns:dc="http://purl.org/dc/elements/1.1/"
+    exif:ExposureTime="1/60">
+   <dc:rights>sic</dc:rights>
+  </rdf:Description>
+ </rdf:RDF>
+</x:xmpmeta>"#;
+
+    #[test]
+    fn strips_gps_attributes_and_elements() {
+        let stripped = strip_gps(PACKET.as_bytes()).unwrap();
+
+        assert_eq!(String::from_utf8(stripped).unwrap(), EXPECTED);
+    }
+
+    #[test]
+    fn without_gps_is_unchanged() {
+        let stripped = strip_gps(EXPECTED.as_bytes()).unwrap();
+
+        assert_eq!(String::from_utf8(stripped).unwrap(), EXPECTED);
+    }
+
+    #[test]
+    fn resolves_prefix_of_exif_namespace() {
+        let packet = r#"<rdf:Description
+    xmlns:e="http://ns.adobe.com/exif/1.0/"
+    xmlns:exif="http://ns.example.com/not-exif/"
+    e:GPSLatitude="51,55.2N"
+    exif:GPSLatitude="kept">
+   <e:GPSAltitude>12/1</e:GPSAltitude>
+   <exif:GPSAltitude>kept</exif:GPSAltitude>
+  </rdf:Description>"#;
+
+        let expected = r#"<rdf:Description
+    xmlns:e="http://ns.adobe.com/exif/1.0/"
+    xmlns:exif="http://ns.example.com/not-exif/"
+    exif:GPSLatitude="kept">
+   <exif:GPSAltitude>kept</exif:GPSAltitude>
+  </rdf:Description>"#;
+
+        let stripped = strip_gps(packet.as_bytes()).unwrap();
+
+        assert_eq!(String::from_utf8(stripped).unwrap(), expected);
+    }
+
+    #[test]
+    fn strips_elements_of_default_exif_namespace() {
+        let packet = r#"<rdf:Description rdf:about="">
+   <exif:Properties xmlns='http://ns.adobe.com/exif/1.0/'>
+    <GPSLatitude>51,55.2N</GPSLatitude>
+    <ExposureTime>1/60</ExposureTime>
+   </exif:Properties>
+  </rdf:Description>"#;
+
+        let stripped = String::from_utf8(strip_gps(packet.as_bytes()).unwrap()).unwrap();
+
+        assert!(!stripped.contains("GPS"));
+        assert!(stripped.contains("<ExposureTime>1/60</ExposureTime>"));
+    }
+
+    #[test]
+    fn malformed_namespace_declaration_is_rejected() {
+        let packet = r#"<rdf:Description xmlns:exif=http://ns.adobe.com/exif/1.0/ />"#;
+
+        assert!(strip_gps(packet.as_bytes()).is_none());
+    }
+
+    #[test]
+    fn invalid_utf8_is_rejected() {
+        assert!(strip_gps(&[0x3C, 0xFF, 0xFE, 0x3E]).is_none());
+    }
+}
@@ -17,7 +17,7 @@ impl Preprocess for PickFramePreprocessor {
 
     fn preprocess(&self, image: SicImage) -> Result<SicImage, Self::Err> {
         match image {
-            SicImage::Animated(animated) if self.image_format != image::ImageFormat::Gif => {
+            SicImage::Animated(animated) if !supports_animation(self.image_format) => {
                 eprintln!("WARN: Unable to encode animated image buffer with format '{:?}': encoding first frame only", self.image_format);
                 let image = animated.try_into_static_image(0)?;
                 Ok(SicImage::Static(image))
@@ -26,3 +26,11 @@ impl Preprocess for PickFramePreprocessor {
         }
     }
 }
+
+/// Image formats for which animated images can be encoded.
+fn supports_animation(format: image::ImageFormat) -> bool {
+    matches!(
+        format,
+        image::ImageFormat::Gif | image::ImageFormat::Png | image::ImageFormat::WebP
+    )
+}
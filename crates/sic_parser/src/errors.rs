@@ -19,6 +19,9 @@ pub enum SicParserError {
     #[error("string value expected an inner value, but none was found")]
     NoInnerString,
 
+    #[error("named value '{0}' is not supported by operation '{1}'")]
+    UnsupportedNamedValue(String, String),
+
     #[error("{0}")]
     OperationError(OperationParamError),
 
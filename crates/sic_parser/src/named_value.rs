@@ -78,8 +78,14 @@ impl FromStr for NamedValue {
             .and_then(|right_side| right_side.rsplitn(2, ')').last())
             .ok_or(NamedValueError::UnableToCreateNamedValueWithArgs(ident))?;
 
+        // the arguments of points(..) are tuples, which contain commas themselves
+        let arguments = match ident {
+            Ident::Points => split_tuples(arguments),
+            _ => arguments.split(',').collect(),
+        };
+
         let arguments = arguments
-            .split(',')
+            .into_iter()
             .map(|arg| Value::try_from_str(arg.trim(), ident))
             .collect::<NVResult<Vec<_>>>()?;
 
@@ -100,6 +106,27 @@ pub enum Ident {
 
     // coord(<u32>, <u32>)
     Coord,
+
+    // tolerance(<u8>) or tolerance(<u8>,<u8>,<u8>,<u8>)
+    Tolerance,
+
+    // same(<u8>,<u8>,<u8>,<u8>)
+    Same,
+
+    // different(<u8>,<u8>,<u8>,<u8>)
+    Different,
+
+    // no-overlap(<u8>,<u8>,<u8>,<u8>)
+    NoOverlap,
+
+    // dim(<f32>)
+    Dim,
+
+    // margin(<u32>)
+    Margin,
+
+    // points((<u8>, <u8>), ...)
+    Points,
 }
 
 impl Display for Ident {
@@ -109,6 +136,13 @@ impl Display for Ident {
             Self::Size => f.write_str("Size"),
             Self::Font => f.write_str("Font"),
             Self::Coord => f.write_str("Coord"),
+            Self::Tolerance => f.write_str("Tolerance"),
+            Self::Same => f.write_str("Same"),
+            Self::Different => f.write_str("Different"),
+            Self::NoOverlap => f.write_str("NoOverlap"),
+            Self::Dim => f.write_str("Dim"),
+            Self::Margin => f.write_str("Margin"),
+            Self::Points => f.write_str("Points"),
         }
     }
 }
@@ -119,6 +153,13 @@ fn parse_ident(ident: &str) -> NVResult<Ident> {
         "size" => Ident::Size,
         "font" => Ident::Font,
         "coord" => Ident::Coord,
+        "tolerance" => Ident::Tolerance,
+        "same" => Ident::Same,
+        "different" => Ident::Different,
+        "no-overlap" => Ident::NoOverlap,
+        "dim" => Ident::Dim,
+        "margin" => Ident::Margin,
+        "points" => Ident::Points,
         _ => return Err(NamedValueError::IdentifierInvalid(ident.to_string())),
     };
 
@@ -128,6 +169,7 @@ fn parse_ident(ident: &str) -> NVResult<Ident> {
 #[derive(Debug, Clone)]
 enum Value<'a> {
     Byte(u8),
+    BytePair((u8, u8)),
     Float(f32),
     Integer(i32),
     NatNum(u32),
@@ -138,8 +180,19 @@ impl<'a> Value<'a> {
     pub fn try_from_pair(pair: Pair<'a, Rule>, ident: Ident) -> NVResult<Self> {
         match (pair.as_rule(), ident) {
             (Rule::fp, Ident::Rgba) => Ok(Value::parse_byte(pair.as_str())?),
-            (Rule::fp, Ident::Size) => Ok(Value::parse_float(pair.as_str())?),
+            (Rule::fp, Ident::Tolerance | Ident::Same | Ident::Different | Ident::NoOverlap) => {
+                Ok(Value::parse_byte(pair.as_str())?)
+            }
+            (Rule::fp, Ident::Size | Ident::Dim) => Ok(Value::parse_float(pair.as_str())?),
             (Rule::fp, Ident::Coord) => Ok(Value::parse_integer(pair.as_str())?),
+            (Rule::fp, Ident::Margin) => Ok(Value::parse_nat_num(pair.as_str())?),
+            (Rule::tuple, Ident::Points) => {
+                let values = pair
+                    .into_inner()
+                    .map(|pair| pair.as_str())
+                    .collect::<Vec<_>>();
+                Ok(Value::parse_byte_pair(&values)?)
+            }
             (Rule::string_unicode, _) => Ok(Value::parse_string(pair.into_inner().as_str())?),
             _ => Err(NamedValueError::InvalidArgumentType),
         }
@@ -147,9 +200,23 @@ impl<'a> Value<'a> {
 
     pub fn try_from_str(s: &'a str, ident: Ident) -> NVResult<Self> {
         match ident {
-            Ident::Rgba => Ok(Value::parse_byte(s)?),
-            Ident::Size => Ok(Value::parse_float(s)?),
+            Ident::Rgba | Ident::Tolerance | Ident::Same | Ident::Different | Ident::NoOverlap => {
+                Ok(Value::parse_byte(s)?)
+            }
+            Ident::Size | Ident::Dim => Ok(Value::parse_float(s)?),
             Ident::Coord => Ok(Value::parse_integer(s)?),
+            Ident::Margin => Ok(Value::parse_nat_num(s)?),
+            Ident::Points => {
+                let values = s
+                    .strip_prefix('(')
+                    .and_then(|s| s.strip_suffix(')'))
+                    .ok_or(NamedValueError::InvalidArgumentType)?
+                    .split(',')
+                    .map(str::trim)
+                    .collect::<Vec<_>>();
+
+                Ok(Value::parse_byte_pair(&values)?)
+            }
             Ident::Font => Ok(Value::parse_string(slice_str_tokens(s)?)?),
         }
     }
@@ -165,6 +232,17 @@ impl<'a> Value<'a> {
         }
     }
 
+    pub fn extract_byte_pair(&self) -> NVResult<(u8, u8)> {
+        if let Self::BytePair(inner) = self {
+            Ok(*inner)
+        } else {
+            Err(NamedValueError::UnableToExtractValue(
+                String::from("BytePair"),
+                self.error_type(),
+            ))
+        }
+    }
+
     pub fn extract_float(&self) -> NVResult<f32> {
         if let Self::Float(inner) = self {
             Ok(*inner)
@@ -187,7 +265,6 @@ impl<'a> Value<'a> {
         }
     }
 
-    #[allow(unused)]
     pub fn extract_nat_num(&self) -> NVResult<u32> {
         if let Self::NatNum(inner) = self {
             Ok(*inner)
@@ -217,6 +294,19 @@ impl<'a> Value<'a> {
             .map_err(|_err| NamedValueError::UnableToParse(value.to_string(), String::from("Byte")))
     }
 
+    fn parse_byte_pair(values: &[&str]) -> NVResult<Self> {
+        match values {
+            [x, y] => match (Value::parse_byte(x)?, Value::parse_byte(y)?) {
+                (Value::Byte(x), Value::Byte(y)) => Ok(Value::BytePair((x, y))),
+                _ => Err(NamedValueError::InvalidArgumentType),
+            },
+            _ => Err(NamedValueError::UnableToParse(
+                format!("({})", values.join(", ")),
+                String::from("BytePair"),
+            )),
+        }
+    }
+
     fn parse_float(value: &str) -> NVResult<Self> {
         value.parse::<f32>().map(Value::Float).map_err(|_err| {
             NamedValueError::UnableToParse(value.to_string(), String::from("Float"))
@@ -229,7 +319,6 @@ impl<'a> Value<'a> {
         })
     }
 
-    #[allow(unused)]
     fn parse_nat_num(value: &str) -> NVResult<Self> {
         value.parse::<u32>().map(Value::NatNum).map_err(|_err| {
             NamedValueError::UnableToParse(value.to_string(), String::from("NatNum"))
@@ -243,6 +332,7 @@ impl<'a> Value<'a> {
     fn error_type(&self) -> String {
         let typ = match self {
             Self::Byte(_) => "Byte",
+            Self::BytePair(_) => "BytePair",
             Self::Float(_) => "Float",
             Self::Integer(_) => "Integer",
             Self::NatNum(_) => "NatNum",
@@ -265,7 +355,7 @@ impl AnnotatedArgs<'_> {
     }
 
     // moves arguments!
-    fn arguments(&self) -> &[Value] {
+    fn arguments(&self) -> &[Value<'_>] {
         self.arguments.as_slice()
     }
 }
@@ -276,6 +366,13 @@ pub enum NamedValue {
     Size(f32),
     Font(PathBuf),
     Coord((i32, i32)),
+    Tolerance([u8; 4]),
+    Same([u8; 4]),
+    Different([u8; 4]),
+    NoOverlap([u8; 4]),
+    Dim(f32),
+    Margin(u32),
+    Points(Vec<(u8, u8)>),
 }
 
 impl NamedValue {
@@ -285,6 +382,17 @@ impl NamedValue {
             Ident::Size => NamedValue::create_size(args.arguments()),
             Ident::Font => NamedValue::create_font(args.arguments()),
             Ident::Coord => NamedValue::create_coord(args.arguments()),
+            Ident::Tolerance => NamedValue::create_tolerance(args.arguments()),
+            Ident::Same => NamedValue::create_bytes(args.arguments(), Ident::Same, Self::Same),
+            Ident::Different => {
+                NamedValue::create_bytes(args.arguments(), Ident::Different, Self::Different)
+            }
+            Ident::NoOverlap => {
+                NamedValue::create_bytes(args.arguments(), Ident::NoOverlap, Self::NoOverlap)
+            }
+            Ident::Dim => NamedValue::create_dim(args.arguments()),
+            Ident::Margin => NamedValue::create_margin(args.arguments()),
+            Ident::Points => NamedValue::create_points(args.arguments()),
         }
     }
 
@@ -332,6 +440,17 @@ impl NamedValue {
         }
     }
 
+    pub fn extract_points(&self) -> NVResult<Vec<(u8, u8)>> {
+        if let Self::Points(points) = self {
+            Ok(points.clone())
+        } else {
+            Err(NamedValueError::UnableToExtractValue(
+                String::from("Points"),
+                self.error_type(),
+            ))
+        }
+    }
+
     fn create_rgba(args: &[Value]) -> NVResult<Self> {
         match args {
             [r, g, b, a] => Ok(Self::Rgba(
@@ -373,18 +492,98 @@ impl NamedValue {
         }
     }
 
-    fn error_type(&self) -> String {
+    // tolerance(t) applies the tolerance t to each channel
+    fn create_tolerance(args: &[Value]) -> NVResult<Self> {
+        match args {
+            [tolerance] => Ok(Self::Tolerance([tolerance.extract_byte()?; 4])),
+            _ => NamedValue::create_bytes(args, Ident::Tolerance, Self::Tolerance),
+        }
+    }
+
+    fn create_bytes(args: &[Value], ident: Ident, variant: fn([u8; 4]) -> Self) -> NVResult<Self> {
+        match args {
+            [r, g, b, a] => Ok(variant([
+                r.extract_byte()?,
+                g.extract_byte()?,
+                b.extract_byte()?,
+                a.extract_byte()?,
+            ])),
+            _ => Err(NamedValueError::UnableToCreateNamedValueWithArgs(ident)),
+        }
+    }
+
+    fn create_dim(args: &[Value]) -> NVResult<Self> {
+        match args {
+            [factor] => match factor.extract_float()? {
+                factor if (0.0..=1.0).contains(&factor) => Ok(Self::Dim(factor)),
+                factor => Err(NamedValueError::UnableToParse(
+                    factor.to_string(),
+                    String::from("Float between 0 and 1"),
+                )),
+            },
+            _ => Err(NamedValueError::UnableToCreateNamedValueWithArgs(
+                Ident::Dim,
+            )),
+        }
+    }
+
+    fn create_margin(args: &[Value]) -> NVResult<Self> {
+        match args {
+            [margin] => Ok(Self::Margin(margin.extract_nat_num()?)),
+            _ => Err(NamedValueError::UnableToCreateNamedValueWithArgs(
+                Ident::Margin,
+            )),
+        }
+    }
+
+    fn create_points(args: &[Value]) -> NVResult<Self> {
+        args.iter()
+            .map(Value::extract_byte_pair)
+            .collect::<NVResult<Vec<_>>>()
+            .map(Self::Points)
+    }
+
+    pub(crate) fn error_type(&self) -> String {
         let typ = match self {
             Self::Rgba(_, _, _, _) => "Rgba",
             Self::Size(_) => "Size",
             Self::Font(_) => "Font",
             Self::Coord(_) => "Coord",
+            Self::Tolerance(_) => "Tolerance",
+            Self::Same(_) => "Same",
+            Self::Different(_) => "Different",
+            Self::NoOverlap(_) => "NoOverlap",
+            Self::Dim(_) => "Dim",
+            Self::Margin(_) => "Margin",
+            Self::Points(_) => "Points",
         };
 
         typ.to_string()
     }
 }
 
+// Splits the arguments `(a, b), (c, d)` into the tuples `(a, b)` and `(c, d)`.
+fn split_tuples(arguments: &str) -> Vec<&str> {
+    let mut tuples = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (index, char) in arguments.char_indices() {
+        match char {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                tuples.push(&arguments[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    tuples.push(&arguments[start..]);
+    tuples
+}
+
 fn slice_str_tokens(s: &str) -> NVResult<&str> {
     if (s.starts_with('"') && s.ends_with('"')) || (s.starts_with('\'') && s.ends_with('\'')) {
         Ok(&s[1..s.len() - 1])
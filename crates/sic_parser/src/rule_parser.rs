@@ -4,12 +4,19 @@ use super::Rule;
 use crate::errors::{OperationParamError, SicParserError};
 use crate::value_parser::ParseInputsFromIter;
 use pest::iterators::{Pair, Pairs};
+use sic_core::FrameRange;
 use sic_image_engine::engine::{EnvItem, Instr, ItemName};
+use sic_image_engine::wrapper::diff::DiffInputs;
 #[cfg(feature = "imageproc-ops")]
 use sic_image_engine::wrapper::draw_text_inner::DrawTextInner;
 use sic_image_engine::wrapper::filter_type::FilterTypeWrap;
+use sic_image_engine::wrapper::frame_delay::FrameDelay;
+use sic_image_engine::wrapper::gravity::Gravity;
+use sic_image_engine::wrapper::icc_profile::IccProfileTarget;
 use sic_image_engine::wrapper::image_path::ImageFromPath;
 use sic_image_engine::wrapper::overlay::OverlayInputs;
+use sic_image_engine::wrapper::pad::{Margins, PadSize};
+use sic_image_engine::wrapper::resize_mode::ResizeMode;
 use sic_image_engine::ImgOp;
 
 // This function parses statements provided as a single 'script' to an image operations program.
@@ -27,29 +34,45 @@ pub fn parse_image_operations(pairs: Pairs<'_, Rule>) -> Result<Vec<Instr>, SicP
     pairs
         .filter(|pair| pair.as_rule() != Rule::EOI)
         .map(|pair| match pair.as_rule() {
+            Rule::auto_orient => Ok(Instr::Operation(ImgOp::AutoOrient)),
             Rule::blur => Blur(pair),
             Rule::brighten => Brighten(pair),
             Rule::contrast => Contrast(pair),
+            Rule::convert_profile => parse_convert_profile(pair),
             Rule::crop => Crop(pair),
-            Rule::diff => Diff(
-                pair.into_inner()
-                    .next()
-                    .ok_or(SicParserError::NoInnerString)?,
-            ),
+            Rule::curves => parse_curves(pair),
+            Rule::diff => parse_diff(pair),
             #[cfg(feature = "imageproc-ops")]
             Rule::draw_text => Ok(parse_draw_text(pair)?),
+            Rule::drop_frames => DropFrames(pair),
+            Rule::extend | Rule::pad => parse_pad(pair),
             Rule::filter3x3 => Filter3x3(pair),
             Rule::flip_horizontal => Ok(Instr::Operation(ImgOp::FlipHorizontal)),
             Rule::flip_vertical => Ok(Instr::Operation(ImgOp::FlipVertical)),
+            Rule::gamma => Gamma(pair),
             Rule::grayscale => Ok(Instr::Operation(ImgOp::Grayscale)),
             Rule::huerotate => HueRotate(pair),
             Rule::horizontal_gradient => Ok(parse_horizontal_gradient(pair)?),
             Rule::invert => Ok(Instr::Operation(ImgOp::Invert)),
+            Rule::levels => parse_levels(pair),
+            Rule::on_frames => parse_on_frames(pair),
             Rule::overlay => parse_overlay(pair),
             Rule::resize => Resize(pair),
+            Rule::resize_exact
+            | Rule::resize_fit
+            | Rule::resize_cover
+            | Rule::resize_scale
+            | Rule::resize_max_edge => parse_resize_with_mode(pair),
+            Rule::reverse_frames => Ok(Instr::Operation(ImgOp::ReverseFrames)),
+            #[cfg(feature = "imageproc-ops")]
+            Rule::rotate => parse_rotate(pair),
             Rule::rotate90 => Ok(Instr::Operation(ImgOp::Rotate90)),
             Rule::rotate180 => Ok(Instr::Operation(ImgOp::Rotate180)),
             Rule::rotate270 => Ok(Instr::Operation(ImgOp::Rotate270)),
+            Rule::set_frame_delay => SetFrameDelay(pair),
+            Rule::smart_crop => SmartCrop(pair),
+            Rule::speed => Speed(pair),
+            Rule::trim => parse_trim(pair),
             Rule::unsharpen => Unsharpen(pair),
             Rule::setopt => {
                 parse_set_environment(pair.into_inner().next().ok_or({
@@ -92,12 +115,51 @@ parse_op_from_pair!(Blur, f32);
 parse_op_from_pair!(Brighten, i32);
 parse_op_from_pair!(Contrast, f32);
 parse_op_from_pair!(Crop, (u32, u32, u32, u32));
-parse_op_from_pair!(Diff, ImageFromPath);
+parse_op_from_pair!(Gamma, f32);
 parse_op_from_pair!(HueRotate, i32);
 parse_op_from_pair!(Resize, (u32, u32));
+parse_op_from_pair!(SetFrameDelay, FrameDelay);
+parse_op_from_pair!(SmartCrop, (u32, u32));
+parse_op_from_pair!(Speed, f32);
 parse_op_from_pair!(Unsharpen, (f32, i32));
 parse_op_from_pair!(Filter3x3, [f32; 9]);
 
+parse_op_from_pair!(DropFrames, FrameRange);
+
+// The target profile is either the built-in sRGB profile, or a path to an ICC profile.
+fn parse_convert_profile(pair: Pair<'_, Rule>) -> Result<Instr, SicParserError> {
+    let target = pair
+        .into_inner()
+        .next()
+        .ok_or(SicParserError::NoInnerString)?;
+
+    let target = match target.as_rule() {
+        Rule::icc_srgb => IccProfileTarget::Srgb,
+        _ => parse_primitive_from_pair!(target, IccProfileTarget)?,
+    };
+
+    Ok(Instr::Operation(ImgOp::ConvertProfile(target)))
+}
+
+// expected pair with inner pairs:
+// - rule: 'frame_range'; represents: the frames to which the operations will be applied
+// - rule: 'on_frames_block'; represents: the statements which will be applied to these frames
+fn parse_on_frames(pair: Pair<'_, Rule>) -> Result<Instr, SicParserError> {
+    let mut pairs = pair.into_inner();
+
+    let range = pairs
+        .next()
+        .ok_or_else(|| SicParserError::ExpectedValue("frame range".to_string()))?;
+    let range: FrameRange = ParseInputsFromIter::parse(&[range.as_str()])?;
+
+    let block = pairs
+        .next()
+        .ok_or_else(|| SicParserError::ExpectedValue("block".to_string()))?;
+    let instructions = parse_image_operations(block.into_inner())?;
+
+    Ok(Instr::Operation(ImgOp::OnFrames(range, instructions)))
+}
+
 macro_rules! parse_setenv_from_pair {
     ($env_item:tt, $ty:ty) => {
         #[allow(non_snake_case)]
@@ -115,11 +177,13 @@ macro_rules! parse_setenv_from_pair {
 
 parse_setenv_from_pair!(CustomSamplingFilter, FilterTypeWrap);
 parse_setenv_from_pair!(PreserveAspectRatio, bool);
+parse_setenv_from_pair!(ShrinkOnly, bool);
 
 fn parse_set_environment(pair: Pair<'_, Rule>) -> Result<Instr, SicParserError> {
     let environment_item = match pair.as_rule() {
         Rule::set_resize_sampling_filter => CustomSamplingFilter(pair)?,
         Rule::set_resize_preserve_aspect_ratio => PreserveAspectRatio(pair)?,
+        Rule::set_resize_shrink_only => ShrinkOnly(pair)?,
         _ => {
             return Err(SicParserError::OperationError(
                 OperationParamError::SetEnvironmentElement(format!("{}", pair)),
@@ -134,6 +198,7 @@ fn parse_unset_environment(pair: Pair<'_, Rule>) -> Result<Instr, SicParserError
     let environment_item = match pair.as_rule() {
         Rule::env_resize_sampling_filter_name => ItemName::CustomSamplingFilter,
         Rule::env_resize_preserve_aspect_ratio_name => ItemName::PreserveAspectRatio,
+        Rule::env_resize_shrink_only_name => ItemName::ShrinkOnly,
         _ => {
             return Err(SicParserError::OperationError(
                 OperationParamError::UnsetEnvironmentElement(format!("{}", pair)),
@@ -144,6 +209,178 @@ fn parse_unset_environment(pair: Pair<'_, Rule>) -> Result<Instr, SicParserError
     Ok(Instr::EnvRemove(environment_item))
 }
 
+// expected pair with inner pairs, depending on the mode:
+// - exact, fit: rule: 'uint' (two); represents: the width and height
+// - cover: rule: 'uint' (two), and 'gravity' (optional); represents: the width and height, and
+//   which part of the image is kept
+// - scale: rule: 'percentage'; represents: the scale factor
+// - max-edge: rule: 'uint'; represents: the maximum length of the longest edge
+fn parse_resize_with_mode(pair: Pair<'_, Rule>) -> Result<Instr, SicParserError> {
+    let rule = pair.as_rule();
+    let inputs = pair
+        .into_inner()
+        .map(|pair| pair.as_str())
+        .collect::<Vec<_>>();
+
+    let mode = match rule {
+        Rule::resize_exact => ResizeMode::Exact(ParseInputsFromIter::parse(&inputs)?),
+        Rule::resize_fit => ResizeMode::Fit(ParseInputsFromIter::parse(&inputs)?),
+        Rule::resize_cover => {
+            let (size, gravity) = inputs.split_at(inputs.len().min(2));
+            let gravity = match gravity {
+                [] => Gravity::default(),
+                gravity => ParseInputsFromIter::parse(gravity)?,
+            };
+
+            ResizeMode::Cover(ParseInputsFromIter::parse(size)?, gravity)
+        }
+        Rule::resize_scale => ResizeMode::Scale(ParseInputsFromIter::parse(&inputs)?),
+        Rule::resize_max_edge => ResizeMode::MaxEdge(ParseInputsFromIter::parse(&inputs)?),
+        _ => return Err(SicParserError::UnknownOperationError),
+    };
+
+    Ok(Instr::Operation(ImgOp::ResizeWithMode(mode)))
+}
+
+// expected pair with inner pairs, depending on the operation:
+// - pad: rule: 'uint' (two), and 'gravity' (optional); represents: the dimensions of the canvas,
+//   and where the image is placed on the canvas
+// - extend: rule: 'uint' (one or four); represents: the margin of each side, or the top, right,
+//   bottom and left margins
+// followed by:
+// - rule: 'named_value' (optional); which: rgba(r, g, b, a); represents: the fill color
+fn parse_pad(pair: Pair<'_, Rule>) -> Result<Instr, SicParserError> {
+    use crate::named_value::parse_named_value;
+    use crate::value_parser::pad_inputs;
+
+    let rule = pair.as_rule();
+    let (named_values, inputs): (Vec<_>, Vec<_>) = pair
+        .into_inner()
+        .partition(|pair| pair.as_rule() == Rule::named_value);
+    let inputs = inputs.iter().map(|pair| pair.as_str()).collect::<Vec<_>>();
+
+    let size = match rule {
+        Rule::pad => {
+            let (size, gravity) = inputs.split_at(inputs.len().min(2));
+            let gravity = match gravity {
+                [] => Gravity::default(),
+                gravity => ParseInputsFromIter::parse(gravity)?,
+            };
+
+            PadSize::Canvas(ParseInputsFromIter::parse(size)?, gravity)
+        }
+        Rule::extend => match inputs.as_slice() {
+            [margin] => PadSize::Margins(Margins::uniform(ParseInputsFromIter::parse(&[*margin])?)),
+            margins => {
+                let (top, right, bottom, left) = ParseInputsFromIter::parse(margins)?;
+
+                PadSize::Margins(Margins {
+                    top,
+                    right,
+                    bottom,
+                    left,
+                })
+            }
+        },
+        _ => return Err(SicParserError::UnknownOperationError),
+    };
+
+    let fill = named_values
+        .into_iter()
+        .next()
+        .map(|pair| parse_named_value(pair).map_err(SicParserError::NamedValueParsingError))
+        .transpose()?;
+
+    Ok(Instr::Operation(ImgOp::Pad(pad_inputs(size, fill)?)))
+}
+
+// expected pair with inner pairs:
+// - rule: 'named_value'; which: points((x, y), ...) with x,y =: u8; represents: the control
+//   points of the curve
+fn parse_curves(pair: Pair<'_, Rule>) -> Result<Instr, SicParserError> {
+    use crate::named_value::{parse_named_value, NamedValue};
+
+    let named_value = pair
+        .into_inner()
+        .next()
+        .ok_or_else(|| SicParserError::ExpectedValue(String::from("Points")))?;
+
+    match parse_named_value(named_value).map_err(SicParserError::NamedValueParsingError)? {
+        NamedValue::Points(points) => Ok(Instr::Operation(ImgOp::Curves(points))),
+        other => Err(SicParserError::UnsupportedNamedValue(
+            other.error_type(),
+            String::from("curves"),
+        )),
+    }
+}
+
+// expected pair with inner pairs:
+// - rule: 'color_channel' (optional); represents: the color channel of which the levels are adjusted
+// - rule: 'uint' (two); represents: the black point and the white point
+// - rule: 'fp' (optional); represents: the gamma
+fn parse_levels(pair: Pair<'_, Rule>) -> Result<Instr, SicParserError> {
+    use sic_image_engine::wrapper::levels::LevelsInputs;
+
+    let (channels, inputs): (Vec<_>, Vec<_>) = pair
+        .into_inner()
+        .partition(|pair| pair.as_rule() == Rule::color_channel);
+    let inputs = inputs.iter().map(|pair| pair.as_str()).collect::<Vec<_>>();
+
+    let (range, gamma) = inputs.split_at(inputs.len().min(2));
+    let (black, white) = ParseInputsFromIter::parse(range)?;
+    let mut levels = LevelsInputs::new(black, white);
+
+    if !gamma.is_empty() {
+        levels = levels.with_gamma(ParseInputsFromIter::parse(gamma)?);
+    }
+
+    if let Some(channels) = channels.first() {
+        levels = levels.with_channels(ParseInputsFromIter::parse(&[channels.as_str()])?);
+    }
+
+    Ok(Instr::Operation(ImgOp::Levels(levels)))
+}
+
+// expected pair with inner pairs:
+// - rule: 'string_unicode'; represents: the image to diff against
+// - rule: 'named_value' (zero or more); which: tolerance(t), same(r, g, b, a),
+//   different(r, g, b, a), no-overlap(r, g, b, a) or dim(f); represents: the options of the diff
+fn parse_diff(pair: Pair<'_, Rule>) -> Result<Instr, SicParserError> {
+    use crate::named_value::parse_named_value;
+    use crate::value_parser::diff_options;
+
+    let mut pairs = pair.into_inner();
+
+    let image_path = parse_primitive_from_pair!(
+        pairs.next().ok_or(SicParserError::NoInnerString)?,
+        ImageFromPath
+    )?;
+
+    let named_values = pairs
+        .map(|pair| parse_named_value(pair).map_err(SicParserError::NamedValueParsingError))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Instr::Operation(ImgOp::Diff(DiffInputs::with_options(
+        image_path,
+        diff_options(named_values)?,
+    ))))
+}
+
+// expected pair with inner pairs:
+// - rule: 'named_value' (zero or more); which: rgba(r, g, b, a), tolerance(t) or margin(m);
+//   represents: the color of the border, the tolerance of the color, and the margin to keep
+fn parse_trim(pair: Pair<'_, Rule>) -> Result<Instr, SicParserError> {
+    use crate::named_value::parse_named_value;
+    use crate::value_parser::trim_inputs;
+
+    let named_values = pair
+        .into_inner()
+        .map(|pair| parse_named_value(pair).map_err(SicParserError::NamedValueParsingError))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Instr::Operation(ImgOp::Trim(trim_inputs(named_values)?)))
+}
+
 fn parse_overlay(pair: Pair<'_, Rule>) -> Result<Instr, SicParserError> {
     let mut pairs = pair.into_inner();
 
@@ -166,6 +403,48 @@ fn parse_overlay(pair: Pair<'_, Rule>) -> Result<Instr, SicParserError> {
     ))))
 }
 
+// expected pair with inner pairs:
+// - rule: 'fp'; represents: the angle in degrees by which the image is rotated clockwise
+// - rule: 'rotate_canvas' (optional); represents: whether the canvas is expanded or cropped
+// - rule: 'sampling_filter' (optional); represents: the interpolation of the rotated pixels
+// - rule: 'named_value' (optional); which: rgba(r, g, b, a); represents: the fill color of the
+//   exposed corners
+#[cfg(feature = "imageproc-ops")]
+fn parse_rotate(pair: Pair<'_, Rule>) -> Result<Instr, SicParserError> {
+    use crate::named_value::{parse_named_value, NamedValue};
+    use sic_core::image::Rgba;
+    use sic_image_engine::wrapper::rotate::RotateInputs;
+
+    let mut pairs = pair.into_inner();
+
+    let degrees = pairs
+        .next()
+        .ok_or_else(|| SicParserError::ExpectedValue("fp".to_string()))?;
+    let degrees: f32 = ParseInputsFromIter::parse(&[degrees.as_str()])?;
+
+    pairs
+        .try_fold(RotateInputs::new(degrees), |inputs, pair| {
+            match pair.as_rule() {
+                Rule::rotate_canvas => {
+                    Ok(inputs.with_canvas(ParseInputsFromIter::parse(&[pair.as_str()])?))
+                }
+                Rule::sampling_filter => {
+                    Ok(inputs.with_interpolation(ParseInputsFromIter::parse(&[pair.as_str()])?))
+                }
+                _ => {
+                    match parse_named_value(pair).map_err(SicParserError::NamedValueParsingError)? {
+                        NamedValue::Rgba(r, g, b, a) => Ok(inputs.with_fill(Rgba([r, g, b, a]))),
+                        other => Err(SicParserError::UnsupportedNamedValue(
+                            other.error_type(),
+                            String::from("rotate"),
+                        )),
+                    }
+                }
+            }
+        })
+        .map(|inputs| Instr::Operation(ImgOp::Rotate(inputs)))
+}
+
 #[cfg(feature = "imageproc-ops")]
 // expected pair with inner pairs:
 // - rule: 'string_unicode'; represents: text to draw
@@ -433,6 +712,29 @@ mod tests {
             .unwrap_or_else(|e| panic!("error: {:?}", e));
     }
 
+    #[test]
+    fn test_auto_orient_single_stmt_parse_correct() {
+        let pairs = SICParser::parse(Rule::main, "auto-orient;")
+            .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+        assert_eq!(
+            vec![Instr::Operation(ImgOp::AutoOrient)],
+            parse_image_operations(pairs).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_auto_orient_before_other_operations() {
+        let pairs = SICParser::parse(Rule::main, "auto-orient; resize 10 10")
+            .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+        assert_eq!(
+            vec![
+                Instr::Operation(ImgOp::AutoOrient),
+                Instr::Operation(ImgOp::Resize((10, 10)))
+            ],
+            parse_image_operations(pairs).unwrap()
+        );
+    }
+
     #[test]
     fn test_blur_with_int_accept() {
         let pairs = SICParser::parse(Rule::main, "blur 15;")
@@ -593,6 +895,8 @@ mod tests {
     #[cfg(test)]
     mod diff_test {
         use super::*;
+        use sic_core::image::Rgba;
+        use sic_image_engine::wrapper::diff::DiffOptions;
 
         ide!();
 
@@ -608,14 +912,14 @@ mod tests {
                 "diff 'C:\\Users\\Some Name\\input.jpg';",
             },
             expected_ops = {
-                vec![Instr::Operation(ImgOp::Diff(ImageFromPath::new("/my/path/input.jpg".into())))],
-                vec![Instr::Operation(ImgOp::Diff(ImageFromPath::new("input.jpg".into())))],
-                vec![Instr::Operation(ImgOp::Diff(ImageFromPath::new("C:/Users/Some Name/input.jpg".into())))],
-                vec![Instr::Operation(ImgOp::Diff(ImageFromPath::new("C:\\Users\\Some Name\\input.jpg".into())))],
-                vec![Instr::Operation(ImgOp::Diff(ImageFromPath::new("/my/path/input.jpg".into())))],
-                vec![Instr::Operation(ImgOp::Diff(ImageFromPath::new("input.jpg".into())))],
-                vec![Instr::Operation(ImgOp::Diff(ImageFromPath::new("C:/Users/Some Name/input.jpg".into())))],
-                vec![Instr::Operation(ImgOp::Diff(ImageFromPath::new("C:\\Users\\Some Name\\input.jpg".into())))],
+                vec![Instr::Operation(ImgOp::Diff(DiffInputs::new(ImageFromPath::new("/my/path/input.jpg".into()))))],
+                vec![Instr::Operation(ImgOp::Diff(DiffInputs::new(ImageFromPath::new("input.jpg".into()))))],
+                vec![Instr::Operation(ImgOp::Diff(DiffInputs::new(ImageFromPath::new("C:/Users/Some Name/input.jpg".into()))))],
+                vec![Instr::Operation(ImgOp::Diff(DiffInputs::new(ImageFromPath::new("C:\\Users\\Some Name\\input.jpg".into()))))],
+                vec![Instr::Operation(ImgOp::Diff(DiffInputs::new(ImageFromPath::new("/my/path/input.jpg".into()))))],
+                vec![Instr::Operation(ImgOp::Diff(DiffInputs::new(ImageFromPath::new("input.jpg".into()))))],
+                vec![Instr::Operation(ImgOp::Diff(DiffInputs::new(ImageFromPath::new("C:/Users/Some Name/input.jpg".into()))))],
+                vec![Instr::Operation(ImgOp::Diff(DiffInputs::new(ImageFromPath::new("C:\\Users\\Some Name\\input.jpg".into()))))],
             }
         )]
         fn test_diff_ok(input: &str, expected_ops: Vec<Instr>) {
@@ -635,6 +939,314 @@ mod tests {
             let pairs = SICParser::parse(Rule::main, input);
             assert!(pairs.is_err());
         }
+
+        fn diff_options(input: &str) -> DiffOptions {
+            let pairs = SICParser::parse(Rule::main, input)
+                .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+
+            match parse_image_operations(pairs).unwrap().as_slice() {
+                [Instr::Operation(ImgOp::Diff(inputs))] => *inputs.options(),
+                other => panic!("Expected a single diff operation, but got: {:?}", other),
+            }
+        }
+
+        #[test]
+        fn test_diff_with_options() {
+            let options = diff_options(
+                "diff 'input.jpg' tolerance(8) same(0, 0, 0, 255) different(0, 0, 255, 128) \
+                 no-overlap(255, 255, 255, 0) dim(0.5);",
+            );
+
+            assert_eq!(
+                options,
+                DiffOptions {
+                    tolerance: [8; 4],
+                    same: Rgba([0, 0, 0, 255]),
+                    different: Rgba([0, 0, 255, 128]),
+                    no_overlap: Rgba([255, 255, 255, 0]),
+                    dim: Some(0.5),
+                }
+            );
+        }
+
+        #[test]
+        fn test_diff_with_tolerance_per_channel() {
+            let options = diff_options("diff 'input.jpg' tolerance(1, 2, 3, 0);");
+
+            assert_eq!(options.tolerance, [1, 2, 3, 0]);
+            assert_eq!(options.dim, None);
+        }
+
+        #[parameterized(
+            input = {
+                "diff 'input.jpg' size(8);",
+                "diff 'input.jpg' tolerance(1, 2);",
+                "diff 'input.jpg' tolerance(256);",
+                "diff 'input.jpg' same(255, 255, 255);",
+                "diff 'input.jpg' dim(1.5);",
+                "diff 'input.jpg' unknown(1);",
+            }
+        )]
+        fn test_diff_with_invalid_options(input: &str) {
+            let pairs = SICParser::parse(Rule::main, input)
+                .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+
+            assert!(parse_image_operations(pairs).is_err());
+        }
+    }
+
+    #[cfg(test)]
+    mod convert_profile_test {
+        use super::*;
+
+        ide!();
+
+        #[parameterized(
+            input = {
+                "convert-profile srgb;",
+                "convert-profile SRGB;",
+                "convert-profile 'srgb';",
+                "convert-profile \"/my/path/display_p3.icc\";",
+                "convert-profile 'C:\\Users\\Some Name\\profile.icc';",
+            },
+            expected = {
+                IccProfileTarget::Srgb,
+                IccProfileTarget::Srgb,
+                IccProfileTarget::Srgb,
+                IccProfileTarget::Path("/my/path/display_p3.icc".into()),
+                IccProfileTarget::Path("C:\\Users\\Some Name\\profile.icc".into()),
+            }
+        )]
+        fn test_convert_profile_ok(input: &str, expected: IccProfileTarget) {
+            let pairs = SICParser::parse(Rule::main, input)
+                .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+
+            assert_eq!(
+                parse_image_operations(pairs).unwrap(),
+                vec![Instr::Operation(ImgOp::ConvertProfile(expected))]
+            );
+        }
+
+        #[parameterized(
+            input = {
+                "convert-profile;",
+                "convert-profile adobe-rgb;",
+                "convert-profile '/my/path/profile.icc'';",
+            }
+        )]
+        fn test_convert_profile_err(input: &str) {
+            let pairs = SICParser::parse(Rule::main, input);
+            assert!(pairs.is_err());
+        }
+    }
+
+    mod frame_timing_test {
+        use super::*;
+        use std::time::Duration;
+
+        ide!();
+
+        #[parameterized(
+            input = {
+                "set-frame-delay 50ms;",
+                "set-frame-delay 50MS;",
+                "set-frame-delay 1.5s;",
+                "set-frame-delay 0ms;",
+            },
+            expected = {
+                Duration::from_millis(50),
+                Duration::from_millis(50),
+                Duration::from_millis(1500),
+                Duration::from_millis(0),
+            }
+        )]
+        fn test_set_frame_delay_ok(input: &str, expected: Duration) {
+            let pairs = SICParser::parse(Rule::main, input)
+                .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+
+            assert_eq!(
+                parse_image_operations(pairs).unwrap(),
+                vec![Instr::Operation(ImgOp::SetFrameDelay(FrameDelay::new(
+                    expected
+                )))]
+            );
+        }
+
+        #[parameterized(
+            input = {
+                "set-frame-delay;",
+                "set-frame-delay 50;",
+                "set-frame-delay 50 ms;",
+                "set-frame-delay 1m;",
+            }
+        )]
+        fn test_set_frame_delay_err(input: &str) {
+            let pairs = SICParser::parse(Rule::main, input);
+            assert!(pairs.is_err());
+        }
+
+        #[test]
+        fn test_set_frame_delay_negative_err() {
+            let pairs = SICParser::parse(Rule::main, "set-frame-delay -50ms;")
+                .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+
+            assert!(parse_image_operations(pairs).is_err());
+        }
+
+        #[parameterized(
+            input = { "speed 2;", "speed 0.5;", "speed -1;" },
+            expected = { 2.0, 0.5, -1.0 }
+        )]
+        fn test_speed_ok(input: &str, expected: f32) {
+            let pairs = SICParser::parse(Rule::main, input)
+                .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+
+            assert_eq!(
+                parse_image_operations(pairs).unwrap(),
+                vec![Instr::Operation(ImgOp::Speed(expected))]
+            );
+        }
+
+        #[test]
+        fn test_reverse_frames() {
+            let pairs = SICParser::parse(Rule::main, "reverse-frames;\nspeed 2")
+                .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+
+            assert_eq!(
+                parse_image_operations(pairs).unwrap(),
+                vec![
+                    Instr::Operation(ImgOp::ReverseFrames),
+                    Instr::Operation(ImgOp::Speed(2.0))
+                ]
+            );
+        }
+    }
+
+    mod frame_selection_test {
+        use super::*;
+
+        ide!();
+
+        fn range(input: &str) -> FrameRange {
+            FrameRange::try_from_str(input).unwrap()
+        }
+
+        #[parameterized(
+            input = {
+                "drop-frames 3;",
+                "drop-frames 0..5;",
+                "drop-frames 2..=4;",
+                "drop-frames 10..;",
+                "drop-frames ::2;",
+                "drop-frames 1:9:3;",
+            },
+            expected = { "3", "0..5", "2..=4", "10..", "::2", "1:9:3" }
+        )]
+        fn test_drop_frames_ok(input: &str, expected: &str) {
+            let pairs = SICParser::parse(Rule::main, input)
+                .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+
+            assert_eq!(
+                parse_image_operations(pairs).unwrap(),
+                vec![Instr::Operation(ImgOp::DropFrames(range(expected)))]
+            );
+        }
+
+        #[parameterized(
+            input = {
+                "drop-frames;",
+                "drop-frames -1;",
+                "drop-frames 1..2..3;",
+                "drop-frames 1:2:3:4;",
+                "drop-frames a..b;",
+            }
+        )]
+        fn test_drop_frames_err(input: &str) {
+            let pairs = SICParser::parse(Rule::main, input);
+            assert!(pairs.is_err());
+        }
+
+        #[parameterized(input = { "drop-frames ::0;", "drop-frames 1..=;" })]
+        fn test_drop_frames_invalid_range_err(input: &str) {
+            let pairs = SICParser::parse(Rule::main, input)
+                .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+
+            assert!(parse_image_operations(pairs).is_err());
+        }
+
+        #[parameterized(
+            input = {
+                "on-frames 0..3 { blur 2 }",
+                "on-frames 0..3 { blur 2; }",
+                "on-frames 0..3 {blur 2}",
+                "on-frames 0..3 {\n  blur 2;\n}",
+            }
+        )]
+        fn test_on_frames_single_statement(input: &str) {
+            let pairs = SICParser::parse(Rule::main, input)
+                .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+
+            assert_eq!(
+                parse_image_operations(pairs).unwrap(),
+                vec![Instr::Operation(ImgOp::OnFrames(
+                    range("0..3"),
+                    vec![Instr::Operation(ImgOp::Blur(2.0))]
+                ))]
+            );
+        }
+
+        #[test]
+        fn test_on_frames_multiple_statements() {
+            let input = "flip-vertical;\non-frames ::2 {\n  set preserve-aspect-ratio true;\n  \
+                         invert;\n  on-frames 1 { grayscale }\n};\nrotate90";
+            let pairs = SICParser::parse(Rule::main, input)
+                .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+
+            assert_eq!(
+                parse_image_operations(pairs).unwrap(),
+                vec![
+                    Instr::Operation(ImgOp::FlipVertical),
+                    Instr::Operation(ImgOp::OnFrames(
+                        range("::2"),
+                        vec![
+                            Instr::EnvAdd(EnvItem::PreserveAspectRatio(true)),
+                            Instr::Operation(ImgOp::Invert),
+                            Instr::Operation(ImgOp::OnFrames(
+                                range("1"),
+                                vec![Instr::Operation(ImgOp::Grayscale)]
+                            )),
+                        ]
+                    )),
+                    Instr::Operation(ImgOp::Rotate90),
+                ]
+            );
+        }
+
+        #[test]
+        fn test_on_frames_empty_block() {
+            let pairs = SICParser::parse(Rule::main, "on-frames 0 {}")
+                .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+
+            assert_eq!(
+                parse_image_operations(pairs).unwrap(),
+                vec![Instr::Operation(ImgOp::OnFrames(range("0"), vec![]))]
+            );
+        }
+
+        #[parameterized(
+            input = {
+                "on-frames { blur 2 }",
+                "on-frames 0..3 blur 2",
+                "on-frames 0..3 { blur 2 blur 3 }",
+                "on-frames 0..3 { blur 2",
+                "on-frames0..3 { blur 2 }",
+                "on-frames 0..3 { blur 2 } invert",
+            }
+        )]
+        fn test_on_frames_err(input: &str) {
+            let pairs = SICParser::parse(Rule::main, input);
+            assert!(pairs.is_err());
+        }
     }
 
     #[test]
@@ -1007,6 +1619,309 @@ mod tests {
         }
     }
 
+    mod pad_test {
+        use super::*;
+        use sic_core::image::Rgba;
+        use sic_image_engine::wrapper::pad::PadInputs;
+
+        ide!();
+
+        fn margins(top: u32, right: u32, bottom: u32, left: u32) -> PadSize {
+            PadSize::Margins(Margins {
+                top,
+                right,
+                bottom,
+                left,
+            })
+        }
+
+        #[parameterized(
+            input = {
+                "pad 10 20;",
+                "pad 10 20 north-west;",
+                "pad 10 20 rgba(255, 255, 255, 255);",
+                "PAD 10 20 South rgba(0, 0, 255, 128);",
+                "extend 5;",
+                "extend 1 2 3 4;",
+                "extend 1 2 3 4 rgba(0, 0, 0, 255);",
+            },
+            expected = {
+                PadInputs::new(PadSize::Canvas((10, 20), Gravity::Center)),
+                PadInputs::new(PadSize::Canvas((10, 20), Gravity::NorthWest)),
+                PadInputs::with_fill(PadSize::Canvas((10, 20), Gravity::Center), Rgba([255, 255, 255, 255])),
+                PadInputs::with_fill(PadSize::Canvas((10, 20), Gravity::South), Rgba([0, 0, 255, 128])),
+                PadInputs::new(PadSize::Margins(Margins::uniform(5))),
+                PadInputs::new(margins(1, 2, 3, 4)),
+                PadInputs::with_fill(margins(1, 2, 3, 4), Rgba([0, 0, 0, 255])),
+            }
+        )]
+        fn test_pad_ok(input: &str, expected: PadInputs) {
+            let pairs = SICParser::parse(Rule::main, input)
+                .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+
+            assert_eq!(
+                parse_image_operations(pairs).unwrap(),
+                vec![Instr::Operation(ImgOp::Pad(expected))]
+            );
+        }
+
+        #[parameterized(
+            input = {
+                "pad 10;",
+                "pad 10 20 up;",
+                "pad 10 20 rgba(0, 0, 0, 0) north;",
+                "extend;",
+                "extend 1 2;",
+                "extend 1 2 3;",
+                "extend 1 2 3 4 5;",
+            }
+        )]
+        fn test_pad_err(input: &str) {
+            let pairs = SICParser::parse(Rule::main, input);
+            assert!(pairs.is_err());
+        }
+
+        #[parameterized(
+            input = {
+                "pad 10 20 size(8);",
+                "extend 1 rgba(0, 0, 0);",
+            }
+        )]
+        fn test_pad_with_invalid_fill(input: &str) {
+            let pairs = SICParser::parse(Rule::main, input)
+                .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+
+            assert!(parse_image_operations(pairs).is_err());
+        }
+    }
+
+    mod tone_test {
+        use super::*;
+        use sic_image_engine::wrapper::color_channels::ColorChannels;
+        use sic_image_engine::wrapper::levels::LevelsInputs;
+
+        ide!();
+
+        #[parameterized(
+            input = {
+                "gamma 2.2;",
+                "GAMMA 0.5;",
+                "levels 10 240;",
+                "levels 10 240 1.2;",
+                "levels red 0 200;",
+                "levels Blue 5 250 0.8;",
+                "curves points((0, 0), (128, 150), (255, 255));",
+                "curves points((0,255),(255,0));",
+            },
+            expected = {
+                ImgOp::Gamma(2.2),
+                ImgOp::Gamma(0.5),
+                ImgOp::Levels(LevelsInputs::new(10, 240)),
+                ImgOp::Levels(LevelsInputs::new(10, 240).with_gamma(1.2)),
+                ImgOp::Levels(LevelsInputs::new(0, 200).with_channels(ColorChannels::Red)),
+                ImgOp::Levels(LevelsInputs::new(5, 250).with_gamma(0.8).with_channels(ColorChannels::Blue)),
+                ImgOp::Curves(vec![(0, 0), (128, 150), (255, 255)]),
+                ImgOp::Curves(vec![(0, 255), (255, 0)]),
+            }
+        )]
+        fn test_tone_ok(input: &str, expected: ImgOp) {
+            let pairs = SICParser::parse(Rule::main, input)
+                .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+
+            assert_eq!(
+                parse_image_operations(pairs).unwrap(),
+                vec![Instr::Operation(expected)]
+            );
+        }
+
+        #[parameterized(
+            input = {
+                "gamma;",
+                "gamma fast;",
+                "levels 10;",
+                "levels -1 240;",
+                "levels 10 240 1.2 3;",
+                "levels alpha 10 240;",
+                "levels 10 240 red;",
+                "curves;",
+                "curves (0, 0), (255, 255);",
+                "curves points((0, 0), (255, 255);",
+            }
+        )]
+        fn test_tone_err(input: &str) {
+            let pairs = SICParser::parse(Rule::main, input);
+            assert!(pairs.is_err());
+        }
+
+        #[parameterized(
+            input = {
+                "levels 10 256;",
+                "curves points((0, 0), (256, 255));",
+                "curves points((0, 0, 0), (255, 255));",
+                "curves points(0, 255);",
+                "curves rgba(0, 0, 0, 0);",
+                "diff 'input.jpg' tolerance((1, 2));",
+            }
+        )]
+        fn test_tone_with_invalid_values(input: &str) {
+            let pairs = SICParser::parse(Rule::main, input)
+                .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+
+            assert!(parse_image_operations(pairs).is_err());
+        }
+    }
+
+    mod trim_test {
+        use super::*;
+        use sic_core::image::Rgba;
+        use sic_image_engine::wrapper::trim::TrimInputs;
+
+        ide!();
+
+        #[parameterized(
+            input = {
+                "trim;",
+                "TRIM;",
+                "trim rgba(255, 255, 255, 255);",
+                "trim tolerance(8);",
+                "trim tolerance(1, 2, 3, 0);",
+                "trim margin(4);",
+                "trim margin(4) rgba(0, 0, 0, 0) tolerance(8);",
+            },
+            expected = {
+                TrimInputs::default(),
+                TrimInputs::default(),
+                TrimInputs { color: Some(Rgba([255, 255, 255, 255])), ..TrimInputs::default() },
+                TrimInputs { tolerance: [8; 4], ..TrimInputs::default() },
+                TrimInputs { tolerance: [1, 2, 3, 0], ..TrimInputs::default() },
+                TrimInputs { margin: 4, ..TrimInputs::default() },
+                TrimInputs { color: Some(Rgba([0, 0, 0, 0])), tolerance: [8; 4], margin: 4 },
+            }
+        )]
+        fn test_trim_ok(input: &str, expected: TrimInputs) {
+            let pairs = SICParser::parse(Rule::main, input)
+                .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+
+            assert_eq!(
+                parse_image_operations(pairs).unwrap(),
+                vec![Instr::Operation(ImgOp::Trim(expected))]
+            );
+        }
+
+        #[parameterized(
+            input = {
+                "trim 4;",
+                "trim white;",
+                "trim margin(4) 4;",
+            }
+        )]
+        fn test_trim_err(input: &str) {
+            let pairs = SICParser::parse(Rule::main, input);
+            assert!(pairs.is_err());
+        }
+
+        #[parameterized(
+            input = {
+                "trim size(8);",
+                "trim margin(-1);",
+                "trim margin(1, 2);",
+                "trim rgba(0, 0, 0);",
+            }
+        )]
+        fn test_trim_with_invalid_named_value(input: &str) {
+            let pairs = SICParser::parse(Rule::main, input)
+                .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+
+            assert!(parse_image_operations(pairs).is_err());
+        }
+    }
+
+    mod resize_mode_test {
+        use super::*;
+        use sic_image_engine::wrapper::gravity::Gravity;
+        use sic_image_engine::wrapper::resize_mode::{ResizeMode, ScaleFactor};
+
+        ide!();
+
+        fn scale(percentage: &str) -> ResizeMode {
+            ResizeMode::Scale(ScaleFactor::try_from_str(percentage).unwrap())
+        }
+
+        #[parameterized(
+            input = {
+                "resize exact 10 20;",
+                "resize fit 10 20;",
+                "resize cover 10 20;",
+                "resize cover 10 20 north-west;",
+                "resize COVER 10 20 South;",
+                "resize scale 50%;",
+                "resize scale 12.5%;",
+                "resize max-edge 1024;",
+            },
+            expected = {
+                ResizeMode::Exact((10, 20)),
+                ResizeMode::Fit((10, 20)),
+                ResizeMode::Cover((10, 20), Gravity::Center),
+                ResizeMode::Cover((10, 20), Gravity::NorthWest),
+                ResizeMode::Cover((10, 20), Gravity::South),
+                scale("50%"),
+                scale("12.5%"),
+                ResizeMode::MaxEdge(1024),
+            }
+        )]
+        fn test_resize_with_mode_ok(input: &str, expected: ResizeMode) {
+            let pairs = SICParser::parse(Rule::main, input)
+                .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+
+            assert_eq!(
+                parse_image_operations(pairs).unwrap(),
+                vec![Instr::Operation(ImgOp::ResizeWithMode(expected))]
+            );
+        }
+
+        #[parameterized(
+            input = {
+                "resize fit 10;",
+                "resize fit 10 20 north;",
+                "resize cover 10 20 up;",
+                "resize scale 50;",
+                "resize scale;",
+                "resize max-edge 10 20;",
+                "resize stretch 10 20;",
+            }
+        )]
+        fn test_resize_with_mode_err(input: &str) {
+            let pairs = SICParser::parse(Rule::main, input);
+            assert!(pairs.is_err());
+        }
+
+        #[parameterized(input = { "resize scale 0%;", "resize scale -50%;" })]
+        fn test_resize_scale_not_positive_err(input: &str) {
+            let pairs = SICParser::parse(Rule::main, input)
+                .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+
+            assert!(parse_image_operations(pairs).is_err());
+        }
+
+        #[test]
+        fn test_set_and_del_shrink_only() {
+            let pairs = SICParser::parse(
+                Rule::main,
+                "set shrink-only true;\nresize fit 100 200;\ndel shrink-only;",
+            )
+            .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+
+            assert_eq!(
+                parse_image_operations(pairs).unwrap(),
+                vec![
+                    Instr::EnvAdd(EnvItem::ShrinkOnly(true)),
+                    Instr::Operation(ImgOp::ResizeWithMode(ResizeMode::Fit((100, 200)))),
+                    Instr::EnvRemove(ItemName::ShrinkOnly),
+                ]
+            );
+        }
+    }
+
     #[test]
     fn test_resize_single_stmt_parse_correct() {
         let pairs = SICParser::parse(Rule::main, "resize 99 88;")
@@ -1047,6 +1962,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_smart_crop_single_stmt_parse_correct() {
+        let pairs = SICParser::parse(Rule::main, "smart-crop 16 9;")
+            .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+        assert_eq!(
+            vec![Instr::Operation(ImgOp::SmartCrop((16, 9)))],
+            parse_image_operations(pairs).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_smart_crop_requires_two_arguments() {
+        let pairs = SICParser::parse(Rule::main, "smart-crop 16;");
+        assert!(pairs.is_err());
+    }
+
     #[test]
     fn test_unsharpen_single_stmt_parse_correct_ints() {
         let pairs = SICParser::parse(Rule::main, "unsharpen 99 88;")
@@ -1419,6 +2350,93 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "imageproc-ops")]
+    mod rotate_test {
+        use super::*;
+        use sic_core::image::Rgba;
+        use sic_image_engine::wrapper::rotate::{RotateCanvas, RotateInputs};
+
+        ide!();
+
+        fn filter(name: &str) -> FilterTypeWrap {
+            FilterTypeWrap::try_from_str(name).unwrap()
+        }
+
+        #[parameterized(
+            input = {
+                "rotate 45;",
+                "rotate -30.5;",
+                "rotate 45 crop;",
+                "rotate 45 EXPAND;",
+                "rotate 45 nearest;",
+                "rotate 45 rgba(255, 255, 255, 255);",
+                "rotate 45 crop triangle rgba(0, 0, 0, 255);",
+            },
+            expected = {
+                RotateInputs::new(45.0),
+                RotateInputs::new(-30.5),
+                RotateInputs::new(45.0).with_canvas(RotateCanvas::Crop),
+                RotateInputs::new(45.0).with_canvas(RotateCanvas::Expand),
+                RotateInputs::new(45.0).with_interpolation(filter("nearest")),
+                RotateInputs::new(45.0).with_fill(Rgba([255, 255, 255, 255])),
+                RotateInputs::new(45.0)
+                    .with_canvas(RotateCanvas::Crop)
+                    .with_interpolation(filter("triangle"))
+                    .with_fill(Rgba([0, 0, 0, 255])),
+            }
+        )]
+        fn test_rotate_ok(input: &str, expected: RotateInputs) {
+            let pairs = SICParser::parse(Rule::main, input)
+                .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+
+            assert_eq!(
+                parse_image_operations(pairs).unwrap(),
+                vec![Instr::Operation(ImgOp::Rotate(expected))]
+            );
+        }
+
+        #[parameterized(
+            input = {
+                "rotate;",
+                "rotate 45 grow;",
+                "rotate 45 nearest crop;",
+                "rotate 45 rgba(0, 0, 0, 0) crop;",
+                "rotate 45 45;",
+            }
+        )]
+        fn test_rotate_err(input: &str) {
+            let pairs = SICParser::parse(Rule::main, input);
+            assert!(pairs.is_err());
+        }
+
+        #[parameterized(
+            input = {
+                "rotate 45 size(8);",
+                "rotate 45 rgba(0, 0, 0);",
+            }
+        )]
+        fn test_rotate_with_invalid_fill(input: &str) {
+            let pairs = SICParser::parse(Rule::main, input)
+                .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+
+            assert!(parse_image_operations(pairs).is_err());
+        }
+
+        #[test]
+        fn test_rotate_does_not_replace_rotate90() {
+            let pairs = SICParser::parse(Rule::main, "rotate90; rotate 90;")
+                .unwrap_or_else(|e| panic!("Unable to parse sic image operations script: {:?}", e));
+
+            assert_eq!(
+                parse_image_operations(pairs).unwrap(),
+                vec![
+                    Instr::Operation(ImgOp::Rotate90),
+                    Instr::Operation(ImgOp::Rotate(RotateInputs::new(90.0))),
+                ]
+            );
+        }
+    }
+
     #[cfg(feature = "imageproc-ops")]
     mod imageproc_ops_tests {
         use super::*;
@@ -1,11 +1,24 @@
 use crate::errors::SicParserError;
+use crate::named_value::NamedValue;
+use sic_core::image::Rgba;
+use sic_core::FrameRange;
+use sic_image_engine::wrapper::color_channels::ColorChannels;
+use sic_image_engine::wrapper::diff::{DiffInputs, DiffOptions};
+use sic_image_engine::wrapper::frame_delay::FrameDelay;
+use sic_image_engine::wrapper::gravity::Gravity;
+use sic_image_engine::wrapper::icc_profile::IccProfileTarget;
 use sic_image_engine::wrapper::image_path::ImageFromPath;
+use sic_image_engine::wrapper::pad::{PadInputs, PadSize};
+use sic_image_engine::wrapper::resize_mode::ScaleFactor;
+use sic_image_engine::wrapper::trim::TrimInputs;
 use sic_image_engine::wrapper::{filter_type::FilterTypeWrap, gradient_input::GradientInput};
 use std::path::PathBuf;
 
 #[cfg(feature = "imageproc-ops")]
 use sic_image_engine::wrapper::draw_text_inner::DrawTextInner;
 use sic_image_engine::wrapper::overlay::OverlayInputs;
+#[cfg(feature = "imageproc-ops")]
+use sic_image_engine::wrapper::rotate::RotateCanvas;
 
 /// The value parser module has a goal to parse image operation inputs.
 
@@ -186,6 +199,47 @@ impl ParseInputsFromIter for (u32, u32) {
     }
 }
 
+// for: levels
+impl ParseInputsFromIter for (u8, u8) {
+    type Error = SicParserError;
+
+    fn parse<'a, T>(iterable: T) -> Result<Self, Self::Error>
+    where
+        T: IntoIterator,
+        T::Item: Into<Describable<'a>> + std::fmt::Debug,
+        Self: std::marker::Sized,
+    {
+        let mut iter = iterable.into_iter();
+        const ERR_MSG: &str = "Unable to map a value to (u8, u8). v2";
+
+        let res: (u8, u8) = (
+            parse_next!(iter, u8, ERR_MSG),
+            parse_next!(iter, u8, ERR_MSG),
+        );
+
+        return_if_complete!(iter, res)
+    }
+}
+
+// for: curves
+impl ParseInputsFromIter for Vec<(u8, u8)> {
+    type Error = SicParserError;
+
+    fn parse<'a, T>(iterable: T) -> Result<Self, Self::Error>
+    where
+        T: IntoIterator,
+        T::Item: Into<Describable<'a>> + std::fmt::Debug,
+        Self: std::marker::Sized,
+    {
+        let mut iter = iterable.into_iter();
+        let points = parse_next!(iter, NamedValue, "Points")
+            .extract_points()
+            .map_err(SicParserError::NamedValueParsingError)?;
+
+        return_if_complete!(iter, points)
+    }
+}
+
 // for: unsharpen
 impl ParseInputsFromIter for (f32, i32) {
     type Error = SicParserError;
@@ -267,6 +321,123 @@ impl ParseInputsFromIter for ImageFromPath {
     }
 }
 
+// The image path, optionally followed by named values which set the options of the diff, e.g.
+// `tolerance(8)` or `different(0, 0, 255, 255)`.
+impl ParseInputsFromIter for DiffInputs {
+    type Error = SicParserError;
+
+    fn parse<'a, T>(iterable: T) -> Result<Self, Self::Error>
+    where
+        T: IntoIterator,
+        T::Item: Into<Describable<'a>> + std::fmt::Debug,
+        Self: std::marker::Sized,
+    {
+        let mut iter = iterable.into_iter();
+        let image_path = parse_to_path_buf(iter.next().map(Into::<Describable>::into))?;
+
+        let named_values = iter
+            .map(|value| {
+                let value: Describable = value.into();
+                value
+                    .0
+                    .parse::<NamedValue>()
+                    .map_err(SicParserError::NamedValueParsingError)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(DiffInputs::with_options(
+            ImageFromPath::new(image_path),
+            diff_options(named_values)?,
+        ))
+    }
+}
+
+/// Creates the options of a diff from the given named values; options which are not given keep
+/// their default value.
+pub(crate) fn diff_options(
+    named_values: impl IntoIterator<Item = NamedValue>,
+) -> Result<DiffOptions, SicParserError> {
+    named_values
+        .into_iter()
+        .try_fold(DiffOptions::default(), |mut options, named_value| {
+            match named_value {
+                NamedValue::Tolerance(tolerance) => options.tolerance = tolerance,
+                NamedValue::Same(color) => options.same = Rgba(color),
+                NamedValue::Different(color) => options.different = Rgba(color),
+                NamedValue::NoOverlap(color) => options.no_overlap = Rgba(color),
+                NamedValue::Dim(factor) => options.dim = Some(factor),
+                other => {
+                    return Err(SicParserError::UnsupportedNamedValue(
+                        other.error_type(),
+                        String::from("diff"),
+                    ))
+                }
+            }
+
+            Ok(options)
+        })
+}
+
+/// Creates the inputs of a pad operation; without a named value, the canvas is filled with
+/// transparent pixels.
+pub(crate) fn pad_inputs(
+    size: PadSize,
+    fill: Option<NamedValue>,
+) -> Result<PadInputs, SicParserError> {
+    match fill {
+        None => Ok(PadInputs::new(size)),
+        Some(NamedValue::Rgba(r, g, b, a)) => Ok(PadInputs::with_fill(size, Rgba([r, g, b, a]))),
+        Some(other) => Err(SicParserError::UnsupportedNamedValue(
+            other.error_type(),
+            String::from("pad"),
+        )),
+    }
+}
+
+pub(crate) fn trim_inputs(
+    named_values: impl IntoIterator<Item = NamedValue>,
+) -> Result<TrimInputs, SicParserError> {
+    named_values
+        .into_iter()
+        .try_fold(TrimInputs::default(), |mut inputs, named_value| {
+            match named_value {
+                NamedValue::Rgba(r, g, b, a) => inputs.color = Some(Rgba([r, g, b, a])),
+                NamedValue::Tolerance(tolerance) => inputs.tolerance = tolerance,
+                NamedValue::Margin(margin) => inputs.margin = margin,
+                other => {
+                    return Err(SicParserError::UnsupportedNamedValue(
+                        other.error_type(),
+                        String::from("trim"),
+                    ))
+                }
+            }
+
+            Ok(inputs)
+        })
+}
+
+impl ParseInputsFromIter for IccProfileTarget {
+    type Error = SicParserError;
+
+    fn parse<'a, T>(iterable: T) -> Result<Self, Self::Error>
+    where
+        T: IntoIterator,
+        T::Item: Into<Describable<'a>> + std::fmt::Debug,
+        Self: std::marker::Sized,
+    {
+        let mut iter = iterable.into_iter();
+
+        let path = parse_to_path_buf(iter.next().map(Into::<Describable>::into))?;
+        let target = if path.as_os_str().eq_ignore_ascii_case("srgb") {
+            IccProfileTarget::Srgb
+        } else {
+            IccProfileTarget::Path(path)
+        };
+
+        return_if_complete!(iter, target)
+    }
+}
+
 impl ParseInputsFromIter for OverlayInputs {
     type Error = SicParserError;
 
@@ -324,6 +495,174 @@ impl ParseInputsFromIter for FilterTypeWrap {
     }
 }
 
+impl ParseInputsFromIter for FrameDelay {
+    type Error = SicParserError;
+
+    fn parse<'a, T>(iterable: T) -> Result<Self, Self::Error>
+    where
+        T: IntoIterator,
+        T::Item: Into<Describable<'a>> + std::fmt::Debug,
+        Self: std::marker::Sized,
+    {
+        let mut iter = iterable.into_iter();
+
+        let err_msg_no_such_element =
+            || "A frame delay was expected but none was found.".to_string();
+
+        let delay = iter
+            .next()
+            .map(Into::<Describable>::into)
+            .ok_or_else(|| SicParserError::ValueParsingError(err_msg_no_such_element()))
+            .and_then(|v: Describable| {
+                FrameDelay::try_from_str(v.0).map_err(|err| {
+                    SicParserError::ValueParsingErrorWithInnerError(v.0.to_string(), Box::new(err))
+                })
+            })?;
+
+        return_if_complete!(iter, delay)
+    }
+}
+
+impl ParseInputsFromIter for FrameRange {
+    type Error = SicParserError;
+
+    fn parse<'a, T>(iterable: T) -> Result<Self, Self::Error>
+    where
+        T: IntoIterator,
+        T::Item: Into<Describable<'a>> + std::fmt::Debug,
+        Self: std::marker::Sized,
+    {
+        let mut iter = iterable.into_iter();
+
+        let err_msg_no_such_element =
+            || "A frame range was expected but none was found.".to_string();
+
+        let range = iter
+            .next()
+            .map(Into::<Describable>::into)
+            .ok_or_else(|| SicParserError::ValueParsingError(err_msg_no_such_element()))
+            .and_then(|v: Describable| {
+                FrameRange::try_from_str(v.0).map_err(|err| {
+                    SicParserError::ValueParsingErrorWithInnerError(v.0.to_string(), Box::new(err))
+                })
+            })?;
+
+        return_if_complete!(iter, range)
+    }
+}
+
+impl ParseInputsFromIter for Gravity {
+    type Error = SicParserError;
+
+    fn parse<'a, T>(iterable: T) -> Result<Self, Self::Error>
+    where
+        T: IntoIterator,
+        T::Item: Into<Describable<'a>> + std::fmt::Debug,
+        Self: std::marker::Sized,
+    {
+        let mut iter = iterable.into_iter();
+
+        let err_msg_no_such_element = || "A gravity was expected but none was found.".to_string();
+
+        let gravity = iter
+            .next()
+            .map(Into::<Describable>::into)
+            .ok_or_else(|| SicParserError::ValueParsingError(err_msg_no_such_element()))
+            .and_then(|v: Describable| {
+                Gravity::try_from_str(v.0).map_err(|err| {
+                    SicParserError::ValueParsingErrorWithInnerError(v.0.to_string(), Box::new(err))
+                })
+            })?;
+
+        return_if_complete!(iter, gravity)
+    }
+}
+
+#[cfg(feature = "imageproc-ops")]
+impl ParseInputsFromIter for RotateCanvas {
+    type Error = SicParserError;
+
+    fn parse<'a, T>(iterable: T) -> Result<Self, Self::Error>
+    where
+        T: IntoIterator,
+        T::Item: Into<Describable<'a>> + std::fmt::Debug,
+        Self: std::marker::Sized,
+    {
+        let mut iter = iterable.into_iter();
+
+        let err_msg_no_such_element =
+            || "A rotate canvas (expand or crop) was expected but none was found.".to_string();
+
+        let canvas = iter
+            .next()
+            .map(Into::<Describable>::into)
+            .ok_or_else(|| SicParserError::ValueParsingError(err_msg_no_such_element()))
+            .and_then(|v: Describable| {
+                RotateCanvas::try_from_str(v.0).map_err(|err| {
+                    SicParserError::ValueParsingErrorWithInnerError(v.0.to_string(), Box::new(err))
+                })
+            })?;
+
+        return_if_complete!(iter, canvas)
+    }
+}
+
+impl ParseInputsFromIter for ColorChannels {
+    type Error = SicParserError;
+
+    fn parse<'a, T>(iterable: T) -> Result<Self, Self::Error>
+    where
+        T: IntoIterator,
+        T::Item: Into<Describable<'a>> + std::fmt::Debug,
+        Self: std::marker::Sized,
+    {
+        let mut iter = iterable.into_iter();
+
+        let err_msg_no_such_element =
+            || "A color channel (red, green or blue) was expected but none was found.".to_string();
+
+        let channels = iter
+            .next()
+            .map(Into::<Describable>::into)
+            .ok_or_else(|| SicParserError::ValueParsingError(err_msg_no_such_element()))
+            .and_then(|v: Describable| {
+                ColorChannels::try_from_str(v.0).map_err(|err| {
+                    SicParserError::ValueParsingErrorWithInnerError(v.0.to_string(), Box::new(err))
+                })
+            })?;
+
+        return_if_complete!(iter, channels)
+    }
+}
+
+impl ParseInputsFromIter for ScaleFactor {
+    type Error = SicParserError;
+
+    fn parse<'a, T>(iterable: T) -> Result<Self, Self::Error>
+    where
+        T: IntoIterator,
+        T::Item: Into<Describable<'a>> + std::fmt::Debug,
+        Self: std::marker::Sized,
+    {
+        let mut iter = iterable.into_iter();
+
+        let err_msg_no_such_element =
+            || "A scale factor was expected but none was found.".to_string();
+
+        let scale = iter
+            .next()
+            .map(Into::<Describable>::into)
+            .ok_or_else(|| SicParserError::ValueParsingError(err_msg_no_such_element()))
+            .and_then(|v: Describable| {
+                ScaleFactor::try_from_str(v.0).map_err(|err| {
+                    SicParserError::ValueParsingErrorWithInnerError(v.0.to_string(), Box::new(err))
+                })
+            })?;
+
+        return_if_complete!(iter, scale)
+    }
+}
+
 fn parse_to_path_buf(value: Option<Describable>) -> Result<PathBuf, SicParserError> {
     let err_msg_no_such_element = || "A path was expected but none was found.".to_string();
 
@@ -0,0 +1,474 @@
+//! Golden image (snapshot) tests: the output of an image operation is compared with a stored
+//! reference image.
+//!
+//! References are stored as RGBA8 PNG images; a static image has a single reference
+//! `<name>.png`, while each frame of an animated image is stored as `<name>/<index>.png`. The
+//! output is compared with its reference in RGBA8 as well, so the color type of the output is not
+//! part of the comparison.
+//!
+//! When the environment variable `SIC_BLESS` is set (to any value other than `0`), missing or
+//! mismatching references are (re)written from the output instead, e.g. after adding a test, or
+//! after an intended change of an operation. When a comparison fails, the output and a diff image,
+//! in which the pixels which differ are red, are written to the artifact directory.
+//!
+//! ```no_run
+//! use sic_testing::golden::Golden;
+//! # let image: sic_core::SicImage = sic_testing::open_test_image(sic_testing::in_!("rainbow_8x6.bmp"));
+//!
+//! Golden::new("tests/golden")
+//!     .with_tolerance(1)
+//!     .assert_matches("blur", &image);
+//! ```
+
+use sic_core::image::{self, Pixel, Rgba, RgbaImage};
+use sic_core::SicImage;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// The environment variable which enables the blessing of references.
+pub const BLESS_ENV_VAR: &str = "SIC_BLESS";
+
+#[derive(Debug, Error)]
+pub enum GoldenError {
+    #[error("No reference image found at '{0}' (set {BLESS_ENV_VAR}=1 to create it)")]
+    MissingReference(PathBuf),
+
+    #[error("Expected {expected} frame(s), but the image has {actual} frame(s)")]
+    FrameCountMismatch { expected: usize, actual: usize },
+
+    #[error(
+        "Expected frame {frame} to have dimensions {expected:?}, but it has dimensions {actual:?}"
+    )]
+    DimensionMismatch {
+        frame: usize,
+        expected: (u32, u32),
+        actual: (u32, u32),
+    },
+
+    #[error("{0}")]
+    PixelMismatch(PixelMismatch),
+
+    #[error("Unable to read or write golden image '{0}': {1}")]
+    Image(PathBuf, image::ImageError),
+
+    #[error("Unable to create directory '{0}': {1}")]
+    Io(PathBuf, std::io::Error),
+}
+
+/// The pixels of a frame which differ more than the tolerance from the reference.
+#[derive(Debug)]
+pub struct PixelMismatch {
+    pub frame: usize,
+    pub differing_pixels: usize,
+    pub max_difference: u8,
+    pub diff: RgbaImage,
+}
+
+impl fmt::Display for PixelMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} pixel(s) of frame {} differ from the reference, by up to {}",
+            self.differing_pixels, self.frame, self.max_difference
+        )
+    }
+}
+
+/// Compares images with the references in a directory.
+#[derive(Clone, Debug)]
+pub struct Golden {
+    references: PathBuf,
+    artifacts: PathBuf,
+    tolerance: u8,
+    max_differing_pixels: usize,
+}
+
+impl Golden {
+    /// References are read from (and blessed into) the given directory. Artifacts of failed
+    /// comparisons are written to `sic_golden` in the temporary directory, unless set with
+    /// [`Golden::with_artifact_dir`].
+    pub fn new<P: Into<PathBuf>>(references: P) -> Self {
+        Self {
+            references: references.into(),
+            artifacts: std::env::temp_dir().join("sic_golden"),
+            tolerance: 0,
+            max_differing_pixels: 0,
+        }
+    }
+
+    pub fn with_artifact_dir<P: Into<PathBuf>>(mut self, artifacts: P) -> Self {
+        self.artifacts = artifacts.into();
+        self
+    }
+
+    /// The largest difference of any channel, for which a pixel still matches its reference.
+    pub fn with_tolerance(mut self, tolerance: u8) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// The number of pixels of each frame which may differ more than the tolerance.
+    pub fn with_max_differing_pixels(mut self, max_differing_pixels: usize) -> Self {
+        self.max_differing_pixels = max_differing_pixels;
+        self
+    }
+
+    /// Asserts that the image matches the reference with the given name. The reference is written
+    /// instead if blessing is enabled. On failure, the image and a diff image are written to the
+    /// artifact directory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image does not match its reference.
+    #[track_caller]
+    pub fn assert_matches(&self, name: &str, image: &SicImage) {
+        let frames = frames_of(image);
+
+        if is_blessing() {
+            if let Err(err) = self.bless(name, &frames) {
+                panic!("Unable to bless golden image '{}': {}", name, err);
+            }
+
+            return;
+        }
+
+        if let Err(err) = self.compare_frames(name, &frames) {
+            let artifacts = self.write_artifacts(name, &frames, &err);
+
+            panic!(
+                "Golden image '{}' does not match: {}\n{}",
+                name,
+                err,
+                artifacts.unwrap_or_else(|err| format!("Unable to write artifacts: {}", err))
+            );
+        }
+    }
+
+    /// Compares the image with the reference with the given name, without blessing or writing
+    /// artifacts.
+    pub fn compare(&self, name: &str, image: &SicImage) -> Result<(), GoldenError> {
+        self.compare_frames(name, &frames_of(image))
+    }
+
+    fn compare_frames(&self, name: &str, frames: &[RgbaImage]) -> Result<(), GoldenError> {
+        let references = self.read_references(name)?;
+
+        if references.len() != frames.len() {
+            return Err(GoldenError::FrameCountMismatch {
+                expected: references.len(),
+                actual: frames.len(),
+            });
+        }
+
+        for (index, (expected, actual)) in references.iter().zip(frames).enumerate() {
+            self.compare_frame(index, expected, actual)?;
+        }
+
+        Ok(())
+    }
+
+    fn compare_frame(
+        &self,
+        frame: usize,
+        expected: &RgbaImage,
+        actual: &RgbaImage,
+    ) -> Result<(), GoldenError> {
+        if expected.dimensions() != actual.dimensions() {
+            return Err(GoldenError::DimensionMismatch {
+                frame,
+                expected: expected.dimensions(),
+                actual: actual.dimensions(),
+            });
+        }
+
+        let mut differing_pixels = 0;
+        let mut max_difference = 0;
+
+        let diff = RgbaImage::from_fn(actual.width(), actual.height(), |x, y| {
+            let expected = expected.get_pixel(x, y);
+            let difference = max_channel_difference(expected, actual.get_pixel(x, y));
+            max_difference = max_difference.max(difference);
+
+            if difference > self.tolerance {
+                differing_pixels += 1;
+                Rgba([255, 0, 0, 255])
+            } else {
+                // the matching pixels are shown as a faded grayscale version of the reference
+                let luma = expected.to_luma().0[0] / 4 + 160;
+                Rgba([luma, luma, luma, 255])
+            }
+        });
+
+        if differing_pixels > self.max_differing_pixels {
+            return Err(GoldenError::PixelMismatch(PixelMismatch {
+                frame,
+                differing_pixels,
+                max_difference,
+                diff,
+            }));
+        }
+
+        Ok(())
+    }
+
+    fn read_references(&self, name: &str) -> Result<Vec<RgbaImage>, GoldenError> {
+        let still = self.still_path(name);
+
+        if still.is_file() {
+            return Ok(vec![read_image(&still)?]);
+        }
+
+        let references = (0..)
+            .map(|index| self.frame_path(name, index))
+            .take_while(|path| path.is_file())
+            .map(|path| read_image(&path))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if references.is_empty() {
+            return Err(GoldenError::MissingReference(still));
+        }
+
+        Ok(references)
+    }
+
+    fn bless(&self, name: &str, frames: &[RgbaImage]) -> Result<(), GoldenError> {
+        if self.compare_frames(name, frames).is_ok() {
+            return Ok(());
+        }
+
+        // Remove the previous reference, which may have had a different number of frames.
+        let _ = std::fs::remove_file(self.still_path(name));
+        let _ = std::fs::remove_dir_all(self.references.join(name));
+
+        match frames {
+            [frame] => write_image(&self.still_path(name), frame),
+            frames => frames
+                .iter()
+                .enumerate()
+                .try_for_each(|(index, frame)| write_image(&self.frame_path(name, index), frame)),
+        }
+    }
+
+    /// Writes the image, and the diff image if available, to the artifact directory, and returns a
+    /// description of the written files.
+    fn write_artifacts(
+        &self,
+        name: &str,
+        frames: &[RgbaImage],
+        err: &GoldenError,
+    ) -> Result<String, GoldenError> {
+        let mut written = Vec::new();
+
+        for (index, frame) in frames.iter().enumerate() {
+            let path = self
+                .artifacts
+                .join(format!("{}.{}.actual.png", name, index));
+            write_image(&path, frame)?;
+            written.push(path);
+        }
+
+        if let GoldenError::PixelMismatch(mismatch) = err {
+            let path = self
+                .artifacts
+                .join(format!("{}.{}.diff.png", name, mismatch.frame));
+            write_image(&path, &mismatch.diff)?;
+            written.push(path);
+        }
+
+        Ok(written
+            .iter()
+            .map(|path| format!("Written artifact: {}", path.display()))
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    fn still_path(&self, name: &str) -> PathBuf {
+        self.references.join(format!("{}.png", name))
+    }
+
+    fn frame_path(&self, name: &str, index: usize) -> PathBuf {
+        self.references.join(name).join(format!("{}.png", index))
+    }
+}
+
+fn is_blessing() -> bool {
+    std::env::var_os(BLESS_ENV_VAR).map_or(false, |value| !value.is_empty() && value != "0")
+}
+
+fn frames_of(image: &SicImage) -> Vec<RgbaImage> {
+    match image {
+        SicImage::Static(image) => vec![image.to_rgba8()],
+        SicImage::Animated(animated) => animated
+            .frames()
+            .iter()
+            .map(|frame| frame.buffer().clone())
+            .collect(),
+    }
+}
+
+fn max_channel_difference(lhs: &Rgba<u8>, rhs: &Rgba<u8>) -> u8 {
+    lhs.0
+        .iter()
+        .zip(rhs.0.iter())
+        .map(|(l, r)| l.abs_diff(*r))
+        .max()
+        .unwrap_or(0)
+}
+
+fn read_image(path: &Path) -> Result<RgbaImage, GoldenError> {
+    image::open(path)
+        .map(|image| image.to_rgba8())
+        .map_err(|err| GoldenError::Image(path.to_path_buf(), err))
+}
+
+fn write_image(path: &Path, image: &RgbaImage) -> Result<(), GoldenError> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|err| GoldenError::Io(dir.to_path_buf(), err))?;
+    }
+
+    image
+        .save_with_format(path, image::ImageFormat::Png)
+        .map_err(|err| GoldenError::Image(path.to_path_buf(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sic_core::image::{DynamicImage, Frame};
+    use sic_core::AnimatedImage;
+
+    fn static_image(color: [u8; 4]) -> SicImage {
+        SicImage::Static(DynamicImage::ImageRgba8(RgbaImage::from_pixel(
+            3,
+            2,
+            Rgba(color),
+        )))
+    }
+
+    fn golden(test: &str) -> Golden {
+        let dir = std::env::temp_dir().join("sic_testing_golden").join(test);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        Golden::new(dir.join("references")).with_artifact_dir(dir.join("artifacts"))
+    }
+
+    fn store(golden: &Golden, name: &str, image: &SicImage) {
+        golden.bless(name, &frames_of(image)).unwrap();
+    }
+
+    #[test]
+    fn matches_equal_image() {
+        let golden = golden("matches_equal_image");
+        store(&golden, "image", &static_image([10, 20, 30, 255]));
+
+        assert!(golden
+            .compare("image", &static_image([10, 20, 30, 255]))
+            .is_ok());
+    }
+
+    #[test]
+    fn missing_reference() {
+        let golden = golden("missing_reference");
+
+        assert!(matches!(
+            golden.compare("image", &static_image([0, 0, 0, 255])),
+            Err(GoldenError::MissingReference(_))
+        ));
+    }
+
+    #[test]
+    fn within_tolerance() {
+        let golden = golden("within_tolerance");
+        store(&golden, "image", &static_image([10, 20, 30, 255]));
+
+        let image = static_image([12, 20, 28, 255]);
+
+        assert!(golden.compare("image", &image).is_err());
+        assert!(golden
+            .clone()
+            .with_tolerance(2)
+            .compare("image", &image)
+            .is_ok());
+    }
+
+    #[test]
+    fn max_differing_pixels() {
+        let golden = golden("max_differing_pixels");
+        store(&golden, "image", &static_image([0, 0, 0, 255]));
+
+        let mut image = RgbaImage::from_pixel(3, 2, Rgba([0, 0, 0, 255]));
+        image.put_pixel(1, 1, Rgba([255, 255, 255, 255]));
+        let image = SicImage::Static(DynamicImage::ImageRgba8(image));
+
+        match golden.compare("image", &image) {
+            Err(GoldenError::PixelMismatch(mismatch)) => {
+                assert_eq!(mismatch.differing_pixels, 1);
+                assert_eq!(mismatch.max_difference, 255);
+                assert_eq!(mismatch.diff.get_pixel(1, 1), &Rgba([255, 0, 0, 255]));
+                assert_ne!(mismatch.diff.get_pixel(0, 0), &Rgba([255, 0, 0, 255]));
+            }
+            other => panic!("Expected a pixel mismatch, but got: {:?}", other),
+        }
+
+        assert!(golden
+            .with_max_differing_pixels(1)
+            .compare("image", &image)
+            .is_ok());
+    }
+
+    #[test]
+    fn dimension_mismatch() {
+        let golden = golden("dimension_mismatch");
+        store(&golden, "image", &static_image([0, 0, 0, 255]));
+
+        let image = SicImage::Static(DynamicImage::ImageRgba8(RgbaImage::new(2, 3)));
+
+        assert!(matches!(
+            golden.compare("image", &image),
+            Err(GoldenError::DimensionMismatch {
+                expected: (3, 2),
+                actual: (2, 3),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn animated_image_has_reference_per_frame() {
+        let golden = golden("animated_image_has_reference_per_frame");
+        let frames = [[255, 0, 0, 255], [0, 0, 255, 255]]
+            .map(|color| Frame::new(RgbaImage::from_pixel(2, 2, Rgba(color))));
+        let image = SicImage::Animated(AnimatedImage::from_frames(frames.to_vec()));
+
+        store(&golden, "animated", &image);
+
+        assert!(golden.frame_path("animated", 0).is_file());
+        assert!(golden.frame_path("animated", 1).is_file());
+        assert!(golden.compare("animated", &image).is_ok());
+
+        let fewer_frames = SicImage::Animated(AnimatedImage::from_frames(frames[..1].to_vec()));
+        assert!(matches!(
+            golden.compare("animated", &fewer_frames),
+            Err(GoldenError::FrameCountMismatch {
+                expected: 2,
+                actual: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn artifacts_are_written_on_failure() {
+        let golden = golden("artifacts_are_written_on_failure");
+        store(&golden, "image", &static_image([0, 0, 0, 255]));
+
+        let image = static_image([255, 255, 255, 255]);
+        let frames = frames_of(&image);
+        let err = golden.compare_frames("image", &frames).unwrap_err();
+
+        golden.write_artifacts("image", &frames, &err).unwrap();
+
+        assert!(golden.artifacts.join("image.0.actual.png").is_file());
+        assert!(golden.artifacts.join("image.0.diff.png").is_file());
+    }
+}
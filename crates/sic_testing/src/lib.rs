@@ -8,6 +8,8 @@ pub use parameterized::parameterized as pm;
 use sic_core::image::GenericImageView;
 use sic_core::SicImage;
 
+pub mod golden;
+
 // just enough, absolute tolerance, floating point comparison.
 #[macro_export]
 macro_rules! approx_eq_f32 {
@@ -44,6 +46,12 @@ pub fn clean_up_output_path(test_output_path: &str) {
         .expect("Unable to remove file after test.");
 }
 
+/// Golden image harness of the sic crates: references are stored in `resources/golden`, and the
+/// artifacts of failed comparisons are written to `target/golden`.
+pub fn golden() -> golden::Golden {
+    golden::Golden::new(in_!("golden")).with_artifact_dir(out_!("golden"))
+}
+
 pub fn open_test_image<P: AsRef<Path>>(path: P) -> sic_core::SicImage {
     sic_core::image::open(path.as_ref()).unwrap().into()
 }
@@ -89,7 +97,7 @@ pub trait SicImageDirectAccess {
         self.as_ref().dimensions()
     }
 
-    fn pixels<I: GenericImageView>(&self) -> sic_core::image::Pixels<I>
+    fn pixels<I: GenericImageView>(&self) -> sic_core::image::Pixels<'_, I>
     where
         Self: AsRef<I>,
     {
@@ -1,15 +1,23 @@
 use crate::cli::config::{
-    validate_jpeg_quality, Config, ConfigBuilder, InputOutputModeType, SelectedLicenses,
+    parse_avif_quality, parse_avif_speed, parse_jpeg_quality, parse_webp_quality, Config,
+    ConfigBuilder, InputOutputModeType, SelectedLicenses,
 };
+use crate::cli::output_template::OutputTemplate;
+use crate::cli::output_variant::OutputVariant;
+use crate::cli::stats::StatsFormat;
 use anyhow::anyhow;
 use arg_names::*;
 use clap::{App, AppSettings, Arg, ArgGroup, ArgMatches};
 use sic_cli_ops::create_image_ops;
 use sic_cli_ops::operations::OperationId;
+use sic_core::FrameRange;
+use sic_image_engine::wrapper::frame_delay::FrameDelay;
 use sic_io::decode::FrameIndex;
 use sic_io::encode_settings::gif::RepeatAnimation;
-use std::path::Path;
-use std::str::FromStr;
+use sic_io::encode_settings::png::{PngCompression, PngFilter};
+use sic_io::encode_settings::tiff::TiffCompression;
+use sic_io::metadata::MetadataRetention;
+use std::path::{Path, PathBuf};
 
 macro_rules! define_arg_consts {
     ($mod:ident, { $($argdef:ident),+ $(,)? } ) => {
@@ -26,17 +34,34 @@ define_arg_consts!(arg_names, {
     ARG_LICENSE,
     ARG_DEP_LICENSES,
 
+    // image properties and comparison
+    ARG_INFO,
+    ARG_COMPARE,
+    ARG_COMPARE_THRESHOLD,
+    ARG_JSON,
+
     // input and output images
     ARG_INPUT,
     ARG_INPUT_GLOB,
     ARG_OUTPUT,
     ARG_OUTPUT_GLOB,
+    ARG_OUTPUT_TEMPLATE,
+    ARG_OUTPUT_VARIANT,
+    ARG_EXPLODE_FRAMES,
+    ARG_ASSEMBLE,
 
     // config for glob/batch mode
     ARG_GLOB_NO_SKIP_UNSUPPORTED_EXTENSIONS,
+    ARG_JOBS,
+    ARG_KEEP_GOING,
+    ARG_BATCH_REPORT,
+    ARG_INCREMENTAL,
 
     // set specific configurations for decoding
     ARG_SELECT_FRAME,
+    ARG_FRAMES,
+    ARG_FRAME_DELAY,
+    ARG_DISABLE_AUTOMATIC_ORIENTATION,
 
     // set specific configurations for encoding
     ARG_DISABLE_AUTOMATIC_COLOR_TYPE_ADJUSTMENT,
@@ -44,6 +69,14 @@ define_arg_consts!(arg_names, {
     ARG_JPEG_ENCODING_QUALITY,
     ARG_PNM_ENCODING_ASCII,
     ARG_GIF_REPEAT,
+    ARG_AVIF_QUALITY,
+    ARG_AVIF_SPEED,
+    ARG_PNG_COMPRESSION,
+    ARG_PNG_FILTER,
+    ARG_TIFF_COMPRESSION,
+    ARG_WEBP_QUALITY,
+    ARG_METADATA,
+    ARG_STATS,
 
     // provide image operations using image script
     ARG_APPLY_OPERATIONS,
@@ -71,6 +104,16 @@ fn wrap_with(app: App<'static, 'static>) -> App<'static, 'static> {
             .number_of_values(5)
             .multiple(true),
     )
+    .arg(
+        Arg::with_name(OperationId::Rotate.as_str())
+            .help("Operation: rotate the input image clockwise by the given number of degrees, and expand the canvas to fit the rotated image; the exposed corners are transparent")
+            .long(OperationId::Rotate.as_str())
+            .takes_value(true)
+            .value_name("degrees")
+            .number_of_values(1)
+            .multiple(true)
+            .allow_hyphen_values(true),
+    )
     .arg(
         Arg::with_name(OperationId::Threshold.as_str())
             .help("Operation: Threshold the input image using Otsu's method")
@@ -112,6 +155,51 @@ pub fn create_app(
             .takes_value(false)
             .conflicts_with_all(&[ARG_LICENSE, ARG_INPUT, ARG_OUTPUT, ARG_INPUT_GLOB, ARG_OUTPUT_GLOB]))
 
+        // image properties:
+        .arg(Arg::with_name(ARG_INFO)
+            .long("info")
+            .help("Displays the properties of the input image, such as its format, dimensions, color type and frames, \
+                      instead of writing an output image.")
+            .long_help("Displays the properties of the input image instead of writing an output image: its format, \
+                      dimensions, color type and bit depth, number of frames, and for animated images, the delay of each \
+                      frame and the number of times the animation is played (its loop count). Also displays whether the \
+                      image contains Exif or XMP metadata, or an embedded ICC profile. Use with --input, or pipe the \
+                      image to the stdin.")
+            .takes_value(false)
+            .conflicts_with_all(&[ARG_LICENSE, ARG_DEP_LICENSES, ARG_OUTPUT, ARG_INPUT_GLOB, ARG_OUTPUT_GLOB,
+                ARG_OUTPUT_TEMPLATE, ARG_OUTPUT_VARIANT, ARG_EXPLODE_FRAMES, ARG_ASSEMBLE]))
+        .arg(Arg::with_name(ARG_COMPARE)
+            .long("compare")
+            .value_name("REFERENCE_PATH")
+            .help("Compares the input image, after the image operations have been applied, with the given reference image, \
+                      and displays the PSNR, SSIM, and the mean and maximum absolute error.")
+            .long_help("Compares the input image, after the image operations have been applied, with the given reference \
+                      image, and displays the peak signal-to-noise ratio (PSNR), the structural similarity (SSIM), and the \
+                      mean and maximum absolute error of the samples. Both images should have the same dimensions. If an \
+                      output path is given with --output, a heat map of the differences is written to it, in which the \
+                      error of each pixel is scaled by the maximum error. With --compare-threshold, sic exits with an \
+                      error if the images differ too much.")
+            .takes_value(true)
+            .conflicts_with_all(&[ARG_LICENSE, ARG_DEP_LICENSES, ARG_INFO, ARG_INPUT_GLOB, ARG_OUTPUT_GLOB,
+                ARG_OUTPUT_TEMPLATE, ARG_OUTPUT_VARIANT, ARG_EXPLODE_FRAMES, ARG_ASSEMBLE]))
+        .arg(Arg::with_name(ARG_COMPARE_THRESHOLD)
+            .long("compare-threshold")
+            .value_name("METRIC=VALUE")
+            .help("Exit with an error if the compared images differ more than the given threshold, e.g. 'psnr=40', \
+                      'ssim=0.98', 'mae=1.5' or 'max-error=16'. May be repeated.")
+            .long_help("Exit with an error if the compared images differ more than the given threshold. The PSNR ('psnr') \
+                      and SSIM ('ssim') should not be below their threshold, while the mean absolute error ('mae') and \
+                      maximum absolute error ('max-error') should not be above their threshold, e.g. 'psnr=40', \
+                      'ssim=0.98', 'mae=1.5' or 'max-error=16'. May be repeated.")
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1)
+            .requires(ARG_COMPARE))
+        .arg(Arg::with_name(ARG_JSON)
+            .long("json")
+            .help("Displays the image properties of --info, or the metrics of --compare, as JSON.")
+            .takes_value(false))
+
         // io(input):
         .arg(Arg::with_name(ARG_INPUT)
             .long("input")
@@ -149,6 +237,68 @@ pub fn create_app(
             .conflicts_with_all(&[ARG_LICENSE, ARG_DEP_LICENSES, ARG_INPUT, ARG_OUTPUT])
         )
 
+        .arg(Arg::with_name(ARG_OUTPUT_TEMPLATE)
+            .long("output-template")
+            .value_name("TEMPLATE")
+            .takes_value(true)
+            .help("Derive the output path of each image from the given template, after it has been processed")
+            .long_help("Derive the output path of each image from the given template, after it has been processed. \
+                      The template may contain the placeholders '{dir}' (directory of the input image; in glob mode \
+                      relative to the common directory of the inputs), '{stem}' (file name of the input image without \
+                      extension), '{ext}' (the --output-format, or else the extension of the input image), '{width}' and \
+                      '{height}' (dimensions of the output image) and '{hashN}' (the first N hexadecimal digits of the \
+                      hash of the output image, e.g. '{hash8}'). The output format is determined by the extension of the \
+                      expanded template, unless --output-format is given. In glob mode, the path is relative to the \
+                      --glob-output folder, e.g. '{dir}/{stem}@2x.{ext}' or '{stem}-{width}x{height}.{ext}'.")
+            .conflicts_with_all(&[ARG_LICENSE, ARG_DEP_LICENSES, ARG_OUTPUT, ARG_EXPLODE_FRAMES, ARG_ASSEMBLE])
+        )
+
+        .arg(Arg::with_name(ARG_OUTPUT_VARIANT)
+            .long("output-variant")
+            .value_name("VARIANT")
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1)
+            .help("Write an additional variant of each image, e.g. 'thumb.webp [webp-quality=80]: resize 128 128'. May be repeated.")
+            .long_help("Write an additional variant of each image. A variant consists of an output path, optionally \
+                      followed by encoding options within square brackets, and a colon followed by the image operations \
+                      which are only applied to this variant, e.g. 'thumb.webp [webp-quality=80]: resize 128 128' or \
+                      'full.avif [avif-quality=60, avif-speed=8]: resize 2048 2048'. The input image is decoded, \
+                      and the image operations given by --apply-operations are applied, only once; the image \
+                      operations of each variant are then applied to a copy of the image. The encoding options \
+                      replace the encoding options of the same name for this variant only; valid options are \
+                      'jpeg-encoding-quality', 'pnm-encoding-ascii', 'gif-repeat', 'avif-quality', 'avif-speed', \
+                      'png-compression', 'png-filter', 'tiff-compression' and 'webp-quality'. The output format of \
+                      each variant is determined by the extension of its output path. The output path may contain \
+                      the same placeholders as --output-template, where '{ext}' is the extension of the input image, \
+                      and in glob mode, it is relative to the --glob-output folder. May be repeated to write several \
+                      variants.")
+            .conflicts_with_all(&[ARG_LICENSE, ARG_DEP_LICENSES, ARG_OUTPUT, ARG_OUTPUT_TEMPLATE, ARG_EXPLODE_FRAMES, ARG_ASSEMBLE, ARG_FORCED_OUTPUT_FORMAT])
+        )
+
+        .arg(Arg::with_name(ARG_EXPLODE_FRAMES)
+            .long("explode-frames")
+            .value_name("OUTPUT_PATTERN")
+            .takes_value(true)
+            .help("Write each frame of the (animated) input image to its own output image. The output path of each frame \
+                      is derived from the given pattern, which should contain a '{frame}' placeholder. The placeholder is \
+                      replaced by the zero-indexed frame number, padded with zeros so the output images sort in frame order. \
+                      A fixed number of digits can be given with '{frame:N}'. For example, 'frames/frame_{frame:4}.png' \
+                      results in 'frames/frame_0000.png', 'frames/frame_0001.png', etc.")
+            .conflicts_with_all(&[ARG_LICENSE, ARG_DEP_LICENSES, ARG_OUTPUT, ARG_INPUT_GLOB, ARG_OUTPUT_GLOB])
+        )
+
+        .arg(Arg::with_name(ARG_ASSEMBLE)
+            .long("assemble")
+            .value_name("GLOB_INPUT_PATTERN")
+            .takes_value(true)
+            .help("Assemble an animated image from the images matching the given glob pattern. Each image becomes a \
+                      frame, ordered by its path. The image operations are applied to the assembled animated image. \
+                      Use with --output, and optionally --frame-delay. \
+                      Depending on your shell you may need to add explicit quotation marks around the argument.")
+            .conflicts_with_all(&[ARG_LICENSE, ARG_DEP_LICENSES, ARG_INPUT, ARG_INPUT_GLOB, ARG_OUTPUT_GLOB, ARG_EXPLODE_FRAMES])
+        )
+
         // config for glob/batch mode
         .arg(Arg::with_name(ARG_GLOB_NO_SKIP_UNSUPPORTED_EXTENSIONS)
             .long("no-skip-unsupported-extensions")
@@ -156,6 +306,44 @@ pub fn create_app(
             .long_help("Only has an effect when combined with --glob-input")
             .takes_value(false)
         )
+        .arg(Arg::with_name(ARG_JOBS)
+            .long("jobs")
+            .short("j")
+            .value_name("N")
+            .help("Number of images which are processed concurrently in glob mode")
+            .long_help("Number of images which are processed concurrently in glob mode. Defaults to 1, \
+                        which processes the images one after another. Only has an effect when combined \
+                        with --glob-input.")
+            .takes_value(true)
+        )
+        .arg(Arg::with_name(ARG_KEEP_GOING)
+            .long("keep-going")
+            .help("Continue processing the remaining images in glob mode when an image fails")
+            .long_help("Continue processing the remaining images in glob mode when an image fails. Once all \
+                        images have been processed, the failed images are listed, and sic exits with a \
+                        non-zero exit code. Only has an effect when combined with --glob-input.")
+            .takes_value(false)
+        )
+        .arg(Arg::with_name(ARG_BATCH_REPORT)
+            .long("batch-report")
+            .value_name("FILE")
+            .help("Write a JSON report of the processed and failed images in glob mode to the given file")
+            .long_help("Write a JSON report of the processed and failed images in glob mode to the given file. \
+                        The report contains the number of succeeded, failed and skipped images, and for each \
+                        failed image, its input and output path and the chain of errors which caused it to \
+                        fail. Only has an effect when combined with --glob-input.")
+            .takes_value(true)
+        )
+        .arg(Arg::with_name(ARG_INCREMENTAL)
+            .long("incremental")
+            .help("Skip images in glob mode of which the output is up to date")
+            .long_help("Skip images in glob mode of which the output is up to date. An output is up to date \
+                        when both the input image and the image operations and settings are unchanged since \
+                        the output was last written by an incremental run. Which images were processed is \
+                        stored in a manifest file ('.sic-manifest.json') in the output folder. Only has an \
+                        effect when combined with --glob-input.")
+            .takes_value(false)
+        )
 
         // config(in):
         .arg(Arg::with_name(ARG_SELECT_FRAME)
@@ -167,6 +355,29 @@ pub fn create_app(
                       For example, to select the first frame, the argument would be '0', for the second '1', etc.")
             .takes_value(true))
 
+        .arg(Arg::with_name(ARG_FRAMES)
+            .long("frames")
+            .value_name("RANGE")
+            .help("Only keep the given frames of an animated input image; all other frames are dropped. \
+                      The frames can be given as a single zero-indexed frame index (e.g. '3'), as a range with an exclusive \
+                      end (e.g. '5..20') or inclusive end (e.g. '5..=19'), or as a slice with a step (e.g. '::2' for every other frame). \
+                      If --select-frame is also provided, the frame is selected from the kept frames.")
+            .takes_value(true))
+
+        .arg(Arg::with_name(ARG_FRAME_DELAY)
+            .long("frame-delay")
+            .value_name("DELAY")
+            .help("The delay of each frame of an animated image assembled with --assemble, in milliseconds (e.g. '40ms') \
+                      or seconds (e.g. '1.5s'). Defaults to 100ms.")
+            .takes_value(true)
+            .requires(ARG_ASSEMBLE))
+
+        .arg(Arg::with_name(ARG_DISABLE_AUTOMATIC_ORIENTATION)
+            .long("disable-automatic-orientation")
+            .help("By default, the orientation stored in the Exif metadata of the input image is applied before \
+                      any image operation. If this flag is provided, the image will be left as is. \
+                      The orientation can still be applied explicitly with the 'auto-orient' image operation."))
+
         // config(out):
         .arg(Arg::with_name(ARG_DISABLE_AUTOMATIC_COLOR_TYPE_ADJUSTMENT)
             .long("disable-automatic-color-type-adjustment")
@@ -179,7 +390,7 @@ pub fn create_app(
             .long("output-format")
             .value_name("FORMAT")
             .help("Force the output image format to use FORMAT, regardless of the (if any) extension of the given output file path. \
-                      Output formats (FORMAT values) supported: AVIF, BMP, Farbfeld, GIF, ICO, JPEG, PNG, PAM, PBM, PGM, PPM and TGA.")
+                      Output formats (FORMAT values) supported: AVIF, BMP, Farbfeld, GIF, ICO, JPEG, PNG, PAM, PBM, PGM, PPM, TGA, TIFF and WebP.")
             .takes_value(true))
 
         .arg(Arg::with_name(ARG_JPEG_ENCODING_QUALITY)
@@ -194,11 +405,73 @@ pub fn create_app(
 
         .arg(Arg::with_name(ARG_GIF_REPEAT)
             .long("gif-repeat")
-            .help("Repeat the frames of a (to be) gif encoded image `n` times, `infinite` times or `never`.")
+            .help("Repeat the frames of a (to be) gif, animated png or animated webp encoded image `n` times, `infinite` times or `never`.")
             .value_name("REPETITIONS")
             .takes_value(true)
         )
 
+        .arg(Arg::with_name(ARG_AVIF_QUALITY)
+            .long("avif-quality")
+            .help("Set the AVIF quality to QUALITY. Valid values are positive numbers from 1 up to and including 100. Will only be used when the output format is determined to be AVIF.")
+            .value_name("QUALITY")
+            .takes_value(true))
+
+        .arg(Arg::with_name(ARG_AVIF_SPEED)
+            .long("avif-speed")
+            .help("Set the AVIF encoding speed to SPEED. Valid values are positive numbers from 1 (slowest, smallest output) up to and including 10 (fastest). \
+                      Will only be used when the output format is determined to be AVIF.")
+            .value_name("SPEED")
+            .takes_value(true))
+
+        .arg(Arg::with_name(ARG_PNG_COMPRESSION)
+            .long("png-compression")
+            .help("Set the compression level used when the output format is determined to be PNG: 'fast' (default), 'default' or 'best'.")
+            .value_name("LEVEL")
+            .possible_values(&["fast", "default", "best"])
+            .takes_value(true))
+
+        .arg(Arg::with_name(ARG_PNG_FILTER)
+            .long("png-filter")
+            .help("Set the filter type used when the output format is determined to be PNG: \
+                      'none', 'sub', 'up', 'avg', 'paeth' or 'adaptive' (default).")
+            .value_name("FILTER")
+            .possible_values(&["none", "sub", "up", "avg", "paeth", "adaptive"])
+            .takes_value(true))
+
+        .arg(Arg::with_name(ARG_TIFF_COMPRESSION)
+            .long("tiff-compression")
+            .help("Set the compression used when the output format is determined to be TIFF: \
+                      'none' (default), 'lzw', 'deflate' or 'packbits'.")
+            .value_name("COMPRESSION")
+            .possible_values(&["none", "lzw", "deflate", "packbits"])
+            .takes_value(true))
+
+        .arg(Arg::with_name(ARG_WEBP_QUALITY)
+            .long("webp-quality")
+            .help("Encode WebP images lossy, with quality QUALITY. Valid values are numbers from 0 up to and including 100. \
+                      By default, WebP images are encoded lossless. Will only be used when the output format is determined to be WebP.")
+            .value_name("QUALITY")
+            .takes_value(true))
+
+        .arg(Arg::with_name(ARG_METADATA)
+            .long("metadata")
+            .help("Which metadata of the input image (Exif, XMP and ICC profile) to retain in the output image: \
+                      'keep-all' (default), 'keep-icc', 'strip-gps' or 'strip-all'. \
+                      Metadata can be embedded in JPEG, PNG and WebP output images.")
+            .value_name("MODE")
+            .possible_values(&["keep-all", "keep-icc", "strip-gps", "strip-all"])
+            .takes_value(true))
+        .arg(Arg::with_name(ARG_STATS)
+            .long("stats")
+            .help("Compute statistics of each processed image: a histogram, the minimum, maximum, mean and standard \
+                      deviation of each channel, and the number of unique colors. With 'text', these are written to \
+                      stderr (without the histograms); with 'json', they are written to a file alongside the output \
+                      image, e.g. 'out.png.stats.json'.")
+            .value_name("FORMAT")
+            .possible_values(&["text", "json"])
+            .takes_value(true)
+            .conflicts_with_all(&[ARG_LICENSE, ARG_DEP_LICENSES, ARG_INFO, ARG_COMPARE]))
+
         // image-operations(script):
         .arg(Arg::with_name(ARG_APPLY_OPERATIONS)
             .long("apply-operations")
@@ -221,6 +494,10 @@ pub fn create_app(
             .args(OperationId::variants())
             .conflicts_with(ARG_APPLY_OPERATIONS)
             .multiple(true))
+        .arg(Arg::with_name(OperationId::AutoOrient.as_str())
+            .help("Operation: rotate and flip the input image according to the orientation stored in its Exif metadata")
+            .long(OperationId::AutoOrient.as_str())
+            .multiple(true))
         .arg(Arg::with_name(OperationId::Blur.as_str())
             .help("Operation: perform a gaussian blur on the input image")
             .long(OperationId::Blur.as_str())
@@ -245,6 +522,14 @@ pub fn create_app(
             .number_of_values(1)
             .multiple(true)
             .allow_hyphen_values(true))
+        .arg(Arg::with_name(OperationId::ConvertProfile.as_str())
+            .help("Operation: convert the colors of the input image from its embedded ICC color profile (or sRGB, if it has none) \
+                      to 'srgb', or to the ICC color profile at the given path")
+            .long(OperationId::ConvertProfile.as_str())
+            .takes_value(true)
+            .value_name("srgb or path to icc profile")
+            .number_of_values(1)
+            .multiple(true))
         .arg(Arg::with_name(OperationId::Crop.as_str())
             .help("Operation: crop the input image to a bounding rectangle ranging from top-left (lx, ly) to bottom-right (rx, ry) coordinates")
             .long(OperationId::Crop.as_str())
@@ -252,14 +537,38 @@ pub fn create_app(
             .value_names(&["lx", "ly", "rx", "ry"])
             .number_of_values(4)
             .multiple(true))
+        .arg(Arg::with_name(OperationId::Curves.as_str())
+            .help("Operation: map the color channels of the input image with a smooth curve through the given control points, where each point maps an input value to an output value, e.g. 'points((0, 0), (128, 150), (255, 255))'")
+            .long(OperationId::Curves.as_str())
+            .takes_value(true)
+            .value_name("<points((x,y),...)>")
+            .number_of_values(1)
+            .multiple(true))
         .arg(Arg::with_name(OperationId::Diff.as_str())
-            .help("Operation: show ")
+            .help("Operation: diff the input image against the given image, to show which pixels are the same (white), \
+                      different (red) or not part of either image (transparent); the tolerance and colours of the diff \
+                      can be set with the diff image script operation")
             .long(OperationId::Diff.as_str())
             .takes_value(true)
             .value_name("path to image")
             .number_of_values(1)
             .multiple(true))
 
+        .arg(Arg::with_name(OperationId::DropFrames.as_str())
+            .help("Operation: remove the given frames from an animated image; frames can be given as a frame index (e.g. '3'), a range (e.g. '0..5') or a slice with a step (e.g. '::2')")
+            .long(OperationId::DropFrames.as_str())
+            .takes_value(true)
+            .value_name("range")
+            .number_of_values(1)
+            .multiple(true))
+        .arg(Arg::with_name(OperationId::Extend.as_str())
+            .help("Operation: grow the canvas of the input image by a margin on each side, filled with transparent pixels")
+            .long(OperationId::Extend.as_str())
+            .takes_value(true)
+            .value_names(&["top", "right", "bottom", "left"])
+            .number_of_values(4)
+            .multiple(true))
+
         .arg(Arg::with_name(OperationId::Filter3x3.as_str())
             .help("Operation: apply a 3x3 convolution filter to the input image (matrix arguments should be given left-to-right, top-to-bottom)")
             .long(OperationId::Filter3x3.as_str())
@@ -276,6 +585,13 @@ pub fn create_app(
             .help("Operation: flip the input image vertically")
             .long(OperationId::FlipVertical.as_str())
             .multiple(true))
+        .arg(Arg::with_name(OperationId::Gamma.as_str())
+            .help("Operation: adjust the gamma of the input image; a gamma larger than 1 brightens the midtones, and a gamma smaller than 1 darkens them")
+            .long(OperationId::Gamma.as_str())
+            .takes_value(true)
+            .value_name("gamma")
+            .number_of_values(1)
+            .multiple(true))
         .arg(Arg::with_name(OperationId::Grayscale.as_str())
             .help("Operation: discard the chrominance signal from the input image, so it becomes achromatic")
             .long_help("Note that (depending on the provided settings flags), the processed image may still be stored in a format which encodes its chrominance")
@@ -304,6 +620,13 @@ pub fn create_app(
             .help("Operation: invert the each pixel of the input image ")
             .long(OperationId::Invert.as_str())
             .multiple(true))
+        .arg(Arg::with_name(OperationId::Levels.as_str())
+            .help("Operation: stretch the values of the color channels of the input image from the black point up to the white point to the full range; the gamma and a single color channel can be chosen with the levels image script operation")
+            .long(OperationId::Levels.as_str())
+            .takes_value(true)
+            .value_names(&["black", "white"])
+            .number_of_values(2)
+            .multiple(true))
         .arg(Arg::with_name(OperationId::Overlay.as_str())
             .help("Operation: overlay an image loaded from the provided path argument, over the input image (at a certain position)")
             .long(OperationId::Overlay.as_str())
@@ -311,6 +634,13 @@ pub fn create_app(
             .takes_value(true)
             .number_of_values(3)
             .multiple(true))
+        .arg(Arg::with_name(OperationId::Pad.as_str())
+            .help("Operation: grow the canvas of the input image to at least width by height pixels, with the image at its center, and fill the rest with transparent pixels")
+            .long(OperationId::Pad.as_str())
+            .takes_value(true)
+            .value_names(&["width", "height"])
+            .number_of_values(2)
+            .multiple(true))
         .arg(Arg::with_name(OperationId::Resize.as_str())
             .help("Operation: resize the input image to x by y pixels")
             .long(OperationId::Resize.as_str())
@@ -318,6 +648,45 @@ pub fn create_app(
             .value_names(&["x", "y"])
             .number_of_values(2)
             .multiple(true))
+        .arg(Arg::with_name(OperationId::ResizeExact.as_str())
+            .help("Operation: resize the input image to exactly width by height pixels, regardless of the 'preserve-aspect-ratio' modifier")
+            .long(OperationId::ResizeExact.as_str())
+            .takes_value(true)
+            .value_names(&["width", "height"])
+            .number_of_values(2)
+            .multiple(true))
+        .arg(Arg::with_name(OperationId::ResizeFit.as_str())
+            .help("Operation: resize the input image to the largest size which fits within width by height pixels, while preserving its aspect ratio")
+            .long(OperationId::ResizeFit.as_str())
+            .takes_value(true)
+            .value_names(&["width", "height"])
+            .number_of_values(2)
+            .multiple(true))
+        .arg(Arg::with_name(OperationId::ResizeCover.as_str())
+            .help("Operation: resize the input image to the smallest size which covers width by height pixels, while preserving its aspect ratio, and crop it to width by height pixels around its center")
+            .long(OperationId::ResizeCover.as_str())
+            .takes_value(true)
+            .value_names(&["width", "height"])
+            .number_of_values(2)
+            .multiple(true))
+        .arg(Arg::with_name(OperationId::ResizeScale.as_str())
+            .help("Operation: scale the width and height of the input image by a percentage, e.g. '50%'")
+            .long(OperationId::ResizeScale.as_str())
+            .takes_value(true)
+            .value_name("percentage")
+            .number_of_values(1)
+            .multiple(true))
+        .arg(Arg::with_name(OperationId::ResizeMaxEdge.as_str())
+            .help("Operation: resize the input image such that its longest edge is at most the given number of pixels, while preserving its aspect ratio")
+            .long(OperationId::ResizeMaxEdge.as_str())
+            .takes_value(true)
+            .value_name("pixels")
+            .number_of_values(1)
+            .multiple(true))
+        .arg(Arg::with_name(OperationId::ReverseFrames.as_str())
+            .help("Operation: reverse the order of the frames of an animated image; each frame keeps its own delay")
+            .long(OperationId::ReverseFrames.as_str())
+            .multiple(true))
         .arg(Arg::with_name(OperationId::Rotate90.as_str())
             .help("Operation: rotate the input image by 90 degrees")
             .long(OperationId::Rotate90.as_str())
@@ -330,6 +699,31 @@ pub fn create_app(
             .help("Operation: rotate the input image by 270 degrees")
             .long(OperationId::Rotate270.as_str())
             .multiple(true))
+        .arg(Arg::with_name(OperationId::SetFrameDelay.as_str())
+            .help("Operation: set the delay of each frame of an animated image, in milliseconds (e.g. '50ms') or seconds (e.g. '1.5s')")
+            .long(OperationId::SetFrameDelay.as_str())
+            .takes_value(true)
+            .value_name("delay")
+            .number_of_values(1)
+            .multiple(true))
+        .arg(Arg::with_name(OperationId::SmartCrop.as_str())
+            .help("Operation: crop the most interesting region (by detail, saturated colors and skin tones) with the aspect ratio width:height from the input image, and scale it down to width by height pixels; the frames of an animated image share the same region")
+            .long(OperationId::SmartCrop.as_str())
+            .takes_value(true)
+            .value_names(&["width", "height"])
+            .number_of_values(2)
+            .multiple(true))
+        .arg(Arg::with_name(OperationId::Speed.as_str())
+            .help("Operation: change the playback speed of an animated image by dividing the delay of each frame by the given factor")
+            .long(OperationId::Speed.as_str())
+            .takes_value(true)
+            .value_name("factor")
+            .number_of_values(1)
+            .multiple(true))
+        .arg(Arg::with_name(OperationId::Trim.as_str())
+            .help("Operation: crop away the uniform border of the input image, of which the color is the color of its top left pixel; the color, tolerance and margin to keep can be set with the trim image script operation; the frames of an animated image are cropped to the same region")
+            .long(OperationId::Trim.as_str())
+            .multiple(true))
         .arg(Arg::with_name(OperationId::Unsharpen.as_str())
             .help("Operation: sharpen an image by combining an unsharp (blurred) mask of the input image with the (original) input image, sharpening for pixels where the difference is bigger than the provided threshold")
             .long(OperationId::Unsharpen.as_str())
@@ -367,6 +761,16 @@ pub fn create_app(
             .number_of_values(1)
             .multiple(true)
             .possible_values(&["catmullrom", "gaussian", "lanczos3", "nearest", "triangle"])
+        )
+
+        .arg(Arg::with_name(OperationId::ShrinkOnly.as_str())
+            .help("Operation modifier for 'resize': never enlarge the image when resizing")
+            .long(OperationId::ShrinkOnly.as_str())
+            .takes_value(true)
+            .value_name("bool")
+            .number_of_values(1)
+            .multiple(true)
+            .possible_values(&["true", "false"])
         ))
 }
 
@@ -401,11 +805,62 @@ pub fn build_app_config<'a>(matches: &'a ArgMatches) -> anyhow::Result<Config<'a
         builder = builder.select_frame(Some(index));
     }
 
+    // config(in)/frames:
+    if let Some(value) = matches.value_of(ARG_FRAMES) {
+        let range = FrameRange::try_from_str(value)?;
+        builder = builder.frame_range(Some(range));
+    }
+
+    // config(in)/frame-delay:
+    if let Some(value) = matches.value_of(ARG_FRAME_DELAY) {
+        let delay = FrameDelay::try_from_str(value)?;
+        builder = builder.frame_delay(delay);
+    }
+
+    // config(batch)/jobs:
+    if let Some(value) = matches.value_of(ARG_JOBS) {
+        let jobs = parse_jobs(value)?;
+        builder = builder.jobs(jobs);
+    }
+
+    // config(batch)/keep-going:
+    if matches.is_present(ARG_KEEP_GOING) {
+        builder = builder.keep_going(true);
+    }
+
+    // config(batch)/batch-report:
+    if let Some(path) = matches.value_of(ARG_BATCH_REPORT) {
+        builder = builder.batch_report(PathBuf::from(path));
+    }
+
+    // config(batch)/incremental:
+    if matches.is_present(ARG_INCREMENTAL) {
+        builder = builder.incremental(true);
+    }
+
+    // config(in)/disable-automatic-orientation:
+    if matches.is_present(ARG_DISABLE_AUTOMATIC_ORIENTATION) {
+        builder = builder.disable_automatic_orientation(true);
+    }
+
     // config(out)/disable-automatic-color-type-adjustment:
     if matches.is_present(ARG_DISABLE_AUTOMATIC_COLOR_TYPE_ADJUSTMENT) {
         builder = builder.disable_automatic_color_type_adjustment(true);
     }
 
+    // config(out)/output-template:
+    if let Some(template) = matches.value_of(ARG_OUTPUT_TEMPLATE) {
+        builder = builder.output_template(OutputTemplate::try_new(template)?);
+    }
+
+    // config(out)/output-variant:
+    if let Some(variants) = matches.values_of(ARG_OUTPUT_VARIANT) {
+        let variants = variants
+            .map(OutputVariant::try_from_str)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        builder = builder.output_variants(variants);
+    }
+
     // config(out)/output-format:
     if let Some(format) = matches.value_of(ARG_FORCED_OUTPUT_FORMAT) {
         builder = builder.forced_output_format(format);
@@ -413,12 +868,7 @@ pub fn build_app_config<'a>(matches: &'a ArgMatches) -> anyhow::Result<Config<'a
 
     // config(out)/jpeg-encoding-quality:
     if let Some(value) = matches.value_of(ARG_JPEG_ENCODING_QUALITY) {
-        let requested_jpeg_quality = u8::from_str(value)
-            .map_err(|_| {
-                anyhow!("JPEG Encoding quality should be a value between 1 and 100 (inclusive).")
-            })
-            .and_then(validate_jpeg_quality)?;
-        builder = builder.jpeg_quality(requested_jpeg_quality);
+        builder = builder.jpeg_quality(parse_jpeg_quality(value)?);
     }
 
     // config(out)/pnm-encoding-type:
@@ -431,6 +881,50 @@ pub fn build_app_config<'a>(matches: &'a ArgMatches) -> anyhow::Result<Config<'a
         builder = builder.gif_repeat(repeat);
     }
 
+    // config(out)/avif-quality:
+    if let Some(value) = matches.value_of(ARG_AVIF_QUALITY) {
+        builder = builder.avif_quality(parse_avif_quality(value)?);
+    }
+
+    // config(out)/avif-speed:
+    if let Some(value) = matches.value_of(ARG_AVIF_SPEED) {
+        builder = builder.avif_speed(parse_avif_speed(value)?);
+    }
+
+    // config(out)/png-compression:
+    if let Some(value) = matches.value_of(ARG_PNG_COMPRESSION) {
+        let compression = PngCompression::try_from_str(value)?;
+        builder = builder.png_compression(compression);
+    }
+
+    // config(out)/png-filter:
+    if let Some(value) = matches.value_of(ARG_PNG_FILTER) {
+        let filter = PngFilter::try_from_str(value)?;
+        builder = builder.png_filter(filter);
+    }
+
+    // config(out)/tiff-compression:
+    if let Some(value) = matches.value_of(ARG_TIFF_COMPRESSION) {
+        let compression = TiffCompression::try_from_str(value)?;
+        builder = builder.tiff_compression(compression);
+    }
+
+    // config(out)/webp-quality:
+    if let Some(value) = matches.value_of(ARG_WEBP_QUALITY) {
+        builder = builder.webp_quality(parse_webp_quality(value)?);
+    }
+
+    // config(out)/metadata:
+    if let Some(value) = matches.value_of(ARG_METADATA) {
+        let retention = MetadataRetention::try_from_str(value)?;
+        builder = builder.metadata_retention(retention);
+    }
+
+    // config(out)/stats:
+    if let Some(value) = matches.value_of(ARG_STATS) {
+        builder = builder.stats(StatsFormat::try_from_str(value)?);
+    }
+
     // image-operations:
     //
     // Image operations are a bit more involved.
@@ -484,3 +978,14 @@ fn parse_frame_index(input: &str) -> anyhow::Result<FrameIndex> {
         }
     }
 }
+
+fn parse_jobs(input: &str) -> anyhow::Result<usize> {
+    match input.parse::<usize>() {
+        Ok(jobs) if jobs >= 1 => Ok(jobs),
+        _ => Err(anyhow!(
+            "Provided argument for --jobs is not valid. Expected a positive number of jobs, \
+             but got '{}'.",
+            input
+        )),
+    }
+}
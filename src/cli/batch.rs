@@ -0,0 +1,194 @@
+//! Outcome of processing the images of a batch (glob mode), and the report which summarizes it.
+
+use anyhow::Context;
+use serde::Serialize;
+use std::fmt;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+/// The outcome of processing a single image of a batch.
+#[derive(Debug)]
+pub enum BatchOutcome {
+    Succeeded,
+    Failed(anyhow::Error),
+    /// The image was not processed, because an earlier image failed.
+    Skipped,
+    /// The image was not processed, because its output is up to date.
+    UpToDate,
+}
+
+/// An image of a batch which could not be processed.
+#[derive(Debug, Serialize)]
+pub struct BatchFailure {
+    pub input: PathBuf,
+    /// The output paths, if they are known; when an output template or output variants are used,
+    /// the output paths depend on the processed image.
+    pub outputs: Vec<PathBuf>,
+    /// The error, followed by each of its underlying causes.
+    pub errors: Vec<String>,
+}
+
+impl BatchFailure {
+    /// Recreates the error of the failed image, with the same chain of causes, in the context of
+    /// its input path.
+    pub fn to_error(&self) -> anyhow::Error {
+        let mut errors = self.errors.iter().rev();
+        let root = anyhow::anyhow!("{}", errors.next().map_or("Unknown error", String::as_str));
+
+        errors
+            .fold(root, |err, context| err.context(context.to_string()))
+            .context(format!("With input: {}", self.input.display()))
+    }
+}
+
+/// Counts of the outcomes of the images of a batch, and the images which failed, in input order.
+#[derive(Debug, Default, Serialize)]
+pub struct BatchSummary {
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub up_to_date: usize,
+    pub failures: Vec<BatchFailure>,
+}
+
+impl BatchSummary {
+    /// Summarizes the outcomes of a batch. Each outcome is accompanied by the input and the known
+    /// output paths of its image.
+    pub fn from_outcomes<'p, I>(outcomes: I) -> Self
+    where
+        I: IntoIterator<Item = (&'p Path, Vec<PathBuf>, BatchOutcome)>,
+    {
+        outcomes.into_iter().fold(
+            BatchSummary::default(),
+            |mut summary, (input, outputs, outcome)| {
+                summary.total += 1;
+
+                match outcome {
+                    BatchOutcome::Succeeded => summary.succeeded += 1,
+                    BatchOutcome::Skipped => summary.skipped += 1,
+                    BatchOutcome::UpToDate => summary.up_to_date += 1,
+                    BatchOutcome::Failed(err) => {
+                        summary.failed += 1;
+                        summary.failures.push(BatchFailure {
+                            input: input.to_path_buf(),
+                            outputs,
+                            errors: err.chain().map(ToString::to_string).collect(),
+                        });
+                    }
+                }
+
+                summary
+            },
+        )
+    }
+
+    /// Writes the summary as a JSON report to the given path.
+    pub fn write_json_report(&self, path: &Path) -> anyhow::Result<()> {
+        let file = File::create(path)
+            .with_context(|| format!("Unable to create batch report '{}'", path.display()))?;
+
+        serde_json::to_writer_pretty(BufWriter::new(file), self)
+            .with_context(|| format!("Unable to write batch report '{}'", path.display()))
+    }
+}
+
+impl fmt::Display for BatchSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Processed {} of {} images: {} succeeded, {} failed, {} skipped",
+            self.succeeded + self.failed,
+            self.total,
+            self.succeeded,
+            self.failed,
+            self.skipped,
+        )?;
+
+        if self.up_to_date > 0 {
+            write!(f, ", {} up to date", self.up_to_date)?;
+        }
+
+        for failure in &self.failures {
+            write!(
+                f,
+                "\n  failed: {}: {}",
+                failure.input.display(),
+                failure.errors.join(": ")
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    fn summary() -> BatchSummary {
+        let a = Path::new("in/a.png");
+        let b = Path::new("in/b.png");
+        let c = Path::new("in/c.png");
+        let d = Path::new("in/d.png");
+
+        BatchSummary::from_outcomes(vec![
+            (a, vec![PathBuf::from("out/a.png")], BatchOutcome::Succeeded),
+            (
+                b,
+                vec![],
+                BatchOutcome::Failed(anyhow!("invalid signature").context("Unable to decode")),
+            ),
+            (c, vec![PathBuf::from("out/c.png")], BatchOutcome::Skipped),
+            (d, vec![PathBuf::from("out/d.png")], BatchOutcome::UpToDate),
+        ])
+    }
+
+    #[test]
+    fn counts_outcomes() {
+        let summary = summary();
+
+        assert_eq!(summary.total, 4);
+        assert_eq!(summary.succeeded, 1);
+        assert_eq!(summary.failed, 1);
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(summary.up_to_date, 1);
+    }
+
+    #[test]
+    fn failure_includes_error_chain() {
+        let summary = summary();
+
+        assert_eq!(summary.failures.len(), 1);
+        assert_eq!(summary.failures[0].input, PathBuf::from("in/b.png"));
+        assert_eq!(
+            summary.failures[0].errors,
+            vec!["Unable to decode", "invalid signature"]
+        );
+    }
+
+    #[test]
+    fn failure_to_error() {
+        let err = summary().failures[0].to_error();
+
+        assert_eq!(
+            err.chain().map(ToString::to_string).collect::<Vec<_>>(),
+            vec![
+                "With input: in/b.png",
+                "Unable to decode",
+                "invalid signature"
+            ]
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            summary().to_string(),
+            "Processed 2 of 4 images: 1 succeeded, 1 failed, 1 skipped, 1 up to date\n  \
+             failed: in/b.png: Unable to decode: invalid signature"
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//! Comparison of the processed input image with a reference image, as given by `--compare`, and
+//! the thresholds which decide whether the images are similar enough.
+
+use anyhow::{anyhow, bail};
+use serde::Serialize;
+use sic_image_engine::compare::Comparison;
+use std::fmt;
+
+/// A metric of a comparison, which should not exceed a threshold.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CompareMetric {
+    Psnr,
+    Ssim,
+    MeanAbsoluteError,
+    MaxAbsoluteError,
+}
+
+impl CompareMetric {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Psnr => "psnr",
+            Self::Ssim => "ssim",
+            Self::MeanAbsoluteError => "mae",
+            Self::MaxAbsoluteError => "max-error",
+        }
+    }
+
+    fn value(&self, comparison: &Comparison) -> f64 {
+        match self {
+            Self::Psnr => comparison.psnr,
+            Self::Ssim => comparison.ssim,
+            Self::MeanAbsoluteError => comparison.mean_absolute_error,
+            Self::MaxAbsoluteError => f64::from(comparison.max_absolute_error),
+        }
+    }
+
+    /// The PSNR and SSIM measure similarity, so they should not be below their threshold, while
+    /// the errors should not be above their threshold.
+    fn is_similarity(&self) -> bool {
+        matches!(self, Self::Psnr | Self::Ssim)
+    }
+}
+
+/// A threshold of a comparison metric, written as `<metric>=<value>`, e.g. `psnr=40`, `ssim=0.98`,
+/// `mae=1.5` or `max-error=16`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CompareThreshold {
+    metric: CompareMetric,
+    value: f64,
+}
+
+impl CompareThreshold {
+    pub fn try_from_str(threshold: &str) -> anyhow::Result<Self> {
+        let (metric, value) = threshold.split_once('=').ok_or_else(|| {
+            anyhow!(
+                "A comparison threshold should be written as '<metric>=<value>', e.g. 'psnr=40', \
+                 but was '{}'",
+                threshold
+            )
+        })?;
+
+        let metric = match metric.trim() {
+            "psnr" => CompareMetric::Psnr,
+            "ssim" => CompareMetric::Ssim,
+            "mae" => CompareMetric::MeanAbsoluteError,
+            "max-error" => CompareMetric::MaxAbsoluteError,
+            other => bail!(
+                "Unknown comparison metric '{}'; supported metrics are 'psnr', 'ssim', 'mae' and \
+                 'max-error'",
+                other
+            ),
+        };
+
+        let value = value
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .ok_or_else(|| {
+                anyhow!(
+                    "The value of comparison threshold '{}' should be a number",
+                    threshold
+                )
+            })?;
+
+        Ok(Self { metric, value })
+    }
+
+    /// Returns a description of the violation if the comparison does not satisfy the threshold.
+    pub fn check(&self, comparison: &Comparison) -> Option<String> {
+        let actual = self.metric.value(comparison);
+        let name = self.metric.as_str();
+
+        if self.metric.is_similarity() && actual < self.value {
+            Some(format!("{} {:.4} is below {}", name, actual, self.value))
+        } else if !self.metric.is_similarity() && actual > self.value {
+            Some(format!("{} {:.4} is above {}", name, actual, self.value))
+        } else {
+            None
+        }
+    }
+}
+
+/// The metrics of a comparison. The PSNR is `null` in JSON when the images are equal, since it is
+/// infinite.
+#[derive(Debug, Serialize)]
+pub struct CompareReport {
+    psnr: Option<f64>,
+    ssim: f64,
+    mean_absolute_error: f64,
+    max_absolute_error: u8,
+}
+
+impl CompareReport {
+    pub fn new(comparison: &Comparison) -> Self {
+        Self {
+            psnr: Some(comparison.psnr).filter(|psnr| psnr.is_finite()),
+            ssim: comparison.ssim,
+            mean_absolute_error: comparison.mean_absolute_error,
+            max_absolute_error: comparison.max_absolute_error,
+        }
+    }
+
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+impl fmt::Display for CompareReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.psnr {
+            Some(psnr) => writeln!(f, "PSNR:                {:.2} dB", psnr)?,
+            None => writeln!(f, "PSNR:                infinite (the images are equal)")?,
+        }
+
+        writeln!(f, "SSIM:                {:.4}", self.ssim)?;
+        writeln!(f, "Mean absolute error: {:.4}", self.mean_absolute_error)?;
+        write!(f, "Max absolute error:  {}", self.max_absolute_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sic_core::image::{DynamicImage, RgbImage};
+    use sic_core::SicImage;
+
+    fn comparison(psnr: f64, ssim: f64, mae: f64, max: u8) -> Comparison {
+        Comparison {
+            psnr,
+            ssim,
+            mean_absolute_error: mae,
+            max_absolute_error: max,
+            heat_map: SicImage::Static(DynamicImage::ImageRgb8(RgbImage::new(1, 1))),
+        }
+    }
+
+    #[yare::parameterized(
+        psnr = { "psnr=40", CompareMetric::Psnr, 40.0 },
+        ssim = { "ssim=0.98", CompareMetric::Ssim, 0.98 },
+        mae = { "mae=1.5", CompareMetric::MeanAbsoluteError, 1.5 },
+        max_error = { "max-error=16", CompareMetric::MaxAbsoluteError, 16.0 },
+        whitespace = { " psnr = 30 ", CompareMetric::Psnr, 30.0 },
+    )]
+    fn parse_threshold(input: &str, metric: CompareMetric, value: f64) {
+        assert_eq!(
+            CompareThreshold::try_from_str(input).unwrap(),
+            CompareThreshold { metric, value }
+        );
+    }
+
+    #[yare::parameterized(
+        no_value = { "psnr" },
+        unknown_metric = { "mse=1" },
+        not_a_number = { "ssim=high" },
+        infinite = { "psnr=inf" },
+    )]
+    fn invalid_threshold(input: &str) {
+        assert!(CompareThreshold::try_from_str(input).is_err());
+    }
+
+    #[yare::parameterized(
+        psnr_below = { "psnr=40", false },
+        psnr_above = { "psnr=30", true },
+        ssim_below = { "ssim=0.99", false },
+        ssim_above = { "ssim=0.9", true },
+        mae_above = { "mae=1", false },
+        mae_below = { "mae=2", true },
+        max_error_above = { "max-error=8", false },
+        max_error_equal = { "max-error=10", true },
+    )]
+    fn check_threshold(threshold: &str, satisfied: bool) {
+        let comparison = comparison(35.0, 0.95, 1.5, 10);
+        let threshold = CompareThreshold::try_from_str(threshold).unwrap();
+
+        assert_eq!(threshold.check(&comparison).is_none(), satisfied);
+    }
+
+    #[test]
+    fn infinite_psnr_satisfies_threshold() {
+        let threshold = CompareThreshold::try_from_str("psnr=100").unwrap();
+
+        assert!(threshold
+            .check(&comparison(f64::INFINITY, 1.0, 0.0, 0))
+            .is_none());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            CompareReport::new(&comparison(35.123, 0.95, 1.5, 10)).to_string(),
+            "PSNR:                35.12 dB\n\
+             SSIM:                0.9500\n\
+             Mean absolute error: 1.5000\n\
+             Max absolute error:  10"
+        );
+    }
+
+    #[test]
+    fn json_of_equal_images() {
+        let json: serde_json::Value = serde_json::from_str(
+            &CompareReport::new(&comparison(f64::INFINITY, 1.0, 0.0, 0))
+                .to_json()
+                .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "psnr": null,
+                "ssim": 1.0,
+                "mean_absolute_error": 0.0,
+                "max_absolute_error": 0,
+            })
+        );
+    }
+}
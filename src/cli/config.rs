@@ -1,17 +1,34 @@
 use crate::cli::app::arg_names::{
-    ARG_GLOB_NO_SKIP_UNSUPPORTED_EXTENSIONS, ARG_INPUT, ARG_INPUT_GLOB, ARG_OUTPUT, ARG_OUTPUT_GLOB,
+    ARG_ASSEMBLE, ARG_COMPARE, ARG_COMPARE_THRESHOLD, ARG_EXPLODE_FRAMES,
+    ARG_GLOB_NO_SKIP_UNSUPPORTED_EXTENSIONS, ARG_INFO, ARG_INPUT, ARG_INPUT_GLOB, ARG_JSON,
+    ARG_OUTPUT, ARG_OUTPUT_GLOB,
 };
 use crate::cli::common_dir::CommonDir;
+use crate::cli::compare::CompareThreshold;
+use crate::cli::frame_pattern::FramePattern;
 use crate::cli::glob_base_dir::glob_builder_base;
-use anyhow::{bail, Context};
+use crate::cli::info::InfoFormat;
+use crate::cli::output_template::OutputTemplate;
+use crate::cli::output_variant::OutputVariant;
+use crate::cli::stats::StatsFormat;
+use anyhow::{anyhow, bail, Context};
 use clap::ArgMatches;
 use globwalk::{FileType, GlobWalker};
-use sic_core::image;
+use sic_core::{image, FrameRange};
 use sic_image_engine::engine::Instr;
+use sic_image_engine::wrapper::frame_delay::FrameDelay;
+use sic_image_engine::wrapper::image_path::ImageFromPath;
 use sic_io::decode::FrameIndex;
+use sic_io::encode_settings::avif::{AvifQuality, AvifSpeed};
 use sic_io::encode_settings::gif::RepeatAnimation;
+use sic_io::encode_settings::png::{PngCompression, PngFilter};
+use sic_io::encode_settings::tiff::TiffCompression;
+use sic_io::encode_settings::webp::WebpQuality;
+use sic_io::metadata::MetadataRetention;
 use std::fmt;
 use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
 
 #[derive(Debug, Clone)]
 pub enum PathVariant {
@@ -45,12 +62,49 @@ pub enum InputOutputMode {
         inputs: CommonDir,
         output_root_folder: PathBuf,
     },
+    /// Each frame of the input image is written to its own output file, of which the path is
+    /// derived from the pattern.
+    ExplodeFrames {
+        input: PathVariant,
+        pattern: FramePattern,
+    },
+    /// The input images are assembled into the frames of a single animated image, ordered by
+    /// their path relative to the common directory of the inputs.
+    Assemble {
+        inputs: CommonDir,
+        output: PathVariant,
+    },
+    /// The properties of the input image are printed, instead of writing an output image.
+    Info {
+        input: PathVariant,
+        format: InfoFormat,
+    },
+    /// The processed input image is compared with a reference image, and the metrics of the
+    /// comparison are printed. If an output is given, a heat map of the differences is written
+    /// to it.
+    Compare {
+        input: PathVariant,
+        reference: ImageFromPath,
+        heat_map: Option<PathVariant>,
+        thresholds: Vec<CompareThreshold>,
+        format: InfoFormat,
+    },
 }
 
 impl InputOutputMode {
     pub fn try_from_matches(matches: &ArgMatches) -> anyhow::Result<Self> {
         let mode = InputOutputModeType::from_arg_matches(matches);
 
+        // --json applies to more than one mode, which clap can't express as a requirement
+        if matches.is_present(ARG_JSON)
+            && !matches!(
+                mode,
+                InputOutputModeType::Info | InputOutputModeType::Compare
+            )
+        {
+            bail!("The --json flag can only be used together with --info or --compare");
+        }
+
         match mode {
             InputOutputModeType::Simple => Ok(InputOutputMode::Single {
                 input: match matches.value_of(ARG_INPUT) {
@@ -84,6 +138,79 @@ impl InputOutputMode {
                     output_root_folder: { output.into() },
                 })
             }
+            InputOutputModeType::ExplodeFrames => {
+                let pattern = matches
+                    .value_of(ARG_EXPLODE_FRAMES)
+                    .with_context(|| "Exploding frames requires an output pattern")?;
+
+                Ok(InputOutputMode::ExplodeFrames {
+                    input: match matches.value_of(ARG_INPUT) {
+                        Some(p) => PathVariant::Path(p.into()),
+                        None => PathVariant::StdStream,
+                    },
+                    pattern: FramePattern::try_new(pattern)?,
+                })
+            }
+            InputOutputModeType::Assemble => {
+                let inputs = matches
+                    .value_of(ARG_ASSEMBLE)
+                    .with_context(|| "Assembling an animated image requires an input pattern")?;
+
+                Ok(InputOutputMode::Assemble {
+                    inputs: {
+                        let inputs = Self::create_glob_walker(inputs)?;
+
+                        let paths = Self::lookup_paths(
+                            inputs,
+                            !matches.is_present(ARG_GLOB_NO_SKIP_UNSUPPORTED_EXTENSIONS),
+                        )?;
+
+                        CommonDir::try_new(paths)?
+                    },
+                    output: match matches.value_of(ARG_OUTPUT) {
+                        Some(p) => PathVariant::Path(p.into()),
+                        None => PathVariant::StdStream,
+                    },
+                })
+            }
+            InputOutputModeType::Compare => {
+                let reference = matches
+                    .value_of(ARG_COMPARE)
+                    .with_context(|| "Comparing images requires a reference image")?;
+
+                Ok(InputOutputMode::Compare {
+                    input: match matches.value_of(ARG_INPUT) {
+                        Some(p) => PathVariant::Path(p.into()),
+                        None => PathVariant::StdStream,
+                    },
+                    reference: ImageFromPath::new(reference.into()),
+                    heat_map: matches
+                        .value_of(ARG_OUTPUT)
+                        .map(|p| PathVariant::Path(p.into())),
+                    thresholds: matches
+                        .values_of(ARG_COMPARE_THRESHOLD)
+                        .into_iter()
+                        .flatten()
+                        .map(CompareThreshold::try_from_str)
+                        .collect::<anyhow::Result<Vec<_>>>()?,
+                    format: if matches.is_present(ARG_JSON) {
+                        InfoFormat::Json
+                    } else {
+                        InfoFormat::Text
+                    },
+                })
+            }
+            InputOutputModeType::Info => Ok(InputOutputMode::Info {
+                input: match matches.value_of(ARG_INPUT) {
+                    Some(p) => PathVariant::Path(p.into()),
+                    None => PathVariant::StdStream,
+                },
+                format: if matches.is_present(ARG_JSON) {
+                    InfoFormat::Json
+                } else {
+                    InfoFormat::Text
+                },
+            }),
         }
     }
 
@@ -141,12 +268,24 @@ fn filter_unsupported_paths(paths: Vec<PathBuf>) -> Vec<PathBuf> {
 pub enum InputOutputModeType {
     Simple,
     Batch,
+    ExplodeFrames,
+    Assemble,
+    Info,
+    Compare,
 }
 
 impl InputOutputModeType {
     pub fn from_arg_matches(matches: &ArgMatches) -> InputOutputModeType {
         if matches.is_present(ARG_INPUT_GLOB) {
             InputOutputModeType::Batch
+        } else if matches.is_present(ARG_EXPLODE_FRAMES) {
+            InputOutputModeType::ExplodeFrames
+        } else if matches.is_present(ARG_ASSEMBLE) {
+            InputOutputModeType::Assemble
+        } else if matches.is_present(ARG_INFO) {
+            InputOutputModeType::Info
+        } else if matches.is_present(ARG_COMPARE) {
+            InputOutputModeType::Compare
         } else {
             InputOutputModeType::Simple
         }
@@ -165,15 +304,50 @@ pub struct Config<'a> {
 
     pub selected_frame: Option<FrameIndex>,
 
+    /// Frames of an animated input image which will be kept; other frames are dropped.
+    pub frame_range: Option<FrameRange>,
+
+    /// Delay of each frame of an animated image which is assembled from still images.
+    pub frame_delay: FrameDelay,
+
+    /// Number of images which are processed concurrently in batch (glob) mode.
+    pub jobs: usize,
+
+    /// Continue processing the remaining images in batch (glob) mode when an image fails.
+    pub keep_going: bool,
+
+    /// Path to which a JSON report of a batch (glob) run will be written.
+    pub batch_report: Option<PathBuf>,
+
+    /// Skip images in batch (glob) mode of which the output is up to date.
+    pub incremental: bool,
+
+    /// Disable applying the Exif orientation of the input image before the image operations.
+    pub disable_automatic_orientation: bool,
+
     /// Disable color type adjustments on save.
     pub disable_automatic_color_type_adjustment: bool,
 
     /// Format to which an image will be converted (enforced).
     pub forced_output_format: Option<&'a str>,
 
+    /// Template from which the output path of each image is derived, after it has been processed.
+    pub output_template: Option<OutputTemplate>,
+
+    /// Additional output branches, each with its own image operations, which are applied after
+    /// the common image operations.
+    pub output_variants: Vec<OutputVariant>,
+
     /// Encoding settings for specific output formats.
     pub encoding_settings: FormatEncodingSettings,
 
+    /// Which metadata of the input image should be retained in the output image. If not set, all
+    /// metadata is retained, and it's silently discarded for formats which can't embed it.
+    pub metadata_retention: Option<MetadataRetention>,
+
+    /// Format in which the statistics of each processed image are written, if requested.
+    pub stats: Option<StatsFormat>,
+
     /// If a user wants to perform image operations on input image, they will need to provide
     /// the image operation commands.
     /// THe value set here should be presented as a [sic_image_engine::engine::Program].
@@ -195,12 +369,39 @@ impl Default for Config<'_> {
             // By default no frame is selected
             selected_frame: None,
 
+            // By default all frames are kept
+            frame_range: None,
+
+            // Defaults to a delay of 100 milliseconds between frames, when assembling an animated image
+            frame_delay: FrameDelay::new(Duration::from_millis(100)),
+
+            // Defaults to processing batch inputs one after another.
+            jobs: 1,
+
+            // Defaults to stopping a batch at the first failed input.
+            keep_going: false,
+
+            // Defaults to not writing a batch report.
+            batch_report: None,
+
+            // Defaults to processing all images of a batch.
+            incremental: false,
+
+            // Defaults to applying the orientation stored in the metadata of the image.
+            disable_automatic_orientation: false,
+
             // Defaults to using automatic color type adjustment where appropriate.
             disable_automatic_color_type_adjustment: false,
 
             // Defaults to not forcing a specific image output format.
             forced_output_format: None,
 
+            // Defaults to deriving the output path from the output option.
+            output_template: None,
+
+            // Defaults to no output variants.
+            output_variants: Vec::new(),
+
             // Default format encoding settings.
             encoding_settings: FormatEncodingSettings {
                 // Default JPEG quality is set to 80.
@@ -211,8 +412,32 @@ impl Default for Config<'_> {
 
                 // Defaults to infinite repeat
                 gif_repeat: RepeatAnimation::default(),
+
+                // Default AVIF quality is set to 80.
+                avif_quality: AvifQuality::default(),
+
+                // Default AVIF encoding speed is set to 4.
+                avif_speed: AvifSpeed::default(),
+
+                // Defaults to fast PNG compression.
+                png_compression: PngCompression::default(),
+
+                // Defaults to an adaptive PNG filter.
+                png_filter: PngFilter::default(),
+
+                // Defaults to uncompressed TIFF images.
+                tiff_compression: TiffCompression::default(),
+
+                // Defaults to lossless WebP encoding.
+                webp_quality: None,
             },
 
+            // Defaults to retaining all metadata.
+            metadata_retention: None,
+
+            // Defaults to not computing image statistics.
+            stats: None,
+
             // Defaults to no provided image operations script.
             image_operations_program: Vec::new(),
         }
@@ -252,12 +477,66 @@ impl<'a> ConfigBuilder<'a> {
         self
     }
 
+    // config(in)
+    pub fn frame_range(mut self, range: Option<FrameRange>) -> ConfigBuilder<'a> {
+        self.settings.frame_range = range;
+        self
+    }
+
+    // config(in)
+    pub fn frame_delay(mut self, delay: FrameDelay) -> ConfigBuilder<'a> {
+        self.settings.frame_delay = delay;
+        self
+    }
+
+    // config(batch)
+    pub fn jobs(mut self, jobs: usize) -> ConfigBuilder<'a> {
+        self.settings.jobs = jobs;
+        self
+    }
+
+    // config(batch)
+    pub fn keep_going(mut self, toggle: bool) -> ConfigBuilder<'a> {
+        self.settings.keep_going = toggle;
+        self
+    }
+
+    // config(batch)
+    pub fn batch_report(mut self, path: PathBuf) -> ConfigBuilder<'a> {
+        self.settings.batch_report = Some(path);
+        self
+    }
+
+    // config(batch)
+    pub fn incremental(mut self, toggle: bool) -> ConfigBuilder<'a> {
+        self.settings.incremental = toggle;
+        self
+    }
+
+    // config(in)
+    pub fn disable_automatic_orientation(mut self, toggle: bool) -> ConfigBuilder<'a> {
+        self.settings.disable_automatic_orientation = toggle;
+        self
+    }
+
     // config(out)
     pub fn forced_output_format(mut self, format: &'a str) -> ConfigBuilder<'a> {
         self.settings.forced_output_format = Some(format);
         self
     }
 
+    // config(out)
+    pub fn output_template(mut self, template: OutputTemplate) -> ConfigBuilder<'a> {
+        self.settings.output_template = Some(template);
+        self
+    }
+
+    // config(out)
+    pub fn output_variants(mut self, variants: Vec<OutputVariant>) -> ConfigBuilder<'a> {
+        self.settings.output_variants = variants;
+        self
+    }
+
     // config(out)
     pub fn disable_automatic_color_type_adjustment(mut self, toggle: bool) -> ConfigBuilder<'a> {
         self.settings.disable_automatic_color_type_adjustment = toggle;
@@ -282,6 +561,54 @@ impl<'a> ConfigBuilder<'a> {
         self
     }
 
+    // config(out)
+    pub fn avif_quality(mut self, quality: AvifQuality) -> ConfigBuilder<'a> {
+        self.settings.encoding_settings.avif_quality = quality;
+        self
+    }
+
+    // config(out)
+    pub fn avif_speed(mut self, speed: AvifSpeed) -> ConfigBuilder<'a> {
+        self.settings.encoding_settings.avif_speed = speed;
+        self
+    }
+
+    // config(out)
+    pub fn png_compression(mut self, compression: PngCompression) -> ConfigBuilder<'a> {
+        self.settings.encoding_settings.png_compression = compression;
+        self
+    }
+
+    // config(out)
+    pub fn png_filter(mut self, filter: PngFilter) -> ConfigBuilder<'a> {
+        self.settings.encoding_settings.png_filter = filter;
+        self
+    }
+
+    // config(out)
+    pub fn tiff_compression(mut self, compression: TiffCompression) -> ConfigBuilder<'a> {
+        self.settings.encoding_settings.tiff_compression = compression;
+        self
+    }
+
+    // config(out)
+    pub fn webp_quality(mut self, quality: WebpQuality) -> ConfigBuilder<'a> {
+        self.settings.encoding_settings.webp_quality = Some(quality);
+        self
+    }
+
+    // config(out)
+    pub fn metadata_retention(mut self, retention: MetadataRetention) -> ConfigBuilder<'a> {
+        self.settings.metadata_retention = Some(retention);
+        self
+    }
+
+    // config(out)
+    pub fn stats(mut self, format: StatsFormat) -> ConfigBuilder<'a> {
+        self.settings.stats = Some(format);
+        self
+    }
+
     // image-operations
     pub fn image_operations_program(mut self, program: Vec<Instr>) -> ConfigBuilder<'a> {
         self.settings.image_operations_program = program;
@@ -304,6 +631,13 @@ pub struct FormatEncodingSettings {
     pub jpeg_quality: u8,
     pub pnm_use_ascii_format: bool,
     pub gif_repeat: RepeatAnimation,
+    pub avif_quality: AvifQuality,
+    pub avif_speed: AvifSpeed,
+    pub png_compression: PngCompression,
+    pub png_filter: PngFilter,
+    pub tiff_compression: TiffCompression,
+    /// If set, WebP images are encoded lossy, and lossless otherwise.
+    pub webp_quality: Option<WebpQuality>,
 }
 
 /// Strictly speaking not necessary here since the responsible owners will validate the quality as well.
@@ -324,6 +658,36 @@ pub fn validate_jpeg_quality(quality: u8) -> anyhow::Result<u8> {
     within_range(quality)
 }
 
+pub fn parse_jpeg_quality(value: &str) -> anyhow::Result<u8> {
+    u8::from_str(value)
+        .map_err(|_| {
+            anyhow!("JPEG Encoding quality should be a value between 1 and 100 (inclusive).")
+        })
+        .and_then(validate_jpeg_quality)
+}
+
+pub fn parse_avif_quality(value: &str) -> anyhow::Result<AvifQuality> {
+    let quality = u8::from_str(value)
+        .map_err(|_| anyhow!("AVIF quality should be a value between 1 and 100 (inclusive)."))?;
+
+    Ok(AvifQuality::try_from(quality)?)
+}
+
+pub fn parse_avif_speed(value: &str) -> anyhow::Result<AvifSpeed> {
+    let speed = u8::from_str(value).map_err(|_| {
+        anyhow!("AVIF encoding speed should be a value between 1 and 10 (inclusive).")
+    })?;
+
+    Ok(AvifSpeed::try_from(speed)?)
+}
+
+pub fn parse_webp_quality(value: &str) -> anyhow::Result<WebpQuality> {
+    let quality = u8::from_str(value)
+        .map_err(|_| anyhow!("WebP quality should be a value between 0 and 100 (inclusive)."))?;
+
+    Ok(WebpQuality::try_from(quality)?)
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -0,0 +1,121 @@
+//! A file name pattern, used to derive an output path for each frame of an animated image, when
+//! extracting its frames to a numbered image sequence.
+//!
+//! The pattern should contain a `{frame}` placeholder, which will be replaced by the zero-indexed
+//! frame number. By default, frame numbers are padded with zeros to the number of digits of the
+//! largest frame number, so the output files sort in frame order. A fixed width can be given with
+//! `{frame:N}`, e.g. `frame_{frame:4}.png` results in `frame_0000.png`, `frame_0001.png`, etc.
+
+use anyhow::bail;
+use std::path::PathBuf;
+
+const PLACEHOLDER_START: &str = "{frame";
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FramePattern {
+    prefix: String,
+    width: Option<usize>,
+    suffix: String,
+}
+
+impl FramePattern {
+    pub fn try_new(pattern: &str) -> anyhow::Result<Self> {
+        let (prefix, rest) = match pattern.split_once(PLACEHOLDER_START) {
+            Some(parts) => parts,
+            None => bail!(
+                "The frame pattern '{}' should contain a '{{frame}}' placeholder, e.g. 'frame_{{frame}}.png'",
+                pattern
+            ),
+        };
+
+        let (width, suffix) = match rest.split_once('}') {
+            Some(("", suffix)) => (None, suffix),
+            Some((width, suffix)) => match width.strip_prefix(':').map(str::parse::<usize>) {
+                Some(Ok(width)) => (Some(width), suffix),
+                _ => bail!(
+                    "The frame placeholder of the frame pattern '{}' should be '{{frame}}' or '{{frame:N}}', \
+                     where N is the number of digits",
+                    pattern
+                ),
+            },
+            None => bail!(
+                "The frame placeholder of the frame pattern '{}' is not closed",
+                pattern
+            ),
+        };
+
+        if suffix.contains(PLACEHOLDER_START) {
+            bail!(
+                "The frame pattern '{}' should contain exactly one '{{frame}}' placeholder",
+                pattern
+            );
+        }
+
+        Ok(Self {
+            prefix: prefix.to_string(),
+            width,
+            suffix: suffix.to_string(),
+        })
+    }
+
+    /// The output path for the frame with the given (zero-indexed) index, of an image which
+    /// consists of `frame_count` frames.
+    pub fn path_for(&self, index: usize, frame_count: usize) -> PathBuf {
+        let width = self
+            .width
+            .unwrap_or_else(|| digits(frame_count.saturating_sub(1)));
+
+        PathBuf::from(format!(
+            "{}{:0width$}{}",
+            self.prefix,
+            index,
+            self.suffix,
+            width = width
+        ))
+    }
+}
+
+fn digits(mut number: usize) -> usize {
+    let mut count = 1;
+
+    while number >= 10 {
+        number /= 10;
+        count += 1;
+    }
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        default_width_single_digit = { "frame_{frame}.png", 3, 8, "frame_3.png" },
+        default_width_two_digits = { "frame_{frame}.png", 3, 11, "frame_03.png" },
+        default_width_last_frame = { "frame_{frame}.png", 10, 11, "frame_10.png" },
+        fixed_width = { "frame_{frame:4}.png", 1, 8, "frame_0001.png" },
+        fixed_width_exceeded = { "{frame:1}.png", 12, 20, "12.png" },
+        directory = { "out/{frame}/image.gif", 0, 1, "out/0/image.gif" },
+    )]
+    fn path_for(pattern: &str, index: usize, frame_count: usize, expected: &str) {
+        let pattern = FramePattern::try_new(pattern).unwrap();
+
+        assert_eq!(
+            pattern.path_for(index, frame_count),
+            PathBuf::from(expected)
+        );
+    }
+
+    #[yare::parameterized(
+        no_placeholder = { "frame.png" },
+        not_closed = { "frame_{frame.png" },
+        invalid_width = { "frame_{frame:x}.png" },
+        missing_width = { "frame_{frame:}.png" },
+        other_placeholder = { "frame_{framenumber}.png" },
+        multiple_placeholders = { "{frame}_{frame}.png" },
+    )]
+    fn invalid(pattern: &str) {
+        assert!(FramePattern::try_new(pattern).is_err());
+    }
+}
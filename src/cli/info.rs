@@ -0,0 +1,197 @@
+//! A report of the properties of an image, as given by `--info`, which is printed either
+//! human-readable, or as JSON with `--json`.
+
+use serde::Serialize;
+use sic_io::info::{ImageInfo, LoopCount};
+use std::fmt;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum InfoFormat {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InfoReport {
+    format: String,
+    width: u32,
+    height: u32,
+    color_type: String,
+    bit_depth: u8,
+    frame_count: usize,
+    /// Delay of each frame in milliseconds; empty for still images.
+    frame_delays_ms: Vec<u64>,
+    /// Either the number of times an animated image is played, or `"infinite"`; `null` for
+    /// still images, or when unknown.
+    loop_count: Option<LoopCountReport>,
+    metadata: MetadataReport,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum LoopCountReport {
+    Infinite,
+    #[serde(untagged)]
+    Finite(u32),
+}
+
+#[derive(Debug, Serialize)]
+struct MetadataReport {
+    exif: bool,
+    xmp: bool,
+    icc_profile: bool,
+}
+
+impl InfoReport {
+    pub fn new(info: &ImageInfo) -> Self {
+        let (width, height) = info.dimensions();
+
+        Self {
+            format: format!("{:?}", info.format()).to_lowercase(),
+            width,
+            height,
+            color_type: format!("{:?}", info.color_type()).to_lowercase(),
+            bit_depth: info.bit_depth(),
+            frame_count: info.frame_count(),
+            frame_delays_ms: info
+                .frame_delays()
+                .iter()
+                .map(|delay| delay.as_millis() as u64)
+                .collect(),
+            loop_count: info.loop_count().map(|loop_count| match loop_count {
+                LoopCount::Infinite => LoopCountReport::Infinite,
+                LoopCount::Finite(plays) => LoopCountReport::Finite(plays),
+            }),
+            metadata: MetadataReport {
+                exif: info.has_exif(),
+                xmp: info.has_xmp(),
+                icc_profile: info.has_icc_profile(),
+            },
+        }
+    }
+
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+impl fmt::Display for InfoReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Format:       {}", self.format)?;
+        writeln!(f, "Dimensions:   {}x{}", self.width, self.height)?;
+        writeln!(f, "Color type:   {}", self.color_type)?;
+        writeln!(f, "Bit depth:    {}", self.bit_depth)?;
+        writeln!(f, "Frames:       {}", self.frame_count)?;
+
+        if !self.frame_delays_ms.is_empty() {
+            let delays = self
+                .frame_delays_ms
+                .iter()
+                .map(|delay| format!("{}ms", delay))
+                .collect::<Vec<_>>();
+            writeln!(f, "Frame delays: {}", delays.join(", "))?;
+        }
+
+        match &self.loop_count {
+            Some(LoopCountReport::Infinite) => writeln!(f, "Loop count:   infinite")?,
+            Some(LoopCountReport::Finite(plays)) => writeln!(f, "Loop count:   {}", plays)?,
+            None => {}
+        }
+
+        let metadata = [
+            (self.metadata.exif, "exif"),
+            (self.metadata.xmp, "xmp"),
+            (self.metadata.icc_profile, "icc profile"),
+        ]
+        .iter()
+        .filter(|(present, _)| *present)
+        .map(|(_, name)| *name)
+        .collect::<Vec<_>>();
+
+        if metadata.is_empty() {
+            write!(f, "Metadata:     none")
+        } else {
+            write!(f, "Metadata:     {}", metadata.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sic_io::decode::{file_reader, SicImageDecoder};
+
+    fn report(name: &str) -> InfoReport {
+        let path = format!("{}/resources/{}", env!("CARGO_MANIFEST_DIR"), name);
+        let info = SicImageDecoder::default()
+            .inspect(&mut file_reader(path).unwrap())
+            .unwrap();
+
+        InfoReport::new(&info)
+    }
+
+    #[test]
+    fn display_still_image() {
+        assert_eq!(
+            report("unsplash_763569_cropped.jpg").to_string(),
+            "Format:       jpeg\n\
+             Dimensions:   217x447\n\
+             Color type:   rgb8\n\
+             Bit depth:    8\n\
+             Frames:       1\n\
+             Metadata:     exif, icc profile"
+        );
+    }
+
+    #[test]
+    fn display_animated_image() {
+        assert_eq!(
+            report("apng_sample.png").to_string(),
+            "Format:       png\n\
+             Dimensions:   4x4\n\
+             Color type:   rgba8\n\
+             Bit depth:    8\n\
+             Frames:       3\n\
+             Frame delays: 1000ms, 1000ms, 1000ms\n\
+             Loop count:   infinite\n\
+             Metadata:     none"
+        );
+    }
+
+    #[yare::parameterized(
+        infinite = { "loop.gif", serde_json::json!("infinite") },
+        finite = { "noloop.gif", serde_json::json!(1) },
+        still = { "rainbow_8x6.bmp", serde_json::Value::Null },
+    )]
+    fn json_loop_count(name: &str, expected: serde_json::Value) {
+        let json: serde_json::Value =
+            serde_json::from_str(&report(name).to_json().unwrap()).unwrap();
+
+        assert_eq!(json["loop_count"], expected);
+    }
+
+    #[test]
+    fn json() {
+        let json: serde_json::Value =
+            serde_json::from_str(&report("noloop.gif").to_json().unwrap()).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "format": "gif",
+                "width": 100,
+                "height": 100,
+                "color_type": "rgba8",
+                "bit_depth": 8,
+                "frame_count": 8,
+                "frame_delays_ms": [720, 720, 720, 720, 720, 720, 720, 720],
+                "loop_count": 1,
+                "metadata": {
+                    "exif": false,
+                    "xmp": false,
+                    "icc_profile": false,
+                },
+            })
+        );
+    }
+}
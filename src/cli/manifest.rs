@@ -0,0 +1,292 @@
+//! A manifest of the images which were processed in batch (glob) mode, which is stored in the
+//! output root folder. It is used by incremental batch runs to skip images which are up to date.
+//!
+//! An image is considered up to date when its outputs exist, and both its input and the settings
+//! with which it was processed are unchanged since it was last processed. An input is unchanged
+//! when its size and modification time are the same as recorded in the manifest, or, when only
+//! its modification time differs, when the hash of its contents is the same. The settings are
+//! compared by their [`fingerprint`].
+//!
+//! Inputs are only hashed when the hash can't be taken from the manifest, that is, when the input
+//! is new, or when its size or modification time changed.
+
+use crate::cli::config::Config;
+use crate::cli::hash::{hash_bytes, hash_file};
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use sic_image_engine::engine::Instr;
+use sic_image_engine::wrapper::icc_profile::IccProfileTarget;
+use sic_image_engine::ImgOp;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+pub const MANIFEST_FILE_NAME: &str = ".sic-manifest.json";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Entries by the path of their input, relative to the common directory of the inputs.
+    entries: BTreeMap<PathBuf, ManifestEntry>,
+}
+
+/// The state of an input image when it was last processed.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    modified: SystemTime,
+    len: u64,
+    hash: u64,
+    fingerprint: u64,
+    /// Paths of the output images which were written.
+    outputs: Vec<PathBuf>,
+}
+
+impl ManifestEntry {
+    pub fn with_outputs(self, outputs: Vec<PathBuf>) -> Self {
+        Self { outputs, ..self }
+    }
+
+    pub fn outputs(&self) -> &[PathBuf] {
+        &self.outputs
+    }
+}
+
+impl Manifest {
+    /// Loads the manifest from the given output root folder. If no manifest exists yet, or if the
+    /// existing manifest can't be read, an empty manifest is returned, so all images are processed.
+    pub fn load(output_root_folder: &Path) -> Self {
+        let path = output_root_folder.join(MANIFEST_FILE_NAME);
+
+        let file = match File::open(&path) {
+            Ok(file) => file,
+            Err(_) => return Manifest::default(),
+        };
+
+        serde_json::from_reader(io::BufReader::new(file)).unwrap_or_else(|err| {
+            eprintln!(
+                "warn: Unable to read manifest '{}', all images will be processed: {}",
+                path.display(),
+                err
+            );
+
+            Manifest::default()
+        })
+    }
+
+    /// Writes the manifest to the given output root folder.
+    pub fn save(&self, output_root_folder: &Path) -> anyhow::Result<()> {
+        let path = output_root_folder.join(MANIFEST_FILE_NAME);
+        // Write to a temporary file first, so an interrupted run does not leave a partially
+        // written manifest behind.
+        let tmp_path = path.with_extension("json.tmp");
+
+        std::fs::create_dir_all(output_root_folder)?;
+
+        let file = File::create(&tmp_path)
+            .with_context(|| format!("Unable to create manifest '{}'", tmp_path.display()))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)
+            .with_context(|| format!("Unable to write manifest '{}'", tmp_path.display()))?;
+
+        std::fs::rename(&tmp_path, &path)
+            .with_context(|| format!("Unable to write manifest '{}'", path.display()))
+    }
+
+    /// Compares the current state of the given input with its entry. The input is up to date if
+    /// it is unchanged since it was last processed with settings of the same fingerprint, and its
+    /// outputs still exist. If only its modification time changed, the returned entry is updated
+    /// accordingly.
+    ///
+    /// Otherwise, the input should be processed, and the returned entry records its current
+    /// state; its output paths should be set with [`ManifestEntry::with_outputs`], once the
+    /// outputs are written. The hash of an input which is unchanged is taken from the manifest,
+    /// so inputs which are processed again because the settings changed are not hashed.
+    pub fn status(&self, key: &Path, input: &Path, fingerprint: u64) -> InputStatus {
+        let previous = self.entries.get(key);
+
+        let entry = match current_state(previous, input, fingerprint) {
+            Ok(entry) => entry,
+            Err(_) => return InputStatus::Outdated(None),
+        };
+
+        match previous {
+            Some(previous)
+                if previous.fingerprint == fingerprint
+                    && previous.len == entry.len
+                    && previous.hash == entry.hash
+                    && !previous.outputs.is_empty()
+                    && previous.outputs.iter().all(|output| output.exists()) =>
+            {
+                InputStatus::UpToDate(ManifestEntry {
+                    modified: entry.modified,
+                    ..previous.clone()
+                })
+            }
+            _ => InputStatus::Outdated(Some(entry)),
+        }
+    }
+
+    pub fn insert(&mut self, key: PathBuf, entry: ManifestEntry) {
+        self.entries.insert(key, entry);
+    }
+
+    pub fn remove(&mut self, key: &Path) {
+        self.entries.remove(key);
+    }
+}
+
+/// The state of an input, compared with the state recorded in the manifest.
+#[derive(Debug)]
+pub enum InputStatus {
+    /// The input does not need to be processed again.
+    UpToDate(ManifestEntry),
+    /// The input should be processed. The entry records its current state, if it could be read.
+    Outdated(Option<ManifestEntry>),
+}
+
+/// Records the current state of the given input, without outputs. The input is only hashed if its
+/// size or modification time differ from the previous entry.
+fn current_state(
+    previous: Option<&ManifestEntry>,
+    input: &Path,
+    fingerprint: u64,
+) -> io::Result<ManifestEntry> {
+    let metadata = std::fs::metadata(input)?;
+    let modified = metadata.modified()?;
+    let len = metadata.len();
+
+    let hash = match previous {
+        Some(previous) if previous.modified == modified && previous.len == len => previous.hash,
+        _ => hash_file(input)?,
+    };
+
+    Ok(ManifestEntry {
+        modified,
+        len,
+        hash,
+        fingerprint,
+        outputs: Vec::new(),
+    })
+}
+
+/// A fingerprint of the settings which determine the output image: the version of sic, the image
+/// operations and the contents of the files they read, the decoding and encoding settings, the
+/// output path template and variants, and the statistics format. Settings which only affect how a
+/// batch is run, such as the number of jobs, are not included.
+pub fn fingerprint(config: &Config) -> u64 {
+    let settings = format!(
+        "{}|{:?}|{:?}|{:?}|{:?}|{:?}|{:?}|{:?}|{:?}|{:?}|{:?}|{:?}|{:?}",
+        env!("CARGO_PKG_VERSION"),
+        config.image_operations_program,
+        config.selected_frame,
+        config.frame_range,
+        config.disable_automatic_orientation,
+        config.disable_automatic_color_type_adjustment,
+        config.forced_output_format,
+        config.encoding_settings,
+        config.metadata_retention.unwrap_or_default(),
+        config.output_template,
+        config.output_variants,
+        config.stats,
+        referenced_files(config),
+    );
+
+    hash_bytes(settings.as_bytes())
+}
+
+/// The hashes of the files which are read by the image operations, such as the image of an
+/// `overlay`, so the fingerprint changes when such a file is edited. Files which can't be read
+/// have no hash; images processed with them will fail anyway.
+fn referenced_files(config: &Config) -> Vec<Option<u64>> {
+    let mut paths = Vec::new();
+
+    collect_referenced_files(&config.image_operations_program, &mut paths);
+    for variant in &config.output_variants {
+        collect_referenced_files(variant.program(), &mut paths);
+    }
+
+    paths.into_iter().map(|path| hash_file(path).ok()).collect()
+}
+
+fn collect_referenced_files<'program>(program: &'program [Instr], paths: &mut Vec<&'program Path>) {
+    for instruction in program {
+        match instruction {
+            Instr::Operation(ImgOp::ConvertProfile(IccProfileTarget::Path(path))) => {
+                paths.push(path)
+            }
+            Instr::Operation(ImgOp::Diff(inputs)) => paths.push(inputs.image_path().path()),
+            #[cfg(feature = "imageproc-ops")]
+            Instr::Operation(ImgOp::DrawText(inner)) => paths.push(&inner.font_options().font_path),
+            Instr::Operation(ImgOp::OnFrames(_, program)) => {
+                collect_referenced_files(program, paths)
+            }
+            Instr::Operation(ImgOp::Overlay(inputs)) => paths.push(inputs.image_path().path()),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprint_depends_on_output_settings_only() {
+        let default = fingerprint(&Config::default());
+
+        let batch_settings = Config {
+            jobs: 4,
+            keep_going: true,
+            ..Config::default()
+        };
+        assert_eq!(fingerprint(&batch_settings), default);
+
+        let output_format = Config {
+            forced_output_format: Some("jpg"),
+            ..Config::default()
+        };
+        assert_ne!(fingerprint(&output_format), default);
+    }
+
+    #[test]
+    fn unchanged_inputs_are_not_hashed() {
+        let input = std::env::temp_dir().join("sic_manifest_unchanged_inputs_are_not_hashed.png");
+        std::fs::write(&input, b"image").unwrap();
+        let metadata = std::fs::metadata(&input).unwrap();
+
+        // The recorded hash is not the hash of the input, so a matching hash means it was taken
+        // from the manifest.
+        let mut manifest = Manifest::default();
+        manifest.insert(
+            PathBuf::from("input.png"),
+            ManifestEntry {
+                modified: metadata.modified().unwrap(),
+                len: metadata.len(),
+                hash: 0,
+                fingerprint: 1,
+                outputs: vec![input.clone()],
+            },
+        );
+
+        let status = manifest.status(Path::new("input.png"), &input, 1);
+        assert!(matches!(status, InputStatus::UpToDate(entry) if entry.hash == 0));
+
+        let status = manifest.status(Path::new("input.png"), &input, 2);
+        assert!(matches!(status, InputStatus::Outdated(Some(entry)) if entry.hash == 0));
+
+        // Once the modification time differs, the input is hashed.
+        File::options()
+            .write(true)
+            .open(&input)
+            .unwrap()
+            .set_modified(metadata.modified().unwrap() + std::time::Duration::from_secs(60))
+            .unwrap();
+
+        let status = manifest.status(Path::new("input.png"), &input, 1);
+        assert!(
+            matches!(status, InputStatus::Outdated(Some(entry)) if entry.hash == hash_bytes(b"image"))
+        );
+
+        std::fs::remove_file(input).unwrap();
+    }
+}
@@ -1,6 +1,15 @@
 pub mod app;
+pub mod batch;
 pub mod common_dir;
+pub mod compare;
 pub mod config;
+pub mod frame_pattern;
 pub mod glob_base_dir;
+pub mod hash;
+pub mod info;
 pub mod license;
+pub mod manifest;
+pub mod output_template;
+pub mod output_variant;
 pub mod pipeline;
+pub mod stats;
@@ -0,0 +1,206 @@
+//! A template from which the output path of an image is derived, after it has been processed.
+//!
+//! The template may contain the following placeholders:
+//! * `{dir}`: the directory of the input image; in glob mode relative to the common directory of
+//!   the inputs, so the input tree is mirrored in the output folder
+//! * `{stem}`: the file name of the input image, without its extension
+//! * `{ext}`: the extension of the output format if given by `--output-format`, and otherwise the
+//!   extension of the input image
+//! * `{width}` and `{height}`: the dimensions of the processed image
+//! * `{hashN}`: the first `N` (1 up to and including 16) hexadecimal digits of the hash of the
+//!   encoded output image, e.g. `{hash8}`
+//!
+//! For example, `{dir}/{stem}@2x.{ext}` or `{stem}-{width}x{height}.{ext}`.
+
+use anyhow::bail;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct OutputTemplate {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Part {
+    Literal(String),
+    Dir,
+    Stem,
+    Ext,
+    Width,
+    Height,
+    Hash(usize),
+}
+
+/// The values with which the placeholders of an output template are replaced.
+#[derive(Debug)]
+pub struct TemplateValues<'a> {
+    pub dir: &'a Path,
+    pub stem: &'a str,
+    pub ext: &'a str,
+    pub width: u32,
+    pub height: u32,
+    /// Hash of the encoded output image.
+    pub hash: u64,
+}
+
+impl OutputTemplate {
+    pub fn try_new(template: &str) -> anyhow::Result<Self> {
+        let mut parts = Vec::new();
+        let mut rest = template;
+
+        while let Some(start) = rest.find('{') {
+            if start > 0 {
+                parts.push(Part::Literal(rest[..start].to_string()));
+            }
+
+            let end = match rest[start..].find('}') {
+                Some(end) => start + end,
+                None => bail!(
+                    "A placeholder of the output template '{}' is not closed",
+                    template
+                ),
+            };
+
+            parts.push(Self::parse_placeholder(&rest[start + 1..end], template)?);
+            rest = &rest[end + 1..];
+        }
+
+        if !rest.is_empty() {
+            parts.push(Part::Literal(rest.to_string()));
+        }
+
+        if parts.is_empty() {
+            bail!("The output template should not be empty");
+        }
+
+        Ok(Self { parts })
+    }
+
+    fn parse_placeholder(name: &str, template: &str) -> anyhow::Result<Part> {
+        let part = match name {
+            "dir" => Part::Dir,
+            "stem" => Part::Stem,
+            "ext" => Part::Ext,
+            "width" => Part::Width,
+            "height" => Part::Height,
+            _ => match name.strip_prefix("hash").map(str::parse::<usize>) {
+                Some(Ok(digits)) if (1..=16).contains(&digits) => Part::Hash(digits),
+                _ => bail!(
+                    "Unknown placeholder '{{{}}}' in output template '{}'. Valid placeholders are \
+                     '{{dir}}', '{{stem}}', '{{ext}}', '{{width}}', '{{height}}' and '{{hashN}}', \
+                     where N is a number of digits between 1 and 16",
+                    name,
+                    template,
+                ),
+            },
+        };
+
+        Ok(part)
+    }
+
+    /// Whether the template contains the `{ext}` placeholder.
+    pub fn uses_ext(&self) -> bool {
+        self.parts.contains(&Part::Ext)
+    }
+
+    pub fn expand(&self, values: &TemplateValues) -> PathBuf {
+        let expanded = self
+            .parts
+            .iter()
+            .map(|part| match part {
+                Part::Literal(literal) => literal.clone(),
+                // An input in the common directory itself has no directory; `.` is used instead,
+                // so the template does not turn into an absolute path, e.g. for `{dir}/{stem}`.
+                Part::Dir if values.dir.as_os_str().is_empty() => ".".to_string(),
+                Part::Dir => values.dir.display().to_string(),
+                Part::Stem => values.stem.to_string(),
+                Part::Ext => values.ext.to_string(),
+                Part::Width => values.width.to_string(),
+                Part::Height => values.height.to_string(),
+                Part::Hash(digits) => format!("{:016x}", values.hash)[..*digits].to_string(),
+            })
+            .collect::<String>();
+
+        PathBuf::from(expanded)
+    }
+}
+
+impl fmt::Display for OutputTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.parts.iter().try_for_each(|part| match part {
+            Part::Literal(literal) => f.write_str(literal),
+            Part::Dir => f.write_str("{dir}"),
+            Part::Stem => f.write_str("{stem}"),
+            Part::Ext => f.write_str("{ext}"),
+            Part::Width => f.write_str("{width}"),
+            Part::Height => f.write_str("{height}"),
+            Part::Hash(digits) => write!(f, "{{hash{}}}", digits),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values() -> TemplateValues<'static> {
+        TemplateValues {
+            dir: Path::new("icons/small"),
+            stem: "logo",
+            ext: "png",
+            width: 64,
+            height: 32,
+            hash: 0x0123_4567_89ab_cdef,
+        }
+    }
+
+    #[yare::parameterized(
+        dir_stem_ext = { "{dir}/{stem}@2x.{ext}", "icons/small/logo@2x.png" },
+        dimensions = { "{stem}-{width}x{height}.{ext}", "logo-64x32.png" },
+        hash8 = { "{hash8}.{ext}", "01234567.png" },
+        hash16 = { "{hash16}", "0123456789abcdef" },
+        literal = { "out.jpg", "out.jpg" },
+        repeated = { "{stem}/{stem}.{ext}", "logo/logo.png" },
+    )]
+    fn expand(template: &str, expected: &str) {
+        let template = OutputTemplate::try_new(template).unwrap();
+
+        assert_eq!(template.expand(&values()), PathBuf::from(expected));
+    }
+
+    #[test]
+    fn expand_empty_dir() {
+        let template = OutputTemplate::try_new("{dir}/{stem}.{ext}").unwrap();
+        let values = TemplateValues {
+            dir: Path::new(""),
+            ..values()
+        };
+
+        assert_eq!(template.expand(&values), PathBuf::from("./logo.png"));
+    }
+
+    #[yare::parameterized(
+        placeholders = { "{dir}/{stem}-{width}x{height}.{ext}" },
+        hash = { "{hash8}.png" },
+        literal = { "out.jpg" },
+    )]
+    fn display(template: &str) {
+        assert_eq!(
+            OutputTemplate::try_new(template).unwrap().to_string(),
+            template
+        );
+    }
+
+    #[yare::parameterized(
+        empty = { "" },
+        not_closed = { "{stem.png" },
+        unknown = { "{name}.png" },
+        hash_without_digits = { "{hash}.png" },
+        hash_too_long = { "{hash17}.png" },
+        hash_zero = { "{hash0}.png" },
+    )]
+    fn invalid(template: &str) {
+        assert!(OutputTemplate::try_new(template).is_err());
+    }
+}
@@ -0,0 +1,270 @@
+//! An output variant is an additional output branch of an image: after the image has been decoded
+//! and the common image operations have been applied, each variant applies its own image
+//! operations to a copy of the image, and writes it to its own output path.
+//!
+//! A variant is written as `<output template>: <image operations>`, e.g.
+//! `thumb.webp: resize 128 128` or `{stem}-full.{ext}: resize 2048 2048`. The image operations
+//! may be omitted, e.g. `full.png`, in which case the image is written as is.
+//!
+//! The output path may be followed by encoding options within square brackets, which replace the
+//! encoding options of the same name for this variant only, e.g.
+//! `thumb.webp [webp-quality=80]: resize 128 128` or `full.avif [avif-quality=60, avif-speed=8]`.
+//! The output format of a variant is always determined by the extension of its output path.
+
+use crate::cli::config::{
+    parse_avif_quality, parse_avif_speed, parse_jpeg_quality, parse_webp_quality,
+    FormatEncodingSettings,
+};
+use crate::cli::output_template::OutputTemplate;
+use anyhow::{bail, Context};
+use sic_image_engine::engine::Instr;
+use sic_io::encode_settings::avif::{AvifQuality, AvifSpeed};
+use sic_io::encode_settings::gif::RepeatAnimation;
+use sic_io::encode_settings::png::{PngCompression, PngFilter};
+use sic_io::encode_settings::tiff::TiffCompression;
+use sic_io::encode_settings::webp::WebpQuality;
+use std::borrow::Cow;
+
+#[derive(Debug, Clone)]
+pub struct OutputVariant {
+    template: OutputTemplate,
+    encoding_options: Vec<EncodingOption>,
+    program: Vec<Instr>,
+}
+
+/// An encoding option of a single variant, named after the command line option it replaces.
+#[derive(Debug, Clone, Copy)]
+enum EncodingOption {
+    JpegQuality(u8),
+    PnmAscii,
+    GifRepeat(RepeatAnimation),
+    AvifQuality(AvifQuality),
+    AvifSpeed(AvifSpeed),
+    PngCompression(PngCompression),
+    PngFilter(PngFilter),
+    TiffCompression(TiffCompression),
+    WebpQuality(WebpQuality),
+}
+
+impl EncodingOption {
+    fn try_from_str(option: &str) -> anyhow::Result<Self> {
+        let (name, value) = match option.split_once('=') {
+            Some((name, value)) => (name.trim(), Some(value.trim())),
+            None => (option.trim(), None),
+        };
+
+        let option = match (name, value) {
+            ("jpeg-encoding-quality", Some(value)) => Self::JpegQuality(parse_jpeg_quality(value)?),
+            ("pnm-encoding-ascii", None) => Self::PnmAscii,
+            ("gif-repeat", Some(value)) => Self::GifRepeat(RepeatAnimation::try_from_str(value)?),
+            ("avif-quality", Some(value)) => Self::AvifQuality(parse_avif_quality(value)?),
+            ("avif-speed", Some(value)) => Self::AvifSpeed(parse_avif_speed(value)?),
+            ("png-compression", Some(value)) => {
+                Self::PngCompression(PngCompression::try_from_str(value)?)
+            }
+            ("png-filter", Some(value)) => Self::PngFilter(PngFilter::try_from_str(value)?),
+            ("tiff-compression", Some(value)) => {
+                Self::TiffCompression(TiffCompression::try_from_str(value)?)
+            }
+            ("webp-quality", Some(value)) => Self::WebpQuality(parse_webp_quality(value)?),
+            _ => bail!(
+                "Unknown encoding option '{}'. Valid options are 'jpeg-encoding-quality=<QUALITY>', \
+                 'pnm-encoding-ascii', 'gif-repeat=<REPETITIONS>', 'avif-quality=<QUALITY>', \
+                 'avif-speed=<SPEED>', 'png-compression=<LEVEL>', 'png-filter=<FILTER>', \
+                 'tiff-compression=<COMPRESSION>' and 'webp-quality=<QUALITY>'",
+                option
+            ),
+        };
+
+        Ok(option)
+    }
+
+    fn apply(self, settings: &mut FormatEncodingSettings) {
+        match self {
+            Self::JpegQuality(quality) => settings.jpeg_quality = quality,
+            Self::PnmAscii => settings.pnm_use_ascii_format = true,
+            Self::GifRepeat(repeat) => settings.gif_repeat = repeat,
+            Self::AvifQuality(quality) => settings.avif_quality = quality,
+            Self::AvifSpeed(speed) => settings.avif_speed = speed,
+            Self::PngCompression(compression) => settings.png_compression = compression,
+            Self::PngFilter(filter) => settings.png_filter = filter,
+            Self::TiffCompression(compression) => settings.tiff_compression = compression,
+            Self::WebpQuality(quality) => settings.webp_quality = Some(quality),
+        }
+    }
+}
+
+impl OutputVariant {
+    pub fn try_from_str(variant: &str) -> anyhow::Result<Self> {
+        let (path, script) = split_variant(variant);
+        let (template, options) = split_encoding_options(path.trim());
+
+        let template = OutputTemplate::try_new(template.trim())
+            .with_context(|| format!("Invalid output path of output variant '{}'", variant))?;
+
+        let encoding_options = options
+            .split(',')
+            .filter(|option| !option.trim().is_empty())
+            .map(EncodingOption::try_from_str)
+            .collect::<anyhow::Result<Vec<_>>>()
+            .with_context(|| format!("Invalid encoding options of output variant '{}'", variant))?;
+
+        let program = if script.trim().is_empty() {
+            Vec::new()
+        } else {
+            sic_parser::parse_script(script).with_context(|| {
+                format!("Invalid image operations of output variant '{}'", variant)
+            })?
+        };
+
+        Ok(Self {
+            template,
+            encoding_options,
+            program,
+        })
+    }
+
+    pub fn template(&self) -> &OutputTemplate {
+        &self.template
+    }
+
+    /// The encoding settings of this variant: the given settings, of which the options of this
+    /// variant replace the options of the same name.
+    pub fn encoding_settings<'s>(
+        &self,
+        settings: &'s FormatEncodingSettings,
+    ) -> Cow<'s, FormatEncodingSettings> {
+        if self.encoding_options.is_empty() {
+            return Cow::Borrowed(settings);
+        }
+
+        let mut settings = settings.clone();
+        for option in &self.encoding_options {
+            option.apply(&mut settings);
+        }
+
+        Cow::Owned(settings)
+    }
+
+    /// The image operations which are applied to this variant only.
+    pub fn program(&self) -> &[Instr] {
+        &self.program
+    }
+}
+
+/// Splits the variant at the first colon which is followed by whitespace, or which ends the
+/// variant, so colons within paths, such as in `C:\images\thumb.png`, are not mistaken for the
+/// separator.
+fn split_variant(variant: &str) -> (&str, &str) {
+    variant
+        .char_indices()
+        .filter(|(_, c)| *c == ':')
+        .map(|(index, _)| index)
+        .find(|index| {
+            variant[index + 1..]
+                .chars()
+                .next()
+                .map_or(true, char::is_whitespace)
+        })
+        .map_or((variant, ""), |index| {
+            (&variant[..index], &variant[index + 1..])
+        })
+}
+
+/// Splits the encoding options within square brackets from the end of the output path, e.g.
+/// `thumb.webp [webp-quality=80]`.
+fn split_encoding_options(path: &str) -> (&str, &str) {
+    path.strip_suffix(']')
+        .and_then(|rest| rest.rsplit_once('['))
+        .unwrap_or((path, ""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::config::Config;
+
+    #[yare::parameterized(
+        with_operations = { "thumb.webp: resize 128 128", "thumb.webp", " resize 128 128" },
+        without_operations = { "full.png", "full.png", "" },
+        trailing_separator = { "full.png:", "full.png", "" },
+        colon_in_path = { r"C:\images\thumb.png: blur 1", r"C:\images\thumb.png", " blur 1" },
+        multiple_statements = { "a.png: blur 1; flip-horizontal", "a.png", " blur 1; flip-horizontal" },
+    )]
+    fn split(variant: &str, expected_path: &str, expected_script: &str) {
+        assert_eq!(split_variant(variant), (expected_path, expected_script));
+    }
+
+    #[test]
+    fn parse_operations() {
+        let variant = OutputVariant::try_from_str("thumb.webp: resize 128 128; blur 1").unwrap();
+
+        assert_eq!(variant.program().len(), 2);
+    }
+
+    #[test]
+    fn parse_without_operations() {
+        let variant = OutputVariant::try_from_str("{stem}.png").unwrap();
+
+        assert!(variant.program().is_empty());
+    }
+
+    #[yare::parameterized(
+        with_options = { "thumb.webp [webp-quality=80]", "thumb.webp ", "webp-quality=80" },
+        without_space = { "thumb.webp[webp-quality=80]", "thumb.webp", "webp-quality=80" },
+        without_options = { "thumb.webp", "thumb.webp", "" },
+        brackets_within_path = { "[a]/thumb.webp", "[a]/thumb.webp", "" },
+    )]
+    fn split_options(path: &str, expected_path: &str, expected_options: &str) {
+        assert_eq!(
+            split_encoding_options(path),
+            (expected_path, expected_options)
+        );
+    }
+
+    #[test]
+    fn parse_encoding_options() {
+        let variant =
+            OutputVariant::try_from_str("thumb.avif [avif-quality=60, avif-speed=8]: blur 1")
+                .unwrap();
+
+        let defaults = Config::default().encoding_settings;
+        let settings = variant.encoding_settings(&defaults);
+
+        assert_eq!(settings.avif_quality, AvifQuality::try_from(60).unwrap());
+        assert_eq!(settings.avif_speed, AvifSpeed::try_from(8).unwrap());
+        assert_eq!(settings.jpeg_quality, defaults.jpeg_quality);
+        assert_eq!(variant.template().to_string(), "thumb.avif");
+        assert_eq!(variant.program().len(), 1);
+    }
+
+    #[test]
+    fn encoding_options_replace_given_settings() {
+        let variant = OutputVariant::try_from_str("thumb.webp [webp-quality=80]").unwrap();
+
+        let given = FormatEncodingSettings {
+            webp_quality: Some(WebpQuality::try_from(20).unwrap()),
+            jpeg_quality: 50,
+            ..Config::default().encoding_settings
+        };
+        let settings = variant.encoding_settings(&given);
+
+        assert_eq!(
+            settings.webp_quality,
+            Some(WebpQuality::try_from(80).unwrap())
+        );
+        assert_eq!(settings.jpeg_quality, 50);
+    }
+
+    #[yare::parameterized(
+        empty_path = { ": blur 1" },
+        invalid_template = { "{name}.png: blur 1" },
+        invalid_operations = { "thumb.png: resize 1" },
+        unknown_encoding_option = { "thumb.png [quality=80]" },
+        invalid_encoding_option_value = { "thumb.webp [webp-quality=101]" },
+        missing_encoding_option_value = { "thumb.webp [webp-quality]" },
+    )]
+    fn invalid(variant: &str) {
+        assert!(OutputVariant::try_from_str(variant).is_err());
+    }
+}
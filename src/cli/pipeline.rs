@@ -1,19 +1,37 @@
-use std::borrow::BorrowMut;
+use std::borrow::{BorrowMut, Cow};
 use std::fs::File;
-use std::io::{self, Read, Seek, SeekFrom, Stdout, Write};
-
-use crate::cli::config::{Config, InputOutputMode, InputOutputModeType, PathVariant};
+use std::io::{self, Cursor, Read, Seek, SeekFrom, Stdout, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::cli::batch::{BatchOutcome, BatchSummary};
+use crate::cli::common_dir::CommonDir;
+use crate::cli::compare::{CompareReport, CompareThreshold};
+use crate::cli::config::{
+    Config, FormatEncodingSettings, InputOutputMode, InputOutputModeType, PathVariant,
+};
+use crate::cli::frame_pattern::FramePattern;
+use crate::cli::hash::hash_bytes;
+use crate::cli::info::{InfoFormat, InfoReport};
 use crate::cli::license::LicenseTexts;
 use crate::cli::license::PrintTextFor;
+use crate::cli::manifest::{self, InputStatus, Manifest};
+use crate::cli::output_template::{OutputTemplate, TemplateValues};
+use crate::cli::stats::{StatsFormat, StatsReport};
 use anyhow::{anyhow, bail, Context};
-use sic_core::image;
-use sic_image_engine::engine::ImageEngine;
+use rayon::prelude::*;
+use sic_core::{image, AnimatedImage, ImageMetadata, SicImage};
+use sic_image_engine::compare::compare;
+use sic_image_engine::engine::{ImageEngine, Instr};
+use sic_image_engine::wrapper::image_path::ImageFromPath;
+use sic_image_engine::ImgOp;
 use sic_io::decode;
 use sic_io::decode::SicImageDecoder;
 use sic_io::encode::dynamic::{DynamicEncoder, IntoImageEncoder};
 use sic_io::encode::SicImageEncoder;
 use sic_io::encode_settings::jpeg::JpegQuality;
 use sic_io::encode_settings::EncodeSettings;
+use sic_io::metadata::embed_metadata;
 use sic_io::preprocessor::Preprocessors;
 
 pub fn run_with_devices<'c>(
@@ -22,6 +40,19 @@ pub fn run_with_devices<'c>(
 ) -> anyhow::Result<()> {
     match in_and_output {
         InputOutputMode::Single { input, output } => {
+            let templated_outputs = templated_outputs(config);
+
+            if !templated_outputs.is_empty() {
+                let dir = match &input {
+                    PathVariant::Path(path) => path.parent().unwrap_or_else(|| Path::new("")),
+                    PathVariant::StdStream => Path::new(""),
+                };
+
+                return run_with_templates(&input, dir, Path::new(""), &templated_outputs, config)
+                    .map(|_| ())
+                    .with_context(|| format!("With: {}", input.describe_input()));
+            }
+
             if output.is_std_stream() {
                 warn_default_std_output_format();
             }
@@ -37,24 +68,37 @@ pub fn run_with_devices<'c>(
         InputOutputMode::Batch {
             inputs,
             output_root_folder,
-        } => {
-            for (input, branch) in inputs.path_combinations() {
-                let input = &PathVariant::Path(input.to_path_buf());
-
-                let output = output_root_folder.join(branch);
-                let output = &PathVariant::Path(output);
-
-                run(
-                    || create_reader(input),
-                    |ext: Option<&str>| create_writer(output, ext),
-                    config,
-                    output,
-                )
-                .with_context(|| format!("With input: {}", input.describe_input()))?
+        } => run_batch(&inputs, &output_root_folder, config),
+        InputOutputMode::ExplodeFrames { input, pattern } => {
+            run_explode_frames(|| create_reader(&input), config, &pattern)
+                .with_context(|| format!("With: {}", input.describe_input()))
+        }
+        InputOutputMode::Assemble { inputs, output } => {
+            if output.is_std_stream() {
+                warn_default_std_output_format();
             }
 
-            Ok(())
+            run_assemble(&inputs, config, &output)
         }
+        InputOutputMode::Info { input, format } => {
+            run_info(|| create_reader(&input), format, &mut io::stdout().lock())
+                .with_context(|| format!("With: {}", input.describe_input()))
+        }
+        InputOutputMode::Compare {
+            input,
+            reference,
+            heat_map,
+            thresholds,
+            format,
+        } => run_compare(
+            || create_reader(&input),
+            &reference,
+            heat_map.as_ref(),
+            &thresholds,
+            format,
+            config,
+        )
+        .with_context(|| format!("With: {}", input.describe_input())),
     }
 }
 
@@ -77,17 +121,7 @@ where
     W: Fn(Option<&str>) -> anyhow::Result<WS>,
     WS: Write + Seek,
 {
-    let mut reader = supply_reader()?;
-
-    // Decode
-    let decoder = SicImageDecoder::new(config.selected_frame);
-    let img = decoder.decode(&mut reader)?;
-
-    // Apply image operations
-    let image_engine = ImageEngine::new(img);
-    let buffer = image_engine
-        .ignite(&config.image_operations_program)
-        .with_context(|| "Unable to apply image operations.")?;
+    let (image, metadata) = decode_and_process(supply_reader, config)?;
 
     // FIXME: decide whether in simple mode, extension should also change by default,
     //        unless an option is set e.g. --keep-extension-unmodified
@@ -97,18 +131,608 @@ where
         None
     };
 
-    // Create a writer and the encoder
-    let writer = supply_writer(format)?;
-    let encode_settings = create_encode_settings(config)?;
-    let dynamic_encoder =
-        create_dynamic_encoder(writer, config, &encode_settings, output_path_variant)?;
+    let stats = compute_stats(&image, config);
+    let encoded = encode(image, metadata, config, output_path_variant)?;
+
+    supply_writer(format)?
+        .write_all(&encoded)
+        .with_context(|| "Unable to write image")?;
+
+    let output = match output_path_variant {
+        PathVariant::Path(path) => PathVariant::Path(adjusted_output_path(path, format)),
+        PathVariant::StdStream => PathVariant::StdStream,
+    };
+
+    write_stats(stats, &output)
+}
+
+/// Processes each input image of a batch, and writes it to the output root folder, at the same
+/// path relative to the root as the input image to the common directory of the inputs.
+///
+/// Up to `config.jobs` images are processed concurrently. The inputs are ordered by their path
+/// relative to the common directory. Once an image fails, no new images will be started, while
+/// images which are already being processed are allowed to finish. The reported error is the one
+/// of the first failed image in input order, regardless of the order in which the images were
+/// processed, so runs with the same inputs report the same error.
+///
+/// If `config.keep_going` is set, all images are processed regardless of failures, and an error
+/// is returned afterwards if any of them failed.
+///
+/// If `config.incremental` is set, images which are up to date according to the manifest in the
+/// output root folder are not processed again, and the manifest is updated afterwards.
+///
+/// In all cases, a summary is written to stderr, and if requested, as JSON report to
+/// `config.batch_report`.
+fn run_batch(inputs: &CommonDir, output_root_folder: &Path, config: &Config) -> anyhow::Result<()> {
+    let mut combinations = inputs.path_combinations();
+    combinations.sort_by_key(|(_, branch)| *branch);
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(config.jobs)
+        .build()
+        .with_context(|| "Unable to start the batch processing thread pool")?;
+
+    let manifest = config
+        .incremental
+        .then(|| Manifest::load(output_root_folder));
+    let fingerprint = manifest::fingerprint(config);
+
+    // Index of the first failed input; unless keep going is set, inputs after it are not started.
+    let first_failure = AtomicUsize::new(usize::MAX);
+
+    let outcomes = pool.install(|| {
+        combinations
+            .par_iter()
+            .enumerate()
+            .map(|(index, (input, branch))| {
+                if !config.keep_going && index > first_failure.load(Ordering::SeqCst) {
+                    let outputs = expected_batch_outputs(branch, output_root_folder, config);
+                    return (*input, *branch, outputs, BatchOutcome::Skipped, None);
+                }
+
+                // The state of the input is recorded before it is processed, so changes made
+                // while it is being processed are picked up by the next run.
+                let entry = match &manifest {
+                    Some(manifest) => match manifest.status(branch, input, fingerprint) {
+                        InputStatus::UpToDate(entry) => {
+                            let outputs = entry.outputs().to_vec();
+                            return (
+                                *input,
+                                *branch,
+                                outputs,
+                                BatchOutcome::UpToDate,
+                                Some(entry),
+                            );
+                        }
+                        InputStatus::Outdated(entry) => entry,
+                    },
+                    None => None,
+                };
+
+                match run_batch_input(input, branch, output_root_folder, config) {
+                    Ok(outputs) => {
+                        let entry = entry.map(|entry| entry.with_outputs(outputs.clone()));
+                        (*input, *branch, outputs, BatchOutcome::Succeeded, entry)
+                    }
+                    Err(err) => {
+                        first_failure.fetch_min(index, Ordering::SeqCst);
+                        let outputs = expected_batch_outputs(branch, output_root_folder, config);
+                        (*input, *branch, outputs, BatchOutcome::Failed(err), None)
+                    }
+                }
+            })
+            .collect::<Vec<_>>()
+    });
+
+    if let Some(mut manifest) = manifest {
+        for (_, branch, _, outcome, entry) in &outcomes {
+            match (outcome, entry) {
+                (BatchOutcome::Failed(_), _) => manifest.remove(branch),
+                (_, Some(entry)) => manifest.insert(branch.to_path_buf(), entry.clone()),
+                _ => {}
+            }
+        }
+
+        manifest.save(output_root_folder)?;
+    }
+
+    let summary = BatchSummary::from_outcomes(
+        outcomes
+            .into_iter()
+            .map(|(input, _, outputs, outcome, _)| (input, outputs, outcome)),
+    );
+    eprintln!("{}", summary);
+
+    if let Some(path) = &config.batch_report {
+        summary.write_json_report(path)?;
+    }
+
+    match summary.failures.first() {
+        Some(failure) if !config.keep_going => Err(failure.to_error()),
+        Some(_) => Err(anyhow!(
+            "{} of {} images failed to process",
+            summary.failed,
+            summary.total
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Processes a single input image of a batch, and returns the paths of the written output images.
+fn run_batch_input(
+    input: &Path,
+    branch: &Path,
+    output_root_folder: &Path,
+    config: &Config,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let input = &PathVariant::Path(input.to_path_buf());
+    let templated_outputs = templated_outputs(config);
+
+    if templated_outputs.is_empty() {
+        let output = output_root_folder.join(branch);
+        let output_path_variant = &PathVariant::Path(output.clone());
+
+        run(
+            || create_reader(input),
+            |ext: Option<&str>| create_writer(output_path_variant, ext),
+            config,
+            output_path_variant,
+        )?;
+
+        Ok(vec![adjusted_output_path(
+            &output,
+            config.forced_output_format,
+        )])
+    } else {
+        let dir = branch.parent().unwrap_or_else(|| Path::new(""));
+        run_with_templates(input, dir, output_root_folder, &templated_outputs, config)
+    }
+}
+
+/// The output paths of an input image of a batch, if they can be known without processing the
+/// image, i.e. if no output template or output variants are used.
+fn expected_batch_outputs(
+    branch: &Path,
+    output_root_folder: &Path,
+    config: &Config,
+) -> Vec<PathBuf> {
+    if templated_outputs(config).is_empty() {
+        vec![adjusted_output_path(
+            &output_root_folder.join(branch),
+            config.forced_output_format,
+        )]
+    } else {
+        Vec::new()
+    }
+}
+
+/// An output path which is derived from a template, with the image operations and encoding
+/// settings which only apply to the image written to that output.
+struct TemplatedOutput<'c> {
+    template: &'c OutputTemplate,
+    program: &'c [Instr],
+    encoding_settings: Cow<'c, FormatEncodingSettings>,
+    /// The output format, if not determined by the extension of the expanded template.
+    forced_output_format: Option<&'c str>,
+}
+
+/// The templated outputs are given by either the output variants, each with their own image
+/// operations and encoding settings, or by the output template, in which case no additional
+/// image operations are applied.
+fn templated_outputs<'c>(config: &'c Config) -> Vec<TemplatedOutput<'c>> {
+    match &config.output_template {
+        Some(template) => vec![TemplatedOutput {
+            template,
+            program: &[],
+            encoding_settings: Cow::Borrowed(&config.encoding_settings),
+            forced_output_format: config.forced_output_format,
+        }],
+        None => config
+            .output_variants
+            .iter()
+            .map(|variant| TemplatedOutput {
+                template: variant.template(),
+                program: variant.program(),
+                encoding_settings: variant.encoding_settings(&config.encoding_settings),
+                forced_output_format: None,
+            })
+            .collect(),
+    }
+}
+
+/// Processes the input image, and writes it to each of the paths derived from the templated
+/// outputs, relative to the output root folder. The image is decoded, and the common image
+/// operations are applied, only once; the image operations of each output are then applied to a
+/// copy of the image. Returns the paths of the written output images.
+///
+/// The `dir` is the directory of the input image, which replaces the `{dir}` placeholder.
+fn run_with_templates(
+    input: &PathVariant,
+    dir: &Path,
+    output_root_folder: &Path,
+    templated_outputs: &[TemplatedOutput],
+    config: &Config,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let input_path = match input {
+        PathVariant::Path(path) => path,
+        PathVariant::StdStream => {
+            bail!("An output template or output variant requires an input file")
+        }
+    };
+
+    let stem = input_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .ok_or_else(|| anyhow!("Unable to determine the file name of the input image"))?;
+
+    let (image, metadata) = decode_and_process(|| create_reader(input), config)?;
+
+    templated_outputs
+        .iter()
+        .map(|output| {
+            let template = output.template;
+            let (image, metadata) = if output.program.is_empty() {
+                (image.clone(), metadata.clone())
+            } else {
+                ImageEngine::new(image.clone())
+                    .with_metadata(metadata.clone())
+                    .ignite_with_metadata(output.program)
+                    .with_context(|| {
+                        format!("Unable to apply image operations of output '{}'", template)
+                    })?
+            };
+
+            let (width, height) = image.dimensions();
+            let mut values = TemplateValues {
+                dir,
+                stem,
+                ext: template_ext(output, input_path)?,
+                width,
+                height,
+                hash: 0,
+            };
+
+            // The output format is determined by the extension of the expanded template, which
+            // does not depend on the hash of the encoded image.
+            let format_path = PathVariant::Path(template.expand(&values));
+            let stats = compute_stats(&image, config);
+            let encoded = encode_with_settings(
+                image,
+                metadata,
+                config,
+                &output.encoding_settings,
+                output.forced_output_format,
+                &format_path,
+            )
+            .with_context(|| format!("Unable to encode output '{}'", template))?;
+
+            values.hash = hash_bytes(&encoded);
+            let output = output_root_folder.join(template.expand(&values));
+
+            let output_path_variant = PathVariant::Path(output.clone());
+            create_writer(&output_path_variant, None)?
+                .write_all(&encoded)
+                .with_context(|| format!("Unable to write image '{}'", output.display()))?;
+
+            write_stats(stats, &output_path_variant)?;
+
+            Ok(output)
+        })
+        .collect()
+}
+
+/// The value of the `{ext}` placeholder of the templated output: the forced output format, or
+/// else the extension of the input image. It is only required if the template contains the
+/// placeholder, so inputs without an extension can be used with other templates.
+fn template_ext<'a>(output: &TemplatedOutput<'a>, input_path: &'a Path) -> anyhow::Result<&'a str> {
+    if !output.template.uses_ext() {
+        return Ok("");
+    }
+
+    match output.forced_output_format {
+        Some(format) => Ok(format),
+        None => input_path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .ok_or_else(|| {
+                anyhow!(
+                    "Unable to determine the extension for the output template '{}', since the \
+                     input image has no extension",
+                    output.template
+                )
+            }),
+    }
+}
+
+/// Writes each frame of the processed image to its own output image, of which the path is
+/// derived from the frame pattern. A static image is written as a single frame.
+fn run_explode_frames<R>(
+    supply_reader: R,
+    config: &Config,
+    pattern: &FramePattern,
+) -> anyhow::Result<()>
+where
+    R: Fn() -> anyhow::Result<Box<dyn Read>>,
+{
+    let (image, metadata) = decode_and_process(supply_reader, config)?;
+
+    let frames = match image {
+        SicImage::Animated(animated) => animated
+            .into_frames()
+            .into_iter()
+            .map(|frame| SicImage::Static(image::DynamicImage::ImageRgba8(frame.into_buffer())))
+            .collect(),
+        SicImage::Static(image) => vec![SicImage::Static(image)],
+    };
+
+    let frame_count = frames.len();
+
+    for (index, frame) in frames.into_iter().enumerate() {
+        let output = PathVariant::Path(pattern.path_for(index, frame_count));
+
+        let stats = compute_stats(&frame, config);
+        let encoded = encode(frame, metadata.clone(), config, &output)
+            .with_context(|| format!("Unable to encode frame {}", index))?;
+
+        create_writer(&output, None)?
+            .write_all(&encoded)
+            .with_context(|| format!("Unable to write frame {}", index))?;
+
+        write_stats(stats, &output)?;
+    }
+
+    Ok(())
+}
+
+fn decode_and_process<R>(
+    supply_reader: R,
+    config: &Config,
+) -> anyhow::Result<(SicImage, ImageMetadata)>
+where
+    R: Fn() -> anyhow::Result<Box<dyn Read>>,
+{
+    let mut reader = supply_reader()?;
+
+    // Decode
+    let decoder = SicImageDecoder::new(config.selected_frame).with_frame_range(config.frame_range);
+    let (img, metadata) = decoder.decode_with_metadata(&mut reader)?;
+
+    process(img, metadata, config)
+}
+
+fn process(
+    image: SicImage,
+    metadata: ImageMetadata,
+    config: &Config,
+) -> anyhow::Result<(SicImage, ImageMetadata)> {
+    // Apply image operations
+    let image_engine = ImageEngine::new(image).with_metadata(metadata);
+    image_engine
+        .ignite_with_metadata(&create_program(config))
+        .with_context(|| "Unable to apply image operations.")
+}
+
+/// Assembles an animated image from the input images, applies the image operations to it, and
+/// writes it to the output.
+fn run_assemble(inputs: &CommonDir, config: &Config, output: &PathVariant) -> anyhow::Result<()> {
+    let image = assemble_frames(inputs, config)?;
+
+    // The metadata of the individual input images does not apply to the assembled image.
+    let (image, metadata) = process(image, ImageMetadata::default(), config)?;
+    let stats = compute_stats(&image, config);
+    let encoded = encode(image, metadata, config, output)?;
+
+    create_writer(output, None)?
+        .write_all(&encoded)
+        .with_context(|| "Unable to write image")?;
+
+    write_stats(stats, output)
+}
+
+/// Writes the properties of the input image, in the given format.
+fn run_info<R, W>(supply_reader: R, format: InfoFormat, writer: &mut W) -> anyhow::Result<()>
+where
+    R: Fn() -> anyhow::Result<Box<dyn Read>>,
+    W: Write,
+{
+    let info = SicImageDecoder::default()
+        .inspect(&mut supply_reader()?)
+        .with_context(|| "Unable to read the properties of the image")?;
+    let report = InfoReport::new(&info);
+
+    match format {
+        InfoFormat::Text => writeln!(writer, "{}", report)?,
+        InfoFormat::Json => writeln!(writer, "{}", report.to_json()?)?,
+    }
+
+    Ok(())
+}
+
+/// Compares the processed input image with the reference image, and writes the metrics of the
+/// comparison to stdout. If a heat map path is given, the heat map of the differences is written
+/// to it. Fails if any of the thresholds is not satisfied.
+fn run_compare<R>(
+    supply_reader: R,
+    reference: &ImageFromPath,
+    heat_map: Option<&PathVariant>,
+    thresholds: &[CompareThreshold],
+    format: InfoFormat,
+    config: &Config,
+) -> anyhow::Result<()>
+where
+    R: Fn() -> anyhow::Result<Box<dyn Read>>,
+{
+    let (image, _) = decode_and_process(supply_reader, config)?;
+
+    // The orientation of the input image is applied, so the orientation of the reference image
+    // is applied too.
+    let reference = PathVariant::Path(reference.path().to_path_buf());
+    let reference = decode_and_orient(&reference, config)
+        .with_context(|| "Unable to open the reference image")?;
+
+    let comparison = compare(&image, &reference).with_context(|| "Unable to compare the images")?;
+    let report = CompareReport::new(&comparison);
+
+    match format {
+        InfoFormat::Text => println!("{}", report),
+        InfoFormat::Json => println!("{}", report.to_json()?),
+    }
+
+    let violations = thresholds
+        .iter()
+        .filter_map(|threshold| threshold.check(&comparison))
+        .collect::<Vec<_>>();
+
+    if let Some(heat_map) = heat_map {
+        let encoded = encode(
+            comparison.heat_map,
+            ImageMetadata::default(),
+            config,
+            heat_map,
+        )?;
+
+        create_writer(heat_map, None)?
+            .write_all(&encoded)
+            .with_context(|| "Unable to write heat map")?;
+    }
+
+    if !violations.is_empty() {
+        bail!(
+            "The images differ more than allowed: {}",
+            violations.join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+/// Decodes each input image into a frame of an animated image. The frames are ordered by the
+/// path of the input images, relative to their common directory. Unless disabled, the orientation
+/// of each input image is applied first, since it may differ between input images.
+///
+/// The frames of animated input images are all added, and keep their own delay.
+fn assemble_frames(inputs: &CommonDir, config: &Config) -> anyhow::Result<SicImage> {
+    let mut paths = inputs.path_combinations();
+    paths.sort_by_key(|(_, branch)| *branch);
+
+    let delay = image::Delay::from(config.frame_delay);
+    let mut frames = Vec::with_capacity(paths.len());
+    let mut dimensions = None;
+
+    for (path, _) in paths {
+        let input = PathVariant::Path(path.to_path_buf());
+        let image = decode_and_orient(&input, config)?;
+
+        let input_frames = match image {
+            SicImage::Animated(animated) => animated.into_frames(),
+            SicImage::Static(image) => {
+                vec![image::Frame::from_parts(image.into_rgba8(), 0, 0, delay)]
+            }
+        };
+
+        for frame in input_frames {
+            let frame_dimensions = frame.buffer().dimensions();
+            let expected = *dimensions.get_or_insert(frame_dimensions);
+
+            if frame_dimensions != expected {
+                bail!(
+                    "Unable to assemble an animated image: all input images should have the same \
+                     dimensions, but {} is {}x{} pixels, while the previous frames are {}x{} pixels",
+                    input.describe_input(),
+                    frame_dimensions.0,
+                    frame_dimensions.1,
+                    expected.0,
+                    expected.1
+                );
+            }
+
+            frames.push(frame);
+        }
+    }
+
+    Ok(SicImage::Animated(AnimatedImage::from_frames(frames)))
+}
+
+/// Decodes the image, and applies its orientation, unless disabled. Unlike
+/// [`decode_and_process`], the image operations are not applied.
+fn decode_and_orient(input: &PathVariant, config: &Config) -> anyhow::Result<SicImage> {
+    let (image, metadata) = SicImageDecoder::default()
+        .decode_with_metadata(&mut create_reader(input)?)
+        .with_context(|| format!("With input: {}", input.describe_input()))?;
+
+    if config.disable_automatic_orientation {
+        return Ok(image);
+    }
+
+    let image = ImageEngine::new(image)
+        .with_metadata(metadata)
+        .ignite(&[Instr::Operation(ImgOp::AutoOrient)])?;
+
+    Ok(image)
+}
+
+/// Computes the statistics of the processed image, if requested. The statistics are computed
+/// before the image is encoded, and written once the output path of the image is known, with
+/// [`write_stats`].
+fn compute_stats(image: &SicImage, config: &Config) -> Option<(StatsFormat, StatsReport)> {
+    config.stats.map(|format| (format, StatsReport::new(image)))
+}
+
+fn write_stats(
+    stats: Option<(StatsFormat, StatsReport)>,
+    output: &PathVariant,
+) -> anyhow::Result<()> {
+    match (stats, output) {
+        (Some((format, report)), PathVariant::Path(path)) => report.write(format, Some(path)),
+        (Some((format, report)), PathVariant::StdStream) => report.write(format, None),
+        (None, _) => Ok(()),
+    }
+}
+
+/// Encode the image into memory, and embed the retained metadata.
+fn encode(
+    buffer: SicImage,
+    metadata: ImageMetadata,
+    config: &Config,
+    output_path_variant: &PathVariant,
+) -> anyhow::Result<Vec<u8>> {
+    encode_with_settings(
+        buffer,
+        metadata,
+        config,
+        &config.encoding_settings,
+        config.forced_output_format,
+        output_path_variant,
+    )
+}
+
+/// Like [`encode`], but with the given encoding settings and output format, instead of those of
+/// the config, e.g. for an output variant.
+fn encode_with_settings(
+    buffer: SicImage,
+    metadata: ImageMetadata,
+    config: &Config,
+    encoding_settings: &FormatEncodingSettings,
+    forced_output_format: Option<&str>,
+    output_path_variant: &PathVariant,
+) -> anyhow::Result<Vec<u8>> {
+    // Create the encoder, which encodes into memory, so the metadata can be embedded afterwards
+    let mut encoded = Vec::new();
+    let encode_settings = create_encode_settings(encoding_settings)?;
+    let dynamic_encoder = create_dynamic_encoder(
+        Cursor::new(&mut encoded),
+        forced_output_format,
+        &encode_settings,
+        output_path_variant,
+    )?;
+    let image_format = dynamic_encoder.image_format();
 
     // Add preprocessors
     //
     // NB: order in which preprocessors are added matters!
     let mut preprocessors = Preprocessors::default();
 
-    preprocessors.pick_frame_preprocessor(dynamic_encoder.image_format());
+    preprocessors.pick_frame_preprocessor(image_format);
 
     if !config.disable_automatic_color_type_adjustment {
         preprocessors.color_type_preprocessor(dynamic_encoder.image_output_format());
@@ -119,7 +743,40 @@ where
 
     encoder
         .encode(buffer, dynamic_encoder)
-        .with_context(|| "Unable to write image")
+        .with_context(|| "Unable to write image")?;
+
+    // Embed the retained metadata
+    let metadata = config
+        .metadata_retention
+        .unwrap_or_default()
+        .retain(metadata)
+        .with_context(|| "Unable to retain metadata")?;
+    let embedded = embed_metadata(encoded, image_format, &metadata)
+        .with_context(|| "Unable to embed metadata")?;
+
+    // Only warn if the user explicitly asked to retain metadata
+    if embedded.metadata_discarded && config.metadata_retention.is_some() {
+        eprintln!(
+            "warn: Unable to embed metadata in an image with format '{:?}': metadata is discarded",
+            image_format
+        );
+    }
+
+    Ok(embedded.encoded)
+}
+
+/// Create the image operations program which will be applied to the decoded image.
+/// Unless disabled, the orientation of the image is applied before any other image operation.
+fn create_program<'c>(config: &'c Config) -> Cow<'c, [Instr]> {
+    if config.disable_automatic_orientation {
+        Cow::Borrowed(&config.image_operations_program)
+    } else {
+        let mut program = Vec::with_capacity(config.image_operations_program.len() + 1);
+        program.push(Instr::Operation(ImgOp::AutoOrient));
+        program.extend_from_slice(&config.image_operations_program);
+
+        Cow::Owned(program)
+    }
 }
 
 /// Create a reader which will be used to load the image.
@@ -136,25 +793,31 @@ fn create_reader(path_variant: &PathVariant) -> anyhow::Result<Box<dyn Read>> {
     }
 }
 
-fn create_encode_settings(config: &Config) -> anyhow::Result<EncodeSettings> {
+fn create_encode_settings(settings: &FormatEncodingSettings) -> anyhow::Result<EncodeSettings> {
     Ok(EncodeSettings {
-        pnm_sample_encoding: if config.encoding_settings.pnm_use_ascii_format {
+        pnm_sample_encoding: if settings.pnm_use_ascii_format {
             image::codecs::pnm::SampleEncoding::Ascii
         } else {
             image::codecs::pnm::SampleEncoding::Binary
         },
-        jpeg_quality: { JpegQuality::try_from(config.encoding_settings.jpeg_quality)? },
-        repeat_animation: config.encoding_settings.gif_repeat,
+        jpeg_quality: { JpegQuality::try_from(settings.jpeg_quality)? },
+        repeat_animation: settings.gif_repeat,
+        avif_quality: settings.avif_quality,
+        avif_speed: settings.avif_speed,
+        png_compression: settings.png_compression,
+        png_filter: settings.png_filter,
+        tiff_compression: settings.tiff_compression,
+        webp_quality: settings.webp_quality,
     })
 }
 
 fn create_dynamic_encoder<W: Write + Seek>(
     writer: W,
-    config: &Config,
+    forced_output_format: Option<&str>,
     encode_settings: &EncodeSettings,
     path_variant: &PathVariant,
 ) -> anyhow::Result<DynamicEncoder<W>> {
-    Ok(match &config.forced_output_format {
+    Ok(match forced_output_format {
         Some(format) => DynamicEncoder::from_identifier(writer, format, encode_settings)?,
         None => match path_variant {
             PathVariant::Path(out) => DynamicEncoder::from_extension(writer, out, encode_settings)?,
@@ -229,12 +892,7 @@ fn create_writer(path_variant: &PathVariant, adjust_ext: Option<&str>) -> anyhow
             })?;
             std::fs::create_dir_all(base)?;
 
-            let out = match (adjust_ext, out.file_stem()) {
-                (Some(new_ext), Some(stem)) => base.join(stem).with_extension(new_ext),
-                _ => out.to_path_buf(),
-            };
-
-            let file = File::create(out)?;
+            let file = File::create(adjusted_output_path(out, adjust_ext))?;
 
             Ok(Output::new_file(file))
         }
@@ -242,6 +900,14 @@ fn create_writer(path_variant: &PathVariant, adjust_ext: Option<&str>) -> anyhow
     }
 }
 
+/// The output path, of which the extension is replaced by `adjust_ext`, if given.
+fn adjusted_output_path(out: &Path, adjust_ext: Option<&str>) -> PathBuf {
+    match (adjust_ext, out.parent(), out.file_stem()) {
+        (Some(new_ext), Some(base), Some(stem)) => base.join(stem).with_extension(new_ext),
+        _ => out.to_path_buf(),
+    }
+}
+
 pub fn run_display_licenses(config: &Config, texts: &LicenseTexts) -> anyhow::Result<()> {
     config
         .show_license_text_of
@@ -0,0 +1,228 @@
+//! Statistics of a processed image, as requested with `--stats`. They are either written to
+//! stderr in a human-readable form, or as JSON to a file alongside the output image.
+
+use anyhow::Context;
+use serde::Serialize;
+use sic_core::SicImage;
+use sic_image_engine::stats::{image_stats, ChannelStats, ImageStats};
+use std::ffi::OsString;
+use std::fmt;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum StatsFormat {
+    /// Written to stderr, without the histograms.
+    Text,
+    /// Written as JSON to a file alongside the output image.
+    Json,
+}
+
+impl StatsFormat {
+    pub fn try_from_str(format: &str) -> anyhow::Result<Self> {
+        match format {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => anyhow::bail!(
+                "Unknown statistics format '{}'; supported formats are 'text' and 'json'",
+                format
+            ),
+        }
+    }
+}
+
+/// The statistics of an image, with one item for each of its frames; a static image has a single
+/// frame.
+#[derive(Debug, Serialize)]
+pub struct StatsReport {
+    frames: Vec<FrameStatsReport>,
+}
+
+#[derive(Debug, Serialize)]
+struct FrameStatsReport {
+    width: u32,
+    height: u32,
+    unique_colors: usize,
+    channels: Vec<ChannelStatsReport>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChannelStatsReport {
+    channel: &'static str,
+    min: u8,
+    max: u8,
+    mean: f64,
+    stddev: f64,
+    histogram: Vec<u64>,
+}
+
+impl StatsReport {
+    pub fn new(image: &SicImage) -> Self {
+        Self {
+            frames: image_stats(image)
+                .into_iter()
+                .map(FrameStatsReport::from)
+                .collect(),
+        }
+    }
+
+    /// Writes the report to stderr, or to the stats file of the output image for the JSON format.
+    /// The JSON report of an image which is written to stdout is written to stderr as well.
+    pub fn write(&self, format: StatsFormat, output: Option<&Path>) -> anyhow::Result<()> {
+        let name = output.map_or_else(|| "<stdout>".into(), |path| path.display().to_string());
+
+        match (format, output) {
+            (StatsFormat::Text, _) => eprintln!("Statistics of {}:\n{}", name, self),
+            (StatsFormat::Json, None) => eprintln!("{}", serde_json::to_string_pretty(self)?),
+            (StatsFormat::Json, Some(output)) => {
+                let path = stats_path(output);
+                let file = File::create(&path).with_context(|| {
+                    format!("Unable to create statistics file '{}'", path.display())
+                })?;
+
+                serde_json::to_writer_pretty(BufWriter::new(file), self).with_context(|| {
+                    format!("Unable to write statistics file '{}'", path.display())
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The path of the JSON statistics of an output image, e.g. `out.png.stats.json` for `out.png`.
+pub fn stats_path(output: &Path) -> PathBuf {
+    let mut path = OsString::from(output.as_os_str());
+    path.push(".stats.json");
+    PathBuf::from(path)
+}
+
+impl From<ImageStats> for FrameStatsReport {
+    fn from(stats: ImageStats) -> Self {
+        Self {
+            width: stats.width,
+            height: stats.height,
+            unique_colors: stats.unique_colors,
+            channels: stats
+                .channels
+                .into_iter()
+                .map(ChannelStatsReport::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<ChannelStats> for ChannelStatsReport {
+    fn from(stats: ChannelStats) -> Self {
+        Self {
+            channel: stats.channel.as_str(),
+            min: stats.min,
+            max: stats.max,
+            mean: stats.mean,
+            stddev: stats.stddev,
+            histogram: stats.histogram,
+        }
+    }
+}
+
+impl fmt::Display for StatsReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let animated = self.frames.len() > 1;
+        let indent = if animated { "    " } else { "  " };
+
+        for (index, frame) in self.frames.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+
+            if animated {
+                writeln!(f, "  frame {}:", index)?;
+            }
+
+            write!(
+                f,
+                "{}{}x{} pixels, {} unique colors",
+                indent, frame.width, frame.height, frame.unique_colors
+            )?;
+
+            for channel in &frame.channels {
+                write!(
+                    f,
+                    "\n{}{:<6} min {}, max {}, mean {:.2}, stddev {:.2}",
+                    indent,
+                    format!("{}:", channel.channel),
+                    channel.min,
+                    channel.max,
+                    channel.mean,
+                    channel.stddev
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sic_core::image::{DynamicImage, Frame, GrayImage, Rgba, RgbaImage};
+    use sic_core::AnimatedImage;
+
+    #[test]
+    fn display_static_image() {
+        let image = GrayImage::from_raw(2, 1, vec![0, 255]).unwrap();
+        let report = StatsReport::new(&SicImage::Static(DynamicImage::ImageLuma8(image)));
+
+        assert_eq!(
+            report.to_string(),
+            "  2x1 pixels, 2 unique colors\n  \
+               luma:  min 0, max 255, mean 127.50, stddev 127.50"
+        );
+    }
+
+    #[test]
+    fn display_animated_image() {
+        let frames = [Rgba([255, 0, 0, 255]), Rgba([0, 0, 255, 255])]
+            .map(|color| Frame::new(RgbaImage::from_pixel(1, 1, color)));
+        let report = StatsReport::new(&SicImage::Animated(AnimatedImage::from_frames(frames)));
+
+        assert_eq!(
+            report.to_string(),
+            "  frame 0:\n    \
+                 1x1 pixels, 1 unique colors\n    \
+                 red:   min 255, max 255, mean 255.00, stddev 0.00\n    \
+                 green: min 0, max 0, mean 0.00, stddev 0.00\n    \
+                 blue:  min 0, max 0, mean 0.00, stddev 0.00\n    \
+                 alpha: min 255, max 255, mean 255.00, stddev 0.00\n  \
+               frame 1:\n    \
+                 1x1 pixels, 1 unique colors\n    \
+                 red:   min 0, max 0, mean 0.00, stddev 0.00\n    \
+                 green: min 0, max 0, mean 0.00, stddev 0.00\n    \
+                 blue:  min 255, max 255, mean 255.00, stddev 0.00\n    \
+                 alpha: min 255, max 255, mean 255.00, stddev 0.00"
+        );
+    }
+
+    #[yare::parameterized(
+        file = { "out.png", "out.png.stats.json" },
+        nested = { "out/images/a.jpg", "out/images/a.jpg.stats.json" },
+    )]
+    fn stats_path_of_output(output: &str, expected: &str) {
+        assert_eq!(stats_path(Path::new(output)), PathBuf::from(expected));
+    }
+
+    #[yare::parameterized(
+        text = { "text", StatsFormat::Text },
+        json = { "json", StatsFormat::Json },
+    )]
+    fn format(input: &str, expected: StatsFormat) {
+        assert_eq!(StatsFormat::try_from_str(input).unwrap(), expected);
+    }
+
+    #[test]
+    fn unknown_format() {
+        assert!(StatsFormat::try_from_str("csv").is_err());
+    }
+}
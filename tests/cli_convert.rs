@@ -6,6 +6,7 @@ extern crate parameterized;
 
 use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use sic_core::image;
 
@@ -314,3 +315,1539 @@ fn convert_jpeg_quality_different() {
     clean_up_output_path(path_buf_str(&out1));
     clean_up_output_path(path_buf_str(&out2));
 }
+
+#[cfg(test)]
+mod encoding_settings {
+    use super::*;
+    use sic_core::image::GenericImageView;
+
+    ide!();
+
+    fn convert(output: &str, flags: &[&str]) -> anyhow::Result<Vec<u8>> {
+        let input_path = setup_input_path("rainbow_8x6.bmp");
+        let output_path = setup_output_path(output);
+
+        let mut args = vec![
+            "sic",
+            "--input",
+            path_buf_str(&input_path),
+            "--output",
+            path_buf_str(&output_path),
+        ];
+        args.extend_from_slice(flags);
+
+        let matches = get_app("", "", "").get_matches_from(args);
+        run_with_devices(
+            InputOutputMode::try_from_matches(&matches).unwrap(),
+            &build_app_config(&matches)?,
+        )?;
+
+        let contents = read_file_to_bytes(&output_path);
+        clean_up_output_path(path_buf_str(&output_path));
+
+        Ok(contents)
+    }
+
+    #[yare::parameterized(
+        png_compression = { "png", &["--png-compression", "best"] },
+        png_filter = { "png", &["--png-filter", "none"] },
+        tiff_lzw = { "tiff", &["--tiff-compression", "lzw"] },
+        tiff_deflate = { "tiff", &["--tiff-compression", "deflate"] },
+        tiff_packbits = { "tiff", &["--tiff-compression", "packbits"] },
+        webp_quality = { "webp", &["--webp-quality", "75"] },
+    )]
+    fn setting_changes_output(ext: &str, flags: &[&str]) {
+        let default_output = format!("cli_convert_encoding_settings_default_{}.{}", flags[0], ext);
+        let custom_output = format!("cli_convert_encoding_settings_custom_{}.{}", flags[0], ext);
+
+        let default = convert(&default_output, &[]).unwrap();
+        let custom = convert(&custom_output, flags).unwrap();
+
+        assert_ne!(default, custom);
+        assert_eq!(
+            image::load_from_memory(&custom).unwrap().dimensions(),
+            (8, 6)
+        );
+    }
+
+    #[yare::parameterized(
+        avif_quality_zero = { &["--avif-quality", "0"] },
+        avif_quality_too_large = { &["--avif-quality", "101"] },
+        avif_speed_zero = { &["--avif-speed", "0"] },
+        avif_speed_too_large = { &["--avif-speed", "11"] },
+        webp_quality_too_large = { &["--webp-quality", "101"] },
+        webp_quality_not_a_number = { &["--webp-quality", "lossy"] },
+    )]
+    fn invalid_setting(flags: &[&str]) {
+        let output = format!(
+            "cli_convert_encoding_settings_invalid{}.png",
+            flags.join("_")
+        );
+
+        assert!(convert(&output, flags).is_err());
+    }
+}
+
+#[cfg(test)]
+mod animated {
+    use super::*;
+    use sic_core::SicImage;
+    use sic_io::decode::{file_reader, SicImageDecoder};
+
+    ide!();
+
+    fn decode_frames(path: &Path) -> Vec<image::Frame> {
+        let image = SicImageDecoder::default()
+            .decode(&mut file_reader(path).unwrap())
+            .unwrap();
+
+        match image {
+            SicImage::Animated(animated) => animated.collect_frames(),
+            SicImage::Static(_) => panic!("Expected an animated image"),
+        }
+    }
+
+    #[yare::parameterized(
+        apng_to_apng = { "apng_sample.png", "png", image::ImageFormat::Png },
+        apng_to_webp = { "apng_sample.png", "webp", image::ImageFormat::WebP },
+        gif_to_apng = { "loop.gif", "png", image::ImageFormat::Png },
+        gif_to_webp = { "loop.gif", "webp", image::ImageFormat::WebP },
+    )]
+    fn frames_are_retained(input: &str, ext: &str, expected_format: image::ImageFormat) {
+        let input_path = setup_input_path(input);
+        let output = format!("cli_convert_animated_{}.{}", input, ext);
+        let output_path = setup_output_path(&output);
+
+        let args = vec![
+            "sic",
+            "--input",
+            path_buf_str(&input_path),
+            "--output",
+            path_buf_str(&output_path),
+        ];
+
+        let matches = get_app("", "", "").get_matches_from(args);
+        run_with_devices(
+            InputOutputMode::try_from_matches(&matches).unwrap(),
+            &build_app_config(&matches).unwrap(),
+        )
+        .unwrap();
+
+        assert!(is_image_format(&output, expected_format));
+
+        let expected = decode_frames(&input_path);
+        let actual = decode_frames(&output_path);
+
+        assert_eq!(actual.len(), expected.len());
+
+        for (actual, expected) in actual.iter().zip(expected.iter()) {
+            assert_eq!(actual.buffer(), expected.buffer());
+            assert_eq!(
+                Duration::from(actual.delay()).as_millis(),
+                Duration::from(expected.delay()).as_millis()
+            );
+        }
+
+        clean_up_output_path(path_buf_str(&output_path));
+    }
+
+    #[yare::parameterized(
+        frames_range = { "frames_range", &["--frames", "2..5"], &[2, 3, 4] },
+        frames_inclusive_range = { "frames_inclusive_range", &["--frames", "6..=7"], &[6, 7] },
+        frames_step = { "frames_step", &["--frames", "::3"], &[0, 3, 6] },
+        frames_then_select_frame = { "frames_select_frame", &["--frames", "4..", "--select-frame", "1"], &[5] },
+        drop_frames_script = { "drop_frames_script", &["--apply-operations", "drop-frames 1..7"], &[0, 7] },
+    )]
+    fn frame_selection(name: &str, flags: &[&str], expected_frames: &[usize]) {
+        let input_path = setup_input_path("loop.gif");
+        let output = format!("cli_convert_animated_{}.png", name);
+        let output_path = setup_output_path(&output);
+
+        let mut args = vec![
+            "sic",
+            "--input",
+            path_buf_str(&input_path),
+            "--output",
+            path_buf_str(&output_path),
+        ];
+        args.extend(flags);
+
+        let matches = get_app("", "", "").get_matches_from(args);
+        run_with_devices(
+            InputOutputMode::try_from_matches(&matches).unwrap(),
+            &build_app_config(&matches).unwrap(),
+        )
+        .unwrap();
+
+        let input_frames = decode_frames(&input_path);
+        let output_image = SicImageDecoder::default()
+            .decode(&mut file_reader(&output_path).unwrap())
+            .unwrap();
+
+        let output_buffers = match output_image {
+            SicImage::Animated(animated) => animated
+                .collect_frames()
+                .into_iter()
+                .map(|frame| frame.into_buffer())
+                .collect::<Vec<_>>(),
+            SicImage::Static(image) => vec![image.to_rgba8()],
+        };
+
+        let expected_buffers = expected_frames
+            .iter()
+            .map(|&index| input_frames[index].buffer().clone())
+            .collect::<Vec<_>>();
+
+        assert_eq!(output_buffers, expected_buffers);
+
+        clean_up_output_path(path_buf_str(&output_path));
+    }
+
+    #[test]
+    fn on_frames() {
+        let input_path = setup_input_path("loop.gif");
+        let output = "cli_convert_animated_on_frames.png";
+        let output_path = setup_output_path(output);
+
+        let args = vec![
+            "sic",
+            "--input",
+            path_buf_str(&input_path),
+            "--output",
+            path_buf_str(&output_path),
+            "--apply-operations",
+            "on-frames 1..3 { invert }",
+        ];
+
+        let matches = get_app("", "", "").get_matches_from(args);
+        run_with_devices(
+            InputOutputMode::try_from_matches(&matches).unwrap(),
+            &build_app_config(&matches).unwrap(),
+        )
+        .unwrap();
+
+        let expected = decode_frames(&input_path);
+        let actual = decode_frames(&output_path);
+
+        assert_eq!(actual.len(), expected.len());
+
+        for (index, (actual, expected)) in actual.iter().zip(expected.iter()).enumerate() {
+            if (1..3).contains(&index) {
+                assert_ne!(actual.buffer(), expected.buffer());
+            } else {
+                assert_eq!(actual.buffer(), expected.buffer());
+            }
+        }
+
+        clean_up_output_path(path_buf_str(&output_path));
+    }
+}
+
+#[cfg(test)]
+mod explode_frames {
+    use super::*;
+    use sic_core::SicImage;
+    use sic_io::decode::{file_reader, SicImageDecoder};
+
+    fn decode(path: &Path) -> SicImage {
+        SicImageDecoder::default()
+            .decode(&mut file_reader(path).unwrap())
+            .unwrap()
+    }
+
+    fn explode(input: &str, pattern: &Path, flags: &[&str]) {
+        let input_path = setup_input_path(input);
+
+        let mut args = vec![
+            "sic",
+            "--input",
+            path_buf_str(&input_path),
+            "--explode-frames",
+            path_buf_str(pattern),
+        ];
+        args.extend(flags);
+
+        let matches = get_app("", "", "").get_matches_from(args);
+        run_with_devices(
+            InputOutputMode::try_from_matches(&matches).unwrap(),
+            &build_app_config(&matches).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn animated_image() {
+        let output_dir = setup_output_path("cli_convert_explode_frames_animated");
+        explode("loop.gif", &output_dir.join("frame_{frame:4}.png"), &[]);
+
+        let frames = match decode(&setup_input_path("loop.gif")) {
+            SicImage::Animated(animated) => animated.into_frames(),
+            SicImage::Static(_) => panic!("Expected an animated image"),
+        };
+
+        for (index, frame) in frames.iter().enumerate() {
+            let path = output_dir.join(format!("frame_{:04}.png", index));
+
+            match decode(&path) {
+                SicImage::Static(image) => assert_eq!(&image.to_rgba8(), frame.buffer()),
+                SicImage::Animated(_) => panic!("Expected a static image"),
+            }
+        }
+
+        let written = std::fs::read_dir(&output_dir).unwrap().count();
+        assert_eq!(written, frames.len());
+
+        std::fs::remove_dir_all(output_dir).unwrap();
+    }
+
+    #[test]
+    fn with_operations_and_frame_range() {
+        let output_dir = setup_output_path("cli_convert_explode_frames_range");
+        explode(
+            "loop.gif",
+            &output_dir.join("{frame}.bmp"),
+            &["--frames", "::4", "--apply-operations", "resize 10 10"],
+        );
+
+        let mut written = std::fs::read_dir(&output_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        written.sort();
+
+        assert_eq!(written, vec!["0.bmp", "1.bmp"]);
+
+        for name in written {
+            let image = decode(&output_dir.join(name));
+            assert_eq!((image.as_ref().width(), image.as_ref().height()), (10, 10));
+        }
+
+        std::fs::remove_dir_all(output_dir).unwrap();
+    }
+
+    #[test]
+    fn static_image() {
+        let output_dir = setup_output_path("cli_convert_explode_frames_static");
+        explode(
+            "rainbow_8x6.bmp",
+            &output_dir.join("frame_{frame}.png"),
+            &[],
+        );
+
+        assert!(output_dir.join("frame_0.png").exists());
+        assert_eq!(std::fs::read_dir(&output_dir).unwrap().count(), 1);
+
+        std::fs::remove_dir_all(output_dir).unwrap();
+    }
+
+    #[test]
+    fn pattern_without_placeholder() {
+        let input_path = setup_input_path("loop.gif");
+        let args = vec![
+            "sic",
+            "--input",
+            path_buf_str(&input_path),
+            "--explode-frames",
+            "frame.png",
+        ];
+
+        let matches = get_app("", "", "").get_matches_from(args);
+        assert!(InputOutputMode::try_from_matches(&matches).is_err());
+    }
+
+    #[test]
+    fn conflicts_with_output() {
+        let args = vec![
+            "sic",
+            "--input",
+            "loop.gif",
+            "--output",
+            "out.png",
+            "--explode-frames",
+            "frame_{frame}.png",
+        ];
+
+        assert!(get_app("", "", "").get_matches_from_safe(args).is_err());
+    }
+}
+
+#[cfg(test)]
+mod assemble {
+    use super::*;
+    use sic_core::image::{Rgba, RgbaImage};
+    use sic_core::SicImage;
+    use sic_io::decode::{file_reader, SicImageDecoder};
+
+    const COLORS: [[u8; 4]; 3] = [[255, 0, 0, 255], [0, 255, 0, 255], [0, 0, 255, 255]];
+
+    // Writes the still images in reverse order, so the assembled order does not depend on the
+    // order in which the files were created.
+    fn setup_frames(dir: &Path, dimensions: &[(u32, u32)]) {
+        std::fs::create_dir_all(dir).unwrap();
+
+        for (index, (width, height)) in dimensions.iter().enumerate().rev() {
+            RgbaImage::from_pixel(*width, *height, Rgba(COLORS[index % COLORS.len()]))
+                .save(dir.join(format!("frame_{}.png", index)))
+                .unwrap();
+        }
+    }
+
+    fn assemble(dir: &Path, output: &Path, flags: &[&str]) -> anyhow::Result<()> {
+        let pattern = format!("{}/frame_*.png", path_buf_str(dir));
+
+        let mut args = vec![
+            "sic",
+            "--assemble",
+            &pattern,
+            "--output",
+            path_buf_str(output),
+        ];
+        args.extend(flags);
+
+        let matches = get_app("", "", "").get_matches_from(args);
+        run_with_devices(
+            InputOutputMode::try_from_matches(&matches)?,
+            &build_app_config(&matches)?,
+        )
+    }
+
+    fn decode_frames(path: &Path) -> Vec<image::Frame> {
+        let image = SicImageDecoder::default()
+            .decode(&mut file_reader(path).unwrap())
+            .unwrap();
+
+        match image {
+            SicImage::Animated(animated) => animated.into_frames(),
+            SicImage::Static(_) => panic!("Expected an animated image"),
+        }
+    }
+
+    #[yare::parameterized(
+        gif = { "gif", &[], 100 },
+        gif_frame_delay = { "gif", &["--frame-delay", "40ms"], 40 },
+        apng_frame_delay = { "png", &["--frame-delay", "1.5s"], 1500 },
+    )]
+    fn frames_in_path_order(ext: &str, flags: &[&str], expected_delay_ms: u128) {
+        let dir = setup_output_path(&format!(
+            "cli_convert_assemble_{}_{}",
+            ext, expected_delay_ms
+        ));
+        let output = dir.join(format!("out.{}", ext));
+        setup_frames(&dir, &[(4, 4), (4, 4), (4, 4)]);
+
+        assemble(&dir, &output, flags).unwrap();
+
+        let frames = decode_frames(&output);
+        assert_eq!(frames.len(), 3);
+
+        for (frame, expected_color) in frames.iter().zip(COLORS.iter()) {
+            assert_eq!(&frame.buffer().get_pixel(0, 0).0, expected_color);
+            assert_eq!(Duration::from(frame.delay()).as_millis(), expected_delay_ms);
+        }
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn operations_apply_to_assembled_image() {
+        let dir = setup_output_path("cli_convert_assemble_operations");
+        let output = dir.join("out.gif");
+        setup_frames(&dir, &[(4, 4), (4, 4)]);
+
+        assemble(
+            &dir,
+            &output,
+            &["--apply-operations", "resize 2 3; reverse-frames"],
+        )
+        .unwrap();
+
+        let frames = decode_frames(&output);
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].buffer().dimensions(), (2, 3));
+        assert_eq!(&frames[0].buffer().get_pixel(0, 0).0, &COLORS[1]);
+        assert_eq!(&frames[1].buffer().get_pixel(0, 0).0, &COLORS[0]);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn different_dimensions() {
+        let dir = setup_output_path("cli_convert_assemble_different_dimensions");
+        let output = dir.join("out.gif");
+        setup_frames(&dir, &[(4, 4), (4, 5)]);
+
+        assert!(assemble(&dir, &output, &[]).is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn frame_delay_requires_assemble() {
+        let args = vec![
+            "sic",
+            "--input",
+            "in.png",
+            "--output",
+            "out.gif",
+            "--frame-delay",
+            "40ms",
+        ];
+
+        assert!(get_app("", "", "").get_matches_from_safe(args).is_err());
+    }
+}
+
+#[cfg(test)]
+mod jobs {
+    use super::*;
+    use sic_core::image::{GenericImageView, Rgba, RgbaImage};
+
+    // Writes `count` still images to the input folder; the images at the indices in `corrupt`
+    // are not valid images.
+    pub(super) fn setup_inputs(dir: &Path, count: usize, corrupt: &[usize]) {
+        let input = dir.join("in");
+        std::fs::create_dir_all(&input).unwrap();
+
+        for index in 0..count {
+            let path = input.join(format!("img_{}.png", index));
+
+            if corrupt.contains(&index) {
+                std::fs::write(path, b"not an image").unwrap();
+            } else {
+                RgbaImage::from_pixel(4, 4, Rgba([255, 0, 0, 255]))
+                    .save(path)
+                    .unwrap();
+            }
+        }
+    }
+
+    pub(super) fn batch(dir: &Path, flags: &[&str]) -> anyhow::Result<()> {
+        let pattern = format!("{}/in/*.png", path_buf_str(dir));
+        let output = dir.join("out");
+
+        let mut args = vec![
+            "sic",
+            "--glob-input",
+            &pattern,
+            "--glob-output",
+            path_buf_str(&output),
+        ];
+        args.extend(flags);
+
+        let matches = get_app("", "", "").get_matches_from(args);
+        run_with_devices(
+            InputOutputMode::try_from_matches(&matches)?,
+            &build_app_config(&matches)?,
+        )
+    }
+
+    #[yare::parameterized(
+        sequential = { "1" },
+        concurrent = { "4" },
+        more_jobs_than_inputs = { "32" },
+    )]
+    fn processes_all_inputs(jobs: &str) {
+        let dir = setup_output_path(&format!("cli_convert_jobs_{}", jobs));
+        setup_inputs(&dir, 12, &[]);
+
+        batch(&dir, &["--jobs", jobs, "--apply-operations", "resize 2 3"]).unwrap();
+
+        for index in 0..12 {
+            let output = dir.join("out").join(format!("img_{}.png", index));
+            let image = image::open(output).unwrap();
+            assert_eq!(image.dimensions(), (2, 3));
+        }
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn reports_first_failure_in_input_order() {
+        let dir = setup_output_path("cli_convert_jobs_first_failure");
+        setup_inputs(&dir, 12, &[3, 9]);
+
+        let err = batch(&dir, &["--jobs", "4"]).unwrap_err();
+        let message = format!("{:?}", err);
+
+        assert!(message.contains("img_3.png"), "{}", message);
+        assert!(!message.contains("img_9.png"), "{}", message);
+
+        // inputs before the first failure are always processed
+        for index in 0..3 {
+            assert!(dir.join("out").join(format!("img_{}.png", index)).exists());
+        }
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[yare::parameterized(
+        sequential = { "1" },
+        concurrent = { "4" },
+    )]
+    fn keep_going_processes_remaining_inputs(jobs: &str) {
+        let dir = setup_output_path(&format!("cli_convert_jobs_keep_going_{}", jobs));
+        setup_inputs(&dir, 12, &[3, 9]);
+
+        let err = batch(&dir, &["--jobs", jobs, "--keep-going"]).unwrap_err();
+        assert_eq!(err.to_string(), "2 of 12 images failed to process");
+
+        for index in (0..12).filter(|index| ![3, 9].contains(index)) {
+            assert!(dir.join("out").join(format!("img_{}.png", index)).exists());
+        }
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    // Inputs are ordered by path, i.e. img_0, img_1, img_10, img_11, img_2, img_3, ..., so five
+    // images precede the first failure.
+    #[yare::parameterized(
+        keep_going = { &["--keep-going"], 10, 0, &["img_3.png", "img_9.png"] },
+        stop_at_first_failure = { &[], 5, 6, &["img_3.png"] },
+    )]
+    fn batch_report(flags: &[&str], succeeded: u64, skipped: u64, failed_inputs: &[&str]) {
+        let dir = setup_output_path(&format!("cli_convert_jobs_batch_report_{}", flags.len()));
+        setup_inputs(&dir, 12, &[3, 9]);
+        let report_path = dir.join("report.json");
+
+        let mut args = vec!["--batch-report", path_buf_str(&report_path)];
+        args.extend(flags);
+        assert!(batch(&dir, &args).is_err());
+
+        let report: serde_json::Value =
+            serde_json::from_reader(std::fs::File::open(&report_path).unwrap()).unwrap();
+
+        assert_eq!(report["total"], 12);
+        assert_eq!(report["succeeded"], succeeded);
+        assert_eq!(report["failed"], failed_inputs.len() as u64);
+        assert_eq!(report["skipped"], skipped);
+
+        let failures = report["failures"].as_array().unwrap();
+        assert_eq!(failures.len(), failed_inputs.len());
+
+        for (failure, expected_input) in failures.iter().zip(failed_inputs) {
+            assert!(failure["input"].as_str().unwrap().ends_with(expected_input));
+            assert!(!failure["errors"].as_array().unwrap().is_empty());
+        }
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[yare::parameterized(
+        zero = { "0" },
+        fraction = { "1.5" },
+        not_a_number = { "many" },
+    )]
+    fn invalid_jobs(jobs: &str) {
+        let args = vec![
+            "sic",
+            "--glob-input",
+            "*.png",
+            "--glob-output",
+            "out",
+            "--jobs",
+            jobs,
+        ];
+
+        let matches = get_app("", "", "").get_matches_from(args);
+        assert!(build_app_config(&matches).is_err());
+    }
+}
+
+#[cfg(test)]
+mod incremental {
+    use super::jobs::{batch, setup_inputs};
+    use super::*;
+    use sic_core::image::{GenericImageView, Rgba, RgbaImage};
+    use std::fs::File;
+    use std::time::SystemTime;
+
+    const MARKER: &[u8] = b"not processed again";
+
+    fn output(dir: &Path, index: usize) -> PathBuf {
+        dir.join("out").join(format!("img_{}.png", index))
+    }
+
+    // Replaces the outputs with a marker, so it can be observed whether they are written again.
+    fn mark_outputs(dir: &Path, count: usize) {
+        for index in 0..count {
+            std::fs::write(output(dir, index), MARKER).unwrap();
+        }
+    }
+
+    fn is_marked(dir: &Path, index: usize) -> bool {
+        std::fs::read(output(dir, index)).unwrap() == MARKER
+    }
+
+    #[test]
+    fn skips_unchanged_inputs() {
+        let dir = setup_output_path("cli_convert_incremental_unchanged");
+        setup_inputs(&dir, 4, &[]);
+
+        batch(&dir, &["--incremental"]).unwrap();
+        assert!(dir.join("out").join(".sic-manifest.json").exists());
+
+        mark_outputs(&dir, 4);
+
+        // a changed input is processed again
+        RgbaImage::from_pixel(4, 4, Rgba([0, 0, 255, 255]))
+            .save(dir.join("in").join("img_1.png"))
+            .unwrap();
+
+        // a missing output is processed again
+        std::fs::remove_file(output(&dir, 2)).unwrap();
+
+        // an input of which only the modification time changed is not processed again
+        File::options()
+            .write(true)
+            .open(dir.join("in").join("img_3.png"))
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(60))
+            .unwrap();
+
+        batch(&dir, &["--incremental", "--jobs", "2"]).unwrap();
+
+        assert!(is_marked(&dir, 0));
+        assert!(!is_marked(&dir, 1));
+        assert!(!is_marked(&dir, 2));
+        assert!(is_marked(&dir, 3));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn changed_operations_process_all_inputs() {
+        let dir = setup_output_path("cli_convert_incremental_changed_operations");
+        setup_inputs(&dir, 3, &[]);
+
+        batch(&dir, &["--incremental", "--apply-operations", "resize 2 3"]).unwrap();
+        batch(&dir, &["--incremental", "--apply-operations", "resize 1 1"]).unwrap();
+
+        for index in 0..3 {
+            let image = image::open(output(&dir, index)).unwrap();
+            assert_eq!(image.dimensions(), (1, 1));
+        }
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn changed_overlay_image_processes_all_inputs() {
+        let dir = setup_output_path("cli_convert_incremental_changed_overlay");
+        setup_inputs(&dir, 3, &[]);
+
+        let overlay = dir.join("overlay.png");
+        RgbaImage::from_pixel(2, 2, Rgba([0, 255, 0, 255]))
+            .save(&overlay)
+            .unwrap();
+        let script = format!("overlay \"{}\" 0 0", path_buf_str(&overlay));
+        let flags = ["--incremental", "--apply-operations", &script];
+
+        batch(&dir, &flags).unwrap();
+        mark_outputs(&dir, 3);
+        batch(&dir, &flags).unwrap();
+
+        for index in 0..3 {
+            assert!(is_marked(&dir, index));
+        }
+
+        // the overlay image is edited, but its path stays the same
+        RgbaImage::from_pixel(2, 2, Rgba([0, 0, 255, 255]))
+            .save(&overlay)
+            .unwrap();
+        batch(&dir, &flags).unwrap();
+
+        for index in 0..3 {
+            assert!(!is_marked(&dir, index));
+        }
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn without_incremental_all_inputs_are_processed() {
+        let dir = setup_output_path("cli_convert_incremental_disabled");
+        setup_inputs(&dir, 3, &[]);
+
+        batch(&dir, &["--incremental"]).unwrap();
+        mark_outputs(&dir, 3);
+        batch(&dir, &[]).unwrap();
+
+        for index in 0..3 {
+            assert!(!is_marked(&dir, index));
+        }
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn failed_inputs_are_retried() {
+        let dir = setup_output_path("cli_convert_incremental_failed");
+        setup_inputs(&dir, 3, &[1]);
+
+        assert!(batch(&dir, &["--incremental", "--keep-going"]).is_err());
+        assert!(batch(&dir, &["--incremental", "--keep-going"]).is_err());
+
+        // once the input is fixed, it is processed
+        RgbaImage::from_pixel(4, 4, Rgba([0, 0, 255, 255]))
+            .save(dir.join("in").join("img_1.png"))
+            .unwrap();
+
+        batch(&dir, &["--incremental"]).unwrap();
+        assert!(output(&dir, 1).exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod output_template {
+    use super::jobs::{batch, setup_inputs};
+    use super::*;
+    use sic::cli::hash::hash_bytes;
+    use sic_core::image::{GenericImageView, Rgba, RgbaImage};
+
+    fn single(input: &Path, template: &str, flags: &[&str]) -> anyhow::Result<()> {
+        let mut args = vec![
+            "sic",
+            "--input",
+            path_buf_str(input),
+            "--output-template",
+            template,
+        ];
+        args.extend(flags);
+
+        let matches = get_app("", "", "").get_matches_from_safe(args)?;
+        run_with_devices(
+            InputOutputMode::try_from_matches(&matches)?,
+            &build_app_config(&matches)?,
+        )
+    }
+
+    #[test]
+    fn batch_mirrors_dir() {
+        let dir = setup_output_path("cli_convert_output_template_dir");
+
+        for sub in ["a", "b/c"] {
+            let input = dir.join("in").join(sub);
+            std::fs::create_dir_all(&input).unwrap();
+            RgbaImage::from_pixel(4, 4, Rgba([255, 0, 0, 255]))
+                .save(input.join("img.png"))
+                .unwrap();
+        }
+
+        batch(&dir, &["--output-template", "{dir}/{stem}@2x.{ext}"]).unwrap();
+
+        assert!(dir.join("out/a/img@2x.png").exists());
+        assert!(dir.join("out/b/c/img@2x.png").exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn batch_dimensions_and_output_format() {
+        let dir = setup_output_path("cli_convert_output_template_dimensions");
+        setup_inputs(&dir, 2, &[]);
+
+        batch(
+            &dir,
+            &[
+                "--output-template",
+                "{stem}-{width}x{height}.{ext}",
+                "--output-format",
+                "jpg",
+                "--apply-operations",
+                "resize 2 3",
+            ],
+        )
+        .unwrap();
+
+        for index in 0..2 {
+            let output = dir.join("out").join(format!("img_{}-2x3.jpg", index));
+            assert_eq!(image::open(output).unwrap().dimensions(), (2, 3));
+        }
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn single_hash() {
+        let dir = setup_output_path("cli_convert_output_template_hash");
+        std::fs::create_dir_all(&dir).unwrap();
+        let template = format!("{}/{{hash8}}.{{ext}}", path_buf_str(&dir));
+
+        single(&setup_input_path("2x3_wrabaa.png"), &template, &[]).unwrap();
+
+        let outputs = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect::<Vec<_>>();
+        assert_eq!(outputs.len(), 1);
+
+        let hash = hash_bytes(&std::fs::read(&outputs[0]).unwrap());
+        assert_eq!(
+            outputs[0].file_name().unwrap().to_str().unwrap(),
+            format!("{}.png", &format!("{:016x}", hash)[..8])
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn single_dir_is_input_dir() {
+        let dir = setup_output_path("cli_convert_output_template_single_dir");
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("in.png");
+        std::fs::copy(setup_input_path("2x3_wrabaa.png"), &input).unwrap();
+
+        single(&input, "{dir}/{stem}.small.bmp", &[]).unwrap();
+
+        assert!(dir.join("in.small.bmp").exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn incremental_remembers_output_path() {
+        let dir = setup_output_path("cli_convert_output_template_incremental");
+        setup_inputs(&dir, 2, &[]);
+        let flags = ["--incremental", "--output-template", "{hash8}.{ext}"];
+
+        batch(&dir, &flags).unwrap();
+        let outputs = std::fs::read_dir(dir.join("out")).unwrap().count();
+        // both inputs are equal, so they share the same output, next to the manifest
+        assert_eq!(outputs, 2);
+
+        let manifest = std::fs::read_to_string(dir.join("out").join(".sic-manifest.json")).unwrap();
+        batch(&dir, &flags).unwrap();
+        let manifest_after =
+            std::fs::read_to_string(dir.join("out").join(".sic-manifest.json")).unwrap();
+        assert_eq!(manifest, manifest_after);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[yare::parameterized(
+        unknown_placeholder = { &["--output-template", "{name}.png"] },
+        conflicts_with_output = { &["--output-template", "{stem}.png", "--output", "out.png"] },
+    )]
+    fn invalid(flags: &[&str]) {
+        let input = setup_input_path("2x3_wrabaa.png");
+        let mut args = vec!["sic", "--input", path_buf_str(&input)];
+        args.extend(flags);
+
+        let result = get_app("", "", "")
+            .get_matches_from_safe(args)
+            .map_err(anyhow::Error::from)
+            .and_then(|matches| build_app_config(&matches).map(|_| ()));
+
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod output_variant {
+    use super::jobs::{batch, setup_inputs};
+    use super::*;
+    use sic_core::image::GenericImageView;
+
+    #[test]
+    fn single_common_and_variant_operations() {
+        let dir = setup_output_path("cli_convert_output_variant_single");
+        std::fs::create_dir_all(&dir).unwrap();
+        let dir_str = path_buf_str(&dir);
+
+        let small = format!("{}/small.png: resize 3 2", dir_str);
+        let full = format!("{}/{{stem}}.bmp", dir_str);
+        let input = setup_input_path("rainbow_8x6.bmp");
+
+        let args = vec![
+            "sic",
+            "--input",
+            path_buf_str(&input),
+            "--apply-operations",
+            "resize 6 4",
+            "--output-variant",
+            &small,
+            "--output-variant",
+            &full,
+        ];
+
+        let matches = get_app("", "", "").get_matches_from(args);
+        run_with_devices(
+            InputOutputMode::try_from_matches(&matches).unwrap(),
+            &build_app_config(&matches).unwrap(),
+        )
+        .unwrap();
+
+        let small = image::open(dir.join("small.png")).unwrap();
+        assert_eq!(small.dimensions(), (3, 2));
+
+        let full = image::open(dir.join("rainbow_8x6.bmp")).unwrap();
+        assert_eq!(full.dimensions(), (6, 4));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn batch_variants() {
+        let dir = setup_output_path("cli_convert_output_variant_batch");
+        setup_inputs(&dir, 2, &[]);
+
+        let flags = [
+            "--output-variant",
+            "thumb/{stem}.png: resize 1 1",
+            "--output-variant",
+            "{stem}-{width}x{height}.bmp",
+            "--incremental",
+        ];
+
+        batch(&dir, &flags).unwrap();
+
+        for index in 0..2 {
+            let thumb = image::open(dir.join(format!("out/thumb/img_{}.png", index))).unwrap();
+            assert_eq!(thumb.dimensions(), (1, 1));
+            assert!(dir.join(format!("out/img_{}-4x4.bmp", index)).exists());
+        }
+
+        // an image is processed again when the output of any of its variants is missing
+        std::fs::remove_file(dir.join("out/thumb/img_0.png")).unwrap();
+        batch(&dir, &flags).unwrap();
+        assert!(dir.join("out/thumb/img_0.png").exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    fn single(input: &Path, variants: &[&str]) {
+        let mut args = vec!["sic", "--input", path_buf_str(input)];
+        for variant in variants {
+            args.extend(["--output-variant", variant]);
+        }
+
+        let matches = get_app("", "", "").get_matches_from(args);
+        run_with_devices(
+            InputOutputMode::try_from_matches(&matches).unwrap(),
+            &build_app_config(&matches).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn encoding_options_per_variant() {
+        let dir = setup_output_path("cli_convert_output_variant_encoding_options");
+        std::fs::create_dir_all(&dir).unwrap();
+        let dir_str = path_buf_str(&dir);
+
+        let low = format!("{}/low.jpg [jpeg-encoding-quality=10]", dir_str);
+        let high = format!("{}/high.jpg [jpeg-encoding-quality=100]", dir_str);
+        let lossy = format!("{}/lossy.webp [webp-quality=50]", dir_str);
+        let lossless = format!("{}/lossless.webp", dir_str);
+
+        single(
+            &setup_input_path("rainbow_8x6.bmp"),
+            &[&low, &high, &lossy, &lossless],
+        );
+
+        assert_ne!(
+            read_file_to_bytes(dir.join("low.jpg")),
+            read_file_to_bytes(dir.join("high.jpg"))
+        );
+
+        // only the lossless variant retains the exact colors
+        let input = image::open(setup_input_path("rainbow_8x6.bmp"))
+            .unwrap()
+            .to_rgba8();
+        let lossless = image::open(dir.join("lossless.webp")).unwrap().to_rgba8();
+        let lossy = image::open(dir.join("lossy.webp")).unwrap().to_rgba8();
+        assert_eq!(lossless, input);
+        assert_ne!(lossy, input);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn output_format_per_variant() {
+        let dir = setup_output_path("cli_convert_output_variant_output_format");
+        std::fs::create_dir_all(&dir).unwrap();
+        let dir_str = path_buf_str(&dir);
+
+        let webp = format!("{}/a.webp", dir_str);
+        let png = format!("{}/b.png", dir_str);
+
+        single(&setup_input_path("rainbow_8x6.bmp"), &[&webp, &png]);
+
+        assert!(is_image_format(
+            "cli_convert_output_variant_output_format/a.webp",
+            image::ImageFormat::WebP
+        ));
+        assert!(is_image_format(
+            "cli_convert_output_variant_output_format/b.png",
+            image::ImageFormat::Png
+        ));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn input_without_extension() {
+        let dir = setup_output_path("cli_convert_output_variant_without_extension");
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("rainbow");
+        std::fs::copy(setup_input_path("rainbow_8x6.bmp"), &input).unwrap();
+
+        // the extension of the input is only required by templates which use it
+        let variant = format!("{}/{{stem}}.png", path_buf_str(&dir));
+        single(&input, &[&variant]);
+        assert!(dir.join("rainbow.png").exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[yare::parameterized(
+        invalid_operations = { &["--output-variant", "small.png: resize 1"] },
+        invalid_encoding_option = { &["--output-variant", "small.png [png-compression=small]"] },
+        conflicts_with_output = { &["--output-variant", "small.png", "--output", "out.png"] },
+        conflicts_with_output_template = { &["--output-variant", "small.png", "--output-template", "{stem}.png"] },
+        conflicts_with_output_format = { &["--output-variant", "small.webp", "--output-variant", "small.png", "--output-format", "jpeg"] },
+    )]
+    fn invalid(flags: &[&str]) {
+        let input = setup_input_path("2x3_wrabaa.png");
+        let mut args = vec!["sic", "--input", path_buf_str(&input)];
+        args.extend(flags);
+
+        let result = get_app("", "", "")
+            .get_matches_from_safe(args)
+            .map_err(anyhow::Error::from)
+            .and_then(|matches| build_app_config(&matches).map(|_| ()));
+
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod automatic_orientation {
+    use super::*;
+    use sic_core::image::GenericImageView;
+
+    ide!();
+
+    #[yare::parameterized(
+        jpeg_oriented = { "rainbow_8x6_orientation_6.jpg", &[], (6, 8) },
+        jpeg_disabled = { "rainbow_8x6_orientation_6.jpg", &["--disable-automatic-orientation"], (8, 6) },
+        png_oriented = { "rainbow_8x6_orientation_6.png", &[], (6, 8) },
+        png_disabled = { "rainbow_8x6_orientation_6.png", &["--disable-automatic-orientation"], (8, 6) },
+        no_orientation = { "rainbow_8x6.bmp", &[], (8, 6) },
+        script_disabled_explicit = { "rainbow_8x6_orientation_6.png", &["--disable-automatic-orientation", "--apply-operations", "auto-orient"], (6, 8) },
+        script_applied_once = { "rainbow_8x6_orientation_6.png", &["--apply-operations", "auto-orient"], (6, 8) },
+    )]
+    fn orientation(input: &str, flags: &[&str], expected_dimensions: (u32, u32)) {
+        let input_path = setup_input_path(input);
+        let output_path = setup_output_path(&format!(
+            "cli_convert_orientation_{}_{}.png",
+            input,
+            flags.len()
+        ));
+
+        let mut args = vec![
+            "sic",
+            "--input",
+            path_buf_str(&input_path),
+            "--output",
+            path_buf_str(&output_path),
+        ];
+        args.extend_from_slice(flags);
+
+        let matches = get_app("", "", "").get_matches_from(args);
+        run_with_devices(
+            InputOutputMode::try_from_matches(&matches).unwrap(),
+            &build_app_config(&matches).unwrap(),
+        )
+        .unwrap();
+
+        let image = image::open(&output_path).unwrap();
+        assert_eq!(image.dimensions(), expected_dimensions);
+
+        clean_up_output_path(path_buf_str(&output_path));
+    }
+}
+
+mod metadata {
+    use super::*;
+    use sic_core::image::metadata::Orientation;
+    use sic_io::metadata::read_metadata;
+
+    ide!();
+
+    #[yare::parameterized(
+        keep_all = { "keep-all", true, true, true },
+        keep_icc = { "keep-icc", false, false, false },
+        strip_gps = { "strip-gps", true, false, true },
+        strip_all = { "strip-all", false, false, false },
+    )]
+    fn retention(mode: &str, exif: bool, gps: bool, xmp: bool) {
+        let input_path = setup_input_path("rainbow_8x6_exif_gps.jpg");
+        let output_path = setup_output_path(&format!("cli_convert_metadata_{}.png", mode));
+
+        let args = vec![
+            "sic",
+            "--input",
+            path_buf_str(&input_path),
+            "--output",
+            path_buf_str(&output_path),
+            "--metadata",
+            mode,
+        ];
+
+        let matches = get_app("", "", "").get_matches_from(args);
+        run_with_devices(
+            InputOutputMode::try_from_matches(&matches).unwrap(),
+            &build_app_config(&matches).unwrap(),
+        )
+        .unwrap();
+
+        let metadata = read_metadata(&std::fs::read(&output_path).unwrap());
+        let contains = |data: Option<&[u8]>, needle: &[u8]| {
+            data.is_some_and(|data| data.windows(needle.len()).any(|window| window == needle))
+        };
+
+        assert_eq!(metadata.exif().is_some(), exif);
+        assert_eq!(contains(metadata.exif(), b"N\0"), gps);
+        assert_eq!(metadata.xmp().is_some(), xmp);
+        assert_eq!(contains(metadata.xmp(), b"GPS"), gps);
+
+        // The orientation has been applied, so it is reset in the output image.
+        assert_eq!(metadata.orientation(), Orientation::NoTransforms);
+
+        clean_up_output_path(path_buf_str(&output_path));
+    }
+
+    #[test]
+    fn unsupported_output_format() {
+        let input_path = setup_input_path("rainbow_8x6_exif_gps.jpg");
+        let output_path = setup_output_path("cli_convert_metadata_unsupported.bmp");
+
+        let args = vec![
+            "sic",
+            "--input",
+            path_buf_str(&input_path),
+            "--output",
+            path_buf_str(&output_path),
+        ];
+
+        let matches = get_app("", "", "").get_matches_from(args);
+        let result = run_with_devices(
+            InputOutputMode::try_from_matches(&matches).unwrap(),
+            &build_app_config(&matches).unwrap(),
+        );
+
+        assert!(result.is_ok());
+        assert!(image::open(&output_path).is_ok());
+
+        clean_up_output_path(path_buf_str(&output_path));
+    }
+}
+
+mod convert_profile {
+    use super::*;
+    use sic_io::metadata::read_metadata;
+
+    ide!();
+
+    fn convert(input_path: &Path, output: &str, operations: &str) -> PathBuf {
+        let output_path = setup_output_path(output);
+
+        let args = vec![
+            "sic",
+            "--input",
+            path_buf_str(input_path),
+            "--output",
+            path_buf_str(&output_path),
+            "--apply-operations",
+            operations,
+        ];
+
+        let matches = get_app("", "", "").get_matches_from(args);
+        run_with_devices(
+            InputOutputMode::try_from_matches(&matches).unwrap(),
+            &build_app_config(&matches).unwrap(),
+        )
+        .unwrap();
+
+        output_path
+    }
+
+    #[test]
+    fn round_trip() {
+        let display_p3 = setup_input_path("display_p3.icc");
+        let to_display_p3 = format!("convert-profile '{}'", path_buf_str(&display_p3));
+
+        // The converted image is tagged with the Display P3 profile
+        let tagged = convert(
+            &setup_input_path("rainbow_8x6.bmp"),
+            "cli_convert_profile_display_p3.png",
+            &to_display_p3,
+        );
+        let metadata = read_metadata(&std::fs::read(&tagged).unwrap());
+        assert_eq!(
+            metadata.icc_profile(),
+            Some(std::fs::read(&display_p3).unwrap().as_slice())
+        );
+
+        // Converting the tagged image back to sRGB removes the profile, and restores the colors
+        let untagged = convert(
+            &tagged,
+            "cli_convert_profile_srgb.png",
+            "convert-profile srgb",
+        );
+        let metadata = read_metadata(&std::fs::read(&untagged).unwrap());
+        assert!(metadata.icc_profile().is_none());
+
+        let original = image::open(setup_input_path("rainbow_8x6.bmp"))
+            .unwrap()
+            .to_rgb8();
+        let restored = image::open(&untagged).unwrap().to_rgb8();
+        let max_difference = original
+            .iter()
+            .zip(restored.iter())
+            .map(|(lhs, rhs)| lhs.abs_diff(*rhs))
+            .max();
+        // Allow for rounding errors, since both conversions use 8-bit pixels
+        assert!(max_difference <= Some(4));
+
+        clean_up_output_path(path_buf_str(&tagged));
+        clean_up_output_path(path_buf_str(&untagged));
+    }
+}
+
+#[cfg(test)]
+mod stats {
+    use super::jobs::{batch, setup_inputs};
+    use super::*;
+    use sic::cli::stats::stats_path;
+
+    fn read_stats(path: &Path) -> serde_json::Value {
+        serde_json::from_slice(&std::fs::read(stats_path(path)).unwrap()).unwrap()
+    }
+
+    fn single(input: &str, output: &Path, flags: &[&str]) {
+        let input_path = setup_input_path(input);
+
+        let mut args = vec![
+            "sic",
+            "--input",
+            path_buf_str(&input_path),
+            "--output",
+            path_buf_str(output),
+        ];
+        args.extend(flags);
+
+        let matches = get_app("", "", "").get_matches_from(args);
+        run_with_devices(
+            InputOutputMode::try_from_matches(&matches).unwrap(),
+            &build_app_config(&matches).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn json_alongside_output() {
+        let output_path = setup_output_path("cli_convert_stats_json.png");
+
+        single("2x3_rrgrbb.bmp", &output_path, &["--stats", "json"]);
+
+        let stats = read_stats(&output_path);
+        let frames = stats["frames"].as_array().unwrap();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0]["width"], 2);
+        assert_eq!(frames[0]["height"], 3);
+        assert_eq!(frames[0]["unique_colors"], 3);
+
+        let red = &frames[0]["channels"][0];
+        assert_eq!(red["channel"], "red");
+        assert_eq!(red["mean"], 127.5);
+        assert_eq!(red["histogram"].as_array().unwrap().len(), 256);
+        assert_eq!(red["histogram"][255], 3);
+
+        clean_up_output_path(path_buf_str(&stats_path(&output_path)));
+        clean_up_output_path(path_buf_str(&output_path));
+    }
+
+    // The statistics are of the processed image.
+    #[test]
+    fn json_of_processed_animated_image() {
+        let output_path = setup_output_path("cli_convert_stats_animated.gif");
+
+        single(
+            "loop.gif",
+            &output_path,
+            &["--stats", "json", "--frames", "..3", "-x", "resize 4 4"],
+        );
+
+        let stats = read_stats(&output_path);
+        let frames = stats["frames"].as_array().unwrap();
+        assert_eq!(frames.len(), 3);
+        assert!(frames.iter().all(|frame| frame["width"] == 4));
+
+        clean_up_output_path(path_buf_str(&stats_path(&output_path)));
+        clean_up_output_path(path_buf_str(&output_path));
+    }
+
+    #[test]
+    fn text_does_not_write_file() {
+        let output_path = setup_output_path("cli_convert_stats_text.png");
+
+        single("2x3_rrgrbb.bmp", &output_path, &["--stats", "text"]);
+
+        assert!(output_path.exists());
+        assert!(!stats_path(&output_path).exists());
+
+        clean_up_output_path(path_buf_str(&output_path));
+    }
+
+    #[test]
+    fn batch_json_alongside_each_output() {
+        let dir = setup_output_path("cli_convert_stats_batch");
+        setup_inputs(&dir, 3, &[]);
+
+        batch(&dir, &["--stats", "json", "--output-format", "bmp"]).unwrap();
+
+        for i in 0..3 {
+            let stats = read_stats(&dir.join(format!("out/img_{}.bmp", i)));
+            assert_eq!(stats["frames"][0]["unique_colors"], 1);
+        }
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn invalid_format() {
+        let args = vec!["sic", "--input", "in.png", "--stats", "csv"];
+
+        assert!(get_app("", "", "").get_matches_from_safe(args).is_err());
+    }
+}
+
+#[cfg(test)]
+mod compare {
+    use super::*;
+
+    fn compare(input: &str, reference: &str, flags: &[&str]) -> anyhow::Result<()> {
+        let input_path = setup_input_path(input);
+        let reference_path = setup_input_path(reference);
+
+        let mut args = vec![
+            "sic",
+            "--input",
+            path_buf_str(&input_path),
+            "--compare",
+            path_buf_str(&reference_path),
+        ];
+        args.extend(flags);
+
+        let matches = get_app("", "", "").get_matches_from(args);
+        run_with_devices(
+            InputOutputMode::try_from_matches(&matches)?,
+            &build_app_config(&matches)?,
+        )
+    }
+
+    #[test]
+    fn equal_images_satisfy_thresholds() {
+        let result = compare(
+            "rainbow_8x6.bmp",
+            "rainbow_8x6.bmp",
+            &[
+                "--compare-threshold",
+                "psnr=60",
+                "--compare-threshold",
+                "max-error=0",
+            ],
+        );
+
+        assert!(result.is_ok());
+    }
+
+    // The image operations are applied to the input image before it is compared.
+    #[yare::parameterized(
+        psnr = { "psnr=60" },
+        ssim = { "ssim=0.999" },
+        mae = { "mae=0.5" },
+        max_error = { "max-error=8" },
+    )]
+    fn processed_image_exceeds_threshold(threshold: &str) {
+        let result = compare(
+            "rainbow_8x6.bmp",
+            "rainbow_8x6.bmp",
+            &["-x", "blur 1", "--compare-threshold", threshold],
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn heat_map_is_written_to_output() {
+        let output_path = setup_output_path("cli_convert_compare_heat_map.png");
+
+        compare(
+            "rainbow_8x6.bmp",
+            "rainbow_8x6.bmp",
+            &["-x", "invert", "--output", path_buf_str(&output_path)],
+        )
+        .unwrap();
+
+        let heat_map = image::open(&output_path).unwrap();
+        assert_eq!((heat_map.width(), heat_map.height()), (8, 6));
+
+        clean_up_output_path(path_buf_str(&output_path));
+    }
+
+    #[test]
+    fn dimension_mismatch() {
+        assert!(compare("rainbow_8x6.bmp", "2x3_rrgrbb.bmp", &[]).is_err());
+    }
+
+    // The orientation is applied to both the input image and the reference image.
+    #[yare::parameterized(
+        oriented = { &["--compare-threshold", "max-error=0"] },
+        disabled = { &["--compare-threshold", "max-error=0", "--disable-automatic-orientation"] },
+    )]
+    fn oriented_image_equals_itself(flags: &[&str]) {
+        let result = compare(
+            "rainbow_8x6_orientation_6.jpg",
+            "rainbow_8x6_orientation_6.jpg",
+            flags,
+        );
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn orientation_of_reference_is_applied() {
+        // the input image isn't tagged, so only the reference image is rotated
+        assert!(compare("rainbow_8x6.bmp", "rainbow_8x6_orientation_6.jpg", &[]).is_err());
+    }
+
+    #[test]
+    fn invalid_threshold() {
+        let result = compare(
+            "rainbow_8x6.bmp",
+            "rainbow_8x6.bmp",
+            &["--compare-threshold", "mse=1"],
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn threshold_requires_compare() {
+        let args = vec!["sic", "--input", "in.png", "--compare-threshold", "psnr=40"];
+
+        assert!(get_app("", "", "").get_matches_from_safe(args).is_err());
+    }
+
+    #[test]
+    fn conflicts_with_info() {
+        let args = vec!["sic", "--input", "in.png", "--compare", "ref.png", "--info"];
+
+        assert!(get_app("", "", "").get_matches_from_safe(args).is_err());
+    }
+}
@@ -17,6 +17,20 @@ fn command(input: &str, output: &str, args: &str) -> Child {
         .spawn_child()
 }
 
+#[cfg(test)]
+mod auto_orient {
+    use super::*;
+    use crate::common::*;
+
+    #[test]
+    fn auto_orient() {
+        let mut process = command(DEFAULT_IN, "cio_auto_orient.png", "--auto-orient");
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert!(result.unwrap().success());
+    }
+}
+
 #[cfg(test)]
 mod blur {
     use crate::common::*;
@@ -70,6 +84,56 @@ mod blur {
     }
 }
 
+#[cfg(test)]
+mod convert_profile {
+    use super::*;
+    use crate::common::*;
+
+    #[test]
+    fn convert_profile_srgb() {
+        let mut process = command(
+            DEFAULT_IN,
+            "cio_convert_profile_1.png",
+            "--convert-profile srgb",
+        );
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert!(result.unwrap().success());
+    }
+
+    #[test]
+    fn convert_profile_path() {
+        let mut process = command(
+            DEFAULT_IN,
+            "cio_convert_profile_2.png",
+            &[
+                "--convert-profile",
+                setup_input_path("display_p3.icc").to_str().unwrap(),
+            ]
+            .join(" "),
+        );
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert!(result.unwrap().success());
+    }
+
+    #[test]
+    fn convert_profile_invalid_path() {
+        let mut process = command(
+            DEFAULT_IN,
+            "cio_convert_profile_3.png",
+            &[
+                "--convert-profile",
+                setup_input_path("missing.icc").to_str().unwrap(),
+            ]
+            .join(" "),
+        );
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert_not!(result.unwrap().success());
+    }
+}
+
 #[cfg(test)]
 mod crop {
     use super::*;
@@ -173,6 +237,32 @@ mod crop {
     }
 }
 
+#[cfg(test)]
+mod curves {
+    use super::*;
+    use crate::common::*;
+
+    #[test]
+    fn curves() {
+        let mut process = command(
+            DEFAULT_IN,
+            "cio_curves1.png",
+            "--curves points((0,0),(128,150),(255,255))",
+        );
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert!(result.unwrap().success());
+    }
+
+    #[test]
+    fn curves_too_few_points() {
+        let mut process = command(DEFAULT_IN, "cio_curves2.png", "--curves points((0,0))");
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert_not!(result.unwrap().success());
+    }
+}
+
 #[cfg(test)]
 mod diff {
     use super::*;
@@ -207,6 +297,57 @@ mod diff {
     }
 }
 
+#[cfg(test)]
+mod drop_frames {
+    use super::*;
+
+    #[test]
+    fn drop_frames() {
+        let mut process = command("loop.gif", "cio_drop_frames1.gif", "--drop-frames ::2");
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert!(result.unwrap().success());
+    }
+
+    #[test]
+    fn drop_frames_all() {
+        let mut process = command("loop.gif", "cio_drop_frames2.gif", "--drop-frames ..");
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert_not!(result.unwrap().success());
+    }
+
+    #[test]
+    fn drop_frames_not() {
+        let mut process = command("loop.gif", "cio_drop_frames3.gif", "--drop-frames 1..2..3");
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert_not!(result.unwrap().success());
+    }
+}
+
+#[cfg(test)]
+mod extend {
+    use super::*;
+    use crate::common::*;
+
+    #[test]
+    fn extend() {
+        let mut process = command(DEFAULT_IN, "cio_extend1.png", "--extend 1 2 3 4");
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert!(result.unwrap().success());
+    }
+
+    #[test]
+    fn extend_not_enough_margins() {
+        let mut process = command(DEFAULT_IN, "cio_extend2.png", "--extend 1 2");
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert_not!(result.unwrap().success());
+    }
+}
+
 #[cfg(test)]
 mod filter3x3 {
     use super::*;
@@ -277,6 +418,28 @@ mod flipv {
     }
 }
 
+#[cfg(test)]
+mod gamma {
+    use super::*;
+    use crate::common::*;
+
+    #[test]
+    fn gamma() {
+        let mut process = command(DEFAULT_IN, "cio_gamma1.png", "--gamma 2.2");
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert!(result.unwrap().success());
+    }
+
+    #[test]
+    fn gamma_zero() {
+        let mut process = command(DEFAULT_IN, "cio_gamma2.png", "--gamma 0");
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert_not!(result.unwrap().success());
+    }
+}
+
 #[cfg(test)]
 mod grayscale {
     use super::*;
@@ -327,6 +490,28 @@ mod invert {
     }
 }
 
+#[cfg(test)]
+mod levels {
+    use super::*;
+    use crate::common::*;
+
+    #[test]
+    fn levels() {
+        let mut process = command(DEFAULT_IN, "cio_levels1.png", "--levels 10 240");
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert!(result.unwrap().success());
+    }
+
+    #[test]
+    fn levels_inverted_range() {
+        let mut process = command(DEFAULT_IN, "cio_levels2.png", "--levels 240 10");
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert_not!(result.unwrap().success());
+    }
+}
+
 #[cfg(test)]
 mod overlay {
     use super::*;
@@ -351,6 +536,28 @@ mod overlay {
     }
 }
 
+#[cfg(test)]
+mod pad {
+    use super::*;
+    use crate::common::*;
+
+    #[test]
+    fn pad() {
+        let mut process = command(DEFAULT_IN, "cio_pad1.png", "--pad 100 100");
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert!(result.unwrap().success());
+    }
+
+    #[test]
+    fn pad_negative() {
+        let mut process = command(DEFAULT_IN, "cio_pad2.png", "--pad 100 -100");
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert_not!(result.unwrap().success());
+    }
+}
+
 #[cfg(test)]
 mod resize {
     use super::*;
@@ -371,6 +578,52 @@ mod resize {
         assert!(result.is_ok());
         assert_not!(result.unwrap().success());
     }
+
+    #[test]
+    fn resize_modes() {
+        let mut process = command(
+            DEFAULT_IN,
+            "cio_resize3.png",
+            "--resize-fit 20 20 --resize-cover 10 5 --resize-exact 8 8 --resize-scale 50% --resize-max-edge 2",
+        );
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert!(result.unwrap().success());
+    }
+
+    #[test]
+    fn resize_shrink_only() {
+        let mut process = command(
+            DEFAULT_IN,
+            "cio_resize4.png",
+            "--shrink-only true --resize-scale 200%",
+        );
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert!(result.unwrap().success());
+    }
+
+    #[test]
+    fn resize_scale_not() {
+        let mut process = command(DEFAULT_IN, "cio_resize5.png", "--resize-scale 0%");
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert_not!(result.unwrap().success());
+    }
+}
+
+#[cfg(test)]
+mod reverse_frames {
+    use super::*;
+    use crate::common::*;
+
+    #[test]
+    fn reverse_frames() {
+        let mut process = command(DEFAULT_IN, "cio_reverse_frames.png", "--reverse-frames");
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert!(result.unwrap().success());
+    }
 }
 
 #[cfg(test)]
@@ -415,6 +668,94 @@ mod rotate270 {
     }
 }
 
+#[cfg(test)]
+mod set_frame_delay {
+    use super::*;
+    use crate::common::*;
+
+    #[test]
+    fn set_frame_delay() {
+        let mut process = command(
+            DEFAULT_IN,
+            "cio_set_frame_delay1.png",
+            "--set-frame-delay 50ms",
+        );
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert!(result.unwrap().success());
+    }
+
+    #[test]
+    fn set_frame_delay_not() {
+        let mut process = command(
+            DEFAULT_IN,
+            "cio_set_frame_delay2.png",
+            "--set-frame-delay 50",
+        );
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert_not!(result.unwrap().success());
+    }
+}
+
+#[cfg(test)]
+mod smart_crop {
+    use super::*;
+    use crate::common::*;
+
+    #[test]
+    fn smart_crop() {
+        let mut process = command(DEFAULT_IN, "cio_smart_crop1.png", "--smart-crop 10 10");
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert!(result.unwrap().success());
+    }
+
+    #[test]
+    fn smart_crop_zero() {
+        let mut process = command(DEFAULT_IN, "cio_smart_crop2.png", "--smart-crop 0 10");
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert_not!(result.unwrap().success());
+    }
+}
+
+#[cfg(test)]
+mod speed {
+    use super::*;
+    use crate::common::*;
+
+    #[test]
+    fn speed() {
+        let mut process = command(DEFAULT_IN, "cio_speed1.png", "--speed 2");
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert!(result.unwrap().success());
+    }
+
+    #[test]
+    fn speed_not() {
+        let mut process = command(DEFAULT_IN, "cio_speed2.png", "--speed 0");
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert_not!(result.unwrap().success());
+    }
+}
+
+#[cfg(test)]
+mod trim {
+    use super::*;
+    use crate::common::*;
+
+    #[test]
+    fn trim() {
+        let mut process = command(DEFAULT_IN, "cio_trim.png", "--trim");
+        let result = process.wait();
+        assert!(result.is_ok());
+        assert!(result.unwrap().success());
+    }
+}
+
 #[cfg(test)]
 mod unsharpen {
     use super::*;
@@ -447,7 +788,8 @@ mod mixed {
         let mut process = command(
             DEFAULT_IN,
             "img_op_arg_mixed_all.png",
-            "--blur 1 \
+            "--auto-orient \
+             --blur 1 \
              --brighten 2 \
              --contrast 3 \
              --crop 0 0 2 2 \
@@ -15,9 +15,11 @@ mod tests {
     #[parameterized(
         ops = {
             r#"draw-text "example" coord(0,1) rgba(0,0,0,255) size(24) font("%font%");"#,
+            "rotate 30 crop nearest rgba(255, 255, 255, 255);",
         },
         output_file = {
-            "imageproc_ops_draw_text_apply_operations"
+            "imageproc_ops_draw_text_apply_operations",
+            "imageproc_ops_rotate_apply_operations",
         },
     )]
     fn check_imageproc_ops_with_script(ops: &str, output_file: &str) {
@@ -47,16 +49,22 @@ mod tests {
             &["--draw-text", "example", "coord(0,1)", "rgba(0,0,0,255)", "size(24)", "font('▲')"],
             &["--draw-text", "example", "coord(0,1)", "rgba(0,0,0,255)", "size(24)", "font(\"▲\")"],
             &["--draw-text", "example", "coord(0,1)", "rgba(0,0,0,255)", "size(24)", "font(\"▲\')"],
+            &["--rotate", "-45"],
+            &["--rotate", "45deg"],
         },
         output_file = {
             "imageproc_ops_draw_text_cli_arg_0_ok",
             "imageproc_ops_draw_text_cli_arg_1_ok",
             "imageproc_ops_draw_text_cli_arg_2_err",
+            "imageproc_ops_rotate_cli_arg_0_ok",
+            "imageproc_ops_rotate_cli_arg_1_err",
         },
         ok = {
             true,
             true,
             false,
+            true,
+            false,
         }
     )]
     fn check_imageproc_ops_with_cli_args(ops: &[&str], output_file: &str, ok: bool) {
@@ -0,0 +1,73 @@
+#![deny(clippy::all)]
+
+#[macro_use]
+pub mod common;
+
+use common::{setup_output_path, SicTestCommandBuilder};
+
+#[test]
+fn info_text() {
+    let output = SicTestCommandBuilder::new()
+        .input_from_resources("loop.gif")
+        .with_args(["--info"])
+        .run_to_completion();
+
+    assert!(output.status.success());
+
+    let stdout = std::str::from_utf8(&output.stdout).unwrap();
+    assert!(stdout.starts_with("Format:       gif\n"));
+    assert!(stdout.contains("Frames:       8\n"));
+    assert!(stdout.contains("Loop count:   infinite\n"));
+}
+
+#[test]
+fn info_json() {
+    let output = SicTestCommandBuilder::new()
+        .input_from_resources("rainbow_8x6_exif_gps.jpg")
+        .with_args(["--info", "--json"])
+        .run_to_completion();
+
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["format"], "jpeg");
+    assert_eq!(json["width"], 8);
+    assert_eq!(json["height"], 6);
+    assert_eq!(json["frame_count"], 1);
+    assert_eq!(json["metadata"]["exif"], true);
+    assert_eq!(json["metadata"]["xmp"], true);
+}
+
+#[test]
+fn info_conflicts_with_output() {
+    const OUT: &str = "info_conflicts_with_output.png";
+
+    let output = SicTestCommandBuilder::new()
+        .input_from_resources("rainbow_8x6.bmp")
+        .output_in_target(OUT)
+        .with_args(["--info"])
+        .run_to_completion();
+
+    assert_not!(output.status.success());
+    assert_not!(setup_output_path(OUT).exists());
+}
+
+#[test]
+fn info_of_unsupported_file() {
+    let output = SicTestCommandBuilder::new()
+        .input_from_resources("credits.txt")
+        .with_args(["--info"])
+        .run_to_completion();
+
+    assert_not!(output.status.success());
+}
+
+#[test]
+fn json_requires_info() {
+    let output = SicTestCommandBuilder::new()
+        .input_from_resources("loop.gif")
+        .with_args(["--json"])
+        .run_to_completion();
+
+    assert_not!(output.status.success());
+}
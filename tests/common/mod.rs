@@ -1,6 +1,6 @@
 use std::ffi::{OsStr, OsString};
 use std::path::{Path, PathBuf};
-use std::process::{Child, Command, Stdio};
+use std::process::{Child, Command, Output, Stdio};
 
 #[derive(Clone, Debug)]
 pub struct SicTestCommandBuilder {
@@ -88,17 +88,7 @@ impl SicTestCommandBuilder {
     }
 
     pub fn spawn_child(self) -> Child {
-        let mut command = Command::new("cargo");
-        command.arg("run");
-
-        if !self.features.is_empty() {
-            command.arg("--features");
-            command.args(&self.features);
-        }
-
-        command.arg("--");
-
-        command.args(self.commands);
+        let mut command = self.command();
 
         command
             .stdout(Stdio::null())
@@ -113,6 +103,29 @@ impl SicTestCommandBuilder {
             .expect("Unable to spawn child process for SicTestCommandBuilder instance")
     }
 
+    /// Runs the command to completion, and collects its output.
+    #[allow(unused)]
+    pub fn run_to_completion(self) -> Output {
+        self.command()
+            .output()
+            .expect("Unable to run child process for SicTestCommandBuilder instance")
+    }
+
+    fn command(self) -> Command {
+        let mut command = Command::new("cargo");
+        command.arg("run");
+
+        if !self.features.is_empty() {
+            command.arg("--features");
+            command.args(&self.features);
+        }
+
+        command.arg("--");
+
+        command.args(self.commands);
+        command
+    }
+
     fn with_resources_path(path: &OsStr) -> OsString {
         Path::new(env!("CARGO_MANIFEST_DIR"))
             .join("resources")